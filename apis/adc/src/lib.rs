@@ -0,0 +1,276 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use libtock_platform::share;
+use libtock_platform::subscribe::{OneId, StandardResultArg2, UpcallResult};
+use libtock_platform::{AllowRw, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall};
+
+/// The ADC driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock::adc::Adc;
+///
+/// // Sample channel 0 and convert the result to millivolts
+/// let raw = Adc::sample(0)?;
+/// let bits = Adc::resolution_bits()?;
+/// let millivolts = raw as u32 * Adc::reference_voltage_mv()? >> bits;
+/// ```
+pub struct Adc<S: Syscalls>(S);
+
+impl<S: Syscalls> Adc<S> {
+    /// Run a check against the adc capsule to ensure it is present.
+    ///
+    /// Returns `Ok(number_of_channels)` if the driver was present. This does
+    /// not necessarily mean that the driver is working.
+    pub fn count() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::COUNT, 0, 0).to_result()
+    }
+
+    /// Returns the number of bits in each sample.
+    pub fn resolution_bits() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::RESOLUTION_BITS, 0, 0).to_result()
+    }
+
+    /// Returns the voltage, in millivolts, that corresponds to a full-scale
+    /// sample.
+    pub fn reference_voltage_mv() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::REFERENCE_VOLTAGE, 0, 0).to_result()
+    }
+
+    /// Takes a single sample from `channel`, blocking until it is available.
+    ///
+    /// Returns `Err(ErrorCode::Busy)` without waiting if the ADC is already
+    /// sampling.
+    pub fn sample(channel: u32) -> Result<u16, ErrorCode> {
+        // The upcall's arguments are (sampling mode, channel, sample). The
+        // single-sample mode is 0, which UpcallResult decodes as success.
+        let result = StandardResultArg2::new(None);
+        share::scope(|subscribe| {
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::SAMPLE }>(
+                subscribe, &result,
+            )?;
+            S::command(DRIVER_NUM, command::SINGLE_SAMPLE, channel as usize, 0)
+                .to_result::<(), ErrorCode>()?;
+            let (_channel, sample) = result.upcall_result_yield::<S>()?;
+            Ok(sample as u16)
+        })
+    }
+
+    /// Takes a single sample from `channel` without blocking. Returns `None`
+    /// if the sample is not ready yet, in which case it stays requested and a
+    /// later call returns it; `channel` is only used when a new sample is
+    /// requested. This suits superloops that cannot block.
+    ///
+    /// While a sample is outstanding, this holds the ADC upcall, so other ADC
+    /// operations should not be started until it has been returned.
+    pub fn try_sample(channel: u32) -> Option<Result<u16, ErrorCode>> {
+        if !POLLED.requested.load(Ordering::Relaxed) {
+            POLLED.sample.store(NO_SAMPLE, Ordering::Relaxed);
+            if let Err(error) =
+                S::subscribe_static::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::SAMPLE }>(
+                    &POLLED,
+                )
+            {
+                return Some(Err(error));
+            }
+            let started = S::command(DRIVER_NUM, command::SINGLE_SAMPLE, channel as usize, 0)
+                .to_result::<(), ErrorCode>();
+            if let Err(error) = started {
+                S::unsubscribe(DRIVER_NUM, subscribe::SAMPLE);
+                return Some(Err(error));
+            }
+            POLLED.requested.store(true, Ordering::Relaxed);
+        }
+        S::poll_once();
+        match POLLED.sample.swap(NO_SAMPLE, Ordering::Relaxed) {
+            NO_SAMPLE => None,
+            sample => {
+                POLLED.requested.store(false, Ordering::Relaxed);
+                S::unsubscribe(DRIVER_NUM, subscribe::SAMPLE);
+                Some(Ok(sample as u16))
+            }
+        }
+    }
+
+    /// Starts sampling `channel` at `frequency_hz` into `buffer`. The kernel
+    /// fills the buffer and then starts over from its beginning, reporting
+    /// each full buffer to `listener`; use the returned `ContinuousSampling`
+    /// to wait for those reports and to stop sampling. The samples themselves
+    /// can be read once the enclosing `share::scope` has returned.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let listener = ContinuousListener::default();
+    /// let mut buffer = [0; 64];
+    /// share::scope(|handle| {
+    ///     let sampling = Adc::sample_continuous(handle, &listener, 0, &mut buffer, 1000)?;
+    ///     let count = sampling.wait();
+    ///     sampling.stop()
+    /// })?;
+    /// ```
+    pub fn sample_continuous<'share>(
+        handle: share::Handle<ContinuousShares<'share, S>>,
+        listener: &'share ContinuousListener,
+        channel: u32,
+        buffer: &'share mut [u16],
+        frequency_hz: u32,
+    ) -> Result<ContinuousSampling<'share, S>, ErrorCode> {
+        let (allow_rw, subscribe) = handle.split();
+        listener.samples.set(None);
+        S::allow_rw_u16::<DefaultConfig, DRIVER_NUM, { allow_rw::BUFFER }>(allow_rw, buffer)?;
+        let started = S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::SAMPLE }>(
+            subscribe, listener,
+        )
+        .and_then(|()| {
+            S::command(
+                DRIVER_NUM,
+                command::CONTINUOUS_BUFFERED_SAMPLE,
+                channel as usize,
+                frequency_hz as usize,
+            )
+            .to_result::<(), ErrorCode>()
+        });
+        if let Err(error) = started {
+            // Don't leave the buffer with the kernel until the end of the
+            // enclosing share::scope.
+            let _ = S::unallow_rw(DRIVER_NUM, allow_rw::BUFFER);
+            return Err(error);
+        }
+        Ok(ContinuousSampling {
+            listener,
+            stopped: false,
+            _syscalls: PhantomData,
+        })
+    }
+}
+
+// The sample requested by `Adc::try_sample`. It is a static, so that its
+// upcall can stay subscribed between calls.
+static POLLED: PolledSample = PolledSample {
+    requested: AtomicBool::new(false),
+    sample: AtomicU32::new(NO_SAMPLE),
+};
+
+// A value of PolledSample::sample that no u16 sample can take.
+const NO_SAMPLE: u32 = u32::MAX;
+
+struct PolledSample {
+    // Whether a sample has been requested and not yet returned.
+    requested: AtomicBool,
+    // The sample delivered by the upcall, or NO_SAMPLE.
+    sample: AtomicU32,
+}
+
+impl Upcall<OneId<DRIVER_NUM, { subscribe::SAMPLE }>> for PolledSample {
+    fn upcall(&self, sample_mode: usize, _channel: usize, sample: usize) {
+        if sample_mode == mode::SINGLE_SAMPLE {
+            self.sample.store(sample as u32, Ordering::Relaxed);
+        }
+    }
+}
+
+/// The shares used by `Adc::sample_continuous`.
+pub type ContinuousShares<'share, S> = (
+    AllowRw<'share, S, DRIVER_NUM, { allow_rw::BUFFER }>,
+    Subscribe<'share, S, DRIVER_NUM, { subscribe::SAMPLE }>,
+);
+
+/// Receives the buffer-full upcalls during continuous sampling.
+#[derive(Default)]
+pub struct ContinuousListener {
+    // The number of samples reported by the latest upcall that hasn't been
+    // consumed yet.
+    samples: Cell<Option<usize>>,
+}
+
+impl Upcall<OneId<DRIVER_NUM, { subscribe::SAMPLE }>> for ContinuousListener {
+    fn upcall(&self, sample_mode: usize, length: usize, _buffer: usize) {
+        if sample_mode == mode::CONTINUOUS_BUFFERED {
+            self.samples.set(Some(length));
+        }
+    }
+}
+
+/// An ongoing continuous sampling run, returned by `Adc::sample_continuous`.
+/// Sampling is stopped, and the buffer unallowed, when this is dropped.
+pub struct ContinuousSampling<'share, S: Syscalls> {
+    listener: &'share ContinuousListener,
+    stopped: bool,
+    _syscalls: PhantomData<S>,
+}
+
+impl<'share, S: Syscalls> ContinuousSampling<'share, S> {
+    /// Waits until the kernel reports a full buffer, and returns the number of
+    /// samples written to it.
+    pub fn wait(&self) -> usize {
+        loop {
+            if let Some(samples) = self.listener.samples.take() {
+                return samples;
+            }
+            S::yield_wait();
+        }
+    }
+
+    /// Stops sampling and waits for the kernel's final upcall. Returns the
+    /// number of samples written to the buffer since it was last reported
+    /// full.
+    pub fn stop(mut self) -> Result<usize, ErrorCode> {
+        self.stopped = true;
+        self.listener.samples.set(None);
+        S::command(DRIVER_NUM, command::STOP, 0, 0).to_result::<(), ErrorCode>()?;
+        Ok(self.wait())
+    }
+}
+
+impl<'share, S: Syscalls> Drop for ContinuousSampling<'share, S> {
+    fn drop(&mut self) {
+        if !self.stopped {
+            let _ = S::command(DRIVER_NUM, command::STOP, 0, 0);
+        }
+        let _ = S::unallow_rw(DRIVER_NUM, allow_rw::BUFFER);
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x5;
+
+// Command IDs
+#[allow(unused)]
+mod command {
+    pub const COUNT: u32 = 0;
+    pub const SINGLE_SAMPLE: u32 = 1;
+    pub const CONTINUOUS_SAMPLE: u32 = 2;
+    pub const SINGLE_BUFFERED_SAMPLE: u32 = 3;
+    pub const CONTINUOUS_BUFFERED_SAMPLE: u32 = 4;
+    pub const STOP: u32 = 5;
+
+    pub const RESOLUTION_BITS: u32 = 101;
+    pub const REFERENCE_VOLTAGE: u32 = 102;
+}
+
+#[allow(unused)]
+mod subscribe {
+    pub const SAMPLE: u32 = 0;
+}
+
+mod allow_rw {
+    pub const BUFFER: u32 = 0;
+}
+
+// Sampling modes, reported as the first upcall argument
+#[allow(unused)]
+mod mode {
+    pub const SINGLE_SAMPLE: usize = 0;
+    pub const CONTINUOUS_SAMPLE: usize = 1;
+    pub const SINGLE_BUFFERED: usize = 2;
+    pub const CONTINUOUS_BUFFERED: usize = 3;
+}
@@ -0,0 +1,199 @@
+use super::ContinuousListener;
+use libtock_platform::{share, ErrorCode};
+use libtock_unittest::{fake, SyscallLogEntry};
+
+type Adc = super::Adc<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Adc::count(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn count() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::<4>::new();
+    kernel.add_driver(&driver);
+    assert_eq!(Adc::count(), Ok(4));
+}
+
+#[test]
+fn resolution_and_reference() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::<1>::new();
+    kernel.add_driver(&driver);
+
+    driver.set_resolution_bits(10);
+    driver.set_reference_voltage_mv(1800);
+    assert_eq!(Adc::resolution_bits(), Ok(10));
+    assert_eq!(Adc::reference_voltage_mv(), Ok(1800));
+}
+
+#[test]
+fn sample() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::<2>::new();
+    kernel.add_driver(&driver);
+
+    driver.push_sample(1234);
+    driver.push_sample(u16::MAX);
+    assert_eq!(Adc::sample(0), Ok(1234));
+    assert_eq!(Adc::sample(1), Ok(u16::MAX));
+    assert_eq!(Adc::sample(2), Err(ErrorCode::Invalid));
+}
+
+// This is the only test of try_sample, as the sample it polls for is shared
+// between threads.
+#[test]
+fn try_sample() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::<2>::new();
+    kernel.add_driver(&driver);
+
+    // The first call starts the sample; later calls poll for it.
+    assert_eq!(Adc::try_sample(1), None);
+    assert!(driver.is_busy());
+    assert_eq!(Adc::try_sample(1), None);
+
+    driver.push_sample(4321);
+    assert_eq!(Adc::try_sample(1), Some(Ok(4321)));
+    assert!(!driver.is_busy());
+
+    driver.push_sample(17);
+    assert_eq!(Adc::try_sample(0), Some(Ok(17)));
+    assert_eq!(Adc::try_sample(2), Some(Err(ErrorCode::Invalid)));
+}
+
+#[test]
+fn sample_busy() {
+    use libtock_unittest::fake::SyscallDriver;
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::<1>::new();
+    kernel.add_driver(&driver);
+
+    // Start a sample that never completes, so the ADC stays busy.
+    assert!(driver.command(1, 0, 0).is_success());
+    kernel.take_syscall_log();
+
+    // The failed request returns immediately rather than waiting for an
+    // upcall, and its upcall is unsubscribed on the way out.
+    assert_eq!(Adc::sample(0), Err(ErrorCode::Busy));
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Subscribe {
+                driver_num: 0x5,
+                subscribe_num: 0,
+            },
+            SyscallLogEntry::Command {
+                driver_id: 0x5,
+                command_id: 1,
+                argument0: 0,
+                argument1: 0,
+            },
+            SyscallLogEntry::Subscribe {
+                driver_num: 0x5,
+                subscribe_num: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn sample_continuous() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::<2>::new();
+    kernel.add_driver(&driver);
+
+    let listener = ContinuousListener::default();
+    let mut buffer = [0; 3];
+    share::scope(|handle| {
+        let sampling = Adc::sample_continuous(handle, &listener, 1, &mut buffer, 100).unwrap();
+        assert_eq!(driver.continuous_sampling(), Some((1, 100)));
+        for value in [1, 2, 3] {
+            driver.push_sample(value);
+        }
+        assert_eq!(sampling.wait(), 3);
+
+        // stop() reports the samples taken since the buffer was last full.
+        driver.push_sample(4);
+        assert_eq!(sampling.stop(), Ok(1));
+        assert!(!driver.is_busy());
+    });
+    assert_eq!(buffer, [4, 2, 3]);
+}
+
+#[test]
+fn sample_continuous_drop() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::<1>::new();
+    kernel.add_driver(&driver);
+
+    let listener = ContinuousListener::default();
+    let mut buffer = [0; 4];
+    share::scope(|handle| {
+        let sampling = Adc::sample_continuous(handle, &listener, 0, &mut buffer, 100).unwrap();
+        kernel.take_syscall_log();
+
+        // Dropping the sampling run stops it and returns the buffer.
+        drop(sampling);
+        assert!(!driver.is_busy());
+        assert_eq!(
+            kernel.take_syscall_log(),
+            [
+                SyscallLogEntry::Command {
+                    driver_id: 0x5,
+                    command_id: 5,
+                    argument0: 0,
+                    argument1: 0,
+                },
+                SyscallLogEntry::AllowRw {
+                    driver_num: 0x5,
+                    buffer_num: 0,
+                    len: 0,
+                },
+            ]
+        );
+    });
+}
+
+#[test]
+fn sample_continuous_start_failure() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Adc::<1>::new();
+    kernel.add_driver(&driver);
+
+    let listener = ContinuousListener::default();
+    let mut buffer = [0; 4];
+    share::scope(|handle| {
+        // The buffer is unallowed immediately if sampling can't start.
+        let result = Adc::sample_continuous(handle, &listener, 1, &mut buffer, 100);
+        assert_eq!(result.err(), Some(ErrorCode::Invalid));
+        assert_eq!(
+            kernel.take_syscall_log(),
+            [
+                SyscallLogEntry::AllowRw {
+                    driver_num: 0x5,
+                    buffer_num: 0,
+                    len: 8,
+                },
+                SyscallLogEntry::Subscribe {
+                    driver_num: 0x5,
+                    subscribe_num: 0,
+                },
+                SyscallLogEntry::Command {
+                    driver_id: 0x5,
+                    command_id: 4,
+                    argument0: 1,
+                    argument1: 100,
+                },
+                SyscallLogEntry::AllowRw {
+                    driver_num: 0x5,
+                    buffer_num: 0,
+                    len: 0,
+                },
+            ]
+        );
+    });
+}
@@ -0,0 +1,198 @@
+#![no_std]
+
+use core::marker::PhantomData;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::{StandardResultArg1, Subscribe, UpcallResult};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// AES-128 encryption and decryption using the AES driver.
+///
+/// The key and IV are kept by the `Aes` and shared with the kernel for the
+/// duration of each `crypt` call, so they only need to be set once for any
+/// number of operations.
+///
+/// # Example
+/// ```ignore
+/// use libtock::aes::{Aes, AesMode};
+///
+/// let mut aes = Aes::new();
+/// aes.set_key(&key);
+/// aes.set_iv(&counter);
+/// let len = aes.crypt(b"plaintext", &mut ciphertext, AesMode::Ctr)?;
+/// ```
+pub struct Aes<'k, S: Syscalls> {
+    key: &'k [u8],
+    iv: &'k [u8],
+    _syscalls: PhantomData<S>,
+}
+
+impl<'k, S: Syscalls> Aes<'k, S> {
+    /// Creates an `Aes` with no key or IV set.
+    pub fn new() -> Self {
+        Aes {
+            key: &[],
+            iv: &[],
+            _syscalls: PhantomData,
+        }
+    }
+
+    /// Returns `Ok(())` if the AES driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::driver_exists(DRIVER_NUM)
+    }
+
+    /// Sets the key used by future operations. AES-128 keys are `KEY_LEN`
+    /// bytes long; the length is checked by `crypt`.
+    pub fn set_key(&mut self, key: &'k [u8]) {
+        self.key = key;
+    }
+
+    /// Sets the IV used by future operations: the initial counter block for
+    /// CTR, the initialization vector for CBC, or the nonce for CCM. Its
+    /// length is checked against the mode by `crypt`.
+    pub fn set_iv(&mut self, iv: &'k [u8]) {
+        self.iv = iv;
+    }
+
+    /// Encrypts or decrypts (as selected by `mode`) `input` into the start of
+    /// `output`, blocking until the kernel is done. Returns the number of
+    /// bytes written to `output`.
+    ///
+    /// Returns `Err(ErrorCode::Size)`, without involving the kernel, if the key
+    /// or IV has the wrong length for `mode`, if `output` is shorter than
+    /// `input`, or if `mode` is CBC and `input` is not a whole number of
+    /// blocks.
+    pub fn crypt(
+        &mut self,
+        input: &[u8],
+        output: &mut [u8],
+        mode: AesMode,
+    ) -> Result<usize, ErrorCode> {
+        if self.key.len() != KEY_LEN || self.iv.len() != mode.iv_len() {
+            return Err(ErrorCode::Size);
+        }
+        let partial_block = input.len() % BLOCK_LEN != 0;
+        if output.len() < input.len() || (mode.is_cbc() && partial_block) {
+            return Err(ErrorCode::Size);
+        }
+        let done = StandardResultArg1::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::KEY }>,
+                AllowRo<_, DRIVER_NUM, { allow_ro::IV }>,
+                AllowRo<_, DRIVER_NUM, { allow_ro::SOURCE }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::DEST }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_key, allow_iv, allow_source, allow_dest, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::KEY }>(allow_key, self.key)?;
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::IV }>(allow_iv, self.iv)?;
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::SOURCE }>(allow_source, input)?;
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::DEST }>(allow_dest, output)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(
+                DRIVER_NUM,
+                command::SET_ALGORITHM,
+                mode.algorithm(),
+                mode.encrypting() as usize,
+            )
+            .to_result::<(), ErrorCode>()?;
+            S::command(DRIVER_NUM, command::CRYPT, 0, 0).to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()
+        })
+    }
+}
+
+impl<'k, S: Syscalls> Default for Aes<'k, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The length of an AES-128 key, in bytes.
+pub const KEY_LEN: usize = 16;
+
+/// The AES block size, in bytes.
+pub const BLOCK_LEN: usize = 16;
+
+/// The length of a CCM nonce, in bytes.
+pub const CCM_NONCE_LEN: usize = 13;
+
+/// The block cipher modes the AES driver may support. CTR mode encrypts and
+/// decrypts alike; the other modes need the direction.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AesMode {
+    Ctr,
+    CbcEncrypt,
+    CbcDecrypt,
+    CcmEncrypt,
+    CcmDecrypt,
+}
+
+impl AesMode {
+    /// Returns the length of the IV (or nonce) the mode takes, in bytes.
+    pub fn iv_len(self) -> usize {
+        match self {
+            AesMode::Ctr | AesMode::CbcEncrypt | AesMode::CbcDecrypt => BLOCK_LEN,
+            AesMode::CcmEncrypt | AesMode::CcmDecrypt => CCM_NONCE_LEN,
+        }
+    }
+
+    // The driver's ID for the mode's algorithm.
+    fn algorithm(self) -> usize {
+        match self {
+            AesMode::Ctr => algorithm::AES128_CTR,
+            AesMode::CbcEncrypt | AesMode::CbcDecrypt => algorithm::AES128_CBC,
+            AesMode::CcmEncrypt | AesMode::CcmDecrypt => algorithm::AES128_CCM,
+        }
+    }
+
+    fn is_cbc(self) -> bool {
+        matches!(self, AesMode::CbcEncrypt | AesMode::CbcDecrypt)
+    }
+
+    fn encrypting(self) -> bool {
+        !matches!(self, AesMode::CbcDecrypt | AesMode::CcmDecrypt)
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40006;
+
+// Command IDs
+mod command {
+    pub const SET_ALGORITHM: u32 = 1;
+    pub const CRYPT: u32 = 2;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const KEY: u32 = 0;
+    pub const IV: u32 = 1;
+    pub const SOURCE: u32 = 2;
+}
+
+mod allow_rw {
+    pub const DEST: u32 = 0;
+}
+
+// Algorithm IDs
+mod algorithm {
+    pub const AES128_CTR: usize = 0;
+    pub const AES128_CBC: usize = 1;
+    pub const AES128_CCM: usize = 3;
+}
@@ -0,0 +1,110 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+use crate::AesMode;
+
+type Aes<'k> = super::Aes<'k, fake::Syscalls>;
+
+// The CTR-AES128.Encrypt test vector in NIST SP 800-38A, section F.5.1.
+const KEY: [u8; 16] = [
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+];
+const COUNTER: [u8; 16] = [
+    0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff,
+];
+const PLAINTEXT: [u8; 32] = [
+    0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+    0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf, 0x8e, 0x51,
+];
+const CIPHERTEXT: [u8; 32] = [
+    0x87, 0x4d, 0x61, 0x91, 0xb6, 0x20, 0xe3, 0x26, 0x1b, 0xef, 0x68, 0x64, 0x99, 0x0d, 0xb6, 0xce,
+    0x98, 0x06, 0xf6, 0x6b, 0x79, 0x70, 0xfd, 0xff, 0x86, 0x17, 0x18, 0x7b, 0xb9, 0xff, 0xfd, 0xff,
+];
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Aes::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+    assert_eq!(Aes::exists(), Ok(()));
+}
+
+#[test]
+fn crypt_ctr() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+
+    let mut aes = Aes::new();
+    aes.set_key(&KEY);
+    aes.set_iv(&COUNTER);
+    let mut ciphertext = [0; 40];
+    assert_eq!(aes.crypt(&PLAINTEXT, &mut ciphertext, AesMode::Ctr), Ok(32));
+    assert_eq!(ciphertext[..32], CIPHERTEXT);
+
+    // CTR decryption is the same operation.
+    let mut plaintext = [0; 32];
+    assert_eq!(aes.crypt(&CIPHERTEXT, &mut plaintext, AesMode::Ctr), Ok(32));
+    assert_eq!(plaintext, PLAINTEXT);
+}
+
+#[test]
+fn crypt_invalid_lengths() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+
+    let mut aes = Aes::new();
+    let mut output = [0; 32];
+    // No key set.
+    aes.set_iv(&COUNTER);
+    assert_eq!(
+        aes.crypt(&PLAINTEXT, &mut output, AesMode::Ctr),
+        Err(ErrorCode::Size)
+    );
+    aes.set_key(&KEY[..15]);
+    assert_eq!(
+        aes.crypt(&PLAINTEXT, &mut output, AesMode::Ctr),
+        Err(ErrorCode::Size)
+    );
+    aes.set_key(&KEY);
+    // CCM takes a 13-byte nonce rather than a block-sized IV.
+    assert_eq!(
+        aes.crypt(&PLAINTEXT, &mut output, AesMode::CcmEncrypt),
+        Err(ErrorCode::Size)
+    );
+    // CBC only works on whole blocks.
+    assert_eq!(
+        aes.crypt(&PLAINTEXT[..20], &mut output, AesMode::CbcEncrypt),
+        Err(ErrorCode::Size)
+    );
+    assert_eq!(
+        aes.crypt(&PLAINTEXT, &mut output[..31], AesMode::Ctr),
+        Err(ErrorCode::Size)
+    );
+    // None of these reached the kernel.
+    assert_eq!(kernel.take_syscall_log(), []);
+}
+
+#[test]
+fn crypt_unsupported_mode() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Aes::new();
+    kernel.add_driver(&driver);
+
+    // fake::Aes only supports CTR.
+    let mut aes = Aes::new();
+    aes.set_key(&KEY);
+    aes.set_iv(&COUNTER);
+    let mut output = [0; 32];
+    assert_eq!(
+        aes.crypt(&PLAINTEXT, &mut output, AesMode::CbcDecrypt),
+        Err(ErrorCode::NoSupport)
+    );
+}
@@ -1,18 +1,28 @@
 #![no_std]
 
 use core::cell::Cell;
+use core::convert::TryFrom;
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use core::task::{Context, Poll};
+use core::time::Duration;
 use libtock_platform as platform;
 use libtock_platform::share;
-use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+use libtock_platform::share::Handle;
+use libtock_platform::subscribe::OneId;
+use libtock_platform::{DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall};
 
 /// The alarm driver
 ///
 /// # Example
 /// ```ignore
+/// use core::time::Duration;
 /// use libtock2::Alarm;
 ///
 /// // Wait for timeout
-/// Alarm::sleep(Alarm::Milliseconds(2500));
+/// Alarm::sleep(Duration::from_millis(2500));
 /// ```
 
 pub struct Alarm<S: Syscalls, C: platform::subscribe::Config = DefaultConfig>(S, C);
@@ -25,7 +35,7 @@ pub trait Convert {
     fn to_ticks(self, freq: Hz) -> Ticks;
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct Ticks(pub u32);
 
 impl Convert for Ticks {
@@ -34,26 +44,35 @@ impl Convert for Ticks {
     }
 }
 
+/// u128::div_ceil is still unstable.
+fn div_ceil(a: u128, other: u128) -> u128 {
+    let d = a / other;
+    let m = a % other;
+    if m == 0 {
+        d
+    } else {
+        d + 1
+    }
+}
+
 #[derive(Copy, Clone)]
 pub struct Milliseconds(pub u32);
 
 impl Convert for Milliseconds {
     fn to_ticks(self, freq: Hz) -> Ticks {
-        // Saturating multiplication will top out at about 1 hour at 1MHz.
-        // It's large enough for an alarm, and much simpler than failing
-        // or losing precision for short sleeps.
-
-        /// u32::div_ceil is still unstable.
-        fn div_ceil(a: u32, other: u32) -> u32 {
-            let d = a / other;
-            let m = a % other;
-            if m == 0 {
-                d
-            } else {
-                d + 1
-            }
-        }
-        Ticks(div_ceil(self.0.saturating_mul(freq.0), 1000))
+        // The product can't overflow a u128. Alarms too long to count in a
+        // u32 saturate, which at 1MHz happens past about 71 minutes.
+        let ticks = div_ceil(self.0 as u128 * freq.0 as u128, 1000);
+        Ticks(u32::try_from(ticks).unwrap_or(u32::MAX))
+    }
+}
+
+impl Convert for Duration {
+    fn to_ticks(self, freq: Hz) -> Ticks {
+        // Rounding up means any non-zero duration lasts at least one tick.
+        // The product can't overflow: it is below 2^94 * 2^32.
+        let ticks = div_ceil(self.as_nanos() * freq.0 as u128, 1_000_000_000);
+        Ticks(u32::try_from(ticks).unwrap_or(u32::MAX))
     }
 }
 
@@ -67,14 +86,38 @@ impl<S: Syscalls, C: platform::subscribe::Config> Alarm<S, C> {
         S::command(DRIVER_NUM, command::DRIVER_CHECK, 0, 0).to_result()
     }
 
+    /// Same as [`frequency`](Self::frequency).
     pub fn get_frequency() -> Result<Hz, ErrorCode> {
+        Self::frequency()
+    }
+
+    /// Returns the frequency at which the alarm's counter ticks.
+    pub fn frequency() -> Result<Hz, ErrorCode> {
         S::command(DRIVER_NUM, command::FREQUENCY, 0, 0)
             .to_result()
             .map(Hz)
     }
 
+    /// Returns the current value of the alarm's tick counter.
+    pub fn get_time() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::TIME, 0, 0).to_result()
+    }
+
+    /// Returns the current value of the alarm's tick counter, for use with
+    /// `elapsed_since` and `set_alarm_at`.
+    pub fn now() -> Result<Ticks, ErrorCode> {
+        Self::get_time().map(Ticks)
+    }
+
+    /// Returns the number of ticks since the tick count `start`, as returned
+    /// by `now`. This is correct across the counter wrapping around, as long
+    /// as less than a full counter period has passed.
+    pub fn elapsed_since(start: Ticks) -> Result<Ticks, ErrorCode> {
+        Self::now().map(|now| Ticks(now.0.wrapping_sub(start.0)))
+    }
+
     pub fn sleep_for<T: Convert>(time: T) -> Result<(), ErrorCode> {
-        let freq = Self::get_frequency()?;
+        let freq = Self::frequency()?;
         let ticks = time.to_ticks(freq);
 
         let called: Cell<Option<(u32, u32)>> = Cell::new(None);
@@ -85,14 +128,234 @@ impl<S: Syscalls, C: platform::subscribe::Config> Alarm<S, C> {
                 .to_result()
                 .map(|_when: u32| ())?;
 
-            loop {
-                S::yield_wait();
-                if let Some((_when, _ref)) = called.get() {
-                    return Ok(());
+            S::yield_wait_for(|| called.get().is_some());
+            Ok(())
+        })
+    }
+
+    /// Blocks until `duration` has passed. Durations that don't correspond to
+    /// a whole number of ticks are rounded up.
+    pub fn sleep(duration: Duration) -> Result<(), ErrorCode> {
+        Self::sleep_for(duration)
+    }
+
+    /// Returns a future that completes once `time` has passed, counted from
+    /// when this is called. Drive it with an executor such as
+    /// `libtock_runtime::block_on`.
+    ///
+    /// Any number of these futures may be pending at once; they share the
+    /// process's alarm between them. That alarm must not be used by anything
+    /// else (such as `sleep_for`) while they are pending.
+    ///
+    /// # Example
+    /// ```ignore
+    /// libtock_runtime::block_on(Alarm::sleep_future(Milliseconds(500)))?;
+    /// ```
+    pub fn sleep_future<T: Convert>(time: T) -> Sleep<S, C> {
+        let state = Self::frequency().and_then(|freq| {
+            let ticks = time.to_ticks(freq);
+            Self::get_time().map(|start| (start, ticks.0))
+        });
+        Sleep {
+            state,
+            waiting: Cell::new(false),
+            _syscalls: PhantomData,
+        }
+    }
+
+    /// Arms the alarm to fire `ticks` from now and returns without waiting.
+    ///
+    /// The caller is responsible for subscribing to [`subscribe::CALLBACK`]
+    /// beforehand. Returns the tick count at which the alarm will fire.
+    pub fn set_relative(ticks: Ticks) -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_RELATIVE, ticks.0 as usize, 0).to_result()
+    }
+
+    /// Arms the alarm to fire `dt` ticks after the tick count `reference`,
+    /// calling `listener` when it does. This matches the kernel's deadline
+    /// model: a periodic task can add its period to the previous deadline
+    /// without accumulating drift from the time spent between alarms.
+    ///
+    /// If the deadline has already passed, the alarm fires immediately,
+    /// rather than when the tick counter next wraps around to it. Returns the
+    /// tick count at which the alarm will fire.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let listener = AlarmListener(|now| {
+    ///     // The alarm fired at tick count `now`.
+    /// });
+    /// share::scope(|subscribe| {
+    ///     let deadline = Alarm::set_alarm_at(reference, period, &listener, subscribe)?;
+    ///     // yield
+    /// });
+    /// ```
+    pub fn set_alarm_at<'share, F: Fn(Ticks)>(
+        reference: Ticks,
+        dt: Ticks,
+        listener: &'share AlarmListener<F>,
+        subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::CALLBACK }>>,
+    ) -> Result<u32, ErrorCode> {
+        S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::CALLBACK }>(subscribe, listener)?;
+        Self::arm_at(reference, dt)
+    }
+
+    /// Blocks until the tick counter reaches `deadline`. A deadline up to half
+    /// a counter period behind the current tick count is taken to have
+    /// already passed, in which case this returns immediately.
+    pub fn sleep_until(deadline: Ticks) -> Result<(), ErrorCode> {
+        let now = Self::get_time()?;
+        let remaining = deadline.0.wrapping_sub(now);
+        // Past deadlines wrap around to the upper half of the counter.
+        if remaining == 0 || remaining > u32::MAX / 2 {
+            return Ok(());
+        }
+        let called: Cell<Option<(u32, u32)>> = Cell::new(None);
+        share::scope(|subscribe| {
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::CALLBACK }>(subscribe, &called)?;
+            Self::arm_at(Ticks(now), Ticks(remaining))?;
+            S::yield_wait_for(|| called.get().is_some());
+            Ok(())
+        })
+    }
+
+    // Arms the alarm for `dt` ticks after `reference`, or immediately if that
+    // has already passed. The kernel's counter wraps, so whether the deadline
+    // has passed depends on how far the current tick count is past
+    // `reference`, not on comparing it to `reference + dt`.
+    fn arm_at(reference: Ticks, dt: Ticks) -> Result<u32, ErrorCode> {
+        let now = Self::get_time()?;
+        if now.wrapping_sub(reference.0) >= dt.0 {
+            return Self::set_relative(Ticks(0));
+        }
+        S::command(
+            DRIVER_NUM,
+            command::SET_ABSOLUTE,
+            reference.0 as usize,
+            dt.0 as usize,
+        )
+        .to_result()
+    }
+
+    /// Cancels the outstanding alarm, if any.
+    pub fn stop() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::STOP, 0, 0).to_result()
+    }
+}
+
+/// A future that completes once a duration has passed, returned by
+/// `Alarm::sleep_future`.
+pub struct Sleep<S: Syscalls, C: platform::subscribe::Config = DefaultConfig> {
+    // The tick count at which the sleep started and its length in ticks, or
+    // the error encountered while starting it.
+    state: Result<(u32, u32), ErrorCode>,
+    // Whether this is counted in WAITING.
+    waiting: Cell<bool>,
+    _syscalls: PhantomData<(S, C)>,
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> Sleep<S, C> {
+    fn finish(&self, result: Result<(), ErrorCode>) -> Poll<Result<(), ErrorCode>> {
+        if self.waiting.replace(false) {
+            WAITING.fetch_sub(1, Ordering::Relaxed);
+        }
+        Poll::Ready(result)
+    }
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> Future for Sleep<S, C> {
+    type Output = Result<(), ErrorCode>;
+
+    fn poll(self: Pin<&mut Self>, _context: &mut Context) -> Poll<Self::Output> {
+        let (start, ticks) = match self.state {
+            Ok(state) => state,
+            Err(error) => return self.finish(Err(error)),
+        };
+        let now = match Alarm::<S, C>::get_time() {
+            Ok(now) => now,
+            Err(error) => return self.finish(Err(error)),
+        };
+        let elapsed = now.wrapping_sub(start);
+        if elapsed >= ticks {
+            return self.finish(Ok(()));
+        }
+        match arm::<S, C>(now, ticks - elapsed) {
+            Ok(()) => {
+                if !self.waiting.replace(true) {
+                    WAITING.fetch_add(1, Ordering::Relaxed);
                 }
+                Poll::Pending
             }
-        })
+            Err(error) => self.finish(Err(error)),
+        }
+    }
+}
+
+impl<S: Syscalls, C: platform::subscribe::Config> Drop for Sleep<S, C> {
+    // If the last pending `Sleep` is dropped before its deadline, nothing is
+    // left to wait for the alarm, so it is cancelled. Otherwise ARMED would
+    // stay set until the upcall arrives, which it never does if something
+    // else subscribes to the alarm in the meantime.
+    fn drop(&mut self) {
+        if !self.waiting.get() || WAITING.fetch_sub(1, Ordering::Relaxed) != 1 {
+            return;
+        }
+        if ARMED.load(Ordering::Relaxed) {
+            let _ = Alarm::<S, C>::stop();
+            ARMED.store(false, Ordering::Relaxed);
+        }
+    }
+}
+
+/// A wrapper around a closure to be registered and called when the alarm
+/// fires. The closure receives the tick count at which the alarm fired.
+pub struct AlarmListener<F: Fn(Ticks)>(pub F);
+
+impl<F: Fn(Ticks)> Upcall<OneId<DRIVER_NUM, { subscribe::CALLBACK }>> for AlarmListener<F> {
+    fn upcall(&self, now: usize, _expiration: usize, _: usize) {
+        self.0(Ticks(now as u32))
+    }
+}
+
+// A process only has one alarm, so all pending `Sleep`s share it: it is kept
+// armed for the earliest of their deadlines. Its upcall only needs to wake the
+// executor, after which every `Sleep` is polled again and re-checks the time.
+//
+// ARMED is cleared by the upcall, so while it is set the upcall is still
+// registered and ARMED_AT holds the tick count the alarm will fire at.
+static ARMED: AtomicBool = AtomicBool::new(false);
+static ARMED_AT: AtomicU32 = AtomicU32::new(0);
+// The number of `Sleep`s that have returned `Pending` and not yet completed.
+static WAITING: AtomicU32 = AtomicU32::new(0);
+
+struct Wakeup;
+
+impl Upcall<OneId<DRIVER_NUM, { subscribe::CALLBACK }>> for Wakeup {
+    fn upcall(&self, _now: usize, _expiration: usize, _: usize) {
+        ARMED.store(false, Ordering::Relaxed);
+    }
+}
+
+static WAKEUP: Wakeup = Wakeup;
+
+// Makes sure the alarm fires no later than `remaining` ticks after `now`.
+fn arm<S: Syscalls, C: platform::subscribe::Config>(
+    now: u32,
+    remaining: u32,
+) -> Result<(), ErrorCode> {
+    if ARMED.load(Ordering::Relaxed) {
+        if ARMED_AT.load(Ordering::Relaxed).wrapping_sub(now) <= remaining {
+            return Ok(());
+        }
+    } else {
+        // Subscribing discards queued upcalls, which is only safe to do when
+        // the alarm's previous upcall has already been delivered.
+        S::subscribe_static::<_, _, C, DRIVER_NUM, { subscribe::CALLBACK }>(&WAKEUP)?;
     }
+    let armed_at = Alarm::<S, C>::set_relative(Ticks(remaining))?;
+    ARMED_AT.store(armed_at, Ordering::Relaxed);
+    ARMED.store(true, Ordering::Relaxed);
+    Ok(())
 }
 
 #[cfg(test)]
@@ -102,7 +365,7 @@ mod tests;
 // Driver number and command IDs
 // -----------------------------------------------------------------------------
 
-const DRIVER_NUM: u32 = 0;
+pub const DRIVER_NUM: u32 = 0;
 
 // Command IDs
 #[allow(unused)]
@@ -117,6 +380,6 @@ mod command {
 }
 
 #[allow(unused)]
-mod subscribe {
+pub mod subscribe {
     pub const CALLBACK: u32 = 0;
 }
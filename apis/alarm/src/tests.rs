@@ -1,6 +1,14 @@
-use libtock_unittest::fake;
+extern crate std;
 
-use crate::{Hz, Milliseconds, Ticks};
+use core::cell::Cell;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use core::time::Duration;
+use libtock_platform::{share, Syscalls};
+use libtock_unittest::{fake, SyscallLogEntry};
+
+use crate::{AlarmListener, Convert, Hz, Milliseconds, Ticks, DRIVER_NUM};
 
 type Alarm = crate::Alarm<fake::Syscalls>;
 
@@ -22,3 +30,288 @@ fn sleep() {
     assert_eq!(Alarm::sleep_for(Ticks(1000)), Ok(()));
     assert_eq!(Alarm::sleep_for(Milliseconds(1000)), Ok(()));
 }
+
+#[test]
+fn elapsed_since() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+
+    driver.set_now(u32::MAX - 2);
+    let start = Alarm::now().unwrap();
+    assert_eq!(start, Ticks(u32::MAX - 2));
+    assert_eq!(Alarm::elapsed_since(start), Ok(Ticks(0)));
+    // The counter wraps around between start and now.
+    driver.set_now(4);
+    assert_eq!(Alarm::now(), Ok(Ticks(4)));
+    assert_eq!(Alarm::elapsed_since(start), Ok(Ticks(7)));
+}
+
+#[test]
+fn duration_rounding() {
+    let freq = Hz(1000);
+    assert_eq!(Duration::ZERO.to_ticks(freq).0, 0);
+    // Sub-tick durations still last a tick.
+    assert_eq!(Duration::from_nanos(1).to_ticks(freq).0, 1);
+    assert_eq!(Duration::from_micros(1).to_ticks(freq).0, 1);
+    assert_eq!(Duration::from_millis(3).to_ticks(freq).0, 3);
+    assert_eq!(Duration::from_micros(1500).to_ticks(freq).0, 2);
+    assert_eq!(Duration::from_secs(2).to_ticks(Hz(32768)).0, 65536);
+    assert_eq!(Duration::MAX.to_ticks(freq).0, u32::MAX);
+}
+
+#[test]
+fn milliseconds_rounding() {
+    assert_eq!(Milliseconds(0).to_ticks(Hz(1000)).0, 0);
+    assert_eq!(Milliseconds(1).to_ticks(Hz(32768)).0, 33);
+    // The product of the milliseconds and the frequency doesn't fit in a u32,
+    // but the number of ticks does.
+    assert_eq!(
+        Milliseconds(4_000_000).to_ticks(Hz(1_000_000)).0,
+        4_000_000_000
+    );
+    assert_eq!(Milliseconds(5_000_000).to_ticks(Hz(1_000_000)).0, u32::MAX);
+}
+
+#[test]
+fn sleep_duration() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Alarm::frequency(), Ok(Hz(1000)));
+    kernel.take_syscall_log();
+    assert_eq!(Alarm::sleep(Duration::from_micros(1)), Ok(()));
+    assert_eq!(Alarm::sleep(Duration::from_micros(2500)), Ok(()));
+    let armed = kernel
+        .take_syscall_log()
+        .into_iter()
+        .filter_map(|entry| match entry {
+            SyscallLogEntry::Command {
+                driver_id: DRIVER_NUM,
+                command_id: crate::command::SET_RELATIVE,
+                argument0,
+                ..
+            } => Some(argument0),
+            _ => None,
+        });
+    assert!(armed.eq([1, 3]));
+}
+
+// Runs two futures concurrently, recording the order in which they complete.
+struct Join<A: Future + Unpin, B: Future + Unpin> {
+    a: A,
+    b: B,
+    a_done: Option<(u32, A::Output)>,
+    b_done: Option<(u32, B::Output)>,
+    completed: u32,
+}
+
+impl<A: Future + Unpin, B: Future + Unpin> Join<A, B> {
+    fn new(a: A, b: B) -> Self {
+        Join {
+            a,
+            b,
+            a_done: None,
+            b_done: None,
+            completed: 0,
+        }
+    }
+}
+
+impl<A: Future + Unpin, B: Future + Unpin> Future for Join<A, B>
+where
+    A::Output: Unpin,
+    B::Output: Unpin,
+{
+    // Each future's output, along with the position in which it completed.
+    type Output = ((u32, A::Output), (u32, B::Output));
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<Self::Output> {
+        let this = &mut *self;
+        if this.a_done.is_none() {
+            if let Poll::Ready(output) = Pin::new(&mut this.a).poll(context) {
+                this.completed += 1;
+                this.a_done = Some((this.completed, output));
+            }
+        }
+        if this.b_done.is_none() {
+            if let Poll::Ready(output) = Pin::new(&mut this.b).poll(context) {
+                this.completed += 1;
+                this.b_done = Some((this.completed, output));
+            }
+        }
+        if this.a_done.is_some() && this.b_done.is_some() {
+            return Poll::Ready((this.a_done.take().unwrap(), this.b_done.take().unwrap()));
+        }
+        Poll::Pending
+    }
+}
+
+// Polls a future once, returning the result of that poll.
+struct PollOnce<'f, F: Future + Unpin>(&'f mut F);
+
+impl<'f, F: Future + Unpin> Future for PollOnce<'f, F> {
+    type Output = Poll<F::Output>;
+
+    fn poll(mut self: Pin<&mut Self>, context: &mut Context) -> Poll<Self::Output> {
+        Poll::Ready(Pin::new(&mut *self.0).poll(context))
+    }
+}
+
+// This is the only test that uses sleep_future, as the futures share
+// process-wide state that concurrent tests would interfere with.
+#[test]
+fn sleep_futures() {
+    overlapping_sleep_futures();
+    dropped_sleep_future();
+}
+
+fn overlapping_sleep_futures() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+
+    let short = Alarm::sleep_future(Milliseconds(10));
+    let long = Alarm::sleep_future(Ticks(30));
+    kernel.take_syscall_log();
+    assert_eq!(
+        libtock_platform::block_on::<fake::Syscalls, _>(Join::new(short, long)),
+        ((1, Ok(())), (2, Ok(())))
+    );
+
+    // The alarm was first armed for the short sleep, then for what was left of
+    // the long one. The fake alarm fires as soon as it is set.
+    let armed = kernel
+        .take_syscall_log()
+        .into_iter()
+        .filter_map(|entry| match entry {
+            SyscallLogEntry::Command {
+                driver_id: DRIVER_NUM,
+                command_id: crate::command::SET_RELATIVE,
+                argument0,
+                ..
+            } => Some(argument0),
+            _ => None,
+        });
+    assert!(armed.eq([10, 20]));
+}
+
+// Returns the commands that armed the alarm since the syscall log was last
+// taken, as (command ID, argument0, argument1).
+fn arm_commands(kernel: &fake::Kernel) -> std::vec::Vec<(u32, usize, usize)> {
+    kernel
+        .take_syscall_log()
+        .into_iter()
+        .filter_map(|entry| match entry {
+            SyscallLogEntry::Command {
+                driver_id: DRIVER_NUM,
+                command_id:
+                    command_id @ (crate::command::SET_RELATIVE | crate::command::SET_ABSOLUTE),
+                argument0,
+                argument1,
+            } => Some((command_id, argument0, argument1)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn set_alarm_at_wraparound() {
+    use crate::command::{SET_ABSOLUTE, SET_RELATIVE};
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+
+    let fired = Cell::new(None);
+    let listener = AlarmListener(|now| fired.set(Some(now)));
+
+    // The deadline is past the point where the counter wraps, so it is still
+    // in the future even though it is numerically below the current time.
+    driver.set_now(u32::MAX - 5);
+    share::scope(|subscribe| {
+        assert_eq!(
+            Alarm::set_alarm_at(Ticks(u32::MAX - 10), Ticks(20), &listener, subscribe),
+            Ok(9)
+        );
+        fake::Syscalls::yield_wait();
+    });
+    assert_eq!(fired.take(), Some(Ticks(9)));
+    assert_eq!(
+        arm_commands(&kernel),
+        [(SET_ABSOLUTE, u32::MAX as usize - 10, 20)]
+    );
+
+    // This deadline has already passed, so the alarm fires immediately rather
+    // than once the counter wraps around to it.
+    driver.set_now(u32::MAX - 5);
+    share::scope(|subscribe| {
+        assert_eq!(
+            Alarm::set_alarm_at(Ticks(u32::MAX - 30), Ticks(20), &listener, subscribe),
+            Ok(u32::MAX - 5)
+        );
+        fake::Syscalls::yield_wait();
+    });
+    assert_eq!(fired.take(), Some(Ticks(u32::MAX - 5)));
+    assert_eq!(arm_commands(&kernel), [(SET_RELATIVE, 0, 0)]);
+}
+
+#[test]
+fn sleep_until_wraparound() {
+    use crate::command::SET_ABSOLUTE;
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+
+    driver.set_now(u32::MAX - 5);
+    assert_eq!(Alarm::sleep_until(Ticks(9)), Ok(()));
+    assert_eq!(
+        arm_commands(&kernel),
+        [(SET_ABSOLUTE, u32::MAX as usize - 5, 15)]
+    );
+    assert_eq!(Alarm::get_time(), Ok(9));
+
+    // Deadlines that have passed, including ones before the counter wrapped,
+    // return without arming the alarm.
+    assert_eq!(Alarm::sleep_until(Ticks(u32::MAX - 10)), Ok(()));
+    assert_eq!(Alarm::sleep_until(Ticks(9)), Ok(()));
+    assert_eq!(arm_commands(&kernel), []);
+}
+
+// Dropping a pending Sleep cancels the alarm, and a later Sleep arms it again.
+fn dropped_sleep_future() {
+    use crate::command::{SET_RELATIVE, STOP};
+    let kernel = fake::Kernel::new();
+    let driver = fake::Alarm::with_virtual_clock(1000);
+    kernel.add_driver(&driver);
+
+    let mut dropped = Alarm::sleep_future(Ticks(10));
+    assert_eq!(
+        libtock_platform::block_on::<fake::Syscalls, _>(PollOnce(&mut dropped)),
+        Poll::Pending
+    );
+    kernel.take_syscall_log();
+    drop(dropped);
+    assert!(kernel
+        .take_syscall_log()
+        .contains(&SyscallLogEntry::Command {
+            driver_id: DRIVER_NUM,
+            command_id: STOP,
+            argument0: 0,
+            argument1: 0,
+        }));
+
+    // The new deadline is later than the cancelled one, so this would not arm
+    // the alarm if the dropped Sleep had left it marked as armed.
+    let mut sleep = Alarm::sleep_future(Ticks(20));
+    assert_eq!(
+        libtock_platform::block_on::<fake::Syscalls, _>(PollOnce(&mut sleep)),
+        Poll::Pending
+    );
+    assert_eq!(arm_commands(&kernel), [(SET_RELATIVE, 20, 0)]);
+    driver.advance(20);
+    assert_eq!(
+        libtock_platform::block_on::<fake::Syscalls, _>(sleep),
+        Ok(())
+    );
+}
@@ -0,0 +1,39 @@
+#![no_std]
+
+libtock_sensor_common::single_value_sensor! {
+    /// The ambient light sensor driver. Light intensity is reported in lux.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use libtock::ambient_light::AmbientLight;
+    ///
+    /// if AmbientLight::exists().is_ok() {
+    ///     let lux = AmbientLight::read_intensity()?;
+    /// }
+    /// ```
+    pub struct AmbientLight;
+    driver_num: DRIVER_NUM;
+    subscribe_num: subscribe::READING;
+    command_num: command::READ_INTENSITY;
+    /// Reads the light intensity, in lux, blocking until the reading is
+    /// available.
+    fn read_intensity() -> u32;
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60002;
+
+// Command IDs
+mod command {
+    pub const READ_INTENSITY: u32 = 1;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
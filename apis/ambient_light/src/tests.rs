@@ -0,0 +1,49 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::{fake, SyscallLogEntry};
+
+type AmbientLight = super::AmbientLight<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(AmbientLight::exists(), Err(ErrorCode::NoDevice));
+    assert_eq!(AmbientLight::read_intensity(), Err(ErrorCode::NoMem));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AmbientLight::new();
+    kernel.add_driver(&driver);
+    assert_eq!(AmbientLight::exists(), Ok(()));
+}
+
+#[test]
+fn read_intensity() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AmbientLight::new();
+    kernel.add_driver(&driver);
+
+    driver.set_value(320);
+    assert_eq!(AmbientLight::read_intensity(), Ok(320));
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Subscribe {
+                driver_num: 0x60002,
+                subscribe_num: 0,
+            },
+            SyscallLogEntry::Command {
+                driver_id: 0x60002,
+                command_id: 1,
+                argument0: 0,
+                argument1: 0,
+            },
+            SyscallLogEntry::YieldWait,
+            SyscallLogEntry::Subscribe {
+                driver_num: 0x60002,
+                subscribe_num: 0,
+            },
+        ]
+    );
+}
@@ -0,0 +1,134 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+use libtock_platform::share;
+use libtock_platform::subscribe::OneId;
+use libtock_platform::{DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall};
+
+/// The analog comparator driver. Each channel compares two input voltages,
+/// and reports whether the first is higher.
+///
+/// # Example
+/// ```ignore
+/// use libtock::analog_comparator::{AnalogComparator, InterruptListener};
+///
+/// let higher = AnalogComparator::comparison(0)?;
+///
+/// // Wait until the comparison on channel 0 flips
+/// let listener = InterruptListener::default();
+/// share::scope(|subscribe| {
+///     let interrupts = AnalogComparator::start_interrupts(subscribe, &listener, 0)?;
+///     let channel = interrupts.wait();
+///     Ok(())
+/// })?;
+/// ```
+pub struct AnalogComparator<S: Syscalls>(S);
+
+impl<S: Syscalls> AnalogComparator<S> {
+    /// Returns `Ok(())` if the analog comparator driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        Self::count().map(|_| ())
+    }
+
+    /// Returns the number of comparator channels.
+    pub fn count() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::COUNT, 0, 0).to_result()
+    }
+
+    /// Returns true if the first input of `channel` is at a higher voltage
+    /// than the second.
+    pub fn comparison(channel: u32) -> Result<bool, ErrorCode> {
+        S::command(DRIVER_NUM, command::COMPARISON, channel as usize, 0)
+            .to_result::<u32, ErrorCode>()
+            .map(|higher| higher != 0)
+    }
+
+    /// Enables interrupts on `channel`, which fire whenever its comparison
+    /// flips, and delivers them to `listener`. Interrupts stay enabled until
+    /// the returned `Interrupts` is dropped.
+    pub fn start_interrupts<'share>(
+        subscribe: share::Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::INTERRUPT }>>,
+        listener: &'share InterruptListener,
+        channel: u32,
+    ) -> Result<Interrupts<'share, S>, ErrorCode> {
+        listener.channel.set(None);
+        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::INTERRUPT }>(
+            subscribe, listener,
+        )?;
+        S::command(DRIVER_NUM, command::START_INTERRUPTS, channel as usize, 0)
+            .to_result::<(), ErrorCode>()?;
+        Ok(Interrupts {
+            listener,
+            channel,
+            _syscalls: PhantomData,
+        })
+    }
+
+    /// Disables interrupts on `channel`.
+    pub fn stop_interrupts(channel: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::STOP_INTERRUPTS, channel as usize, 0).to_result()
+    }
+}
+
+/// Receives the interrupts enabled by `AnalogComparator::start_interrupts`.
+#[derive(Default)]
+pub struct InterruptListener {
+    channel: Cell<Option<u32>>,
+}
+
+impl Upcall<OneId<DRIVER_NUM, { subscribe::INTERRUPT }>> for InterruptListener {
+    fn upcall(&self, channel: usize, _arg1: usize, _arg2: usize) {
+        self.channel.set(Some(channel as u32));
+    }
+}
+
+/// Interrupts enabled on a comparator channel, returned by
+/// `AnalogComparator::start_interrupts`. Dropping it disables them.
+pub struct Interrupts<'share, S: Syscalls> {
+    listener: &'share InterruptListener,
+    channel: u32,
+    _syscalls: PhantomData<S>,
+}
+
+impl<'share, S: Syscalls> Interrupts<'share, S> {
+    /// Returns the channel that triggered if an interrupt has fired since the
+    /// last call to `triggered` or `wait`. This does not yield, so interrupts
+    /// only arrive during the caller's own `yield_wait` calls.
+    pub fn triggered(&self) -> Option<u32> {
+        self.listener.channel.take()
+    }
+
+    /// Yields until an interrupt fires, and returns the channel that
+    /// triggered it.
+    pub fn wait(&self) -> u32 {
+        S::yield_wait_for_result(|| self.triggered())
+    }
+}
+
+impl<'share, S: Syscalls> Drop for Interrupts<'share, S> {
+    fn drop(&mut self) {
+        let _ = AnalogComparator::<S>::stop_interrupts(self.channel);
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x7;
+
+// Command IDs
+mod command {
+    pub const COUNT: u32 = 0;
+    pub const COMPARISON: u32 = 1;
+    pub const START_INTERRUPTS: u32 = 2;
+    pub const STOP_INTERRUPTS: u32 = 3;
+}
+
+mod subscribe {
+    pub const INTERRUPT: u32 = 0;
+}
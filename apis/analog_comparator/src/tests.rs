@@ -0,0 +1,73 @@
+use super::InterruptListener;
+use libtock_platform::{share, ErrorCode, Syscalls};
+use libtock_unittest::fake;
+
+type AnalogComparator = super::AnalogComparator<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(AnalogComparator::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn comparison() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AnalogComparator::new(2);
+    kernel.add_driver(&driver);
+
+    assert_eq!(AnalogComparator::exists(), Ok(()));
+    assert_eq!(AnalogComparator::count(), Ok(2));
+    assert_eq!(AnalogComparator::comparison(1), Ok(false));
+    driver.set_comparison(1, true);
+    assert_eq!(AnalogComparator::comparison(1), Ok(true));
+    assert_eq!(AnalogComparator::comparison(0), Ok(false));
+    assert_eq!(AnalogComparator::comparison(2), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn interrupts() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AnalogComparator::new(2);
+    kernel.add_driver(&driver);
+
+    let listener = InterruptListener::default();
+    share::scope(|subscribe| {
+        let interrupts = AnalogComparator::start_interrupts(subscribe, &listener, 1).unwrap();
+        assert!(driver.interrupts_enabled(1));
+        fake::Syscalls::yield_no_wait();
+        assert_eq!(interrupts.triggered(), None);
+
+        // Only flips on the enabled channel fire.
+        driver.set_comparison(0, true);
+        fake::Syscalls::yield_no_wait();
+        assert_eq!(interrupts.triggered(), None);
+
+        driver.set_comparison(1, true);
+        assert_eq!(interrupts.wait(), 1);
+        driver.set_comparison(1, false);
+        assert_eq!(interrupts.wait(), 1);
+
+        drop(interrupts);
+        assert!(!driver.interrupts_enabled(1));
+    });
+}
+
+#[test]
+fn start_interrupts_invalid_channel() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::AnalogComparator::new(1);
+    kernel.add_driver(&driver);
+
+    let listener = InterruptListener::default();
+    share::scope(|subscribe| {
+        assert!(matches!(
+            AnalogComparator::start_interrupts(subscribe, &listener, 1),
+            Err(ErrorCode::Invalid)
+        ));
+    });
+    assert_eq!(
+        AnalogComparator::stop_interrupts(1),
+        Err(ErrorCode::Invalid)
+    );
+}
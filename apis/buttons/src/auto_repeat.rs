@@ -0,0 +1,151 @@
+use core::cell::Cell;
+use core::marker::PhantomData;
+
+use libtock_alarm::{Convert, Milliseconds, Ticks};
+use libtock_platform::{
+    share::Handle, subscribe::OneId, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
+};
+
+use crate::{ButtonState, Buttons, DRIVER_NUM};
+
+type Alarm<S> = libtock_alarm::Alarm<S>;
+
+const ALARM_DRIVER_NUM: u32 = libtock_alarm::DRIVER_NUM;
+const ALARM_CALLBACK: u32 = libtock_alarm::subscribe::CALLBACK;
+
+/// The subscriptions needed by [`AutoRepeat::start`]: the buttons event
+/// upcall and the alarm upcall.
+pub type AutoRepeatSubscribe<'share, S> = (
+    Subscribe<'share, S, DRIVER_NUM, 0>,
+    Subscribe<'share, S, ALARM_DRIVER_NUM, ALARM_CALLBACK>,
+);
+
+/// Keyboard-style auto-repeat for a single button.
+///
+/// `on_press` is called once when the button goes down. If the button is
+/// still held after `delay`, it is called again, and then once every `rate`
+/// until the button is released.
+///
+/// `AutoRepeat` takes over both the buttons upcall and the alarm upcall while
+/// it is running, so it cannot be combined with a [`ButtonListener`] or with
+/// other users of the alarm.
+///
+/// # Example
+/// ```ignore
+/// let repeat = AutoRepeat::new(0, Milliseconds(500), Milliseconds(100), |_button| {
+///     // move the menu cursor
+/// });
+///
+/// share::scope(|subscribe| {
+///     let _guard = repeat.start(subscribe)?;
+///     loop {
+///         TockSyscalls::yield_wait();
+///     }
+/// });
+/// ```
+///
+/// [`ButtonListener`]: crate::ButtonListener
+pub struct AutoRepeat<S: Syscalls, F: Fn(u32)> {
+    button: u32,
+    delay: Milliseconds,
+    rate: Milliseconds,
+    on_press: F,
+
+    // Converted from `delay` and `rate` by `start`, once the alarm frequency
+    // is known.
+    delay_ticks: Cell<Ticks>,
+    rate_ticks: Cell<Ticks>,
+
+    // True between a press and the matching release. The repeat alarm is only
+    // armed while this is set.
+    held: Cell<bool>,
+
+    _syscalls: PhantomData<S>,
+}
+
+impl<S: Syscalls, F: Fn(u32)> AutoRepeat<S, F> {
+    pub fn new(button: u32, delay: Milliseconds, rate: Milliseconds, on_press: F) -> Self {
+        AutoRepeat {
+            button,
+            delay,
+            rate,
+            on_press,
+            delay_ticks: Cell::new(Ticks(0)),
+            rate_ticks: Cell::new(Ticks(0)),
+            held: Cell::new(false),
+            _syscalls: PhantomData,
+        }
+    }
+
+    /// Subscribes to the buttons and alarm upcalls and enables interrupts for
+    /// the button. Press events are delivered from within `yield` calls until
+    /// the returned guard is dropped.
+    pub fn start<'share>(
+        &'share self,
+        subscribe: Handle<AutoRepeatSubscribe<'share, S>>,
+    ) -> Result<AutoRepeatGuard<'share, S, F>, ErrorCode> {
+        let freq = Alarm::<S>::get_frequency()?;
+        self.delay_ticks.set(self.delay.to_ticks(freq));
+        self.rate_ticks.set(self.rate.to_ticks(freq));
+        self.held.set(false);
+
+        let (buttons, alarm) = subscribe.split();
+        S::subscribe::<OneId<DRIVER_NUM, 0>, _, DefaultConfig, DRIVER_NUM, 0>(buttons, self)?;
+        S::subscribe::<
+            OneId<ALARM_DRIVER_NUM, ALARM_CALLBACK>,
+            _,
+            DefaultConfig,
+            ALARM_DRIVER_NUM,
+            ALARM_CALLBACK,
+        >(alarm, self)?;
+        Buttons::<S>::enable_interrupts(self.button)?;
+        Ok(AutoRepeatGuard { repeat: self })
+    }
+
+    fn release(&self) {
+        if self.held.replace(false) {
+            let _ = Alarm::<S>::stop();
+        }
+    }
+}
+
+impl<S: Syscalls, F: Fn(u32)> Upcall<OneId<DRIVER_NUM, 0>> for AutoRepeat<S, F> {
+    fn upcall(&self, button_index: usize, state: usize, _arg2: usize) {
+        if button_index as u32 != self.button {
+            return;
+        }
+        match ButtonState::from(state as u32) {
+            ButtonState::Pressed => {
+                if !self.held.replace(true) {
+                    (self.on_press)(self.button);
+                    let _ = Alarm::<S>::set_relative(self.delay_ticks.get());
+                }
+            }
+            ButtonState::Released => self.release(),
+        }
+    }
+}
+
+impl<S: Syscalls, F: Fn(u32)> Upcall<OneId<ALARM_DRIVER_NUM, ALARM_CALLBACK>> for AutoRepeat<S, F> {
+    fn upcall(&self, _now: usize, _arg1: usize, _arg2: usize) {
+        // The alarm may have expired just before the release cancelled it, in
+        // which case its upcall is still delivered and must be ignored.
+        if self.held.get() {
+            (self.on_press)(self.button);
+            let _ = Alarm::<S>::set_relative(self.rate_ticks.get());
+        }
+    }
+}
+
+/// Returned by [`AutoRepeat::start`]. Dropping it disables the button's
+/// interrupts and cancels any pending repeat.
+pub struct AutoRepeatGuard<'a, S: Syscalls, F: Fn(u32)> {
+    repeat: &'a AutoRepeat<S, F>,
+}
+
+impl<'a, S: Syscalls, F: Fn(u32)> Drop for AutoRepeatGuard<'a, S, F> {
+    fn drop(&mut self) {
+        let _ = Buttons::<S>::disable_interrupts(self.repeat.button);
+        self.repeat.release();
+    }
+}
@@ -1,7 +1,13 @@
 #![no_std]
 
+mod auto_repeat;
+
+pub use auto_repeat::{AutoRepeat, AutoRepeatGuard, AutoRepeatSubscribe};
+
+use core::cell::Cell;
+use core::marker::PhantomData;
 use libtock_platform::{
-    share::Handle, subscribe::OneId, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
+    share, share::Handle, subscribe::OneId, DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall,
 };
 
 /// The Buttons driver
@@ -11,7 +17,7 @@ use libtock_platform::{
 /// use libtock::Buttons;
 ///
 /// // Read button state
-/// Buttons::is_pressed(0);
+/// Buttons::is_pressed(0)?;
 ///
 /// // Register for events
 ///
@@ -59,16 +65,21 @@ impl<S: Syscalls> Buttons<S> {
         Ok(button_state.into())
     }
 
-    /// Returns `true` if a button is pressed
-    ///
-    /// This function returns `false` if:
-    /// - the button is released
-    /// - the button number is invalid
-    /// - there is an error
-    pub fn is_pressed(button: u32) -> bool {
-        Self::read(button)
-            .map(|state| state == ButtonState::Pressed)
-            .unwrap_or(false)
+    /// Returns `Ok(true)` if a button is pressed
+    pub fn is_pressed(button: u32) -> Result<bool, ErrorCode> {
+        Self::read(button).map(|state| state == ButtonState::Pressed)
+    }
+
+    /// Reads every button, and returns a bitmask with bit `n` set if button
+    /// `n` is pressed. Only the first 32 buttons are read.
+    pub fn read_all() -> Result<u32, ErrorCode> {
+        let mut pressed = 0;
+        for button in 0..Self::count()?.min(u32::BITS) {
+            if Self::is_pressed(button)? {
+                pressed |= 1 << button;
+            }
+        }
+        Ok(pressed)
     }
 
     /// Returns `true` if a button is released
@@ -105,6 +116,47 @@ impl<S: Syscalls> Buttons<S> {
         S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, listener)
     }
 
+    /// Blocks until a button is pressed. Interrupts for the button are
+    /// disabled again before this returns.
+    pub fn wait_for_press(button: u32) -> Result<(), ErrorCode> {
+        let pressed = Cell::new(false);
+        let listener = ButtonListener(|index, state| {
+            if index == button && state == ButtonState::Pressed {
+                pressed.set(true);
+            }
+        });
+        share::scope(|subscribe| {
+            Self::register_listener(&listener, subscribe)?;
+            Self::enable_interrupts(button)?;
+            S::yield_wait_for(|| pressed.get());
+            Self::disable_interrupts(button)
+        })
+    }
+
+    /// Registers `listener` to receive every button event and enables
+    /// interrupts for all buttons. Events are delivered from within `yield`
+    /// calls, one per upcall, until the returned guard is dropped.
+    ///
+    /// Like `register_listener`, this replaces any previously registered
+    /// listener.
+    pub fn subscribe_all<'share, F: Fn(ButtonEvent)>(
+        listener: &'share ButtonEventListener<F>,
+        subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, 0>>,
+    ) -> Result<SubscribeAllGuard<'share, S>, ErrorCode> {
+        let count = Self::count()?;
+        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, listener)?;
+        // The guard disables the interrupts that were enabled if one fails.
+        let mut guard = SubscribeAllGuard {
+            enabled: 0,
+            _subscribe: PhantomData,
+        };
+        for button in 0..count {
+            Self::enable_interrupts(button)?;
+            guard.enabled = button + 1;
+        }
+        Ok(guard)
+    }
+
     /// Unregister the events listener
     ///
     /// This function may be used even if there was no
@@ -129,6 +181,56 @@ impl<F: Fn(u32, ButtonState)> Upcall<OneId<DRIVER_NUM, 0>> for ButtonListener<F>
         self.0(button_index as u32, (state as u32).into())
     }
 }
+/// A button event, delivered by `Buttons::subscribe_all`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ButtonEvent {
+    pub button: u32,
+    pub state: ButtonState,
+}
+
+/// A wrapper around a closure to be registered with `Buttons::subscribe_all`
+/// and called for each button event.
+///
+/// ```ignore
+/// let listener = ButtonEventListener(|event| {
+///     // make use of event.button and event.state
+/// });
+///
+/// share::scope(|subscribe| {
+///     let _guard = Buttons::subscribe_all(&listener, subscribe)?;
+///     loop {
+///         TockSyscalls::yield_wait();
+///     }
+/// });
+/// ```
+pub struct ButtonEventListener<F: Fn(ButtonEvent)>(pub F);
+
+impl<F: Fn(ButtonEvent)> Upcall<OneId<DRIVER_NUM, 0>> for ButtonEventListener<F> {
+    fn upcall(&self, button_index: usize, state: usize, _arg2: usize) {
+        self.0(ButtonEvent {
+            button: button_index as u32,
+            state: (state as u32).into(),
+        })
+    }
+}
+
+/// Returned by `Buttons::subscribe_all`. Dropping it disables the buttons'
+/// interrupts and unregisters the listener.
+pub struct SubscribeAllGuard<'share, S: Syscalls> {
+    // The number of buttons, starting at 0, whose interrupts were enabled.
+    enabled: u32,
+    _subscribe: PhantomData<(S, &'share ())>,
+}
+
+impl<'share, S: Syscalls> Drop for SubscribeAllGuard<'share, S> {
+    fn drop(&mut self) {
+        for button in 0..self.enabled {
+            let _ = Buttons::<S>::disable_interrupts(button);
+        }
+        Buttons::<S>::unregister_listener();
+    }
+}
+
 #[cfg(test)]
 mod tests;
 
@@ -1,9 +1,10 @@
 use core::cell::Cell;
 
+use libtock_alarm::Milliseconds;
 use libtock_platform::{share, ErrorCode, Syscalls, YieldNoWaitReturn};
-use libtock_unittest::fake;
+use libtock_unittest::{fake, SyscallLogEntry};
 
-use crate::{ButtonListener, ButtonState};
+use crate::{AutoRepeat, ButtonEvent, ButtonEventListener, ButtonListener, ButtonState};
 
 type Buttons = super::Buttons<fake::Syscalls>;
 
@@ -113,3 +114,194 @@ fn subscribe() {
     });
     assert!(!pressed_interrupt_count.get());
 }
+
+#[test]
+fn is_pressed() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buttons::<10>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Buttons::is_pressed(3), Ok(false));
+    assert_eq!(driver.set_pressed(3, true), Ok(()));
+    assert_eq!(Buttons::is_pressed(3), Ok(true));
+    assert_eq!(Buttons::is_pressed(11), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn read_all() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buttons::<10>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Buttons::read_all(), Ok(0));
+    driver.set_pressed_mask(0b10_0000_0101);
+    assert_eq!(Buttons::read_all(), Ok(0b10_0000_0101));
+    assert_eq!(Buttons::is_pressed(9), Ok(true));
+}
+
+#[test]
+fn wait_for_press() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buttons::<10>::new();
+    kernel.add_driver(&driver);
+
+    // Presses of other buttons are ignored.
+    assert_eq!(driver.press(1), Ok(()));
+    assert_eq!(driver.press(2), Ok(()));
+    assert_eq!(Buttons::enable_interrupts(1), Ok(()));
+    assert_eq!(Buttons::wait_for_press(2), Ok(()));
+    assert!(!driver.get_button_state(2).unwrap().interrupt_enabled);
+
+    assert_eq!(Buttons::wait_for_press(11), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn subscribe_all() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buttons::<3>::new();
+    kernel.add_driver(&driver);
+
+    let event = |button, state| ButtonEvent { button, state };
+    let expected = [
+        event(0, ButtonState::Pressed),
+        event(0, ButtonState::Released),
+        event(2, ButtonState::Pressed),
+        event(0, ButtonState::Pressed),
+        event(2, ButtonState::Released),
+    ];
+    let received = Cell::new(0);
+    let listener = ButtonEventListener(|event| {
+        assert_eq!(event, expected[received.get()]);
+        received.set(received.get() + 1);
+    });
+    share::scope(|subscribe| {
+        let guard = Buttons::subscribe_all(&listener, subscribe).unwrap();
+        for button in 0..3 {
+            assert!(driver.get_button_state(button).unwrap().interrupt_enabled);
+        }
+
+        // Every event is delivered, even when several arrive between yields.
+        assert_eq!(driver.set_pressed(0, true), Ok(()));
+        assert_eq!(driver.set_pressed(0, false), Ok(()));
+        assert_eq!(driver.set_pressed(2, true), Ok(()));
+        assert_eq!(driver.set_pressed(0, true), Ok(()));
+        assert_eq!(driver.set_pressed(2, false), Ok(()));
+        while fake::Syscalls::yield_no_wait() == YieldNoWaitReturn::Upcall {}
+        assert_eq!(received.get(), expected.len());
+
+        drop(guard);
+        for button in 0..3 {
+            assert!(!driver.get_button_state(button).unwrap().interrupt_enabled);
+        }
+        assert_eq!(driver.set_pressed(1, true), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert_eq!(received.get(), expected.len());
+    });
+}
+
+// Command syscall log entries made by the auto-repeat helper.
+fn set_relative(ticks: usize) -> SyscallLogEntry {
+    SyscallLogEntry::Command {
+        driver_id: 0,
+        command_id: 5,
+        argument0: ticks,
+        argument1: 0,
+    }
+}
+
+fn alarm_stop() -> SyscallLogEntry {
+    SyscallLogEntry::Command {
+        driver_id: 0,
+        command_id: 3,
+        argument0: 0,
+        argument1: 0,
+    }
+}
+
+#[test]
+fn auto_repeat() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buttons::<2>::new();
+    let alarm = fake::Alarm::new(1000);
+    kernel.add_driver(&driver);
+    kernel.add_driver(&alarm);
+
+    let presses: Cell<u32> = Cell::new(0);
+    let repeat =
+        AutoRepeat::<fake::Syscalls, _>::new(1, Milliseconds(500), Milliseconds(100), |button| {
+            assert_eq!(button, 1);
+            presses.set(presses.get() + 1);
+        });
+    share::scope(|subscribe| {
+        let guard = repeat.start(subscribe).unwrap();
+        assert!(driver.get_button_state(1).unwrap().interrupt_enabled);
+        kernel.take_syscall_log();
+
+        // The press is reported immediately, and the first repeat is armed
+        // with the initial delay.
+        assert_eq!(driver.set_pressed(1, true), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(presses.get(), 1);
+        assert_eq!(
+            kernel.take_syscall_log(),
+            [SyscallLogEntry::YieldNoWait, set_relative(500)]
+        );
+
+        // Every later repeat is armed at the repeat rate.
+        for expected_presses in 2..=4 {
+            assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+            assert_eq!(presses.get(), expected_presses);
+            assert_eq!(
+                kernel.take_syscall_log(),
+                [SyscallLogEntry::YieldNoWait, set_relative(100)]
+            );
+        }
+
+        // The fake alarm fires as soon as it is armed, so the next repeat is
+        // already queued ahead of the release.
+        assert_eq!(driver.set_pressed(1, false), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(presses.get(), 5);
+        kernel.take_syscall_log();
+
+        // The release cancels the alarm, and the stale alarm upcall that was
+        // queued before it is ignored.
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(
+            kernel.take_syscall_log(),
+            [SyscallLogEntry::YieldNoWait, alarm_stop()]
+        );
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert_eq!(presses.get(), 5);
+
+        // Presses of other buttons are ignored.
+        assert_eq!(Buttons::enable_interrupts(0), Ok(()));
+        assert_eq!(driver.set_pressed(0, true), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert_eq!(presses.get(), 5);
+
+        // Dropping the guard while the button is held cancels the repeat.
+        assert_eq!(driver.set_pressed(1, true), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(presses.get(), 6);
+        kernel.take_syscall_log();
+        drop(guard);
+        assert_eq!(
+            kernel.take_syscall_log(),
+            [
+                SyscallLogEntry::Command {
+                    driver_id: 3,
+                    command_id: 2,
+                    argument0: 1,
+                    argument1: 0,
+                },
+                alarm_stop(),
+            ]
+        );
+        assert!(!driver.get_button_state(1).unwrap().interrupt_enabled);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(presses.get(), 6);
+    });
+}
@@ -0,0 +1,74 @@
+#![no_std]
+
+use libtock_platform::subscribe::StandardResult;
+use libtock_platform::{ErrorCode, Syscalls};
+use libtock_sensor_common::command_and_wait;
+
+/// The buzzer driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock::buzzer::Buzzer;
+///
+/// // Play an A4 for half a second
+/// Buzzer::tone(440, 500)?;
+/// ```
+pub struct Buzzer<S: Syscalls>(S);
+
+impl<S: Syscalls> Buzzer<S> {
+    /// Returns `Ok(())` if the buzzer driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Plays a tone at `frequency_hz` for `duration_ms`, blocking until it
+    /// finishes. Returns `Err(ErrorCode::Busy)` without waiting if a tone is
+    /// already playing.
+    pub fn tone(frequency_hz: u32, duration_ms: u32) -> Result<(), ErrorCode> {
+        let finished = StandardResult::new(None);
+        command_and_wait::<S, _, _, DRIVER_NUM, { subscribe::FINISHED }>(
+            &finished,
+            command::TONE,
+            frequency_hz as usize,
+            duration_ms as usize,
+        )
+    }
+
+    /// Starts playing a tone at `frequency_hz` for `duration_ms`, and returns
+    /// without waiting for it to finish. Returns `Err(ErrorCode::Busy)` if a
+    /// tone is already playing.
+    pub fn tone_async(frequency_hz: u32, duration_ms: u32) -> Result<(), ErrorCode> {
+        S::command(
+            DRIVER_NUM,
+            command::TONE,
+            frequency_hz as usize,
+            duration_ms as usize,
+        )
+        .to_result()
+    }
+
+    /// Stops the tone that is playing, if any.
+    pub fn stop() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::STOP, 0, 0).to_result()
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90000;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const TONE: u32 = 1;
+    pub const STOP: u32 = 2;
+}
+
+mod subscribe {
+    pub const FINISHED: u32 = 0;
+}
@@ -0,0 +1,43 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Buzzer = super::Buzzer<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Buzzer::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn tone() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buzzer::new();
+    kernel.add_driver(&driver);
+
+    driver.set_auto_finish(true);
+    assert_eq!(Buzzer::tone(440, 500), Ok(()));
+    assert_eq!(driver.last_tone(), Some((440, 500)));
+    assert!(!driver.is_playing());
+}
+
+#[test]
+fn tone_async() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Buzzer::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Buzzer::tone_async(1000, 20), Ok(()));
+    assert_eq!(driver.last_tone(), Some((1000, 20)));
+    assert!(driver.is_playing());
+
+    // Requesting another tone while one is playing fails, whether or not the
+    // caller would wait for it.
+    assert_eq!(Buzzer::tone_async(2000, 20), Err(ErrorCode::Busy));
+    assert_eq!(Buzzer::tone(2000, 20), Err(ErrorCode::Busy));
+    assert_eq!(driver.last_tone(), Some((1000, 20)));
+
+    assert_eq!(Buzzer::stop(), Ok(()));
+    assert!(!driver.is_playing());
+    assert_eq!(Buzzer::tone_async(2000, 20), Ok(()));
+}
@@ -0,0 +1,135 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The CAN bus driver, which sends and receives classic CAN frames of up to 8
+/// data bytes.
+///
+/// # Example
+/// ```ignore
+/// use libtock::can::Can;
+///
+/// Can::set_bitrate(500_000)?;
+/// Can::enable()?;
+/// Can::send(0x123, &[1, 2, 3])?;
+/// let mut data = [0; 8];
+/// let (id, len) = Can::receive(&mut data)?;
+/// ```
+pub struct Can<S: Syscalls>(S);
+
+impl<S: Syscalls> Can<S> {
+    /// Returns `Ok(())` if the CAN driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Sets the bus speed, in bits per second. This must be done before the
+    /// controller is enabled.
+    pub fn set_bitrate(bitrate: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_BITRATE, bitrate as usize, 0).to_result()
+    }
+
+    /// Enables the controller, connecting it to the bus.
+    pub fn enable() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::ENABLE, 0, 0).to_result()
+    }
+
+    /// Sends a frame with identifier `id` and `data`, blocking until it has
+    /// been sent. Returns `ErrorCode::Size` if `data` is longer than
+    /// `MAX_DATA_LEN`.
+    pub fn send(id: u32, data: &[u8]) -> Result<(), ErrorCode> {
+        if data.len() > MAX_DATA_LEN {
+            return Err(ErrorCode::Size);
+        }
+        // The sent upcall reports the result as an error code, or 0 on
+        // success.
+        let status: Cell<Option<(u32,)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::TX }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::SENT }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::TX }>(allow_ro, data)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::SENT }>(
+                subscribe, &status,
+            )?;
+            S::command(DRIVER_NUM, command::SEND, id as usize, data.len())
+                .to_result::<(), ErrorCode>()?;
+            match S::yield_wait_for_result(|| status.get()) {
+                (0,) => Ok(()),
+                (error,) => Err(ErrorCode::from_kernel(error)),
+            }
+        })
+    }
+
+    /// Blocks until a frame is received, and stores its data in `buffer`.
+    /// Returns the frame's identifier and the number of bytes stored; data
+    /// that does not fit in `buffer` is dropped.
+    pub fn receive(buffer: &mut [u8]) -> Result<(u32, usize), ErrorCode> {
+        // The received upcall reports the frame's length and identifier.
+        let frame: Cell<Option<(u32, u32)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::RX }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::RECEIVED }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            let buffer_len = buffer.len();
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::RX }>(allow_rw, buffer)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::RECEIVED }>(
+                subscribe, &frame,
+            )?;
+            S::command(DRIVER_NUM, command::START_RECEIVING, 0, 0).to_result::<(), ErrorCode>()?;
+            let (len, id) = S::yield_wait_for_result(|| frame.get());
+            S::command(DRIVER_NUM, command::STOP_RECEIVING, 0, 0).to_result::<(), ErrorCode>()?;
+            Ok((id, (len as usize).min(buffer_len)))
+        })
+    }
+}
+
+/// The most data bytes a classic CAN frame holds.
+pub const MAX_DATA_LEN: usize = 8;
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20007;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const SET_BITRATE: u32 = 1;
+    pub const ENABLE: u32 = 3;
+    pub const SEND: u32 = 5;
+    pub const START_RECEIVING: u32 = 7;
+    pub const STOP_RECEIVING: u32 = 8;
+}
+
+mod subscribe {
+    pub const SENT: u32 = 1;
+    pub const RECEIVED: u32 = 2;
+}
+
+mod allow_ro {
+    pub const TX: u32 = 0;
+}
+
+mod allow_rw {
+    pub const RX: u32 = 0;
+}
@@ -0,0 +1,63 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Can = super::Can<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Can::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn enable() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Can::new();
+    kernel.add_driver(&driver);
+    assert_eq!(Can::exists(), Ok(()));
+
+    assert_eq!(Can::send(1, &[1]), Err(ErrorCode::Off));
+    assert_eq!(Can::set_bitrate(500_000), Ok(()));
+    assert_eq!(driver.bitrate(), Some(500_000));
+    assert_eq!(Can::enable(), Ok(()));
+    assert!(driver.is_enabled());
+}
+
+#[test]
+fn loopback() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Can::new();
+    kernel.add_driver(&driver);
+    assert_eq!(Can::set_bitrate(500_000), Ok(()));
+    assert_eq!(Can::enable(), Ok(()));
+
+    assert_eq!(Can::send(0x123, &[1, 2, 3]), Ok(()));
+    assert_eq!(Can::send(0x456, &[]), Ok(()));
+    assert_eq!(Can::send(0x789, &[1, 2, 3, 4, 5, 6, 7, 8]), Ok(()));
+
+    let mut buffer = [0; 8];
+    assert_eq!(Can::receive(&mut buffer), Ok((0x123, 3)));
+    assert_eq!(buffer[..3], [1, 2, 3]);
+    assert_eq!(Can::receive(&mut buffer), Ok((0x456, 0)));
+    // Data that doesn't fit is dropped.
+    let mut short = [0; 4];
+    assert_eq!(Can::receive(&mut short), Ok((0x789, 4)));
+    assert_eq!(short, [1, 2, 3, 4]);
+
+    driver.push_frame(0x10, &[0xff]);
+    assert_eq!(Can::receive(&mut buffer), Ok((0x10, 1)));
+    assert_eq!(buffer[0], 0xff);
+}
+
+#[test]
+fn send_too_long() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Can::new();
+    kernel.add_driver(&driver);
+    assert_eq!(Can::set_bitrate(500_000), Ok(()));
+    assert_eq!(Can::enable(), Ok(()));
+
+    kernel.take_syscall_log();
+    assert_eq!(Can::send(1, &[0; 9]), Err(ErrorCode::Size));
+    assert_eq!(kernel.take_syscall_log(), []);
+}
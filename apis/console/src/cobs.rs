@@ -0,0 +1,118 @@
+//! Consistent Overhead Byte Stuffing, used by `Console::write_framed` and
+//! `Console::read_framed`.
+//!
+//! COBS removes every zero byte from a message so that a single zero byte can
+//! be used to mark the end of each frame. The message is split into blocks,
+//! each made of a code byte followed by `code - 1` non-zero data bytes. Every
+//! block except the last, and except those with code 0xFF, is followed by an
+//! implicit zero in the decoded message.
+
+use core::cmp;
+use libtock_platform::ErrorCode;
+
+/// The most data bytes a single block can carry.
+const MAX_RUN: usize = 254;
+
+/// The code byte of a full block, which is not followed by an implicit zero.
+const FULL_BLOCK: u8 = 0xFF;
+
+/// Splits a message into COBS blocks. Yields `(code, data)` pairs; the encoded
+/// frame is each code byte followed by its data, then a zero delimiter.
+pub(crate) struct Blocks<'a> {
+    rest: Option<&'a [u8]>,
+}
+
+impl<'a> Blocks<'a> {
+    pub(crate) fn new(message: &'a [u8]) -> Self {
+        Blocks {
+            rest: Some(message),
+        }
+    }
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = (u8, &'a [u8]);
+
+    fn next(&mut self) -> Option<(u8, &'a [u8])> {
+        let rest = self.rest?;
+        let max = cmp::min(rest.len(), MAX_RUN);
+        match rest[..max].iter().position(|&b| b == 0) {
+            Some(zero) => {
+                // The zero itself is consumed; the decoder re-inserts it.
+                self.rest = Some(&rest[zero + 1..]);
+                Some((zero as u8 + 1, &rest[..zero]))
+            }
+            None if max == MAX_RUN => {
+                self.rest = Some(&rest[MAX_RUN..]);
+                Some((FULL_BLOCK, &rest[..MAX_RUN]))
+            }
+            None => {
+                self.rest = None;
+                Some((max as u8 + 1, rest))
+            }
+        }
+    }
+}
+
+/// Incrementally decodes a COBS frame into a caller-provided buffer.
+pub(crate) struct Decoder<'b> {
+    buf: &'b mut [u8],
+    len: usize,
+    // Data bytes left in the current block. Zero means the next byte is a
+    // code byte.
+    remaining: u8,
+    // Whether the previous block is followed by an implicit zero, which is
+    // only written out once another block starts.
+    zero_pending: bool,
+    overflow: bool,
+}
+
+impl<'b> Decoder<'b> {
+    pub(crate) fn new(buf: &'b mut [u8]) -> Self {
+        Decoder {
+            buf,
+            len: 0,
+            remaining: 0,
+            zero_pending: false,
+            overflow: false,
+        }
+    }
+
+    /// Feeds the next byte of the frame to the decoder. Returns `None` until the
+    /// delimiter is reached, at which point it returns the decoded length, or
+    /// `ErrorCode::Size` if the message did not fit in the buffer (the rest of
+    /// the frame is still consumed), or `ErrorCode::Fail` if the frame ended in
+    /// the middle of a block.
+    pub(crate) fn push(&mut self, byte: u8) -> Option<Result<usize, ErrorCode>> {
+        if byte == 0 {
+            return Some(if self.remaining != 0 {
+                Err(ErrorCode::Fail)
+            } else if self.overflow {
+                Err(ErrorCode::Size)
+            } else {
+                Ok(self.len)
+            });
+        }
+        if self.remaining == 0 {
+            if self.zero_pending {
+                self.store(0);
+            }
+            self.zero_pending = byte != FULL_BLOCK;
+            self.remaining = byte - 1;
+        } else {
+            self.store(byte);
+            self.remaining -= 1;
+        }
+        None
+    }
+
+    fn store(&mut self, byte: u8) {
+        match self.buf.get_mut(self.len) {
+            Some(slot) => {
+                *slot = byte;
+                self.len += 1;
+            }
+            None => self.overflow = true,
+        }
+    }
+}
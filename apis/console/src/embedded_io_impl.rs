@@ -0,0 +1,59 @@
+//! `embedded_io` support, enabled by the `embedded-io` feature. This lets a
+//! `ConsoleWriter` be used by generic code that expects an `embedded_io` byte
+//! stream.
+
+use crate::{Config, Console, ConsoleWriter};
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// The error type of the console's `embedded_io` implementations.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IoError(pub ErrorCode);
+
+impl embedded_io::Error for IoError {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        use embedded_io::ErrorKind;
+        match self.0 {
+            ErrorCode::NoMem => ErrorKind::OutOfMemory,
+            ErrorCode::Invalid | ErrorCode::Size => ErrorKind::InvalidInput,
+            ErrorCode::NoSupport => ErrorKind::Unsupported,
+            ErrorCode::Off | ErrorCode::NoDevice | ErrorCode::Uninstalled => {
+                ErrorKind::NotConnected
+            }
+            ErrorCode::Cancel => ErrorKind::Interrupted,
+            ErrorCode::Reserve => ErrorKind::PermissionDenied,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+impl<S: Syscalls, C: Config> embedded_io::ErrorType for ConsoleWriter<S, C> {
+    type Error = IoError;
+}
+
+impl<S: Syscalls, C: Config> embedded_io::Read for ConsoleWriter<S, C> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, IoError> {
+        // embedded_io requires reads to block until at least one byte is
+        // available.
+        loop {
+            match Console::<S, C>::read(buf) {
+                (_, Err(error)) => return Err(IoError(error)),
+                (0, Ok(())) if !buf.is_empty() => {}
+                (count, Ok(())) => return Ok(count),
+            }
+        }
+    }
+}
+
+impl<S: Syscalls, C: Config> embedded_io::Write for ConsoleWriter<S, C> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, IoError> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        Console::<S, C>::write_partial(buf).map_err(IoError)
+    }
+
+    fn flush(&mut self) -> Result<(), IoError> {
+        // Writes complete before returning, so there is nothing to flush.
+        Ok(())
+    }
+}
@@ -10,6 +10,17 @@ use libtock_platform::share;
 use libtock_platform::subscribe::Subscribe;
 use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
 
+mod cobs;
+#[cfg(feature = "embedded-io")]
+mod embedded_io_impl;
+#[cfg(feature = "ansi")]
+mod style;
+
+#[cfg(feature = "embedded-io")]
+pub use embedded_io_impl::IoError;
+#[cfg(feature = "ansi")]
+pub use style::Color;
+
 /// The console driver.
 ///
 /// It allows libraries to pass strings to the kernel's console driver.
@@ -39,6 +50,32 @@ impl<S: Syscalls, C: Config> Console<S, C> {
     /// This is an alternative to `fmt::Write::write`
     /// because this can actually return an error code.
     pub fn write(s: &[u8]) -> Result<(), ErrorCode> {
+        Self::write_partial(s).map(|_| ())
+    }
+
+    /// Writes `s`. Unlike writing through `ConsoleWriter`, a failure returns
+    /// the kernel's `ErrorCode` rather than `fmt::Error`.
+    pub fn try_write_str(s: &str) -> Result<(), ErrorCode> {
+        Self::write(s.as_bytes())
+    }
+
+    /// Writes formatted output, stopping at the first failed write and
+    /// returning its `ErrorCode`. This is what the `write_console!` macro
+    /// calls.
+    pub fn try_write_fmt(args: fmt::Arguments) -> Result<(), ErrorCode> {
+        let mut writer = ErrorWriter::<S, C> {
+            result: Ok(()),
+            _syscalls: PhantomData,
+        };
+        match fmt::write(&mut writer, args) {
+            // A formatting trait implementation failed, not the console.
+            Err(_) if writer.result.is_ok() => Err(ErrorCode::Fail),
+            _ => writer.result,
+        }
+    }
+
+    // Writes bytes, returning how many of them the kernel accepted.
+    fn write_partial(s: &[u8]) -> Result<usize, ErrorCode> {
         let called: Cell<Option<(usize,)>> = Cell::new(None);
         share::scope::<
             (
@@ -56,12 +93,8 @@ impl<S: Syscalls, C: Config> Console<S, C> {
 
             S::command(DRIVER_NUM, command::WRITE, s.len(), 0).to_result()?;
 
-            loop {
-                S::yield_wait();
-                if let Some((_,)) = called.get() {
-                    return Ok(());
-                }
-            }
+            let (written,) = S::yield_wait_for_result(|| called.get());
+            Ok(written)
         })
     }
 
@@ -69,7 +102,11 @@ impl<S: Syscalls, C: Config> Console<S, C> {
     /// Reads from the device and writes to `buf`, starting from index 0.
     /// No special guarantees about when the read stops.
     /// Returns count of bytes written to `buf`.
+    /// An empty `buf` returns immediately, without making any system calls.
     pub fn read(buf: &mut [u8]) -> (usize, Result<(), ErrorCode>) {
+        if buf.is_empty() {
+            return (0, Ok(()));
+        }
         let called: Cell<Option<(usize, usize)>> = Cell::new(None);
         let mut bytes_received = 0;
         let r = share::scope::<
@@ -89,37 +126,350 @@ impl<S: Syscalls, C: Config> Console<S, C> {
             // because upcalls are never processed until we call `yield`.
             S::command(DRIVER_NUM, command::READ, len, 0).to_result()?;
 
-            loop {
-                S::yield_wait();
-                if let Some((status, bytes_pushed_count)) = called.get() {
-                    bytes_received = bytes_pushed_count;
-                    return match status {
-                        0 => Ok(()),
-                        e_status => Err((e_status as u32).try_into().unwrap_or(ErrorCode::Fail)),
-                    };
-                }
+            let (status, bytes_pushed_count) = S::yield_wait_for_result(|| called.get());
+            bytes_received = bytes_pushed_count;
+            match status {
+                0 => Ok(()),
+                e_status => Err((e_status as u32).try_into().unwrap_or(ErrorCode::Fail)),
             }
         });
         (bytes_received, r)
     }
 
-    pub fn writer() -> ConsoleWriter<S> {
+    /// Reads from the device into `buf`, like `read`, but gives up once
+    /// `timeout` has passed. Returns the number of bytes read, which is 0 if
+    /// the timeout passed before any input arrived.
+    ///
+    /// This takes over the console read and alarm upcalls while it runs. An
+    /// empty `buf` returns immediately, without making any system calls.
+    #[cfg(feature = "alarm")]
+    pub fn read_with_timeout(
+        buf: &mut [u8],
+        timeout: libtock_alarm::Milliseconds,
+    ) -> Result<usize, ErrorCode> {
+        use libtock_alarm::{subscribe::CALLBACK, Convert, DRIVER_NUM as ALARM_DRIVER_NUM};
+        type Alarm<S> = libtock_alarm::Alarm<S>;
+
+        if buf.is_empty() {
+            return Ok(0);
+        }
+        let freq = Alarm::<S>::get_frequency()?;
+        let read: Cell<Option<(usize, usize)>> = Cell::new(None);
+        let expired = Cell::new(false);
+        let (status, count) = share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::READ }>,
+                Subscribe<_, ALARM_DRIVER_NUM, CALLBACK>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe_read, subscribe_alarm) = handle.split();
+            let len = buf.len();
+            S::allow_rw::<C, DRIVER_NUM, { allow_rw::READ }>(allow_rw, buf)?;
+            S::subscribe::<_, _, C, DRIVER_NUM, { subscribe::READ }>(subscribe_read, &read)?;
+            S::subscribe::<_, _, C, ALARM_DRIVER_NUM, CALLBACK>(subscribe_alarm, &expired)?;
+
+            S::command(DRIVER_NUM, command::READ, len, 0).to_result::<(), ErrorCode>()?;
+            if let Err(error) = Alarm::<S>::set_relative(timeout.to_ticks(freq)) {
+                let _ = S::command(DRIVER_NUM, command::ABORT, 0, 0);
+                return Err(error);
+            }
+
+            // Upcalls run one per yield, so at most one of these is set when
+            // this returns.
+            S::yield_wait_for(|| read.get().is_some() || expired.get());
+            match read.get() {
+                Some(result) => {
+                    // The alarm may have just expired, in which case there is
+                    // nothing to stop. Its upcall is discarded when the scope
+                    // unsubscribes it.
+                    let _ = Alarm::<S>::stop();
+                    Ok(result)
+                }
+                None => {
+                    // The aborted read still completes, with whatever it had
+                    // received.
+                    let abort = S::command(DRIVER_NUM, command::ABORT, 0, 0);
+                    if let Err(error) = abort.to_result::<(), ErrorCode>() {
+                        // The read may have completed just before the abort,
+                        // in which case its upcall is already pending.
+                        S::drain_upcalls();
+                        return read.get().ok_or(error);
+                    }
+                    Ok(S::yield_wait_for_result(|| read.get()))
+                }
+            }
+        })?;
+        match status {
+            0 => Ok(count),
+            status if status == ErrorCode::Cancel as usize => Ok(count),
+            status => Err((status as u32).try_into().unwrap_or(ErrorCode::Fail)),
+        }
+    }
+
+    /// Reads bytes into `buf` until a newline has been read or `buf` is full,
+    /// and returns the number of bytes read, including the newline.
+    ///
+    /// The line also ends early if a read returns no bytes, which the kernel
+    /// does when a read is aborted.
+    pub fn read_line(buf: &mut [u8]) -> Result<usize, ErrorCode> {
+        let mut len = 0;
+        while len < buf.len() {
+            // As in read_framed, read a byte at a time, so that nothing after
+            // the newline is taken from the console.
+            let (count, result) = Self::read(&mut buf[len..=len]);
+            result?;
+            if count == 0 {
+                break;
+            }
+            len += 1;
+            if buf[len - 1] == b'\n' {
+                break;
+            }
+        }
+        Ok(len)
+    }
+
+    /// Writes `message` as a single COBS-encoded frame, terminated by a zero
+    /// byte. The frame contains no other zero bytes, so the receiver can split
+    /// the byte stream back into messages unambiguously.
+    pub fn write_framed(message: &[u8]) -> Result<(), ErrorCode> {
+        let mut block = [0; 255];
+        for (code, data) in cobs::Blocks::new(message) {
+            block[0] = code;
+            block[1..=data.len()].copy_from_slice(data);
+            Self::write(&block[..=data.len()])?;
+        }
+        Self::write(&[0])
+    }
+
+    /// Reads one COBS-encoded frame, as written by `write_framed`, and decodes
+    /// it into `buf`. Returns the length of the decoded message.
+    ///
+    /// If the message does not fit in `buf`, the rest of the frame is
+    /// discarded and `ErrorCode::Size` is returned.
+    pub fn read_framed(buf: &mut [u8]) -> Result<usize, ErrorCode> {
+        let mut decoder = cobs::Decoder::new(buf);
+        let mut byte = [0];
+        loop {
+            // Read a byte at a time, so that nothing after the delimiter is
+            // taken from the console.
+            let (count, result) = Self::read(&mut byte);
+            result?;
+            if count == 1 {
+                if let Some(result) = decoder.push(byte[0]) {
+                    return result;
+                }
+            }
+        }
+    }
+
+    /// Writes a hex dump of `bytes` in the style of `hexdump -C`: one line per
+    /// 16 bytes, giving the offset, the bytes in hex, and the bytes as ASCII
+    /// (with `.` for anything that isn't printable). Each line is formatted
+    /// into a buffer on the stack and written with a single write.
+    ///
+    /// # Example
+    /// ```ignore
+    /// Console::hexdump(b"Hello, world!\n")?;
+    /// // 00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a        |Hello, world!.|
+    /// ```
+    pub fn hexdump(bytes: &[u8]) -> Result<(), ErrorCode> {
+        for (row, chunk) in bytes.chunks(16).enumerate() {
+            let mut line = HexdumpLine {
+                buf: [0; HEXDUMP_LINE_LEN],
+                len: 0,
+            };
+            line.format(row * 16, chunk);
+            Self::write(&line.buf[..line.len])?;
+        }
+        Ok(())
+    }
+
+    pub fn writer() -> ConsoleWriter<S, C> {
         ConsoleWriter {
             syscalls: Default::default(),
         }
     }
+
+    /// Returns a writer that collects output in `buf`, and only writes it to
+    /// the console when `buf` is full, when `flush` is called, or when the
+    /// writer is dropped. Formatting a message with several arguments then
+    /// takes one write rather than one per argument.
+    ///
+    /// Output still buffered when the process exits is lost. A writer that
+    /// outlives `main` should be flushed by a hook installed with
+    /// `libtock_platform::exit_hook::set_hook`.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut buf = [0; 64];
+    /// let mut writer = Console::buffered_writer(&mut buf);
+    /// writeln!(writer, "{} + {} = {}", a, b, a + b).unwrap();
+    /// writer.flush()?;
+    /// ```
+    pub fn buffered_writer(buf: &mut [u8]) -> BufferedWriter<S, C> {
+        BufferedWriter {
+            buf,
+            len: 0,
+            _syscalls: PhantomData,
+        }
+    }
+}
+
+/// A handle to the console, returned by `Console::writer`. With the
+/// `embedded-io` feature enabled, it also implements `embedded_io::Read` and
+/// `embedded_io::Write`.
+pub struct ConsoleWriter<S: Syscalls, C: Config = DefaultConfig> {
+    syscalls: PhantomData<(S, C)>,
+}
+
+impl<S: Syscalls, C: Config> fmt::Write for ConsoleWriter<S, C> {
+    fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+        Console::<S, C>::write(s.as_bytes()).map_err(|_e| fmt::Error)
+    }
+}
+
+// Writes to the console, recording the ErrorCode of a failed write so that
+// try_write_fmt can return it.
+struct ErrorWriter<S: Syscalls, C: Config> {
+    result: Result<(), ErrorCode>,
+    _syscalls: PhantomData<(S, C)>,
+}
+
+impl<S: Syscalls, C: Config> fmt::Write for ErrorWriter<S, C> {
+    fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
+        self.result = Console::<S, C>::try_write_str(s);
+        self.result.map_err(|_e| fmt::Error)
+    }
+}
+
+// The longest line written by hexdump: an offset of up to 16 hex digits, 16
+// bytes in hex (in two groups of 8), 16 ASCII characters between bars, and a
+// newline.
+const HEXDUMP_LINE_LEN: usize = 16 + 1 + 16 * 3 + 2 + 2 + 16 + 2;
+
+// A line of hexdump output, formatted on the stack.
+struct HexdumpLine {
+    buf: [u8; HEXDUMP_LINE_LEN],
+    len: usize,
+}
+
+impl HexdumpLine {
+    fn format(&mut self, offset: usize, chunk: &[u8]) {
+        use fmt::Write;
+        // buf is large enough for any line, so these writes cannot fail.
+        let _ = write!(self, "{:08x} ", offset);
+        for i in 0..16 {
+            if i % 8 == 0 {
+                let _ = self.write_str(" ");
+            }
+            let _ = match chunk.get(i) {
+                Some(byte) => write!(self, "{:02x} ", byte),
+                None => self.write_str("   "),
+            };
+        }
+        let _ = self.write_str(" |");
+        for &byte in chunk {
+            let ascii = match byte {
+                0x20..=0x7e => byte as char,
+                _ => '.',
+            };
+            let _ = self.write_char(ascii);
+        }
+        let _ = self.write_str("|\n");
+    }
+}
+
+impl fmt::Write for HexdumpLine {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let end = self.len + s.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(fmt::Error)?
+            .copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
 }
 
-pub struct ConsoleWriter<S: Syscalls> {
-    syscalls: PhantomData<S>,
+/// A console writer that batches output, returned by
+/// `Console::buffered_writer`.
+pub struct BufferedWriter<'b, S: Syscalls, C: Config = DefaultConfig> {
+    buf: &'b mut [u8],
+    // The number of bytes of `buf` waiting to be written.
+    len: usize,
+    _syscalls: PhantomData<(S, C)>,
 }
 
-impl<S: Syscalls> fmt::Write for ConsoleWriter<S> {
+impl<'b, S: Syscalls, C: Config> BufferedWriter<'b, S, C> {
+    /// Writes out all buffered output. If this fails, the output that was not
+    /// written stays buffered.
+    pub fn flush(&mut self) -> Result<(), ErrorCode> {
+        let mut written = 0;
+        let result = loop {
+            if written == self.len {
+                break Ok(());
+            }
+            // The kernel may accept fewer bytes than it was given.
+            match Console::<S, C>::write_partial(&self.buf[written..self.len]) {
+                // Not making progress would otherwise loop forever.
+                Ok(0) => break Err(ErrorCode::Fail),
+                Ok(count) => written += count,
+                Err(error) => break Err(error),
+            }
+        };
+        self.buf.copy_within(written..self.len, 0);
+        self.len -= written;
+        result
+    }
+}
+
+impl<'b, S: Syscalls, C: Config> fmt::Write for BufferedWriter<'b, S, C> {
     fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
-        Console::<S>::write(s.as_bytes()).map_err(|_e| fmt::Error)
+        let mut s = s.as_bytes();
+        if self.buf.is_empty() {
+            return Console::<S, C>::write(s).map_err(|_e| fmt::Error);
+        }
+        while !s.is_empty() {
+            let count = core::cmp::min(s.len(), self.buf.len() - self.len);
+            self.buf[self.len..self.len + count].copy_from_slice(&s[..count]);
+            self.len += count;
+            s = &s[count..];
+            if self.len == self.buf.len() {
+                self.flush().map_err(|_e| fmt::Error)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'b, S: Syscalls, C: Config> Drop for BufferedWriter<'b, S, C> {
+    fn drop(&mut self) {
+        let _ = self.flush();
     }
 }
 
+/// Like `write!`, but writes to the console `$console` (a `Console` type) and
+/// returns `Result<(), ErrorCode>`, so a failed write (such as
+/// `ErrorCode::Busy`) can be handled or propagated with `?` instead of
+/// panicking.
+///
+/// # Example
+/// ```ignore
+/// use libtock::console::{write_console, Console};
+///
+/// write_console!(Console, "{} + {} = {}\n", a, b, a + b)?;
+/// ```
+#[macro_export]
+macro_rules! write_console {
+    ($console:ty, $($ts: tt)*) => {
+        <$console>::try_write_fmt(core::format_args!($($ts)*))
+    };
+}
+
 /// System call configuration trait for `Console`.
 pub trait Config:
     platform::allow_ro::Config + platform::allow_rw::Config + platform::subscribe::Config
@@ -0,0 +1,38 @@
+//! ANSI terminal styling, enabled by the `ansi` feature. The styles are set by
+//! writing escape sequences to the console, so they only have an effect if the
+//! console is connected to a terminal that understands them.
+
+use crate::{Config, Console};
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// The eight standard ANSI colors. The exact shade of each is up to the
+/// terminal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Black = 0,
+    Red = 1,
+    Green = 2,
+    Yellow = 3,
+    Blue = 4,
+    Magenta = 5,
+    Cyan = 6,
+    White = 7,
+}
+
+impl<S: Syscalls, C: Config> Console<S, C> {
+    /// Sets the color of text written from now on.
+    pub fn set_color(color: Color) -> Result<(), ErrorCode> {
+        // SGR 30 to 37 select the foreground colors, in Color's order.
+        Self::write(&[0x1b, b'[', b'3', b'0' + color as u8, b'm'])
+    }
+
+    /// Makes text written from now on bold.
+    pub fn bold() -> Result<(), ErrorCode> {
+        Self::write(b"\x1b[1m")
+    }
+
+    /// Returns to the terminal's default color and weight.
+    pub fn reset_style() -> Result<(), ErrorCode> {
+        Self::write(b"\x1b[0m")
+    }
+}
@@ -1,7 +1,7 @@
 use super::*;
 use core::fmt::Write;
 use libtock_platform::ErrorCode;
-use libtock_unittest::{command_return, fake, ExpectedSyscall};
+use libtock_unittest::{command_return, fake, ExpectedSyscall, SyscallLogEntry};
 
 type Console = super::Console<fake::Syscalls>;
 
@@ -42,6 +42,162 @@ fn write_str() {
     assert_eq!(driver.take_bytes(), b"foo");
 }
 
+#[test]
+fn write_console() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    let (name, value) = ("x", 5);
+
+    assert_eq!(write_console!(Console, "{} = {}", name, value), Ok(()));
+    assert_eq!(driver.take_bytes(), b"x = 5");
+
+    // The first write ("x") fails, so nothing else is written.
+    kernel.take_syscall_log();
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::WRITE,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Subscribe {
+        driver_num: DRIVER_NUM,
+        subscribe_num: subscribe::WRITE,
+        skip_with_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: DRIVER_NUM,
+        command_id: command::WRITE,
+        argument0: 1,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::Busy)),
+    });
+    assert_eq!(
+        write_console!(Console, "{} = {}", name, value),
+        Err(ErrorCode::Busy)
+    );
+    assert_eq!(write_count(&kernel), 1);
+    // The fake driver still receives the command even if a fake error is injected.
+    assert_eq!(driver.take_bytes(), b"x");
+
+    assert_eq!(Console::try_write_str("ok"), Ok(()));
+    assert_eq!(driver.take_bytes(), b"ok");
+}
+
+// Counts the Write commands in the syscall log.
+fn write_count(kernel: &fake::Kernel) -> usize {
+    kernel
+        .take_syscall_log()
+        .into_iter()
+        .filter(|entry| {
+            matches!(
+                entry,
+                SyscallLogEntry::Command {
+                    command_id: command::WRITE,
+                    ..
+                }
+            )
+        })
+        .count()
+}
+
+#[test]
+fn buffered_writer() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    let mut buf = [0; 32];
+    let mut writer = Console::buffered_writer(&mut buf);
+    writeln!(writer, "{} + two = {}", 1, 3.0).unwrap();
+    assert_eq!(write_count(&kernel), 0);
+    writer.flush().unwrap();
+    assert_eq!(write_count(&kernel), 1);
+    assert_eq!(driver.take_bytes(), b"1 + two = 3\n");
+
+    // Flushing with nothing buffered doesn't write.
+    writer.flush().unwrap();
+    assert_eq!(write_count(&kernel), 0);
+
+    // Dropping the writer flushes it.
+    write!(writer, "bye").unwrap();
+    drop(writer);
+    assert_eq!(write_count(&kernel), 1);
+    assert_eq!(driver.take_bytes(), b"bye");
+}
+
+// A process that prints through a long-lived buffered writer, then returns
+// from main, has its output flushed by its exit hook.
+#[test]
+fn buffered_writer_exit_hook() {
+    extern crate std;
+    use core::cell::RefCell;
+    use std::boxed::Box;
+
+    std::thread_local! {
+        static WRITER: RefCell<Option<BufferedWriter<'static, fake::Syscalls>>> =
+            const { RefCell::new(None) };
+    }
+    fn flush() {
+        WRITER.with(|writer| writer.borrow_mut().as_mut().unwrap().flush().unwrap());
+    }
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    let buf = Box::leak(Box::new([0; 16]));
+    WRITER.with(|writer| {
+        let mut writer = writer.borrow_mut();
+        let writer = writer.insert(Console::buffered_writer(buf));
+        write!(writer, "bye").unwrap();
+    });
+    assert_eq!(write_count(&kernel), 0);
+
+    // This is what Termination::complete does before calling Exit.
+    libtock_platform::exit_hook::set_hook(flush);
+    libtock_platform::exit_hook::run_hook();
+    assert_eq!(driver.take_bytes(), b"bye");
+}
+
+#[test]
+fn buffered_writer_full() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    let mut buf = [0; 4];
+    let mut writer = Console::buffered_writer(&mut buf);
+    write!(writer, "abcdefghij").unwrap();
+    // Two full buffers have been written, and "ij" is still buffered.
+    assert_eq!(write_count(&kernel), 2);
+    assert_eq!(driver.take_bytes(), b"abcdefgh");
+    writer.flush().unwrap();
+    assert_eq!(driver.take_bytes(), b"ij");
+}
+
+#[test]
+fn buffered_writer_short_writes() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    driver.set_write_limit(3);
+
+    let mut buf = [0; 16];
+    let mut writer = Console::buffered_writer(&mut buf);
+    write!(writer, "0123456").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(write_count(&kernel), 3);
+    assert_eq!(driver.take_bytes(), b"0123456");
+
+    // A write that makes no progress fails, keeping the output buffered.
+    driver.set_write_limit(0);
+    write!(writer, "xy").unwrap();
+    assert_eq!(writer.flush(), Err(ErrorCode::Fail));
+    driver.set_write_limit(usize::MAX);
+    writer.flush().unwrap();
+    assert_eq!(driver.take_bytes(), b"xy");
+}
+
 #[test]
 fn read_bytes_short() {
     let kernel = fake::Kernel::new();
@@ -100,6 +256,127 @@ fn failed_print() {
     assert_eq!(driver.take_bytes(), b"abcde");
 }
 
+#[test]
+fn read_empty_buffer() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new_with_input(b"Hello");
+    kernel.add_driver(&driver);
+
+    let (count, res) = Console::read(&mut []);
+    assert_eq!((count, res), (0, Ok(())));
+    assert_eq!(kernel.take_syscall_log().len(), 0);
+}
+
+#[test]
+fn read_line() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new_with_input(b"one\ntwo");
+    kernel.add_driver(&driver);
+
+    let mut buf = [0; 10];
+    let len = Console::read_line(&mut buf).unwrap();
+    assert_eq!(&buf[..len], b"one\n");
+
+    // The rest of the input stays queued until it is read. Once it runs out,
+    // the fake returns empty reads, ending the line.
+    driver.push_input(b"s");
+    let len = Console::read_line(&mut buf).unwrap();
+    assert_eq!(&buf[..len], b"twos");
+
+    // A line longer than the buffer is returned in pieces.
+    driver.push_input(b"abcde\n");
+    let mut buf = [0; 3];
+    assert_eq!(Console::read_line(&mut buf), Ok(3));
+    assert_eq!(&buf, b"abc");
+    assert_eq!(Console::read_line(&mut buf), Ok(3));
+    assert_eq!(&buf, b"de\n");
+    assert_eq!(Console::read_line(&mut []), Ok(0));
+}
+
+#[cfg(feature = "alarm")]
+#[test]
+fn read_with_timeout_input() {
+    use libtock_alarm::Milliseconds;
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    let alarm = fake::Alarm::new(1000);
+    kernel.add_driver(&alarm);
+    driver.set_blocking_reads(true);
+
+    // The input arrives before the (immediately-firing) fake alarm.
+    driver.push_input(b"yes");
+    let mut buf = [0; 8];
+    assert_eq!(
+        Console::read_with_timeout(&mut buf, Milliseconds(100)),
+        Ok(3)
+    );
+    assert_eq!(&buf[..3], b"yes");
+    assert_eq!(
+        Console::read_with_timeout(&mut [], Milliseconds(100)),
+        Ok(0)
+    );
+}
+
+#[cfg(feature = "alarm")]
+#[test]
+fn read_with_timeout_expires() {
+    use libtock_alarm::Milliseconds;
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    let alarm = fake::Alarm::new(1000);
+    kernel.add_driver(&alarm);
+    driver.set_blocking_reads(true);
+
+    let mut buf = [0; 8];
+    assert_eq!(
+        Console::read_with_timeout(&mut buf, Milliseconds(100)),
+        Ok(0)
+    );
+
+    // The aborted read no longer takes input.
+    driver.push_input(b"late");
+    let (count, result) = Console::read(&mut buf);
+    assert_eq!((count, result), (4, Ok(())));
+    assert_eq!(&buf[..4], b"late");
+}
+
+#[cfg(feature = "alarm")]
+#[test]
+fn read_with_timeout_abort_fails() {
+    use libtock_alarm::Milliseconds;
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+    let alarm = fake::Alarm::new(1000);
+    kernel.add_driver(&alarm);
+    driver.set_blocking_reads(true);
+
+    // The timeout expires and the abort fails. The fake driver still ends the
+    // read, as if it had completed just before the abort, so its result is
+    // returned rather than the abort's error.
+    let trace: libtock_unittest::Trace = "command 0 1 0 0 -> 129 1000 0 0
+        allow-rw 1 1 8 -> ok
+        subscribe 1 2 -> ok
+        subscribe 0 0 -> ok
+        command 1 2 8 0 -> 128 0 0 0
+        command 0 5 100 0 -> 129 100 0 0
+        yield-wait
+        command 1 3 0 0 -> 0 1 0 0"
+        .parse()
+        .unwrap();
+    kernel.replay(&trace);
+    let mut buf = [0; 8];
+    assert_eq!(
+        Console::read_with_timeout(&mut buf, Milliseconds(100)),
+        Ok(0)
+    );
+}
+
 #[test]
 fn failed_read() {
     let kernel = fake::Kernel::new();
@@ -129,3 +406,157 @@ fn failed_read() {
     assert_eq!(res, Err(ErrorCode::Fail));
     assert_eq!(count, 0);
 }
+
+// Encodes `message` with write_framed, checks the framing, then decodes it with
+// read_framed and checks that the original message comes back.
+fn framed_round_trip(message: &[u8]) {
+    let encoded = {
+        let kernel = fake::Kernel::new();
+        let driver = fake::Console::new();
+        kernel.add_driver(&driver);
+        Console::write_framed(message).unwrap();
+        driver.take_bytes()
+    };
+    let (delimiter, body) = encoded.split_last().unwrap();
+    assert_eq!(*delimiter, 0);
+    assert!(!body.contains(&0));
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new_with_input(&encoded);
+    kernel.add_driver(&driver);
+    let mut buf = [0xff; 1024];
+    assert_eq!(Console::read_framed(&mut buf), Ok(message.len()));
+    assert_eq!(&buf[..message.len()], message);
+}
+
+#[test]
+fn write_framed() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    Console::write_framed(&[]).unwrap();
+    assert_eq!(driver.take_bytes(), [1, 0]);
+    Console::write_framed(&[0]).unwrap();
+    assert_eq!(driver.take_bytes(), [1, 1, 0]);
+    Console::write_framed(&[0x11, 0x22, 0x00, 0x33]).unwrap();
+    assert_eq!(driver.take_bytes(), [3, 0x11, 0x22, 2, 0x33, 0]);
+    Console::write_framed(&[0x11, 0x00, 0x00]).unwrap();
+    assert_eq!(driver.take_bytes(), [2, 0x11, 1, 1, 0]);
+}
+
+#[test]
+fn framed_round_trips() {
+    framed_round_trip(b"");
+    framed_round_trip(b"Hello");
+    framed_round_trip(&[0]);
+    framed_round_trip(&[0, 0, 0]);
+    framed_round_trip(&[1, 0, 2, 0, 0, 3, 0]);
+
+    // Messages around the 254-byte maximum block length.
+    let mut long = [0x5a; 600];
+    framed_round_trip(&long[..253]);
+    framed_round_trip(&long[..254]);
+    framed_round_trip(&long[..255]);
+    framed_round_trip(&long[..509]);
+    long[254] = 0;
+    long[300] = 0;
+    long[599] = 0;
+    framed_round_trip(&long[..255]);
+    framed_round_trip(&long);
+
+    let mut counting = [0; 512];
+    for (i, byte) in counting.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    framed_round_trip(&counting);
+}
+
+#[test]
+fn read_framed_too_large() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new_with_input(&[
+        // A 10-byte message, followed by a 1-byte message (0x42).
+        11, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 0, 2, 0x42, 0,
+    ]);
+    kernel.add_driver(&driver);
+
+    let mut buf = [0; 4];
+    assert_eq!(Console::read_framed(&mut buf), Err(ErrorCode::Size));
+    // The oversized frame is consumed entirely, so the next frame still
+    // decodes correctly.
+    assert_eq!(Console::read_framed(&mut buf), Ok(1));
+    assert_eq!(buf[0], 0x42);
+}
+
+#[cfg(feature = "ansi")]
+#[test]
+fn style() {
+    use crate::Color;
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Console::set_color(Color::Red), Ok(()));
+    assert_eq!(driver.take_bytes(), b"\x1b[31m");
+    assert_eq!(Console::set_color(Color::Cyan), Ok(()));
+    assert_eq!(driver.take_bytes(), b"\x1b[36m");
+    assert_eq!(Console::bold(), Ok(()));
+    assert_eq!(Console::reset_style(), Ok(()));
+    assert_eq!(driver.take_bytes(), b"\x1b[1m\x1b[0m");
+}
+
+#[test]
+fn read_framed_truncated() {
+    let kernel = fake::Kernel::new();
+    // The code byte promises four data bytes, but the frame ends after two.
+    let driver = fake::Console::new_with_input(&[5, 1, 2, 0, 2, 7, 0]);
+    kernel.add_driver(&driver);
+
+    let mut buf = [0; 8];
+    assert_eq!(Console::read_framed(&mut buf), Err(ErrorCode::Fail));
+    assert_eq!(Console::read_framed(&mut buf), Ok(1));
+    assert_eq!(buf[0], 7);
+}
+
+#[test]
+fn hexdump() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    Console::hexdump(b"The quick brown fox jumps\x00\x7f\xff\n").unwrap();
+    assert_eq!(
+        core::str::from_utf8(&driver.take_bytes()).unwrap(),
+        "00000000  54 68 65 20 71 75 69 63  6b 20 62 72 6f 77 6e 20  |The quick brown |\n\
+         00000010  66 6f 78 20 6a 75 6d 70  73 00 7f ff 0a           |fox jumps....|\n"
+    );
+    // One write per line.
+    assert_eq!(write_count(&kernel), 2);
+
+    Console::hexdump(&[]).unwrap();
+    assert_eq!(write_count(&kernel), 0);
+}
+
+#[cfg(feature = "embedded-io")]
+#[test]
+fn embedded_io() {
+    use embedded_io::{Error, ErrorKind};
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new_with_input(b"in");
+    kernel.add_driver(&driver);
+
+    let mut writer = Console::writer();
+    let stream: &mut dyn embedded_io::Write<Error = IoError> = &mut writer;
+    stream.write_all(b"through a trait object").unwrap();
+    stream.flush().unwrap();
+    assert_eq!(driver.take_bytes(), b"through a trait object");
+
+    let mut buf = [0; 4];
+    assert_eq!(embedded_io::Read::read(&mut writer, &mut buf), Ok(2));
+    assert_eq!(&buf[..2], b"in");
+
+    assert_eq!(IoError(ErrorCode::NoMem).kind(), ErrorKind::OutOfMemory);
+    assert_eq!(IoError(ErrorCode::Busy).kind(), ErrorKind::Other);
+}
@@ -0,0 +1,80 @@
+#![no_std]
+
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::share;
+use libtock_platform::subscribe::{StandardResultArg1, Subscribe, UpcallResult};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The CRC driver, which computes cyclic redundancy checks in hardware.
+///
+/// # Example
+/// ```ignore
+/// use libtock::crc::{Crc, CrcAlgorithm};
+///
+/// let crc = Crc::compute(CrcAlgorithm::Crc32, b"123456789")?;
+/// assert_eq!(crc, 0xcbf43926);
+/// ```
+pub struct Crc<S: Syscalls>(S);
+
+impl<S: Syscalls> Crc<S> {
+    /// Returns `Ok(())` if the CRC driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Computes the CRC of `data` with `algorithm`, blocking until it is done.
+    /// The kernel may process long inputs in several pieces, but only reports
+    /// the CRC of the whole input. 16-bit CRCs are returned in the low bits.
+    pub fn compute(algorithm: CrcAlgorithm, data: &[u8]) -> Result<u32, ErrorCode> {
+        let done = StandardResultArg1::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::INPUT }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::INPUT }>(allow_ro, data)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::COMPUTE, algorithm as usize, data.len())
+                .to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>().map(|crc| crc as u32)
+        })
+    }
+}
+
+/// The CRC algorithms the driver can compute.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CrcAlgorithm {
+    /// CRC-32 as used by Ethernet and zlib (polynomial 0x04c11db7, reflected).
+    Crc32 = 0,
+    /// CRC-32C, the Castagnoli CRC (polynomial 0x1edc6f41, reflected).
+    Crc32C = 1,
+    /// CRC-16/CCITT-FALSE (polynomial 0x1021, initial value 0xffff).
+    Crc16CCITT = 2,
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40002;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const COMPUTE: u32 = 1;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const INPUT: u32 = 0;
+}
@@ -0,0 +1,56 @@
+use super::CrcAlgorithm;
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Crc = super::Crc<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Crc::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn check_values() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Crc::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Crc::exists(), Ok(()));
+    assert_eq!(
+        Crc::compute(CrcAlgorithm::Crc32, b"123456789"),
+        Ok(0xcbf43926)
+    );
+    assert_eq!(
+        Crc::compute(CrcAlgorithm::Crc32C, b"123456789"),
+        Ok(0xe3069283)
+    );
+    assert_eq!(
+        Crc::compute(CrcAlgorithm::Crc16CCITT, b"123456789"),
+        Ok(0x29b1)
+    );
+}
+
+#[test]
+fn empty_input() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Crc::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Crc::compute(CrcAlgorithm::Crc32, b""), Ok(0));
+    assert_eq!(Crc::compute(CrcAlgorithm::Crc16CCITT, b""), Ok(0xffff));
+}
+
+#[test]
+fn chunked() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Crc::new();
+    kernel.add_driver(&driver);
+
+    // The CRC of the whole input is reported, regardless of how the kernel
+    // splits it up.
+    let data: [u8; 300] = core::array::from_fn(|i| i as u8);
+    let expected = Crc::compute(CrcAlgorithm::Crc32C, &data).unwrap();
+    driver.set_chunk_size(7);
+    assert_eq!(Crc::compute(CrcAlgorithm::Crc32C, &data), Ok(expected));
+}
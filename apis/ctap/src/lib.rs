@@ -0,0 +1,140 @@
+#![no_std]
+
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::{StandardResult, StandardResultArg1};
+use libtock_platform::{DefaultConfig, ErrorCode, Subscribe, Syscalls};
+use libtock_sensor_common::command_and_wait;
+
+/// The USB CTAP driver, which exchanges 64-byte HID packets with the host for
+/// security key applications.
+///
+/// The packet buffers are shared for as long as their `share::scope` lasts,
+/// so a key can send and receive many packets without sharing them again.
+///
+/// # Example
+/// ```ignore
+/// use libtock::ctap::{Ctap, PACKET_LEN};
+///
+/// let mut request = [0; PACKET_LEN];
+/// let response = [0; PACKET_LEN];
+/// share::scope(|handle| {
+///     let (allow_rw, allow_ro) = handle.split();
+///     Ctap::allow_receive(allow_rw, &mut request)?;
+///     Ctap::allow_send(allow_ro, &response)?;
+///     Ctap::receive()?;
+///     Ctap::send()
+/// })?;
+/// ```
+pub struct Ctap<S: Syscalls>(S);
+
+impl<S: Syscalls> Ctap<S> {
+    /// Returns `Ok(())` if the CTAP driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Shares `buffer` for received packets. Returns `Err(ErrorCode::Size)`
+    /// if it is too small to hold a packet.
+    pub fn allow_receive<'share>(
+        allow_rw: share::Handle<AllowRw<'share, S, DRIVER_NUM, { allow_rw::RECEIVE }>>,
+        buffer: &'share mut [u8],
+    ) -> Result<(), ErrorCode> {
+        if buffer.len() < PACKET_LEN {
+            return Err(ErrorCode::Size);
+        }
+        S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::RECEIVE }>(allow_rw, buffer)
+    }
+
+    /// Shares `buffer` as the packet to send. Returns `Err(ErrorCode::Size)`
+    /// if it is longer than a packet.
+    pub fn allow_send<'share>(
+        allow_ro: share::Handle<AllowRo<'share, S, DRIVER_NUM, { allow_ro::SEND }>>,
+        buffer: &'share [u8],
+    ) -> Result<(), ErrorCode> {
+        if buffer.len() > PACKET_LEN {
+            return Err(ErrorCode::Size);
+        }
+        S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::SEND }>(allow_ro, buffer)
+    }
+
+    /// Sends the packet shared with `allow_send`, and blocks until the host
+    /// has taken it.
+    pub fn send() -> Result<(), ErrorCode> {
+        let sent = StandardResult::new(None);
+        command_and_wait::<S, _, _, DRIVER_NUM, { subscribe::SENT }>(&sent, command::SEND, 0, 0)
+    }
+
+    /// Blocks until a packet arrives in the buffer shared with
+    /// `allow_receive`, and returns its length.
+    pub fn receive() -> Result<usize, ErrorCode> {
+        let received = StandardResultArg1::new(None);
+        command_and_wait::<S, _, _, DRIVER_NUM, { subscribe::RECEIVED }>(
+            &received,
+            command::RECEIVE,
+            0,
+            0,
+        )
+    }
+
+    /// Starts receiving a packet into the buffer shared with `allow_receive`,
+    /// and returns without waiting for it. `received` is set once the packet
+    /// arrives, and yields its length through `UpcallResult::upcall_result`.
+    /// Use `cancel` to stop waiting.
+    pub fn start_receive<'share>(
+        subscribe: share::Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::RECEIVED }>>,
+        received: &'share StandardResultArg1,
+    ) -> Result<(), ErrorCode> {
+        received.set(None);
+        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::RECEIVED }>(
+            subscribe, received,
+        )?;
+        S::command(DRIVER_NUM, command::RECEIVE, 0, 0).to_result()
+    }
+
+    /// Cancels the receive started by `start_receive`, and unsubscribes from
+    /// received packets, so that no upcall arrives for it even if the packet
+    /// came in before the kernel saw the cancellation. Returns
+    /// `Err(ErrorCode::Already)` if no receive was pending.
+    pub fn cancel() -> Result<(), ErrorCode> {
+        let result = S::command(DRIVER_NUM, command::CANCEL, 0, 0).to_result();
+        S::unsubscribe(DRIVER_NUM, subscribe::RECEIVED);
+        result
+    }
+}
+
+/// The length of a CTAP HID packet, in bytes.
+pub const PACKET_LEN: usize = 64;
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20009;
+
+// Command IDs
+#[allow(unused)]
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const SEND: u32 = 1;
+    pub const RECEIVE: u32 = 2;
+    pub const SEND_OR_RECEIVE: u32 = 3;
+    pub const CANCEL: u32 = 4;
+}
+
+mod subscribe {
+    pub const RECEIVED: u32 = 0;
+    pub const SENT: u32 = 1;
+}
+
+mod allow_ro {
+    pub const SEND: u32 = 0;
+}
+
+mod allow_rw {
+    pub const RECEIVE: u32 = 0;
+}
@@ -0,0 +1,139 @@
+use super::PACKET_LEN;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::subscribe::{StandardResultArg1, UpcallResult};
+use libtock_platform::{share, ErrorCode, Subscribe, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::fake;
+
+type Ctap = super::Ctap<fake::Syscalls>;
+
+const DRIVER_NUM: u32 = super::DRIVER_NUM;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Ctap::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn round_trip() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ctap::new();
+    kernel.add_driver(&driver);
+
+    let request = [0x42; PACKET_LEN];
+    let mut response = [0; PACKET_LEN];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, { super::allow_ro::SEND }>,
+            AllowRw<_, DRIVER_NUM, { super::allow_rw::RECEIVE }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw) = handle.split();
+        assert_eq!(Ctap::allow_send(allow_ro, &request), Ok(()));
+        assert_eq!(Ctap::allow_receive(allow_rw, &mut response), Ok(()));
+        assert_eq!(Ctap::send(), Ok(()));
+        assert_eq!(Ctap::receive(), Ok(PACKET_LEN));
+    });
+    assert_eq!(response, request);
+    assert_eq!(driver.take_sent(), [request.to_vec()]);
+}
+
+#[test]
+fn receive_from_host() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ctap::new();
+    kernel.add_driver(&driver);
+
+    driver.push_packet(&[1, 2, 3]);
+    let mut buffer = [0; PACKET_LEN];
+    share::scope::<AllowRw<_, DRIVER_NUM, { super::allow_rw::RECEIVE }>, _, _>(|allow_rw| {
+        assert_eq!(Ctap::allow_receive(allow_rw, &mut buffer), Ok(()));
+        assert_eq!(Ctap::receive(), Ok(3));
+    });
+    assert_eq!(buffer[..3], [1, 2, 3]);
+}
+
+#[test]
+fn buffer_sizes() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ctap::new();
+    kernel.add_driver(&driver);
+
+    let mut small = [0; PACKET_LEN - 1];
+    let large = [0; PACKET_LEN + 1];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, { super::allow_ro::SEND }>,
+            AllowRw<_, DRIVER_NUM, { super::allow_rw::RECEIVE }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw) = handle.split();
+        assert_eq!(Ctap::allow_send(allow_ro, &large), Err(ErrorCode::Size));
+        assert_eq!(
+            Ctap::allow_receive(allow_rw, &mut small),
+            Err(ErrorCode::Size)
+        );
+    });
+}
+
+#[test]
+fn cancel() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ctap::new();
+    kernel.add_driver(&driver);
+
+    let mut buffer = [0; PACKET_LEN];
+    let received = StandardResultArg1::new(None);
+    share::scope::<
+        (
+            AllowRw<_, DRIVER_NUM, { super::allow_rw::RECEIVE }>,
+            Subscribe<_, DRIVER_NUM, { super::subscribe::RECEIVED }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        assert_eq!(Ctap::allow_receive(allow_rw, &mut buffer), Ok(()));
+        assert_eq!(Ctap::start_receive(subscribe, &received), Ok(()));
+        assert!(driver.is_receiving());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        assert_eq!(Ctap::cancel(), Ok(()));
+        assert!(!driver.is_receiving());
+        // A packet arriving after the cancellation is not delivered.
+        driver.push_packet(&[1]);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert_eq!(received.upcall_result(), None);
+        assert_eq!(Ctap::cancel(), Err(ErrorCode::Already));
+    });
+}
+
+#[test]
+fn start_receive() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ctap::new();
+    kernel.add_driver(&driver);
+
+    let mut buffer = [0; PACKET_LEN];
+    let received = StandardResultArg1::new(None);
+    share::scope::<
+        (
+            AllowRw<_, DRIVER_NUM, { super::allow_rw::RECEIVE }>,
+            Subscribe<_, DRIVER_NUM, { super::subscribe::RECEIVED }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        assert_eq!(Ctap::allow_receive(allow_rw, &mut buffer), Ok(()));
+        assert_eq!(Ctap::start_receive(subscribe, &received), Ok(()));
+        driver.push_packet(&[7; 4]);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(received.upcall_result(), Some(Ok(4)));
+    });
+}
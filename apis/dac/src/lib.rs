@@ -0,0 +1,59 @@
+#![no_std]
+
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// The digital-to-analog converter driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock::dac::Dac;
+///
+/// // Output half of the full-scale voltage
+/// Dac::initialize()?;
+/// Dac::set_value(Dac::max_value()? / 2)?;
+/// ```
+pub struct Dac<S: Syscalls>(S);
+
+impl<S: Syscalls> Dac<S> {
+    /// Returns `Ok(())` if the DAC driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Enables the DAC. This must be called before `set_value`.
+    pub fn initialize() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::INITIALIZE, 0, 0).to_result()
+    }
+
+    /// Returns the largest value `set_value` accepts, which produces the
+    /// full-scale output voltage.
+    pub fn max_value() -> Result<u16, ErrorCode> {
+        S::command(DRIVER_NUM, command::MAX_VALUE, 0, 0)
+            .to_result::<u32, ErrorCode>()
+            .map(|max| max as u16)
+    }
+
+    /// Sets the output to `value`. Returns `Err(ErrorCode::Busy)` if the DAC
+    /// is still applying the previous value, and `Err(ErrorCode::Invalid)` if
+    /// `value` is greater than `max_value`.
+    pub fn set_value(value: u16) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_VALUE, value as usize, 0).to_result()
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x6;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const INITIALIZE: u32 = 1;
+    pub const SET_VALUE: u32 = 2;
+    pub const MAX_VALUE: u32 = 3;
+}
@@ -0,0 +1,41 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Dac = super::Dac<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Dac::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn set_value() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Dac::new(4095);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Dac::exists(), Ok(()));
+    assert_eq!(Dac::set_value(100), Err(ErrorCode::Off));
+    assert_eq!(Dac::initialize(), Ok(()));
+    assert_eq!(Dac::max_value(), Ok(4095));
+    assert_eq!(Dac::set_value(2048), Ok(()));
+    assert_eq!(driver.value(), Some(2048));
+    assert_eq!(Dac::set_value(4096), Err(ErrorCode::Invalid));
+    assert_eq!(driver.value(), Some(2048));
+}
+
+#[test]
+fn busy() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Dac::new(255);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Dac::initialize(), Ok(()));
+    driver.set_busy(true);
+    assert_eq!(Dac::set_value(10), Err(ErrorCode::Busy));
+    assert_eq!(driver.value(), None);
+    driver.set_busy(false);
+    assert_eq!(Dac::set_value(10), Ok(()));
+    assert_eq!(driver.value(), Some(10));
+}
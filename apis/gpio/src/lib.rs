@@ -1,5 +1,7 @@
 #![no_std]
 
+#[cfg(feature = "alarm")]
+use core::cell::Cell;
 use core::marker::PhantomData;
 
 use libtock_platform::{
@@ -10,11 +12,16 @@ use libtock_platform::{
 ///
 /// # Example
 /// ```ignore
-/// use libtock::Gpios;
+/// use libtock::gpio::Gpio;
 ///
-/// // Turn on led 0
-/// let pin = Gpios::get_pin(0)?;
+/// // Drive pin 0 high
+/// Gpio::enable_output(0)?;
+/// Gpio::set(0)?;
 ///
+/// // Or use the typed pin wrappers, which disable the pin when dropped
+/// let mut pin = Gpio::get_pin(0)?;
+/// let mut output = pin.make_output()?;
+/// output.toggle()?;
 /// ```
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
@@ -23,6 +30,14 @@ pub enum GpioState {
     High = 1,
 }
 
+/// Pull resistor configuration for `Gpio::enable_input`.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum PullMode {
+    PullNone = 0,
+    PullUp = 1,
+    PullDown = 2,
+}
+
 pub enum PinInterruptEdge {
     Either = 0,
     Rising = 1,
@@ -35,22 +50,22 @@ pub enum Error {
 }
 
 pub trait Pull {
-    const MODE: u32;
+    const MODE: PullMode;
 }
 
 pub struct PullUp;
 impl Pull for PullUp {
-    const MODE: u32 = 1;
+    const MODE: PullMode = PullMode::PullUp;
 }
 
 pub struct PullDown;
 impl Pull for PullDown {
-    const MODE: u32 = 2;
+    const MODE: PullMode = PullMode::PullDown;
 }
 
 pub struct PullNone;
 impl Pull for PullNone {
-    const MODE: u32 = 0;
+    const MODE: PullMode = PullMode::PullNone;
 }
 
 pub struct Gpio<S: Syscalls>(S);
@@ -73,6 +88,61 @@ impl<S: Syscalls> Gpio<S> {
         })
     }
 
+    /// Configures a pin as an output.
+    pub fn enable_output(pin: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, GPIO_ENABLE_OUTPUT, pin as usize, 0).to_result()
+    }
+
+    /// Drives an output pin high.
+    pub fn set(pin: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, GPIO_SET, pin as usize, 0).to_result()
+    }
+
+    /// Drives an output pin low.
+    pub fn clear(pin: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, GPIO_CLEAR, pin as usize, 0).to_result()
+    }
+
+    /// Inverts the level of an output pin.
+    pub fn toggle(pin: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, GPIO_TOGGLE, pin as usize, 0).to_result()
+    }
+
+    /// Configures a pin as an input with the given pull resistor.
+    pub fn enable_input(pin: u32, mode: PullMode) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, GPIO_ENABLE_INPUT, pin as usize, mode as usize).to_result()
+    }
+
+    /// Reads the level of an input pin. Returns `true` if the pin is high.
+    pub fn read(pin: u32) -> Result<bool, ErrorCode> {
+        let pin_state: u32 =
+            S::command(DRIVER_NUM, GPIO_READ_INPUT, pin as usize, 0).to_result()?;
+        Ok(pin_state != 0)
+    }
+
+    /// Enables interrupts for an input pin. When the pin changes in the way
+    /// described by `edge`, the registered `GpioInterruptListener` is called
+    /// with the pin number and its new state.
+    pub fn set_interrupt(pin: u32, edge: PinInterruptEdge) -> Result<(), ErrorCode> {
+        S::command(
+            DRIVER_NUM,
+            GPIO_ENABLE_INTERRUPTS,
+            pin as usize,
+            edge as usize,
+        )
+        .to_result()
+    }
+
+    /// Disables interrupts for a pin.
+    pub fn disable_interrupts(pin: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, GPIO_DISABLE_INTERRUPTS, pin as usize, 0).to_result()
+    }
+
+    /// Disables a pin, returning it to its low-power state.
+    pub fn disable(pin: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, GPIO_DISABLE, pin as usize, 0).to_result()
+    }
+
     /// Register an interrupt listener
     ///
     /// There can be only one single listener registered at a time.
@@ -85,6 +155,54 @@ impl<S: Syscalls> Gpio<S> {
         S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, listener)
     }
 
+    /// Counts the edges of type `edge` on input pin `pin` over `duration`,
+    /// which is useful for measuring a signal's frequency. The pin's
+    /// interrupts are disabled again before this returns, including when it
+    /// fails.
+    ///
+    /// This takes over the GPIO and alarm upcalls while it runs, replacing any
+    /// registered `GpioInterruptListener`.
+    #[cfg(feature = "alarm")]
+    pub fn count_edges(
+        pin: u32,
+        edge: PinInterruptEdge,
+        duration: libtock_alarm::Milliseconds,
+    ) -> Result<u32, ErrorCode> {
+        use libtock_alarm::{subscribe::CALLBACK, Convert, DRIVER_NUM as ALARM_DRIVER_NUM};
+        use libtock_platform::share;
+        type Alarm<S> = libtock_alarm::Alarm<S>;
+
+        let edges = Cell::new(0);
+        let listener = GpioInterruptListener(|index, _| {
+            if index == pin {
+                edges.set(edges.get() + 1);
+            }
+        });
+        let expired = Cell::new(false);
+        share::scope::<
+            (
+                Subscribe<_, DRIVER_NUM, 0>,
+                Subscribe<_, ALARM_DRIVER_NUM, CALLBACK>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (gpio, alarm) = handle.split();
+            Self::register_listener(&listener, gpio)?;
+            Self::set_interrupt(pin, edge)?;
+            let result = Alarm::<S>::get_frequency().and_then(|freq| {
+                S::subscribe::<_, _, DefaultConfig, ALARM_DRIVER_NUM, CALLBACK>(alarm, &expired)?;
+                Alarm::<S>::set_relative(duration.to_ticks(freq))?;
+                S::yield_wait_for(|| expired.get());
+                Ok(edges.get())
+            });
+            // The upcalls are unsubscribed when the scope ends.
+            let disabled = Self::disable_interrupts(pin);
+            let count = result?;
+            disabled.map(|()| count)
+        })
+    }
+
     /// Unregister the interrupt listener
     ///
     /// This function may be used even if there was no
@@ -105,8 +223,8 @@ impl<S: Syscalls> Gpio<S> {
 pub struct GpioInterruptListener<F: Fn(u32, GpioState)>(pub F);
 
 impl<F: Fn(u32, GpioState)> Upcall<OneId<DRIVER_NUM, 0>> for GpioInterruptListener<F> {
-    fn upcall(&self, gpio_index: u32, value: u32, _arg2: u32) {
-        self.0(gpio_index, value.into())
+    fn upcall(&self, gpio_index: usize, value: usize, _arg2: usize) {
+        self.0(gpio_index as u32, (value as u32).into())
     }
 }
 
@@ -126,12 +244,12 @@ pub struct Pin<S: Syscalls> {
 
 impl<S: Syscalls> Pin<S> {
     pub fn make_output(&mut self) -> Result<OutputPin<S>, ErrorCode> {
-        Gpio::<S>::enable_gpio_output(self.pin_number)?;
+        Gpio::<S>::enable_output(self.pin_number)?;
         Ok(OutputPin { pin: self })
     }
 
     pub fn make_input<P: Pull>(&self) -> Result<InputPin<S, P>, ErrorCode> {
-        Gpio::<S>::enable_gpio_input(self.pin_number, P::MODE)?;
+        Gpio::<S>::enable_input(self.pin_number, P::MODE)?;
         Ok(InputPin {
             pin: self,
             _pull: PhantomData,
@@ -148,10 +266,10 @@ impl<'a, S: Syscalls> OutputPin<'a, S> {
         Gpio::<S>::toggle(self.pin.pin_number)
     }
     pub fn set(&mut self) -> Result<(), ErrorCode> {
-        Gpio::<S>::write(self.pin.pin_number, GpioState::High)
+        Gpio::<S>::set(self.pin.pin_number)
     }
     pub fn clear(&mut self) -> Result<(), ErrorCode> {
-        Gpio::<S>::write(self.pin.pin_number, GpioState::Low)
+        Gpio::<S>::clear(self.pin.pin_number)
     }
 }
 
@@ -162,11 +280,11 @@ pub struct InputPin<'a, S: Syscalls, P: Pull> {
 
 impl<'a, S: Syscalls, P: Pull> InputPin<'a, S, P> {
     pub fn read(&self) -> Result<GpioState, ErrorCode> {
-        Gpio::<S>::read(self.pin.pin_number)
+        Gpio::<S>::read(self.pin.pin_number).map(|high| (high as u32).into())
     }
 
     pub fn enable_interrupts(&self, edge: PinInterruptEdge) -> Result<(), ErrorCode> {
-        Gpio::<S>::enable_interrupts(self.pin.pin_number, edge)
+        Gpio::<S>::set_interrupt(self.pin.pin_number, edge)
     }
 
     pub fn disable_interrupts(&self) -> Result<(), ErrorCode> {
@@ -186,56 +304,6 @@ impl<S: Syscalls, P: Pull> Drop for InputPin<'_, S, P> {
     }
 }
 
-// -----------------------------------------------------------------------------
-// Implementation details below
-// -----------------------------------------------------------------------------
-
-impl<S: Syscalls> Gpio<S> {
-    fn enable_gpio_output(pin: u32) -> Result<(), ErrorCode> {
-        S::command(DRIVER_NUM, GPIO_ENABLE_OUTPUT, pin as usize, 0).to_result()
-    }
-
-    fn enable_gpio_input(pin: u32, mode: u32) -> Result<(), ErrorCode> {
-        S::command(DRIVER_NUM, GPIO_ENABLE_INPUT, pin as usize, mode as usize).to_result()
-    }
-
-    fn write(pin: u32, state: GpioState) -> Result<(), ErrorCode> {
-        let action = match state {
-            GpioState::Low => GPIO_CLEAR,
-            _ => GPIO_SET,
-        };
-        S::command(DRIVER_NUM, action, pin as usize, 0).to_result()
-    }
-
-    fn read(pin: u32) -> Result<GpioState, ErrorCode> {
-        let pin_state: u32 =
-            S::command(DRIVER_NUM, GPIO_READ_INPUT, pin as usize, 0).to_result()?;
-        Ok(pin_state.into())
-    }
-
-    fn toggle(pin: u32) -> Result<(), ErrorCode> {
-        S::command(DRIVER_NUM, GPIO_TOGGLE, pin as usize, 0).to_result()
-    }
-
-    fn disable(pin: u32) -> Result<(), ErrorCode> {
-        S::command(DRIVER_NUM, GPIO_DISABLE, pin as usize, 0).to_result()
-    }
-
-    fn enable_interrupts(pin: u32, edge: PinInterruptEdge) -> Result<(), ErrorCode> {
-        S::command(
-            DRIVER_NUM,
-            GPIO_ENABLE_INTERRUPTS,
-            pin as usize,
-            edge as usize,
-        )
-        .to_result()
-    }
-
-    fn disable_interrupts(pin: u32) -> Result<(), ErrorCode> {
-        S::command(DRIVER_NUM, GPIO_DISABLE_INTERRUPTS, pin as usize, 0).to_result()
-    }
-}
-
 #[cfg(test)]
 mod tests;
 
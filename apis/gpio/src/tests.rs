@@ -3,7 +3,10 @@ use core::cell::Cell;
 use libtock_platform::{share, ErrorCode, Syscalls, YieldNoWaitReturn};
 use libtock_unittest::fake::{self, GpioMode, InterruptEdge, PullMode};
 
-use crate::{GpioInterruptListener, GpioState, PinInterruptEdge, PullDown, PullNone, PullUp};
+use crate::{
+    GpioInterruptListener, GpioState, PinInterruptEdge, PullDown, PullMode as Pull, PullNone,
+    PullUp,
+};
 
 type Gpio = super::Gpio<fake::Syscalls>;
 
@@ -21,6 +24,63 @@ fn num_gpio() {
     assert_eq!(Gpio::count(), Ok(10));
 }
 
+// Tests the pin-level functions on Gpio.
+#[test]
+fn pin_functions() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<4>::new();
+    driver.set_missing_gpio(1);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Gpio::enable_output(0), Ok(()));
+    assert_eq!(driver.get_gpio_state(0).unwrap().mode, GpioMode::Output);
+    assert_eq!(Gpio::set(0), Ok(()));
+    assert!(driver.get_gpio_state(0).unwrap().value);
+    assert_eq!(Gpio::clear(0), Ok(()));
+    assert!(!driver.get_gpio_state(0).unwrap().value);
+    assert_eq!(Gpio::toggle(0), Ok(()));
+    assert!(driver.get_gpio_state(0).unwrap().value);
+
+    assert_eq!(Gpio::enable_input(2, Pull::PullUp), Ok(()));
+    assert_eq!(
+        driver.get_gpio_state(2).unwrap().mode,
+        GpioMode::Input(PullMode::PullUp)
+    );
+    assert_eq!(Gpio::enable_input(2, Pull::PullDown), Ok(()));
+    assert_eq!(
+        driver.get_gpio_state(2).unwrap().mode,
+        GpioMode::Input(PullMode::PullDown)
+    );
+    assert_eq!(driver.set_value(2, true), Ok(()));
+    assert_eq!(Gpio::read(2), Ok(true));
+    assert_eq!(driver.set_value(2, false), Ok(()));
+    assert_eq!(Gpio::read(2), Ok(false));
+
+    let fired = Cell::<Option<(u32, GpioState)>>::new(None);
+    let listener = GpioInterruptListener(|gpio, state| fired.set(Some((gpio, state))));
+    share::scope(|subscribe| {
+        assert_eq!(Gpio::register_listener(&listener, subscribe), Ok(()));
+        assert_eq!(Gpio::set_interrupt(2, PinInterruptEdge::Rising), Ok(()));
+        assert_eq!(
+            driver.get_gpio_state(2).unwrap().interrupt_enabled,
+            Some(InterruptEdge::Rising)
+        );
+        assert_eq!(driver.set_value(2, true), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(fired.get(), Some((2, GpioState::High)));
+
+        assert_eq!(Gpio::disable_interrupts(2), Ok(()));
+        assert_eq!(driver.set_value(2, false), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+
+    assert_eq!(Gpio::disable(2), Ok(()));
+    assert_eq!(driver.get_gpio_state(2).unwrap().mode, GpioMode::Disable);
+
+    assert_eq!(Gpio::set(1), Err(ErrorCode::NoDevice));
+    assert_eq!(Gpio::read(7), Err(ErrorCode::Invalid));
+}
+
 // Tests the OutputPin implementation.
 #[test]
 fn output() {
@@ -119,7 +179,7 @@ fn interrupts() {
         gpio_state.set(Some(state));
     });
 
-    assert_eq!(Gpio::enable_interrupts(0, PinInterruptEdge::Either), Ok(()));
+    assert_eq!(Gpio::set_interrupt(0, PinInterruptEdge::Either), Ok(()));
     share::scope(|subscribe| {
         assert_eq!(Gpio::register_listener(&listener, subscribe), Ok(()));
         assert_eq!(driver.set_value(0, true), Ok(()));
@@ -283,7 +343,7 @@ fn subscribe() {
         gpio_state.set(Some(state));
     });
 
-    assert_eq!(Gpio::enable_interrupts(0, PinInterruptEdge::Either), Ok(()));
+    assert_eq!(Gpio::set_interrupt(0, PinInterruptEdge::Either), Ok(()));
     share::scope(|subscribe| {
         assert_eq!(Gpio::register_listener(&listener, subscribe), Ok(()));
         assert_eq!(driver.set_value(0, true), Ok(()));
@@ -294,3 +354,62 @@ fn subscribe() {
     assert_eq!(driver.set_value(0, false), Ok(()));
     assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
 }
+
+#[cfg(feature = "alarm")]
+#[test]
+fn count_edges() {
+    use libtock_alarm::Milliseconds;
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<2>::new();
+    kernel.add_driver(&driver);
+    let alarm = fake::Alarm::new(1000);
+    kernel.add_driver(&alarm);
+
+    // The pulses happen once interrupts are enabled, before the window ends.
+    for _ in 0..5 {
+        assert_eq!(driver.pulse(1), Ok(()));
+    }
+    assert_eq!(driver.pulse(0), Ok(()));
+    assert_eq!(
+        Gpio::count_edges(1, PinInterruptEdge::Rising, Milliseconds(100)),
+        Ok(5)
+    );
+    assert_eq!(driver.get_gpio_state(1).unwrap().interrupt_enabled, None);
+
+    for _ in 0..3 {
+        assert_eq!(driver.pulse(1), Ok(()));
+    }
+    assert_eq!(
+        Gpio::count_edges(1, PinInterruptEdge::Either, Milliseconds(100)),
+        Ok(6)
+    );
+
+    assert_eq!(
+        Gpio::count_edges(2, PinInterruptEdge::Either, Milliseconds(100)),
+        Err(ErrorCode::Invalid)
+    );
+}
+
+#[cfg(feature = "alarm")]
+#[test]
+fn count_edges_no_alarm() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Gpio::<2>::new();
+    kernel.add_driver(&driver);
+
+    // The interrupt is disabled even though the alarm cannot be used.
+    assert_eq!(
+        Gpio::count_edges(0, PinInterruptEdge::Rising, libtock_alarm::Milliseconds(1)),
+        Err(ErrorCode::NoDevice)
+    );
+    assert_eq!(driver.get_gpio_state(0).unwrap().interrupt_enabled, None);
+    assert!(kernel
+        .take_syscall_log()
+        .contains(&libtock_unittest::SyscallLogEntry::Command {
+            driver_id: 4,
+            command_id: 8,
+            argument0: 0,
+            argument1: 0,
+        }));
+}
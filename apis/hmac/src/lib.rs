@@ -0,0 +1,189 @@
+#![no_std]
+
+use core::marker::PhantomData;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::{StandardResult, StandardResultArg1, Subscribe, UpcallResult};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// A message authentication code computation using the HMAC driver.
+///
+/// The kernel keeps one HMAC in progress per process, so only one `Hmac`
+/// should be in use at a time.
+///
+/// # Example
+/// ```ignore
+/// use libtock::hmac::{Hmac, HmacAlgorithm};
+///
+/// let mut hmac = Hmac::new(HmacAlgorithm::Sha256);
+/// hmac.set_key(b"secret key")?;
+/// hmac.update(b"message")?;
+/// if !hmac.verify(&received_mac)? {
+///     // reject the message
+/// }
+/// ```
+pub struct Hmac<S: Syscalls> {
+    algorithm: HmacAlgorithm,
+    _syscalls: PhantomData<S>,
+}
+
+impl<S: Syscalls> Hmac<S> {
+    /// Creates an HMAC computation using `algorithm`. `set_key` must be called
+    /// before adding data.
+    pub fn new(algorithm: HmacAlgorithm) -> Self {
+        Hmac {
+            algorithm,
+            _syscalls: PhantomData,
+        }
+    }
+
+    /// Returns the algorithm the HMAC is computed with.
+    pub fn algorithm(&self) -> HmacAlgorithm {
+        self.algorithm
+    }
+
+    /// Configures the kernel to compute an HMAC with `key`, discarding any
+    /// data added so far. The kernel copies the key while handling this call,
+    /// and `key` is unallowed before this returns.
+    ///
+    /// Returns `Err(ErrorCode::NoSupport)` if the kernel does not support the
+    /// algorithm.
+    pub fn set_key(&mut self, key: &[u8]) -> Result<(), ErrorCode> {
+        share::scope::<AllowRo<_, DRIVER_NUM, { allow_ro::KEY }>, _, _>(|allow_ro| {
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::KEY }>(allow_ro, key)?;
+            S::command(
+                DRIVER_NUM,
+                command::SET_ALGORITHM,
+                self.algorithm as usize,
+                0,
+            )
+            .to_result()
+        })
+    }
+
+    /// Adds `data` to the HMAC, blocking until the kernel has processed it.
+    pub fn update(&mut self, data: &[u8]) -> Result<(), ErrorCode> {
+        let done = StandardResult::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::DATA }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::DATA }>(allow_ro, data)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::UPDATE, 0, 0).to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()
+        })
+    }
+
+    /// Completes the HMAC and writes it to the start of `out`. Returns the
+    /// length of the HMAC.
+    ///
+    /// Returns `Err(ErrorCode::Size)`, without finishing the HMAC, if `out` is
+    /// shorter than the algorithm's digest.
+    pub fn finalize(self, out: &mut [u8]) -> Result<usize, ErrorCode> {
+        let len = self.algorithm.digest_len();
+        let out = out.get_mut(..len).ok_or(ErrorCode::Size)?;
+        let done = StandardResult::new(None);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::DIGEST }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::DIGEST }>(allow_rw, out)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::FINISH, 0, 0).to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()
+        })?;
+        Ok(len)
+    }
+
+    /// Completes the HMAC and checks whether it equals `expected`. The
+    /// comparison is done by the kernel, in constant time, so the HMAC itself
+    /// never reaches userspace.
+    ///
+    /// Returns `Err(ErrorCode::Size)`, without finishing the HMAC, if
+    /// `expected` is not as long as the algorithm's digest.
+    pub fn verify(self, expected: &[u8]) -> Result<bool, ErrorCode> {
+        if expected.len() != self.algorithm.digest_len() {
+            return Err(ErrorCode::Size);
+        }
+        let done = StandardResultArg1::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::COMPARE }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::COMPARE }>(allow_ro, expected)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::VERIFY_FINISH, 0, 0).to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>().map(|equal| equal != 0)
+        })
+    }
+}
+
+/// The hash algorithms the HMAC driver may support.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HmacAlgorithm {
+    Sha256 = 0,
+    Sha384 = 1,
+    Sha512 = 2,
+}
+
+impl HmacAlgorithm {
+    /// Returns the length of the algorithm's HMACs, in bytes.
+    pub fn digest_len(self) -> usize {
+        match self {
+            HmacAlgorithm::Sha256 => 32,
+            HmacAlgorithm::Sha384 => 48,
+            HmacAlgorithm::Sha512 => 64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40003;
+
+// Command IDs
+#[allow(unused)]
+mod command {
+    pub const SET_ALGORITHM: u32 = 0;
+    pub const RUN: u32 = 1;
+    pub const UPDATE: u32 = 2;
+    pub const FINISH: u32 = 3;
+    pub const VERIFY: u32 = 4;
+    pub const VERIFY_FINISH: u32 = 5;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const KEY: u32 = 0;
+    pub const DATA: u32 = 1;
+    pub const COMPARE: u32 = 2;
+}
+
+mod allow_rw {
+    pub const DIGEST: u32 = 2;
+}
@@ -0,0 +1,108 @@
+use super::HmacAlgorithm;
+use libtock_platform::ErrorCode;
+use libtock_unittest::{fake, SyscallLogEntry};
+
+type Hmac = super::Hmac<fake::Syscalls>;
+
+// HMAC-SHA-256 of "what do ya want for nothing?" with key "Jefe", from RFC 4231
+// test case 2.
+const JEFE_HMAC: [u8; 32] = [
+    0x5b, 0xdc, 0xc1, 0x46, 0xbf, 0x60, 0x75, 0x4e, 0x6a, 0x04, 0x24, 0x26, 0x08, 0x95, 0x75, 0xc7,
+    0x5a, 0x00, 0x3f, 0x08, 0x9d, 0x27, 0x39, 0x83, 0x9d, 0xec, 0x58, 0xb9, 0x64, 0xec, 0x38, 0x43,
+];
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    let mut hmac = Hmac::new(HmacAlgorithm::Sha256);
+    assert_eq!(hmac.set_key(b"Jefe"), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn unsupported_algorithm() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Hmac::new();
+    kernel.add_driver(&driver);
+
+    let mut hmac = Hmac::new(HmacAlgorithm::Sha384);
+    assert_eq!(hmac.set_key(b"Jefe"), Err(ErrorCode::NoSupport));
+}
+
+#[test]
+fn set_key_unallows() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Hmac::new();
+    kernel.add_driver(&driver);
+
+    let mut hmac = Hmac::new(HmacAlgorithm::Sha256);
+    assert_eq!(hmac.algorithm(), HmacAlgorithm::Sha256);
+    assert_eq!(hmac.set_key(b"Jefe"), Ok(()));
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::AllowRo {
+                driver_num: 0x40003,
+                buffer_num: 0,
+                len: 4,
+            },
+            SyscallLogEntry::Command {
+                driver_id: 0x40003,
+                command_id: 0,
+                argument0: 0,
+                argument1: 0,
+            },
+            SyscallLogEntry::AllowRo {
+                driver_num: 0x40003,
+                buffer_num: 0,
+                len: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn finalize() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Hmac::new();
+    kernel.add_driver(&driver);
+
+    let mut hmac = Hmac::new(HmacAlgorithm::Sha256);
+    assert_eq!(hmac.set_key(b"Jefe"), Ok(()));
+    assert_eq!(hmac.update(b"what do ya want "), Ok(()));
+    assert_eq!(hmac.update(b"for nothing?"), Ok(()));
+    let mut out = [0; 32];
+    assert_eq!(hmac.finalize(&mut out), Ok(32));
+    assert_eq!(out, JEFE_HMAC);
+}
+
+#[test]
+fn finalize_buffer_too_small() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Hmac::new();
+    kernel.add_driver(&driver);
+
+    let mut hmac = Hmac::new(HmacAlgorithm::Sha256);
+    assert_eq!(hmac.set_key(b"Jefe"), Ok(()));
+    let mut out = [0; 16];
+    assert_eq!(hmac.finalize(&mut out), Err(ErrorCode::Size));
+}
+
+#[test]
+fn verify() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Hmac::new();
+    kernel.add_driver(&driver);
+
+    let mut hmac = Hmac::new(HmacAlgorithm::Sha256);
+    assert_eq!(hmac.set_key(b"Jefe"), Ok(()));
+    assert_eq!(hmac.update(b"what do ya want for nothing?"), Ok(()));
+    assert_eq!(hmac.verify(&JEFE_HMAC), Ok(true));
+
+    let mut hmac = Hmac::new(HmacAlgorithm::Sha256);
+    assert_eq!(hmac.set_key(b"Jefe"), Ok(()));
+    assert_eq!(hmac.update(b"what do ya want for something?"), Ok(()));
+    assert_eq!(hmac.verify(&JEFE_HMAC), Ok(false));
+
+    let hmac = Hmac::new(HmacAlgorithm::Sha256);
+    assert_eq!(hmac.verify(&JEFE_HMAC[..16]), Err(ErrorCode::Size));
+}
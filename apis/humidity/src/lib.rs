@@ -0,0 +1,38 @@
+#![no_std]
+
+libtock_sensor_common::single_value_sensor! {
+    /// The humidity sensor driver. Readings are relative humidity, in
+    /// hundredths of a percent.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use libtock::humidity::Humidity;
+    ///
+    /// // Read the humidity, blocking until it is available
+    /// let centi_percent = Humidity::read_humidity()?;
+    /// ```
+    pub struct Humidity;
+    driver_num: DRIVER_NUM;
+    subscribe_num: subscribe::READING;
+    command_num: command::READ_HUMIDITY;
+    /// Reads the humidity, blocking until the reading is available.
+    fn read_humidity() -> u32;
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60001;
+
+// Command IDs
+mod command {
+    pub const READ_HUMIDITY: u32 = 1;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
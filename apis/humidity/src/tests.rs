@@ -0,0 +1,66 @@
+use core::cell::Cell;
+use libtock_platform::{share, DefaultConfig, ErrorCode, Syscalls};
+use libtock_unittest::fake;
+
+type Humidity = super::Humidity<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Humidity::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Humidity::new();
+    kernel.add_driver(&driver);
+    assert_eq!(Humidity::exists(), Ok(()));
+}
+
+#[test]
+fn read_humidity() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Humidity::new();
+    kernel.add_driver(&driver);
+
+    driver.push_reading(4250);
+    driver.push_reading(10000);
+    assert_eq!(Humidity::read_humidity(), Ok(4250));
+    assert_eq!(Humidity::read_humidity(), Ok(10000));
+}
+
+#[test]
+fn read_humidity_busy() {
+    use libtock_unittest::fake::SyscallDriver;
+    let kernel = fake::Kernel::new();
+    let driver = fake::Humidity::new();
+    kernel.add_driver(&driver);
+
+    // Start a read that never completes. The next read fails immediately
+    // rather than waiting for an upcall.
+    assert!(driver.command(1, 0, 0).is_success());
+    assert_eq!(Humidity::read_humidity(), Err(ErrorCode::Busy));
+}
+
+#[test]
+fn stale_upcall_replaced() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Humidity::new();
+    kernel.add_driver(&driver);
+
+    driver.push_reading(1000);
+    driver.push_reading(2000);
+    let stale = Cell::<Option<(u32,)>>::new(None);
+    share::scope(|subscribe| {
+        // Start a read and don't yield for its result, leaving its upcall
+        // queued.
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, 0x60001, 0>(subscribe, &stale).unwrap();
+        assert!(fake::Syscalls::command(0x60001, 1, 0, 0).is_success());
+
+        // The next read replaces the subscription, which discards the queued
+        // upcall, so it gets its own reading.
+        assert_eq!(Humidity::read_humidity(), Ok(2000));
+        assert_eq!(stale.get(), None);
+    });
+}
@@ -0,0 +1,135 @@
+#![no_std]
+
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::{StandardResultArg1, Subscribe, UpcallResult};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The I2C master driver.
+///
+/// Addresses are 7-bit device addresses. A device that does not acknowledge a
+/// transfer causes it to fail with `ErrorCode::NoAck`.
+///
+/// # Example
+/// ```ignore
+/// use libtock::i2c_master::I2cMaster;
+///
+/// // Read two bytes from register 0x0f of the device at address 0x18
+/// let mut value = [0; 2];
+/// I2cMaster::write_read(0x18, &[0x0f], &mut value)?;
+/// ```
+pub struct I2cMaster<S: Syscalls>(S);
+
+impl<S: Syscalls> I2cMaster<S> {
+    /// Returns `Ok(())` if the I2C master driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Writes `buffer` to the device at `address`.
+    pub fn write(address: u8, buffer: &[u8]) -> Result<(), ErrorCode> {
+        let done = StandardResultArg1::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, buffer)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::WRITE, address as usize, buffer.len())
+                .to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()?;
+            Ok(())
+        })
+    }
+
+    /// Reads from the device at `address` into `buffer`. Returns the number of
+    /// bytes read.
+    pub fn read(address: u8, buffer: &mut [u8]) -> Result<usize, ErrorCode> {
+        let done = StandardResultArg1::new(None);
+        let len = buffer.len();
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::READ }>(allow_rw, buffer)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::READ, address as usize, len)
+                .to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()
+        })
+    }
+
+    /// Writes `write` to the device at `address`, then reads from it into
+    /// `read` without releasing the bus in between. Returns the number of bytes
+    /// read.
+    ///
+    /// Both buffers are unallowed before this returns, including when the
+    /// transfer fails or one of the buffers cannot be shared.
+    pub fn write_read(address: u8, write: &[u8], read: &mut [u8]) -> Result<usize, ErrorCode> {
+        // The kernel takes both lengths packed into a single argument.
+        if write.len() > u16::MAX as usize || read.len() > u16::MAX as usize {
+            return Err(ErrorCode::Size);
+        }
+        let lengths = write.len() | read.len() << 16;
+        let done = StandardResultArg1::new(None);
+        // The shares are all released when the scope ends, whichever step
+        // returns early.
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, allow_rw, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, write)?;
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::READ }>(allow_rw, read)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::WRITE_READ, address as usize, lengths)
+                .to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20003;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const WRITE: u32 = 1;
+    pub const READ: u32 = 2;
+    pub const WRITE_READ: u32 = 3;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 0;
+}
+
+mod allow_rw {
+    pub const READ: u32 = 0;
+}
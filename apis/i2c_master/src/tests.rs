@@ -0,0 +1,143 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::{fake, ExpectedSyscall, SyscallLogEntry};
+
+use super::{allow_ro, allow_rw, DRIVER_NUM};
+
+type I2cMaster = super::I2cMaster<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(I2cMaster::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::I2cMaster::new();
+    kernel.add_driver(&driver);
+
+    driver.push_response(Ok(&[]));
+    assert_eq!(I2cMaster::write(0x40, &[1, 2, 3]), Ok(()));
+    let writes = driver.take_writes();
+    assert_eq!(writes.len(), 1);
+    assert_eq!(writes[0].0, 0x40);
+    assert_eq!(writes[0].1, [1, 2, 3]);
+}
+
+#[test]
+fn read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::I2cMaster::new();
+    kernel.add_driver(&driver);
+
+    // The device may return less data than the buffer can hold.
+    let mut buffer = [0; 4];
+    driver.push_response(Ok(&[9, 8]));
+    assert_eq!(I2cMaster::read(0x40, &mut buffer), Ok(2));
+    assert_eq!(buffer, [9, 8, 0, 0]);
+    assert_eq!(driver.take_writes(), []);
+}
+
+#[test]
+fn write_read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::I2cMaster::new();
+    kernel.add_driver(&driver);
+
+    let mut buffer = [0; 2];
+    driver.push_response(Ok(&[0x12, 0x34]));
+    assert_eq!(I2cMaster::write_read(0x18, &[0x0f], &mut buffer), Ok(2));
+    assert_eq!(buffer, [0x12, 0x34]);
+    let writes = driver.take_writes();
+    assert_eq!(writes.len(), 1);
+    assert_eq!(writes[0].0, 0x18);
+    assert_eq!(writes[0].1, [0x0f]);
+}
+
+#[test]
+fn nak() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::I2cMaster::new();
+    kernel.add_driver(&driver);
+
+    let mut buffer = [0; 2];
+    driver.push_response(Err(ErrorCode::NoAck));
+    assert_eq!(I2cMaster::write(0x50, &[0]), Err(ErrorCode::NoAck));
+    driver.push_response(Err(ErrorCode::NoAck));
+    assert_eq!(I2cMaster::read(0x50, &mut buffer), Err(ErrorCode::NoAck));
+    driver.push_response(Err(ErrorCode::NoAck));
+    assert_eq!(
+        I2cMaster::write_read(0x50, &[0], &mut buffer),
+        Err(ErrorCode::NoAck)
+    );
+}
+
+#[test]
+fn write_read_unallows_on_failure() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::I2cMaster::new();
+    kernel.add_driver(&driver);
+
+    // Fail the second allow, after the write buffer has been shared.
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRo {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_ro::WRITE,
+        return_error: None,
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::AllowRw {
+        driver_num: DRIVER_NUM,
+        buffer_num: allow_rw::READ,
+        return_error: Some(ErrorCode::NoMem),
+    });
+    let mut buffer = [0; 2];
+    assert_eq!(
+        I2cMaster::write_read(0x18, &[0x0f], &mut buffer),
+        Err(ErrorCode::NoMem)
+    );
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::AllowRo {
+                driver_num: DRIVER_NUM,
+                buffer_num: allow_ro::WRITE,
+                len: 1,
+            },
+            SyscallLogEntry::AllowRw {
+                driver_num: DRIVER_NUM,
+                buffer_num: allow_rw::READ,
+                len: 2,
+            },
+            // The upcall is unsubscribed, and both buffers unallowed, in the
+            // reverse of the order they appear in the scope.
+            SyscallLogEntry::Subscribe {
+                driver_num: DRIVER_NUM,
+                subscribe_num: 0,
+            },
+            SyscallLogEntry::AllowRw {
+                driver_num: DRIVER_NUM,
+                buffer_num: allow_rw::READ,
+                len: 0,
+            },
+            SyscallLogEntry::AllowRo {
+                driver_num: DRIVER_NUM,
+                buffer_num: allow_ro::WRITE,
+                len: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn write_read_too_long() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::I2cMaster::new();
+    kernel.add_driver(&driver);
+
+    let mut buffer = [0; 0x10000];
+    assert_eq!(
+        I2cMaster::write_read(0x18, &[0x0f], &mut buffer),
+        Err(ErrorCode::Size)
+    );
+    assert_eq!(kernel.take_syscall_log(), []);
+}
@@ -0,0 +1,162 @@
+#![no_std]
+
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::{StandardResult, Subscribe, UpcallResult, ValueResult};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The IEEE 802.15.4 radio driver, which sends and receives raw frames.
+///
+/// # Example
+/// ```ignore
+/// use libtock::ieee802154::Radio;
+///
+/// Radio::set_pan(0xabcd)?;
+/// Radio::set_address(0x0001)?;
+/// Radio::transmit(b"frame")?;
+/// ```
+pub struct Radio<S: Syscalls>(S);
+
+impl<S: Syscalls> Radio<S> {
+    /// Returns `Ok(())` if the radio driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Returns `Ok(())` if the radio is on, and `Err(ErrorCode::Off)` if it
+    /// is off.
+    pub fn is_on() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::STATUS, 0, 0).to_result()
+    }
+
+    pub fn turn_on() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::TURN_ON, 0, 0).to_result()
+    }
+
+    pub fn turn_off() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::TURN_OFF, 0, 0).to_result()
+    }
+
+    /// Sets the radio's short (16-bit) address.
+    pub fn set_address(address: u16) -> Result<(), ErrorCode> {
+        Self::configure(command::SET_SHORT_ADDR, address as usize)
+    }
+
+    /// Sets the PAN ID the radio sends and receives frames on.
+    pub fn set_pan(pan: u16) -> Result<(), ErrorCode> {
+        Self::configure(command::SET_PAN, pan as usize)
+    }
+
+    /// Sets the radio channel. 802.15.4 uses channels 11 to 26 in the 2.4 GHz
+    /// band.
+    pub fn set_channel(channel: u8) -> Result<(), ErrorCode> {
+        Self::configure(command::SET_CHAN, channel as usize)
+    }
+
+    /// Sends `frame`, and blocks until the radio has transmitted it.
+    pub fn transmit(frame: &[u8]) -> Result<(), ErrorCode> {
+        let done = StandardResult::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::FRAME_TRANSMITTED }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, frame)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::FRAME_TRANSMITTED }>(
+                subscribe, &done,
+            )?;
+            S::command(DRIVER_NUM, command::TRANSMIT, frame.len(), 0)
+                .to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()
+        })
+    }
+
+    /// Blocks until a frame is received, copies it into `buffer`, and returns
+    /// its length. Returns `Err(ErrorCode::Size)` if the frame is longer than
+    /// `buffer`, in which case `buffer` holds the start of the frame.
+    ///
+    /// Frames are only received while `buffer` is shared, that is, during a
+    /// call to `receive`. A buffer of `MAX_FRAME_LEN` bytes fits any frame.
+    pub fn receive(buffer: &mut [u8]) -> Result<usize, ErrorCode> {
+        // Nothing would ever arrive, so fail rather than block forever.
+        Self::is_on()?;
+        let received = ValueResult::new(None);
+        let buffer_len = buffer.len();
+        let len = share::scope::<
+            (
+                Subscribe<_, DRIVER_NUM, { subscribe::FRAME_RECEIVED }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (subscribe, allow_rw) = handle.split();
+            // Subscribe first, so a frame the kernel delivers as soon as the
+            // buffer is shared is not missed.
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::FRAME_RECEIVED }>(
+                subscribe, &received,
+            )?;
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::READ }>(allow_rw, buffer)?;
+            received.upcall_result_yield::<S>()
+        })? as usize;
+        if len > buffer_len {
+            return Err(ErrorCode::Size);
+        }
+        Ok(len)
+    }
+
+    // Changes one configuration value and applies it.
+    fn configure(command_num: u32, value: usize) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command_num, value, 0).to_result::<(), ErrorCode>()?;
+        S::command(DRIVER_NUM, command::COMMIT_CFG, 0, 0).to_result()
+    }
+}
+
+/// The largest frame the radio can send or receive, in bytes.
+pub const MAX_FRAME_LEN: usize = 127;
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x30001;
+
+// Command IDs
+#[allow(unused)]
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const STATUS: u32 = 1;
+    pub const SET_SHORT_ADDR: u32 = 2;
+    pub const SET_PAN: u32 = 4;
+    pub const SET_CHAN: u32 = 5;
+    pub const SET_TX_PWR: u32 = 6;
+    pub const COMMIT_CFG: u32 = 7;
+    pub const GET_SHORT_ADDR: u32 = 8;
+    pub const GET_PAN: u32 = 10;
+    pub const GET_CHAN: u32 = 11;
+    pub const GET_TX_PWR: u32 = 12;
+    pub const TRANSMIT: u32 = 27;
+    pub const TURN_ON: u32 = 30;
+    pub const TURN_OFF: u32 = 31;
+}
+
+mod subscribe {
+    pub const FRAME_RECEIVED: u32 = 0;
+    pub const FRAME_TRANSMITTED: u32 = 1;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 0;
+}
+
+mod allow_rw {
+    pub const READ: u32 = 0;
+}
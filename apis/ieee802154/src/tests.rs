@@ -0,0 +1,69 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Radio = super::Radio<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Radio::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn configure() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Radio::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Radio::exists(), Ok(()));
+    assert_eq!(Radio::set_address(0x1234), Ok(()));
+    assert_eq!(Radio::set_pan(0xabcd), Ok(()));
+    assert_eq!(Radio::set_channel(11), Ok(()));
+    assert_eq!(Radio::set_channel(10), Err(ErrorCode::Invalid));
+    assert_eq!(driver.address(), 0x1234);
+    assert_eq!(driver.pan(), 0xabcd);
+    assert_eq!(driver.channel(), 11);
+}
+
+#[test]
+fn loopback() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Radio::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Radio::transmit(b"ping"), Ok(()));
+    assert_eq!(driver.take_transmitted(), [b"ping".to_vec()]);
+
+    let mut buffer = [0; super::MAX_FRAME_LEN];
+    assert_eq!(Radio::receive(&mut buffer), Ok(4));
+    assert_eq!(&buffer[..4], b"ping");
+}
+
+#[test]
+fn receive_too_long() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Radio::new();
+    kernel.add_driver(&driver);
+
+    driver.push_frame(b"longer frame");
+    let mut buffer = [0; 6];
+    assert_eq!(Radio::receive(&mut buffer), Err(ErrorCode::Size));
+    assert_eq!(&buffer, b"longer");
+}
+
+#[test]
+fn off() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Radio::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Radio::turn_off(), Ok(()));
+    assert_eq!(Radio::is_on(), Err(ErrorCode::Off));
+    assert_eq!(Radio::transmit(b"ping"), Err(ErrorCode::Off));
+    assert_eq!(Radio::receive(&mut [0; 8]), Err(ErrorCode::Off));
+    assert!(driver.take_transmitted().is_empty());
+
+    assert_eq!(Radio::turn_on(), Ok(()));
+    assert_eq!(Radio::is_on(), Ok(()));
+    assert_eq!(Radio::transmit(b"ping"), Ok(()));
+}
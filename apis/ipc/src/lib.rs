@@ -0,0 +1,167 @@
+#![no_std]
+
+use core::marker::PhantomData;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::share::{self, Handle};
+use libtock_platform::subscribe::{OneId, Subscribe, Upcall};
+use libtock_platform::{
+    return_variant, syscall_class, DefaultConfig, ErrorCode, Register, ReturnVariant, Syscalls,
+};
+
+/// The client side of inter-process communication: finds services by package
+/// name, shares buffers with them, and notifies them.
+///
+/// # Example
+/// ```ignore
+/// use libtock::ipc::IpcClient;
+///
+/// let service = IpcClient::discover(b"org.tockos.examples.rng")?;
+/// let mut buffer = [0; 32];
+/// IpcClient::share(service, &mut buffer, || IpcClient::notify(service))??;
+/// ```
+pub struct IpcClient<S: Syscalls>(S);
+
+impl<S: Syscalls> IpcClient<S> {
+    /// Returns `Ok(())` if the IPC driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Looks up the service whose package name is `name`, returning its ID.
+    pub fn discover(name: &[u8]) -> Result<u32, ErrorCode> {
+        share::scope::<AllowRo<_, DRIVER_NUM, { allow_ro::SEARCH }>, _, _>(|handle| {
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::SEARCH }>(handle, name)?;
+            S::command(DRIVER_NUM, command::DISCOVER, 0, 0).to_result()
+        })
+    }
+
+    /// Notifies the service `service_id`, which is passed the length of the
+    /// buffer this process shares with it (if any).
+    pub fn notify(service_id: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::CLIENT_NOTIFY, service_id as usize, 0).to_result()
+    }
+
+    /// Shares `buffer` with the service `service_id` while `f` runs, returning
+    /// `f`'s result. The buffer is unshared before `share` returns.
+    ///
+    /// IPC identifies a shared buffer by the service it is shared with, rather
+    /// than by a fixed buffer number, so this makes the Read-Write Allow call
+    /// itself instead of going through `Syscalls::allow_rw`.
+    pub fn share<R, F: FnOnce() -> R>(
+        service_id: u32,
+        buffer: &mut [u8],
+        f: F,
+    ) -> Result<R, ErrorCode> {
+        // Safety: syscall4's documentation indicates it can be used to call
+        // Read-Write Allow. These arguments follow TRD104. buffer stays
+        // mutably borrowed until the Unallow below has revoked the kernel's
+        // access to it, which happens even if f unwinds.
+        let [r0, r1, _, _] = unsafe {
+            S::syscall4::<{ syscall_class::ALLOW_RW }>([
+                DRIVER_NUM.into(),
+                service_id.into(),
+                Register::from_slice(buffer.as_mut_ptr(), buffer.len()),
+                buffer.len().into(),
+            ])
+        };
+        let variant: ReturnVariant = r0.as_u32().into();
+        if variant == return_variant::FAILURE_2_U32 {
+            return Err(ErrorCode::from_kernel(r1.as_u32()));
+        }
+        let _unallow = Unallow::<S> {
+            service_id,
+            _syscalls: PhantomData,
+        };
+        Ok(f())
+    }
+}
+
+// Revokes the kernel's access to the buffer shared with a service when dropped.
+struct Unallow<S: Syscalls> {
+    service_id: u32,
+    _syscalls: PhantomData<S>,
+}
+
+impl<S: Syscalls> Drop for Unallow<S> {
+    fn drop(&mut self) {
+        // Unallow only fails if the driver is missing, in which case nothing
+        // was shared.
+        let _ = S::unallow_rw(DRIVER_NUM, self.service_id);
+    }
+}
+
+/// The service side of inter-process communication: receives notifications
+/// from clients and notifies them back.
+pub struct IpcService<S: Syscalls>(S);
+
+impl<S: Syscalls> IpcService<S> {
+    /// Registers `listener` to be called whenever a client notifies this
+    /// process. The listener is called from within a yield.
+    pub fn register<'share, F: Fn(IpcNotification)>(
+        listener: &'share IpcListener<F>,
+        subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::SERVICE }>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::SERVICE }>(subscribe, listener)
+    }
+
+    /// Notifies the client `client_id`, as identified by an `IpcNotification`.
+    pub fn notify_client(client_id: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SERVICE_NOTIFY, client_id as usize, 0).to_result()
+    }
+}
+
+/// A notification from a client to this process' service.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct IpcNotification {
+    /// The ID of the client, as passed to `IpcService::notify_client`.
+    pub client_id: u32,
+    /// The length of the buffer the client shares with this service, or 0 if
+    /// it shares none.
+    pub len: usize,
+}
+
+/// A wrapper around a closure to be registered and called when a client
+/// notifies this process.
+///
+/// ```ignore
+/// let listener = IpcListener(|notification| {
+///     // make use of the notification
+/// });
+/// ```
+pub struct IpcListener<F: Fn(IpcNotification)>(pub F);
+
+impl<F: Fn(IpcNotification)> Upcall<OneId<DRIVER_NUM, { subscribe::SERVICE }>> for IpcListener<F> {
+    fn upcall(&self, client_id: usize, len: usize, _: usize) {
+        self.0(IpcNotification {
+            client_id: client_id as u32,
+            len,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x10000;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const DISCOVER: u32 = 1;
+    // A client notifies a service.
+    pub const CLIENT_NOTIFY: u32 = 2;
+    // A service notifies a client.
+    pub const SERVICE_NOTIFY: u32 = 3;
+}
+
+mod subscribe {
+    pub const SERVICE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const SEARCH: u32 = 0;
+}
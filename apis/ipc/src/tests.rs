@@ -0,0 +1,146 @@
+extern crate std;
+
+use core::cell::Cell;
+use libtock_platform::{share, ErrorCode, Syscalls, YieldNoWaitReturn};
+use libtock_unittest::fake;
+use std::vec;
+
+use crate::{IpcListener, IpcNotification};
+
+type IpcClient = super::IpcClient<fake::Syscalls>;
+type IpcService = super::IpcService<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(IpcClient::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::new(b"org.tockos.client");
+    kernel.add_driver(&driver);
+    assert_eq!(IpcClient::exists(), Ok(()));
+}
+
+#[test]
+fn discover() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::new(b"org.tockos.client");
+    kernel.add_driver(&driver);
+    let service = driver.add_process(b"org.tockos.service");
+
+    assert_eq!(IpcClient::discover(b"org.tockos.service"), Ok(service));
+    assert_eq!(IpcClient::discover(b"org.tockos.client"), Ok(0));
+    assert_eq!(
+        IpcClient::discover(b"org.tockos.missing"),
+        Err(ErrorCode::NoDevice)
+    );
+}
+
+#[test]
+fn share_and_notify() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::new(b"org.tockos.client");
+    kernel.add_driver(&driver);
+    let service = driver.add_process(b"org.tockos.service");
+
+    let mut buffer = *b"request";
+    assert_eq!(
+        IpcClient::share(service, &mut buffer, || IpcClient::notify(service)),
+        Ok(Ok(()))
+    );
+    assert_eq!(
+        driver.take_service_notifications(),
+        [(service, b"request".to_vec())]
+    );
+
+    // The buffer is no longer shared once share returns.
+    assert_eq!(IpcClient::notify(service), Ok(()));
+    assert_eq!(driver.take_service_notifications(), [(service, vec![])]);
+
+    assert_eq!(
+        IpcClient::share(7, &mut buffer, || ()),
+        Err(ErrorCode::Invalid)
+    );
+}
+
+// The process under test is both the client and the service, so fake::Ipc
+// routes the client's notification to the service.
+#[test]
+fn service() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::new(b"org.tockos.echo");
+    kernel.add_driver(&driver);
+    let client = driver.add_process(b"org.tockos.client");
+
+    let notification = Cell::new(None);
+    let listener = IpcListener(|n| notification.set(Some(n)));
+    share::scope(|subscribe| {
+        assert_eq!(IpcService::register(&listener, subscribe), Ok(()));
+
+        let service = IpcClient::discover(b"org.tockos.echo").unwrap();
+        let mut buffer = [0; 16];
+        IpcClient::share(service, &mut buffer, || {
+            assert_eq!(IpcClient::notify(service), Ok(()));
+            assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        })
+        .unwrap();
+        assert_eq!(
+            notification.get(),
+            Some(IpcNotification {
+                client_id: 0,
+                len: 16
+            })
+        );
+
+        driver.notify_service(client);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(
+            notification.get(),
+            Some(IpcNotification {
+                client_id: client,
+                len: 0
+            })
+        );
+    });
+
+    assert_eq!(IpcService::notify_client(client), Ok(()));
+    assert_eq!(driver.take_client_notifications(), [client]);
+    assert_eq!(IpcService::notify_client(9), Err(ErrorCode::Invalid));
+}
+
+// Tock's IPC driver uses command 2 to notify a service and command 3 to notify
+// a client.
+#[test]
+fn notify_command_numbers() {
+    use libtock_unittest::SyscallLogEntry;
+    let kernel = fake::Kernel::new();
+    let driver = fake::Ipc::new(b"org.tockos.client");
+    kernel.add_driver(&driver);
+    let other = driver.add_process(b"org.tockos.other");
+
+    kernel.take_syscall_log();
+    assert_eq!(IpcClient::notify(other), Ok(()));
+    assert_eq!(IpcService::notify_client(other), Ok(()));
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Command {
+                driver_id: 0x10000,
+                command_id: 2,
+                argument0: 1,
+                argument1: 0,
+            },
+            SyscallLogEntry::Command {
+                driver_id: 0x10000,
+                command_id: 3,
+                argument0: 1,
+                argument1: 0,
+            },
+        ]
+    );
+    assert_eq!(driver.take_service_notifications(), [(other, vec![])]);
+    assert_eq!(driver.take_client_notifications(), [other]);
+}
@@ -0,0 +1,143 @@
+#![no_std]
+
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::{StandardResultArg1, Subscribe, UpcallResult};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The key-value store driver.
+///
+/// Keys and values are arbitrary byte strings. Errors follow the driver's
+/// contract:
+/// * `get` and `delete` return `ErrorCode::NoSupport` if the key is not in the
+///   store.
+/// * `add` returns `ErrorCode::NoSupport` if the key is already in the store.
+/// * `get` returns `ErrorCode::Size` if the value does not fit in the buffer.
+/// * Any operation may return `ErrorCode::Fail` if the underlying storage
+///   fails.
+///
+/// # Example
+/// ```ignore
+/// use libtock::kv::KvStore;
+///
+/// KvStore::set(b"greeting", b"hello")?;
+/// let mut value = [0; 16];
+/// let len = KvStore::get(b"greeting", &mut value)?;
+/// assert_eq!(&value[..len], b"hello");
+/// ```
+pub struct KvStore<S: Syscalls>(S);
+
+impl<S: Syscalls> KvStore<S> {
+    /// Returns `Ok(())` if the key-value store driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Looks up `key` and copies its value into `value`. Returns the length
+    /// of the value.
+    ///
+    /// If the value is longer than `value`, as much of it as fits is copied
+    /// and `ErrorCode::Size` is returned.
+    pub fn get(key: &[u8], value: &mut [u8]) -> Result<usize, ErrorCode> {
+        let done = StandardResultArg1::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::KEY }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::VALUE_OUT }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_key, allow_value, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::KEY }>(allow_key, key)?;
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::VALUE_OUT }>(allow_value, value)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::GET, 0, 0).to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()
+        })
+    }
+
+    /// Stores `value` under `key`, replacing any existing value.
+    pub fn set(key: &[u8], value: &[u8]) -> Result<(), ErrorCode> {
+        Self::store(command::SET, key, value)
+    }
+
+    /// Stores `value` under `key`, which must not already be in the store.
+    pub fn add(key: &[u8], value: &[u8]) -> Result<(), ErrorCode> {
+        Self::store(command::ADD, key, value)
+    }
+
+    /// Removes `key` and its value from the store.
+    pub fn delete(key: &[u8]) -> Result<(), ErrorCode> {
+        let done = StandardResultArg1::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::KEY }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_key, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::KEY }>(allow_key, key)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::DELETE, 0, 0).to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>().map(|_| ())
+        })
+    }
+
+    // Shares `key` and `value` with the kernel and runs `command_num`, which
+    // stores the value.
+    fn store(command_num: u32, key: &[u8], value: &[u8]) -> Result<(), ErrorCode> {
+        let done = StandardResultArg1::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::KEY }>,
+                AllowRo<_, DRIVER_NUM, { allow_ro::VALUE_IN }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_key, allow_value, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::KEY }>(allow_key, key)?;
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::VALUE_IN }>(allow_value, value)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command_num, 0, 0).to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>().map(|_| ())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x50003;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const GET: u32 = 1;
+    pub const SET: u32 = 2;
+    pub const DELETE: u32 = 3;
+    pub const ADD: u32 = 4;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const KEY: u32 = 0;
+    pub const VALUE_IN: u32 = 1;
+}
+
+mod allow_rw {
+    pub const VALUE_OUT: u32 = 0;
+}
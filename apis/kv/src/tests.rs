@@ -0,0 +1,76 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type KvStore = super::KvStore<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(KvStore::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn set_get() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::KvStore::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(KvStore::exists(), Ok(()));
+    assert_eq!(KvStore::set(b"key", b"value"), Ok(()));
+    assert_eq!(driver.get(b"key"), Some(b"value".to_vec()));
+    let mut value = [0; 8];
+    assert_eq!(KvStore::get(b"key", &mut value), Ok(5));
+    assert_eq!(value[..5], *b"value");
+
+    // set replaces existing values.
+    assert_eq!(KvStore::set(b"key", b"new"), Ok(()));
+    assert_eq!(KvStore::get(b"key", &mut value), Ok(3));
+    assert_eq!(value[..3], *b"new");
+}
+
+#[test]
+fn missing_key() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::KvStore::new();
+    kernel.add_driver(&driver);
+
+    let mut value = [0; 8];
+    assert_eq!(KvStore::get(b"key", &mut value), Err(ErrorCode::NoSupport));
+    assert_eq!(KvStore::delete(b"key"), Err(ErrorCode::NoSupport));
+}
+
+#[test]
+fn add() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::KvStore::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(KvStore::add(b"key", b"first"), Ok(()));
+    assert_eq!(KvStore::add(b"key", b"second"), Err(ErrorCode::NoSupport));
+    assert_eq!(driver.get(b"key"), Some(b"first".to_vec()));
+}
+
+#[test]
+fn delete() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::KvStore::new();
+    kernel.add_driver(&driver);
+
+    driver.insert(b"key", b"value");
+    assert_eq!(KvStore::delete(b"key"), Ok(()));
+    assert_eq!(driver.get(b"key"), None);
+    let mut value = [0; 8];
+    assert_eq!(KvStore::get(b"key", &mut value), Err(ErrorCode::NoSupport));
+}
+
+#[test]
+fn buffer_too_small() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::KvStore::new();
+    kernel.add_driver(&driver);
+
+    driver.insert(b"key", b"long value");
+    let mut value = [0; 4];
+    assert_eq!(KvStore::get(b"key", &mut value), Err(ErrorCode::Size));
+    assert_eq!(value, *b"long");
+}
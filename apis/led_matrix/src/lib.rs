@@ -0,0 +1,115 @@
+#![no_std]
+
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::share;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The LED matrix driver, for two-dimensional grids of LEDs such as the
+/// micro:bit's display. Unlike `Leds`, which numbers each LED, this addresses
+/// LEDs by their (x, y) position, with (0, 0) at the top left.
+///
+/// Upstream Tock has no LED matrix driver; this expects one at driver number
+/// 0x90006 whose commands take the LED's x and y as their arguments. Every
+/// command completes immediately.
+///
+/// # Example
+/// ```ignore
+/// use libtock::led_matrix::LedMatrix;
+///
+/// // Light the top left and bottom right corners.
+/// let (width, height) = LedMatrix::dimensions()?;
+/// LedMatrix::clear()?;
+/// LedMatrix::on(0, 0)?;
+/// LedMatrix::on(width - 1, height - 1)?;
+/// ```
+pub struct LedMatrix<S: Syscalls>(S);
+
+impl<S: Syscalls> LedMatrix<S> {
+    /// Returns `Ok(())` if the LED matrix driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Returns the size of the matrix as `(width, height)`, in LEDs.
+    pub fn dimensions() -> Result<(u32, u32), ErrorCode> {
+        S::command(DRIVER_NUM, command::DIMENSIONS, 0, 0).to_result()
+    }
+
+    /// Turns on the LED at (`x`, `y`). Returns `ErrorCode::Invalid` if it is
+    /// outside the matrix.
+    pub fn on(x: u32, y: u32) -> Result<(), ErrorCode> {
+        Self::led_command(command::ON, x, y)
+    }
+
+    /// Turns off the LED at (`x`, `y`). Returns `ErrorCode::Invalid` if it is
+    /// outside the matrix.
+    pub fn off(x: u32, y: u32) -> Result<(), ErrorCode> {
+        Self::led_command(command::OFF, x, y)
+    }
+
+    /// Toggles the LED at (`x`, `y`). Returns `ErrorCode::Invalid` if it is
+    /// outside the matrix.
+    pub fn toggle(x: u32, y: u32) -> Result<(), ErrorCode> {
+        Self::led_command(command::TOGGLE, x, y)
+    }
+
+    /// Turns off every LED.
+    pub fn clear() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::CLEAR, 0, 0).to_result()
+    }
+
+    /// Sets every LED at once from `bitmap`, which holds one bit per LED, row
+    /// by row from the top: LED (x, y) is on if bit `y * width + x` is set,
+    /// counting from the least significant bit of `bitmap[0]`. Rows are not
+    /// padded to whole bytes. Returns `ErrorCode::Invalid` if `bitmap` is too
+    /// short to cover the matrix.
+    pub fn set_frame(bitmap: &[u8]) -> Result<(), ErrorCode> {
+        let (width, height) = Self::dimensions()?;
+        if bitmap.len() < frame_len(width, height) {
+            return Err(ErrorCode::Invalid);
+        }
+        share::scope::<AllowRo<_, DRIVER_NUM, { allow_ro::FRAME }>, _, _>(|handle| {
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::FRAME }>(handle, bitmap)?;
+            S::command(DRIVER_NUM, command::SET_FRAME, bitmap.len(), 0).to_result()
+        })
+    }
+
+    // Checks that (x, y) is in the matrix, then runs a command on that LED.
+    fn led_command(command_num: u32, x: u32, y: u32) -> Result<(), ErrorCode> {
+        let (width, height) = Self::dimensions()?;
+        if x >= width || y >= height {
+            return Err(ErrorCode::Invalid);
+        }
+        S::command(DRIVER_NUM, command_num, x as usize, y as usize).to_result()
+    }
+}
+
+/// Returns the number of bytes a `set_frame` bitmap needs for a `width` by
+/// `height` matrix.
+pub const fn frame_len(width: u32, height: u32) -> usize {
+    (width as usize * height as usize + 7) / 8
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90006;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const DIMENSIONS: u32 = 1;
+    pub const ON: u32 = 2;
+    pub const OFF: u32 = 3;
+    pub const TOGGLE: u32 = 4;
+    pub const CLEAR: u32 = 5;
+    pub const SET_FRAME: u32 = 6;
+}
+
+mod allow_ro {
+    pub const FRAME: u32 = 0;
+}
@@ -0,0 +1,57 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+use crate::frame_len;
+
+type LedMatrix = super::LedMatrix<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(LedMatrix::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn leds() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::LedMatrix::new(5, 5);
+    kernel.add_driver(&driver);
+
+    assert_eq!(LedMatrix::exists(), Ok(()));
+    assert_eq!(LedMatrix::dimensions(), Ok((5, 5)));
+    assert_eq!(LedMatrix::on(4, 0), Ok(()));
+    assert_eq!(LedMatrix::toggle(0, 4), Ok(()));
+    assert_eq!(driver.get_led(4, 0), Some(true));
+    assert_eq!(driver.get_led(0, 4), Some(true));
+    assert_eq!(LedMatrix::off(4, 0), Ok(()));
+    assert_eq!(LedMatrix::toggle(0, 4), Ok(()));
+    assert_eq!(driver.grid(), [[false; 5]; 5]);
+
+    assert_eq!(LedMatrix::on(5, 0), Err(ErrorCode::Invalid));
+    assert_eq!(LedMatrix::off(0, 5), Err(ErrorCode::Invalid));
+    assert_eq!(LedMatrix::toggle(5, 5), Err(ErrorCode::Invalid));
+
+    assert_eq!(LedMatrix::on(2, 2), Ok(()));
+    assert_eq!(LedMatrix::clear(), Ok(()));
+    assert_eq!(driver.grid(), [[false; 5]; 5]);
+}
+
+#[test]
+fn set_frame() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::LedMatrix::new(4, 3);
+    kernel.add_driver(&driver);
+
+    assert_eq!(frame_len(4, 3), 2);
+    // The top row on, then a diagonal.
+    assert_eq!(LedMatrix::set_frame(&[0b0010_1111, 0b0100]), Ok(()));
+    assert_eq!(
+        driver.grid(),
+        [
+            [true, true, true, true],
+            [false, true, false, false],
+            [false, false, true, false]
+        ]
+    );
+    assert_eq!(LedMatrix::set_frame(&[0xff]), Err(ErrorCode::Invalid));
+}
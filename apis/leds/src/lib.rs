@@ -1,5 +1,6 @@
 #![no_std]
 
+use core::sync::atomic::{AtomicBool, Ordering};
 use libtock_platform::{ErrorCode, Syscalls};
 
 /// The LEDs driver
@@ -17,11 +18,12 @@ pub struct Leds<S: Syscalls>(S);
 impl<S: Syscalls> Leds<S> {
     /// Run a check against the leds capsule to ensure it is present.
     ///
-    /// Returns `Ok(number_of_leds)` if the driver was present. This does not necessarily mean
+    /// Returns `Ok(number_of_leds)` if the driver was present, and
+    /// `Err(ErrorCode::NoDevice)` otherwise. This does not necessarily mean
     /// that the driver is working, as it may still fail to allocate grant
     /// memory.
     pub fn count() -> Result<u32, ErrorCode> {
-        S::command(DRIVER_NUM, LEDS_COUNT, 0, 0).to_result()
+        S::driver_count(DRIVER_NUM)
     }
 
     pub fn on(led: u32) -> Result<(), ErrorCode> {
@@ -35,8 +37,49 @@ impl<S: Syscalls> Leds<S> {
     pub fn toggle(led: u32) -> Result<(), ErrorCode> {
         S::command(DRIVER_NUM, LED_TOGGLE, led as usize, 0).to_result()
     }
+
+    /// Turns each LED on or off to match `mask`: LED `n` is turned on if bit
+    /// `n` is set, and off otherwise. Bits beyond the number of LEDs are
+    /// ignored.
+    ///
+    /// Kernels that support the set-mask command update every LED with a
+    /// single system call. On other kernels, each LED is set individually,
+    /// after the first call finds that the command is missing.
+    pub fn set_mask(mask: u32) -> Result<(), ErrorCode> {
+        // Kernels without the set-mask command return NoSupport, which is how
+        // its absence is detected.
+        if !SET_MASK_UNSUPPORTED.load(Ordering::Relaxed) {
+            match S::command(DRIVER_NUM, LED_SET_MASK, mask as usize, 0).to_result() {
+                Err(ErrorCode::NoSupport) => SET_MASK_UNSUPPORTED.store(true, Ordering::Relaxed),
+                result => return result,
+            }
+        }
+        for led in 0..Self::count()?.min(u32::BITS) {
+            if mask & 1 << led != 0 {
+                Self::on(led)?;
+            } else {
+                Self::off(led)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Lights each LED in turn for `delay`, so that a single lit LED runs
+    /// across all of them, and then turns them all off.
+    #[cfg(feature = "alarm")]
+    pub fn chase(delay: libtock_alarm::Milliseconds) -> Result<(), ErrorCode> {
+        for led in 0..Self::count()?.min(u32::BITS) {
+            Self::set_mask(1 << led)?;
+            libtock_alarm::Alarm::<S>::sleep_for(delay)?;
+        }
+        Self::set_mask(0)
+    }
 }
 
+// Set once the kernel has rejected the set-mask command, so that later calls
+// to set_mask don't issue it again.
+static SET_MASK_UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+
 #[cfg(test)]
 mod tests;
 
@@ -47,7 +90,7 @@ mod tests;
 const DRIVER_NUM: u32 = 2;
 
 // Command IDs
-const LEDS_COUNT: u32 = 0;
 const LED_ON: u32 = 1;
 const LED_OFF: u32 = 2;
 const LED_TOGGLE: u32 = 3;
+const LED_SET_MASK: u32 = 4;
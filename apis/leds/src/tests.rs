@@ -1,6 +1,9 @@
+use core::sync::atomic::Ordering;
 use libtock_platform::ErrorCode;
 use libtock_unittest::fake;
 
+use super::SET_MASK_UNSUPPORTED;
+
 type Leds = super::Leds<fake::Syscalls>;
 
 #[test]
@@ -39,6 +42,16 @@ fn on() {
     assert_eq!(driver.get_led(0), Some(true));
 }
 
+#[test]
+fn on_syscalls() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<10>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Leds::on(3), Ok(()));
+    kernel.expect().command(2, 1, 3, 0).check();
+}
+
 #[test]
 fn off() {
     let kernel = fake::Kernel::new();
@@ -84,3 +97,116 @@ fn no_led() {
         assert_eq!(driver.get_led(led), Some(false));
     }
 }
+
+// set_mask remembers whether the kernel supports the set-mask command in a
+// process-wide flag, so everything that calls it is tested together rather
+// than in tests that could run concurrently. Each part starts with a new
+// kernel, so it clears the flag first.
+#[test]
+fn set_mask() {
+    set_mask_leds();
+    set_mask_syscalls();
+    #[cfg(feature = "alarm")]
+    chase();
+}
+
+fn set_mask_leds() {
+    SET_MASK_UNSUPPORTED.store(false, Ordering::Relaxed);
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<4>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Leds::set_mask(0b0101), Ok(()));
+    assert_eq!(driver.get_led(0), Some(true));
+    assert_eq!(driver.get_led(1), Some(false));
+    assert_eq!(driver.get_led(2), Some(true));
+    assert_eq!(driver.get_led(3), Some(false));
+
+    // Bits beyond the last LED are ignored.
+    assert_eq!(Leds::set_mask(0xffff_fffa), Ok(()));
+    assert_eq!(driver.get_led(0), Some(false));
+    assert_eq!(driver.get_led(1), Some(true));
+    assert_eq!(driver.get_led(2), Some(false));
+    assert_eq!(driver.get_led(3), Some(true));
+}
+
+fn set_mask_syscalls() {
+    use super::{DRIVER_NUM, LED_OFF, LED_ON, LED_SET_MASK};
+
+    SET_MASK_UNSUPPORTED.store(false, Ordering::Relaxed);
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<4>::new();
+    kernel.add_driver(&driver);
+
+    // A legacy kernel rejects the set-mask command, so each LED is set
+    // individually.
+    assert_eq!(Leds::set_mask(0b0110), Ok(()));
+    kernel
+        .expect()
+        .command(DRIVER_NUM, LED_SET_MASK, 0b0110, 0)
+        .command(DRIVER_NUM, 0, 0, 0)
+        .command(DRIVER_NUM, LED_OFF, 0, 0)
+        .command(DRIVER_NUM, LED_ON, 1, 0)
+        .command(DRIVER_NUM, LED_ON, 2, 0)
+        .command(DRIVER_NUM, LED_OFF, 3, 0)
+        .check();
+
+    // Having been rejected once, the set-mask command isn't tried again.
+    assert_eq!(Leds::set_mask(0b0001), Ok(()));
+    kernel
+        .expect()
+        .command(DRIVER_NUM, 0, 0, 0)
+        .command(DRIVER_NUM, LED_ON, 0, 0)
+        .command(DRIVER_NUM, LED_OFF, 1, 0)
+        .command(DRIVER_NUM, LED_OFF, 2, 0)
+        .command(DRIVER_NUM, LED_OFF, 3, 0)
+        .check();
+    drop(kernel);
+
+    // A capable kernel sets every LED with one command.
+    SET_MASK_UNSUPPORTED.store(false, Ordering::Relaxed);
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<4>::new();
+    kernel.add_driver(&driver);
+    driver.set_mask_supported(true);
+    assert_eq!(Leds::set_mask(0b1001), Ok(()));
+    kernel
+        .expect()
+        .command(DRIVER_NUM, LED_SET_MASK, 0b1001, 0)
+        .check();
+    assert_eq!(driver.get_led(0), Some(true));
+    assert_eq!(driver.get_led(1), Some(false));
+    assert_eq!(driver.get_led(2), Some(false));
+    assert_eq!(driver.get_led(3), Some(true));
+}
+
+#[cfg(feature = "alarm")]
+fn chase() {
+    use libtock_unittest::SyscallLogEntry;
+
+    SET_MASK_UNSUPPORTED.store(false, Ordering::Relaxed);
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<3>::new();
+    kernel.add_driver(&driver);
+    let alarm = fake::Alarm::new(1000);
+    kernel.add_driver(&alarm);
+
+    assert_eq!(Leds::chase(libtock_alarm::Milliseconds(100)), Ok(()));
+    for led in 0..3 {
+        assert_eq!(driver.get_led(led), Some(false));
+    }
+    // Each LED is turned on in turn.
+    let lit = kernel
+        .take_syscall_log()
+        .into_iter()
+        .filter_map(|entry| match entry {
+            SyscallLogEntry::Command {
+                driver_id: 2,
+                command_id: 1,
+                argument0,
+                ..
+            } => Some(argument0),
+            _ => None,
+        });
+    assert!(lit.eq([0, 1, 2]));
+}
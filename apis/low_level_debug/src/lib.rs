@@ -1,6 +1,6 @@
 #![no_std]
 
-use libtock_platform::Syscalls;
+use libtock_platform::{ErrorCode, Syscalls};
 
 /// The low-level debug API provides tools to diagnose userspace issues that
 /// make normal debugging workflows (e.g. printing to the console) difficult.
@@ -35,6 +35,19 @@ impl<S: Syscalls> LowLevelDebug<S> {
         let _ = S::command(DRIVER_NUM, PRINT_ALERT_CODE, code as usize, 0);
     }
 
+    /// Print an application-defined alert code. Applications may use any code
+    /// from [`CUSTOM_ALERT_MIN`] upwards to distinguish their own fault
+    /// sources; codes below it are reserved for [`AlertCode`], and are
+    /// rejected with `ErrorCode::Invalid` without printing anything.
+    #[inline(always)]
+    pub fn print_custom_alert(code: u32) -> Result<(), ErrorCode> {
+        if code < CUSTOM_ALERT_MIN {
+            return Err(ErrorCode::Invalid);
+        }
+        let _ = S::command(DRIVER_NUM, PRINT_ALERT_CODE, code as usize, 0);
+        Ok(())
+    }
+
     /// Print a single number. The number will be printed in hexadecimal.
     ///
     /// In general, this should only be added temporarily for debugging and
@@ -57,6 +70,7 @@ impl<S: Syscalls> LowLevelDebug<S> {
 }
 
 /// A predefined alert code, for use with [`LowLevelDebug::print_alert_code`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum AlertCode {
     /// Application panic (e.g. `panic!()` called in Rust code).
     Panic = 0x01,
@@ -67,6 +81,38 @@ pub enum AlertCode {
 
     /// Heap OOM
     HeapOOM = 0x03,
+
+    /// The stack overflowed, as detected by `libtock_runtime`'s stack guard.
+    StackOverflow = 0x04,
+}
+
+/// The smallest alert code that applications may pass to
+/// [`LowLevelDebug::print_custom_alert`]. All codes below this one are reserved
+/// for predefined alerts.
+pub const CUSTOM_ALERT_MIN: u32 = 0x8000_0000;
+
+/// The provided value is not one of the predefined alert codes.
+#[derive(PartialEq, Eq, Debug)]
+pub struct NotAnAlertCode;
+
+impl TryFrom<u32> for AlertCode {
+    type Error = NotAnAlertCode;
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0x01 => Ok(AlertCode::Panic),
+            0x02 => Ok(AlertCode::WrongLocation),
+            0x03 => Ok(AlertCode::HeapOOM),
+            0x04 => Ok(AlertCode::StackOverflow),
+            _ => Err(NotAnAlertCode),
+        }
+    }
+}
+
+impl From<AlertCode> for u32 {
+    fn from(code: AlertCode) -> u32 {
+        code as u32
+    }
 }
 
 #[cfg(test)]
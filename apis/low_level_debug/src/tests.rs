@@ -37,6 +37,46 @@ fn print_alert_code() {
     );
 }
 
+#[test]
+fn print_custom_alert() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::LowLevelDebug::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(LowLevelDebug::print_custom_alert(CUSTOM_ALERT_MIN), Ok(()));
+    assert_eq!(LowLevelDebug::print_custom_alert(u32::MAX), Ok(()));
+    assert_eq!(
+        LowLevelDebug::print_custom_alert(CUSTOM_ALERT_MIN - 1),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        LowLevelDebug::print_custom_alert(AlertCode::Panic.into()),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        driver.take_messages(),
+        [
+            fake::Message::AlertCode(CUSTOM_ALERT_MIN),
+            fake::Message::AlertCode(u32::MAX)
+        ]
+    );
+}
+
+#[test]
+fn alert_code_conversion() {
+    for code in [
+        AlertCode::Panic,
+        AlertCode::WrongLocation,
+        AlertCode::HeapOOM,
+    ] {
+        assert_eq!(AlertCode::try_from(u32::from(code)), Ok(code));
+    }
+    assert_eq!(u32::from(AlertCode::HeapOOM), 0x03);
+    assert_eq!(AlertCode::try_from(0), Err(NotAnAlertCode));
+    assert_eq!(AlertCode::try_from(0x05), Err(NotAnAlertCode));
+    assert_eq!(AlertCode::try_from(CUSTOM_ALERT_MIN), Err(NotAnAlertCode));
+}
+
 #[test]
 fn print_1() {
     let kernel = fake::Kernel::new();
@@ -0,0 +1,39 @@
+#![no_std]
+
+libtock_sensor_common::single_value_sensor! {
+    /// The soil moisture sensor driver. Readings are volumetric water content,
+    /// in hundredths of a percent.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use libtock::moisture::Moisture;
+    ///
+    /// // Read the moisture, blocking until it is available
+    /// let centi_percent = Moisture::read_moisture()?;
+    /// ```
+    pub struct Moisture;
+    driver_num: DRIVER_NUM;
+    subscribe_num: subscribe::READING;
+    command_num: command::READ_MOISTURE;
+    /// Reads the moisture, blocking until the reading is available. Returns
+    /// `Err(ErrorCode::Off)` without waiting if the sensor is powered off.
+    fn read_moisture() -> u32;
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x6000A;
+
+// Command IDs
+mod command {
+    pub const READ_MOISTURE: u32 = 1;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
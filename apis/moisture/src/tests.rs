@@ -0,0 +1,44 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Moisture = super::Moisture<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Moisture::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Moisture::new();
+    kernel.add_driver(&driver);
+    assert_eq!(Moisture::exists(), Ok(()));
+}
+
+#[test]
+fn read_moisture() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Moisture::new();
+    kernel.add_driver(&driver);
+
+    driver.push_reading(3150);
+    driver.push_reading(0);
+    assert_eq!(Moisture::read_moisture(), Ok(3150));
+    assert_eq!(Moisture::read_moisture(), Ok(0));
+}
+
+#[test]
+fn sensor_off() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Moisture::new();
+    kernel.add_driver(&driver);
+
+    // The read fails immediately rather than waiting for an upcall.
+    driver.push_reading(3150);
+    driver.set_enabled(false);
+    assert_eq!(Moisture::read_moisture(), Err(ErrorCode::Off));
+    driver.set_enabled(true);
+    assert_eq!(Moisture::read_moisture(), Ok(3150));
+}
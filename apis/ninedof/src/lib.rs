@@ -0,0 +1,139 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::sync::atomic::{AtomicBool, Ordering};
+use libtock_platform::{share, DefaultConfig, ErrorCode, Syscalls};
+
+/// The nine degrees of freedom (accelerometer, gyroscope and magnetometer)
+/// driver. Each reading is the sensor's (x, y, z) components, in the units
+/// the sensor reports.
+///
+/// All three sensors share a single upcall, so only one reading can be in
+/// flight at a time. A read started while another is waiting (for instance,
+/// from an upcall that runs during the other read's `yield_wait`) fails with
+/// `ErrorCode::Busy` instead of disturbing it.
+///
+/// # Example
+/// ```ignore
+/// use libtock::ninedof::NineDof;
+///
+/// let (x, y, z) = NineDof::read_accelerometer()?;
+/// ```
+pub struct NineDof<S: Syscalls>(S);
+
+impl<S: Syscalls> NineDof<S> {
+    /// Returns `Ok(())` if the ninedof driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::driver_exists(DRIVER_NUM)
+    }
+
+    /// Reads the accelerometer, blocking until the reading is available.
+    pub fn read_accelerometer() -> Result<(i32, i32, i32), ErrorCode> {
+        Self::read(command::READ_ACCELEROMETER)
+    }
+
+    /// Reads the gyroscope, blocking until the reading is available.
+    pub fn read_gyroscope() -> Result<(i32, i32, i32), ErrorCode> {
+        Self::read(command::READ_GYROSCOPE)
+    }
+
+    /// Reads the magnetometer, blocking until the reading is available.
+    pub fn read_magnetometer() -> Result<(i32, i32, i32), ErrorCode> {
+        Self::read(command::READ_MAGNETOMETER)
+    }
+
+    /// Reads the accelerometer, gyroscope and magnetometer, in that order,
+    /// blocking until all three readings are available. The driver takes one
+    /// reading at a time, so each is started as soon as the previous one
+    /// completes. If a reading fails, the remaining ones are not started.
+    pub fn read_all() -> Result<(Vec3, Vec3, Vec3), ErrorCode> {
+        Self::read_each(|reading| {
+            Ok((
+                Self::read_subscribed(reading, command::READ_ACCELEROMETER)?,
+                Self::read_subscribed(reading, command::READ_GYROSCOPE)?,
+                Self::read_subscribed(reading, command::READ_MAGNETOMETER)?,
+            ))
+        })
+        .map(|(accel, gyro, mag)| (accel.into(), gyro.into(), mag.into()))
+    }
+
+    fn read(command_num: u32) -> Result<(i32, i32, i32), ErrorCode> {
+        Self::read_each(|reading| Self::read_subscribed(reading, command_num))
+    }
+
+    // Subscribes `reading` to the readings upcall for the duration of `f`,
+    // which takes its readings with `read_subscribed`.
+    fn read_each<T>(f: impl FnOnce(&ReadingCell) -> Result<T, ErrorCode>) -> Result<T, ErrorCode> {
+        // Processes are single-threaded, so a plain load and store suffice
+        // (and, unlike swap, are available on every target).
+        if IN_FLIGHT.load(Ordering::Relaxed) {
+            return Err(ErrorCode::Busy);
+        }
+        IN_FLIGHT.store(true, Ordering::Relaxed);
+        let reading: ReadingCell = Cell::new(None);
+        let result = share::scope(|subscribe| {
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::READING }>(
+                subscribe, &reading,
+            )?;
+            f(&reading)
+        });
+        IN_FLIGHT.store(false, Ordering::Relaxed);
+        result
+    }
+
+    // Takes a single reading, with `reading` already subscribed.
+    fn read_subscribed(
+        reading: &ReadingCell,
+        command_num: u32,
+    ) -> Result<(i32, i32, i32), ErrorCode> {
+        reading.set(None);
+        S::command(DRIVER_NUM, command_num, 0, 0).to_result::<(), ErrorCode>()?;
+        loop {
+            if let Some((x, y, z)) = reading.get() {
+                return Ok((x as i32, y as i32, z as i32));
+            }
+            S::yield_wait();
+        }
+    }
+}
+
+type ReadingCell = Cell<Option<(u32, u32, u32)>>;
+
+/// A reading's (x, y, z) components, as returned by `NineDof::read_all`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Vec3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl From<(i32, i32, i32)> for Vec3 {
+    fn from((x, y, z): (i32, i32, i32)) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+}
+
+// Set while a reading is waiting for its upcall. Subscribing for a second
+// reading would replace the first reading's upcall, so the first would never
+// complete.
+static IN_FLIGHT: AtomicBool = AtomicBool::new(false);
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60004;
+
+// Command IDs
+mod command {
+    pub const READ_ACCELEROMETER: u32 = 1;
+    pub const READ_MAGNETOMETER: u32 = 100;
+    pub const READ_GYROSCOPE: u32 = 200;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
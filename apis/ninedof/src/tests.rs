@@ -0,0 +1,68 @@
+use core::cell::Cell;
+use libtock_platform::subscribe::AnyId;
+use libtock_platform::{share, DefaultConfig, ErrorCode, Syscalls, Upcall};
+use libtock_unittest::fake;
+
+use crate::Vec3;
+
+type NineDof = super::NineDof<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(NineDof::exists(), Err(ErrorCode::NoDevice));
+}
+
+type Reading = Result<(i32, i32, i32), ErrorCode>;
+
+// Starts a gyroscope reading from within an upcall.
+struct NestedRead<'a>(&'a Cell<Option<Reading>>);
+
+impl<'a> Upcall<AnyId> for NestedRead<'a> {
+    fn upcall(&self, _: usize, _: usize, _: usize) {
+        self.0.set(Some(NineDof::read_gyroscope()));
+    }
+}
+
+// The readings share the crate's in-flight flag, so they are tested together
+// rather than in tests that could run concurrently.
+#[test]
+fn readings() {
+    use libtock_unittest::fake::SyscallDriver;
+    let kernel = fake::Kernel::new();
+    let driver = fake::NineDof::new();
+    kernel.add_driver(&driver);
+    assert_eq!(NineDof::exists(), Ok(()));
+
+    driver.set_accelerometer((1, -2, 3));
+    driver.set_gyroscope((-40, 50, -60));
+    driver.set_magnetometer((700, 800, -900));
+    assert_eq!(NineDof::read_accelerometer(), Ok((1, -2, 3)));
+    assert_eq!(NineDof::read_gyroscope(), Ok((-40, 50, -60)));
+    assert_eq!(NineDof::read_magnetometer(), Ok((700, 800, -900)));
+
+    // Each sensor's reading ends up in its own place.
+    let vec3 = |x, y, z| Vec3 { x, y, z };
+    assert_eq!(
+        NineDof::read_all(),
+        Ok((vec3(1, -2, 3), vec3(-40, 50, -60), vec3(700, 800, -900)))
+    );
+
+    // Queue a button upcall ahead of the accelerometer reading, so that it
+    // runs while the reading is in flight. The nested reading is refused, and
+    // the outer one is unaffected.
+    let buttons = fake::Buttons::<1>::new();
+    kernel.add_driver(&buttons);
+    let nested = Cell::new(None);
+    let listener = NestedRead(&nested);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, 3, 0>(subscribe, &listener).unwrap();
+        assert!(buttons.command(1, 0, 0).is_success());
+        buttons.set_pressed(0, true).unwrap();
+        assert_eq!(NineDof::read_accelerometer(), Ok((1, -2, 3)));
+    });
+    assert_eq!(nested.get(), Some(Err(ErrorCode::Busy)));
+
+    // Once the outer reading completes, reads work again.
+    assert_eq!(NineDof::read_gyroscope(), Ok((-40, 50, -60)));
+}
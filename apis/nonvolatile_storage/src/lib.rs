@@ -0,0 +1,154 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The nonvolatile storage driver, which gives the process access to a region
+/// of persistent storage (e.g. flash) that survives reboots.
+///
+/// # Example
+/// ```ignore
+/// use libtock::nonvolatile_storage::NonvolatileStorage;
+///
+/// let mut boot_count = [0; 4];
+/// NonvolatileStorage::read(0, &mut boot_count)?;
+/// let next = u32::from_le_bytes(boot_count) + 1;
+/// NonvolatileStorage::write(0, &next.to_le_bytes())?;
+/// ```
+pub struct NonvolatileStorage<S: Syscalls>(S);
+
+impl<S: Syscalls> NonvolatileStorage<S> {
+    /// Returns `Ok(())` if the nonvolatile storage driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Returns the number of bytes of storage accessible to this process.
+    pub fn size() -> Result<usize, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_SIZE, 0, 0)
+            .to_result::<u32, ErrorCode>()
+            .map(|size| size as usize)
+    }
+
+    /// Reads `buffer.len()` bytes of storage, starting at `offset`, into
+    /// `buffer`. The kernel may split large reads into several operations;
+    /// this blocks until all of them are done. Returns the number of bytes
+    /// read, which is `buffer.len()` on success, or `Size` if the kernel
+    /// reports reading more bytes than were requested.
+    pub fn read(offset: usize, buffer: &mut [u8]) -> Result<usize, ErrorCode> {
+        let done: Cell<Option<(u32,)>> = Cell::new(None);
+        let mut read = 0;
+        while read < buffer.len() {
+            done.set(None);
+            let count = share::scope::<
+                (
+                    AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+                    Subscribe<_, DRIVER_NUM, { subscribe::READ_DONE }>,
+                ),
+                _,
+                _,
+            >(|handle| {
+                let (allow_rw, subscribe) = handle.split();
+                let remaining = &mut buffer[read..];
+                let len = remaining.len();
+                S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::READ }>(allow_rw, remaining)?;
+                S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::READ_DONE }>(
+                    subscribe, &done,
+                )?;
+                S::command(DRIVER_NUM, command::READ, offset + read, len)
+                    .to_result::<(), ErrorCode>()?;
+                let (count,) = S::yield_wait_for_result(|| done.get());
+                Ok::<usize, ErrorCode>(count as usize)
+            })?;
+            // Not making progress would otherwise loop forever.
+            if count == 0 {
+                return Err(ErrorCode::Fail);
+            }
+            if count > buffer.len() - read {
+                return Err(ErrorCode::Size);
+            }
+            read += count;
+        }
+        Ok(read)
+    }
+
+    /// Writes `data` to storage, starting at `offset`. The kernel may split
+    /// large writes into several operations; this blocks until all of them
+    /// are done. Returns the number of bytes written, which is `data.len()` on
+    /// success, or `Size` if the kernel reports writing more bytes than were
+    /// requested.
+    pub fn write(offset: usize, data: &[u8]) -> Result<usize, ErrorCode> {
+        let done: Cell<Option<(u32,)>> = Cell::new(None);
+        let mut written = 0;
+        while written < data.len() {
+            done.set(None);
+            let remaining = &data[written..];
+            let count = share::scope::<
+                (
+                    AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                    Subscribe<_, DRIVER_NUM, { subscribe::WRITE_DONE }>,
+                ),
+                _,
+                _,
+            >(|handle| {
+                let (allow_ro, subscribe) = handle.split();
+                S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, remaining)?;
+                S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::WRITE_DONE }>(
+                    subscribe, &done,
+                )?;
+                S::command(
+                    DRIVER_NUM,
+                    command::WRITE,
+                    offset + written,
+                    remaining.len(),
+                )
+                .to_result::<(), ErrorCode>()?;
+                let (count,) = S::yield_wait_for_result(|| done.get());
+                Ok::<usize, ErrorCode>(count as usize)
+            })?;
+            // Not making progress would otherwise loop forever.
+            if count == 0 {
+                return Err(ErrorCode::Fail);
+            }
+            if count > remaining.len() {
+                return Err(ErrorCode::Size);
+            }
+            written += count;
+        }
+        Ok(written)
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x50001;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const GET_SIZE: u32 = 1;
+    pub const READ: u32 = 2;
+    pub const WRITE: u32 = 3;
+}
+
+mod subscribe {
+    pub const READ_DONE: u32 = 0;
+    pub const WRITE_DONE: u32 = 1;
+}
+
+mod allow_rw {
+    pub const READ: u32 = 0;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 0;
+}
@@ -0,0 +1,96 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type NonvolatileStorage = super::NonvolatileStorage<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(NonvolatileStorage::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn size() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::NonvolatileStorage::new(64);
+    kernel.add_driver(&driver);
+
+    assert_eq!(NonvolatileStorage::exists(), Ok(()));
+    assert_eq!(NonvolatileStorage::size(), Ok(64));
+}
+
+#[test]
+fn round_trip() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::NonvolatileStorage::new(64);
+    kernel.add_driver(&driver);
+
+    assert_eq!(NonvolatileStorage::write(10, b"hello"), Ok(5));
+    assert_eq!(driver.contents()[10..15], *b"hello");
+    let mut buffer = [0; 7];
+    assert_eq!(NonvolatileStorage::read(9, &mut buffer), Ok(7));
+    assert_eq!(buffer, [0xff, b'h', b'e', b'l', b'l', b'o', 0xff]);
+}
+
+#[test]
+fn split_operations() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::NonvolatileStorage::new(64);
+    kernel.add_driver(&driver);
+
+    // Each kernel operation handles at most 3 bytes, so these take several.
+    driver.set_max_bytes(3);
+    let data: [u8; 20] = core::array::from_fn(|i| i as u8);
+    assert_eq!(NonvolatileStorage::write(4, &data), Ok(20));
+    assert_eq!(driver.contents()[4..24], data);
+    let mut buffer = [0; 20];
+    assert_eq!(NonvolatileStorage::read(4, &mut buffer), Ok(20));
+    assert_eq!(buffer, data);
+}
+
+#[test]
+fn out_of_range() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::NonvolatileStorage::new(16);
+    kernel.add_driver(&driver);
+
+    assert_eq!(
+        NonvolatileStorage::write(12, b"hello"),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(driver.contents(), [0xff; 16]);
+    let mut buffer = [0; 4];
+    assert_eq!(
+        NonvolatileStorage::read(16, &mut buffer),
+        Err(ErrorCode::Invalid)
+    );
+}
+
+#[test]
+fn stalled() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::NonvolatileStorage::new(16);
+    kernel.add_driver(&driver);
+
+    // A kernel that never makes progress fails instead of hanging.
+    driver.set_max_bytes(0);
+    assert_eq!(NonvolatileStorage::write(0, b"hi"), Err(ErrorCode::Fail));
+}
+
+#[test]
+fn overreported() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::NonvolatileStorage::new(16);
+    kernel.add_driver(&driver);
+
+    // A kernel that reports handling more bytes than were left is rejected,
+    // rather than moving past the end of the buffer.
+    driver.set_max_bytes(2);
+    driver.set_extra_bytes(1);
+    assert_eq!(NonvolatileStorage::write(0, b"hi"), Err(ErrorCode::Size));
+    let mut buffer = [0; 2];
+    assert_eq!(
+        NonvolatileStorage::read(0, &mut buffer),
+        Err(ErrorCode::Size)
+    );
+}
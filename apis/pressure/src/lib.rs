@@ -0,0 +1,39 @@
+#![no_std]
+
+libtock_sensor_common::single_value_sensor! {
+    /// The barometric pressure sensor driver. Readings are in hundredths of a
+    /// hectopascal (that is, in pascals).
+    ///
+    /// # Example
+    /// ```ignore
+    /// use libtock::pressure::Pressure;
+    ///
+    /// // Read the pressure, blocking until it is available
+    /// let centi_hpa = Pressure::read_pressure()?;
+    /// ```
+    pub struct Pressure;
+    driver_num: DRIVER_NUM;
+    subscribe_num: subscribe::READING;
+    command_num: command::READ_PRESSURE;
+    /// Reads the pressure, blocking until the reading is available. Returns
+    /// `Err(ErrorCode::Off)` without waiting if the sensor is powered off.
+    fn read_pressure() -> u32;
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60008;
+
+// Command IDs
+mod command {
+    pub const READ_PRESSURE: u32 = 1;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
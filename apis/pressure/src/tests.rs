@@ -0,0 +1,44 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Pressure = super::Pressure<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Pressure::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pressure::new();
+    kernel.add_driver(&driver);
+    assert_eq!(Pressure::exists(), Ok(()));
+}
+
+#[test]
+fn read_pressure() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pressure::new();
+    kernel.add_driver(&driver);
+
+    driver.push_reading(101_325);
+    driver.push_reading(98_000);
+    assert_eq!(Pressure::read_pressure(), Ok(101_325));
+    assert_eq!(Pressure::read_pressure(), Ok(98_000));
+}
+
+#[test]
+fn sensor_off() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pressure::new();
+    kernel.add_driver(&driver);
+
+    // The read fails immediately rather than waiting for an upcall.
+    driver.push_reading(101_325);
+    driver.set_enabled(false);
+    assert_eq!(Pressure::read_pressure(), Err(ErrorCode::Off));
+    driver.set_enabled(true);
+    assert_eq!(Pressure::read_pressure(), Ok(101_325));
+}
@@ -0,0 +1,119 @@
+#![no_std]
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+use libtock_platform::share;
+use libtock_platform::subscribe::OneId;
+use libtock_platform::{DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall};
+
+libtock_sensor_common::single_value_sensor! {
+    /// The proximity sensor driver. Readings range from 0 (far) to 255
+    /// (close).
+    ///
+    /// # Example
+    /// ```ignore
+    /// use libtock::proximity::{Proximity, ThresholdListener};
+    ///
+    /// let proximity = Proximity::read()?;
+    ///
+    /// // Wait until something comes close, or moves away
+    /// let listener = ThresholdListener::default();
+    /// share::scope(|subscribe| {
+    ///     let interrupt = Proximity::read_on_interrupt(subscribe, &listener, 10, 200)?;
+    ///     let proximity = interrupt.wait();
+    ///     Ok(())
+    /// })?;
+    /// ```
+    pub struct Proximity;
+    driver_num: DRIVER_NUM;
+    subscribe_num: subscribe::READING;
+    command_num: command::READ;
+    /// Reads the proximity, blocking until the reading is available.
+    fn read() -> u8;
+}
+
+impl<S: Syscalls> Proximity<S> {
+    /// Asks the sensor for a reading once the proximity leaves the band
+    /// `[lower, upper]`, and returns without waiting for it. The reading is
+    /// delivered to `listener`; use the returned `ThresholdInterrupt` to wait
+    /// for it. The interrupt fires once; call this again to wait for another
+    /// crossing.
+    ///
+    /// Returns `Err(ErrorCode::Invalid)` if `lower` is greater than `upper`.
+    pub fn read_on_interrupt<'share>(
+        subscribe: share::Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::READING }>>,
+        listener: &'share ThresholdListener,
+        lower: u8,
+        upper: u8,
+    ) -> Result<ThresholdInterrupt<'share, S>, ErrorCode> {
+        if lower > upper {
+            return Err(ErrorCode::Invalid);
+        }
+        listener.reading.set(None);
+        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::READING }>(
+            subscribe, listener,
+        )?;
+        S::command(
+            DRIVER_NUM,
+            command::READ_ON_INTERRUPT,
+            lower as usize,
+            upper as usize,
+        )
+        .to_result::<(), ErrorCode>()?;
+        Ok(ThresholdInterrupt {
+            listener,
+            _syscalls: PhantomData,
+        })
+    }
+}
+
+/// Receives the reading for `Proximity::read_on_interrupt`.
+#[derive(Default)]
+pub struct ThresholdListener {
+    reading: Cell<Option<u8>>,
+}
+
+impl Upcall<OneId<DRIVER_NUM, { subscribe::READING }>> for ThresholdListener {
+    fn upcall(&self, proximity: usize, _arg1: usize, _arg2: usize) {
+        self.reading.set(Some(proximity as u8));
+    }
+}
+
+/// A pending threshold interrupt, returned by `Proximity::read_on_interrupt`.
+pub struct ThresholdInterrupt<'share, S: Syscalls> {
+    listener: &'share ThresholdListener,
+    _syscalls: PhantomData<S>,
+}
+
+impl<'share, S: Syscalls> ThresholdInterrupt<'share, S> {
+    /// Returns the reading if the interrupt has fired. This does not yield, so
+    /// the reading only arrives during the caller's own `yield_wait` calls.
+    pub fn reading(&self) -> Option<u8> {
+        self.listener.reading.get()
+    }
+
+    /// Yields until the interrupt fires, and returns the proximity that
+    /// triggered it.
+    pub fn wait(&self) -> u8 {
+        S::yield_wait_for_result(|| self.reading())
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60005;
+
+// Command IDs
+mod command {
+    pub const READ: u32 = 1;
+    pub const READ_ON_INTERRUPT: u32 = 2;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
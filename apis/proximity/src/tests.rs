@@ -0,0 +1,58 @@
+use super::ThresholdListener;
+use libtock_platform::{share, ErrorCode, Syscalls};
+use libtock_unittest::fake;
+
+type Proximity = super::Proximity<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Proximity::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Proximity::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Proximity::exists(), Ok(()));
+    driver.set_value(42);
+    assert_eq!(Proximity::read(), Ok(42));
+}
+
+#[test]
+fn read_on_interrupt() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Proximity::new();
+    kernel.add_driver(&driver);
+
+    driver.set_value(100);
+    let listener = ThresholdListener::default();
+    share::scope(|subscribe| {
+        let interrupt = Proximity::read_on_interrupt(subscribe, &listener, 50, 150).unwrap();
+        // Moving within the band doesn't fire the interrupt.
+        driver.set_value(140);
+        fake::Syscalls::yield_no_wait();
+        assert_eq!(interrupt.reading(), None);
+
+        driver.set_value(180);
+        assert_eq!(interrupt.wait(), 180);
+    });
+}
+
+#[test]
+fn read_on_interrupt_invalid_band() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Proximity::new();
+    kernel.add_driver(&driver);
+
+    let listener = ThresholdListener::default();
+    share::scope(|subscribe| {
+        assert!(matches!(
+            Proximity::read_on_interrupt(subscribe, &listener, 200, 100),
+            Err(ErrorCode::Invalid)
+        ));
+    });
+    assert!(!driver.is_armed());
+}
@@ -0,0 +1,70 @@
+#![no_std]
+
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// The PWM driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock::pwm::Pwm;
+///
+/// // Drive channel 0 at 1 kHz with a 25% duty cycle
+/// Pwm::start(0, 1000, 25)?;
+/// ```
+pub struct Pwm<S: Syscalls>(S);
+
+impl<S: Syscalls> Pwm<S> {
+    /// Run a check against the PWM capsule to ensure it is present.
+    ///
+    /// Returns `Ok(number_of_channels)` if the driver was present.
+    pub fn channel_count() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::CHANNEL_COUNT, 0, 0).to_result()
+    }
+
+    /// Returns the highest frequency, in hertz, that `channel` can output.
+    pub fn max_frequency(channel: u32) -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::MAX_FREQUENCY, channel as usize, 0).to_result()
+    }
+
+    /// Starts outputting a `frequency_hz` signal on `channel`, high for
+    /// `duty_percent` percent of each period. Returns `Err(ErrorCode::Invalid)`
+    /// if `duty_percent` is over 100, or if `channel` can't be represented in
+    /// the kernel's 16-bit channel field.
+    pub fn start(channel: u32, frequency_hz: u32, duty_percent: u8) -> Result<(), ErrorCode> {
+        if duty_percent > 100 || channel > u16::MAX as u32 {
+            return Err(ErrorCode::Invalid);
+        }
+        // The kernel takes the duty cycle in hundredths of a percent, packed
+        // into the upper half of the first argument with the channel.
+        let duty_cycle = duty_percent as usize * 100;
+        S::command(
+            DRIVER_NUM,
+            command::START,
+            duty_cycle << 16 | channel as usize,
+            frequency_hz as usize,
+        )
+        .to_result()
+    }
+
+    /// Stops the output on `channel`.
+    pub fn stop(channel: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::STOP, channel as usize, 0).to_result()
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x10;
+
+// Command IDs
+mod command {
+    pub const START: u32 = 1;
+    pub const STOP: u32 = 2;
+    pub const MAX_FREQUENCY: u32 = 3;
+    pub const CHANNEL_COUNT: u32 = 4;
+}
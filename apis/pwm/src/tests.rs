@@ -0,0 +1,52 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Pwm = super::Pwm<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Pwm::channel_count(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn queries() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pwm::<3>::new();
+    kernel.add_driver(&driver);
+
+    driver.set_max_frequency(16_000_000);
+    assert_eq!(Pwm::channel_count(), Ok(3));
+    assert_eq!(Pwm::max_frequency(0), Ok(16_000_000));
+    assert_eq!(Pwm::max_frequency(3), Err(ErrorCode::Invalid));
+}
+
+#[test]
+fn start_stop() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pwm::<2>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Pwm::start(1, 1000, 25), Ok(()));
+    assert_eq!(driver.output(1), Some((1000, 2500)));
+    assert_eq!(driver.output(0), None);
+    assert_eq!(Pwm::start(0, 50, 100), Ok(()));
+    assert_eq!(driver.output(0), Some((50, 10000)));
+    assert_eq!(Pwm::start(2, 50, 0), Err(ErrorCode::Invalid));
+
+    assert_eq!(Pwm::stop(1), Ok(()));
+    assert_eq!(driver.output(1), None);
+    assert_eq!(driver.output(0), Some((50, 10000)));
+}
+
+#[test]
+fn invalid_duty() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Pwm::<1>::new();
+    kernel.add_driver(&driver);
+
+    // An out-of-range duty cycle is rejected without calling into the kernel.
+    assert_eq!(Pwm::start(0, 1000, 101), Err(ErrorCode::Invalid));
+    assert_eq!(kernel.take_syscall_log(), []);
+    assert_eq!(driver.output(0), None);
+}
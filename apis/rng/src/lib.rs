@@ -0,0 +1,83 @@
+#![no_std]
+
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::{StandardResultArg1, Subscribe, UpcallResult};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The random number generator driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock::rng::Rng;
+///
+/// let mut key = [0; 16];
+/// let mut filled = 0;
+/// while filled < key.len() {
+///     filled += Rng::fill_bytes(&mut key[filled..])?;
+/// }
+/// ```
+pub struct Rng<S: Syscalls>(S);
+
+impl<S: Syscalls> Rng<S> {
+    /// Returns `Ok(())` if the RNG driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Fills `buffer` with random bytes, blocking until the kernel is done.
+    /// The kernel may generate fewer bytes than requested; returns the number
+    /// of bytes written, which are at the start of `buffer`. The rest of
+    /// `buffer` is left untouched.
+    pub fn fill_bytes(buffer: &mut [u8]) -> Result<usize, ErrorCode> {
+        let done = StandardResultArg1::new(None);
+        let len = buffer.len();
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::BUFFER }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::BUFFER }>(allow_rw, buffer)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::GET_RANDOM, len, 0).to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()
+        })
+    }
+
+    /// Returns a random `u32`.
+    pub fn next_u32() -> Result<u32, ErrorCode> {
+        let mut bytes = [0; 4];
+        let mut filled = 0;
+        while filled < bytes.len() {
+            filled += Self::fill_bytes(&mut bytes[filled..])?;
+        }
+        Ok(u32::from_ne_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40001;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const GET_RANDOM: u32 = 1;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_rw {
+    pub const BUFFER: u32 = 0;
+}
@@ -0,0 +1,52 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Rng = super::Rng<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Rng::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn fill_bytes() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Rng::new();
+    kernel.add_driver(&driver);
+
+    let mut buffer = [0; 16];
+    assert_eq!(Rng::fill_bytes(&mut buffer), Ok(16));
+    assert_ne!(buffer, [0; 16]);
+}
+
+#[test]
+fn fill_bytes_short() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Rng::new();
+    kernel.add_driver(&driver);
+
+    // Bytes the kernel didn't generate are left alone.
+    driver.set_max_bytes(3);
+    let mut buffer = [0xaa; 8];
+    assert_eq!(Rng::fill_bytes(&mut buffer), Ok(3));
+    assert_eq!(buffer[3..], [0xaa; 5]);
+}
+
+#[test]
+fn next_u32() {
+    // The fake's sequence is fixed, so the value is the same whether it's
+    // generated in one request or several.
+    let expected = {
+        let kernel = fake::Kernel::new();
+        let driver = fake::Rng::new();
+        kernel.add_driver(&driver);
+        Rng::next_u32().unwrap()
+    };
+
+    let kernel = fake::Kernel::new();
+    let driver = fake::Rng::new();
+    kernel.add_driver(&driver);
+    driver.set_max_bytes(1);
+    assert_eq!(Rng::next_u32(), Ok(expected));
+}
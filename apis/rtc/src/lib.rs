@@ -0,0 +1,143 @@
+#![no_std]
+
+use libtock_platform::subscribe::{StandardResult, StandardResultArg2};
+use libtock_platform::{ErrorCode, Syscalls};
+use libtock_sensor_common::command_and_wait;
+
+/// The real-time clock driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock::rtc::{DateTime, Rtc};
+///
+/// Rtc::set_date_time(&DateTime {
+///     year: 2024,
+///     month: 3,
+///     day: 15,
+///     hour: 9,
+///     minute: 30,
+///     seconds: 0,
+///     day_of_week: 5,
+/// })?;
+/// let now = Rtc::get_date_time()?;
+/// ```
+pub struct Rtc<S: Syscalls>(S);
+
+impl<S: Syscalls> Rtc<S> {
+    /// Returns `Ok(())` if the RTC driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Reads the current date and time, blocking until they are available.
+    pub fn get_date_time() -> Result<DateTime, ErrorCode> {
+        let done = StandardResultArg2::new(None);
+        let (date, time) = command_and_wait::<S, _, _, DRIVER_NUM, { subscribe::DONE }>(
+            &done,
+            command::GET_DATE_TIME,
+            0,
+            0,
+        )?;
+        Ok(DateTime::unpack(date as u32, time as u32))
+    }
+
+    /// Sets the clock to `date_time`, blocking until it is set. Returns
+    /// `Err(ErrorCode::Invalid)`, without making any system calls, if
+    /// `date_time` is not a valid date and time.
+    pub fn set_date_time(date_time: &DateTime) -> Result<(), ErrorCode> {
+        if !date_time.is_valid() {
+            return Err(ErrorCode::Invalid);
+        }
+        let (date, time) = date_time.pack();
+        let done = StandardResult::new(None);
+        command_and_wait::<S, _, _, DRIVER_NUM, { subscribe::DONE }>(
+            &done,
+            command::SET_DATE_TIME,
+            date as usize,
+            time as usize,
+        )
+    }
+}
+
+/// A calendar date and time of day.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct DateTime {
+    pub year: u16,
+    /// 1 (January) to 12 (December).
+    pub month: u8,
+    /// 1 to the number of days in the month.
+    pub day: u8,
+    /// 0 to 23.
+    pub hour: u8,
+    /// 0 to 59.
+    pub minute: u8,
+    /// 0 to 59.
+    pub seconds: u8,
+    /// 0 (Sunday) to 6 (Saturday).
+    pub day_of_week: u8,
+}
+
+impl DateTime {
+    /// Returns true if all fields are in range, and `day` exists in the month.
+    pub fn is_valid(&self) -> bool {
+        let days_in_month = match self.month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if is_leap_year(self.year) => 29,
+            2 => 28,
+            _ => return false,
+        };
+        (1..=days_in_month).contains(&self.day)
+            && self.hour < 24
+            && self.minute < 60
+            && self.seconds < 60
+            && self.day_of_week < 7
+    }
+
+    // Packs the fields into the driver's (date, time) command arguments.
+    fn pack(&self) -> (u32, u32) {
+        let date = (self.year as u32) << 9 | (self.month as u32) << 5 | self.day as u32;
+        let time = (self.day_of_week as u32) << 17
+            | (self.hour as u32) << 12
+            | (self.minute as u32) << 6
+            | self.seconds as u32;
+        (date, time)
+    }
+
+    // The inverse of pack, for the driver's upcall arguments.
+    fn unpack(date: u32, time: u32) -> DateTime {
+        DateTime {
+            year: (date >> 9) as u16,
+            month: (date >> 5 & 0xf) as u8,
+            day: (date & 0x1f) as u8,
+            hour: (time >> 12 & 0x1f) as u8,
+            minute: (time >> 6 & 0x3f) as u8,
+            seconds: (time & 0x3f) as u8,
+            day_of_week: (time >> 17 & 0x7) as u8,
+        }
+    }
+}
+
+fn is_leap_year(year: u16) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90007;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const GET_DATE_TIME: u32 = 1;
+    pub const SET_DATE_TIME: u32 = 2;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
@@ -0,0 +1,113 @@
+use super::DateTime;
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Rtc = super::Rtc<fake::Syscalls>;
+
+const DATE_TIME: DateTime = DateTime {
+    year: 2024,
+    month: 2,
+    day: 29,
+    hour: 23,
+    minute: 59,
+    seconds: 58,
+    day_of_week: 4,
+};
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Rtc::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn set_get() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Rtc::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Rtc::exists(), Ok(()));
+    assert_eq!(Rtc::set_date_time(&DATE_TIME), Ok(()));
+    assert_eq!(
+        driver.date_time(),
+        (2024 << 9 | 2 << 5 | 29, 4 << 17 | 23 << 12 | 59 << 6 | 58)
+    );
+    assert_eq!(Rtc::get_date_time(), Ok(DATE_TIME));
+
+    // Changes to the clock made outside the API are read back, too.
+    driver.set_date_time(2000 << 9 | 1 << 5 | 1, 6 << 17);
+    assert_eq!(
+        Rtc::get_date_time(),
+        Ok(DateTime {
+            year: 2000,
+            month: 1,
+            day: 1,
+            hour: 0,
+            minute: 0,
+            seconds: 0,
+            day_of_week: 6,
+        })
+    );
+}
+
+#[test]
+fn invalid() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Rtc::new();
+    kernel.add_driver(&driver);
+
+    let invalid = [
+        DateTime {
+            month: 0,
+            ..DATE_TIME
+        },
+        DateTime {
+            month: 13,
+            ..DATE_TIME
+        },
+        DateTime {
+            day: 0,
+            ..DATE_TIME
+        },
+        DateTime {
+            year: 2023,
+            ..DATE_TIME
+        },
+        DateTime {
+            year: 2100,
+            ..DATE_TIME
+        },
+        DateTime {
+            month: 4,
+            day: 31,
+            ..DATE_TIME
+        },
+        DateTime {
+            hour: 24,
+            ..DATE_TIME
+        },
+        DateTime {
+            minute: 60,
+            ..DATE_TIME
+        },
+        DateTime {
+            seconds: 60,
+            ..DATE_TIME
+        },
+        DateTime {
+            day_of_week: 7,
+            ..DATE_TIME
+        },
+    ];
+    for date_time in invalid {
+        assert_eq!(Rtc::set_date_time(&date_time), Err(ErrorCode::Invalid));
+    }
+    assert_eq!(driver.date_time(), (0, 0));
+    assert_eq!(
+        Rtc::set_date_time(&DateTime {
+            year: 2000,
+            ..DATE_TIME
+        }),
+        Ok(())
+    );
+}
@@ -0,0 +1,131 @@
+use core::marker::PhantomData;
+use libtock_platform::{ErrorCode, Syscalls};
+
+use crate::Screen;
+
+/// An off-screen copy of a `W` by `H` pixel region at the top left of the
+/// screen, for flicker-free drawing: draw into the buffer with `set_pixel` and
+/// `fill`, then `present` it to update the screen in one go.
+///
+/// Pixels are RGB565 values (5 bits of red, 6 of green and 5 of blue, red in
+/// the top bits), so the screen must be using `PixelFormat::Rgb565`.
+///
+/// The buffer tracks which rows have changed since it was last presented, and
+/// `present` only sends those. A newly created buffer has not been presented,
+/// so all of it is sent the first time.
+///
+/// # Example
+/// ```ignore
+/// use libtock::screen::{FrameBuffer, PixelFormat, Screen};
+///
+/// Screen::set_pixel_format(PixelFormat::Rgb565)?;
+/// let mut frame = FrameBuffer::<64, 32>::new();
+/// frame.fill(0x0000);
+/// frame.set_pixel(10, 10, 0xf800);
+/// frame.present()?;
+/// ```
+pub struct FrameBuffer<S: Syscalls, const W: usize, const H: usize> {
+    // Each pixel is stored in the order it is sent to the screen (big-endian),
+    // so that rows can be written without copying.
+    rows: [[[u8; 2]; W]; H],
+    // The range of rows that changed since the last present.
+    dirty: Option<(usize, usize)>,
+    max_write_len: usize,
+    _syscalls: PhantomData<S>,
+}
+
+impl<S: Syscalls, const W: usize, const H: usize> FrameBuffer<S, W, H> {
+    /// Creates a buffer with every pixel set to 0 (black).
+    pub fn new() -> Self {
+        FrameBuffer {
+            rows: [[[0; 2]; W]; H],
+            dirty: Some((0, H)),
+            max_write_len: usize::MAX,
+            _syscalls: PhantomData,
+        }
+    }
+
+    /// Limits each write `present` makes to `len` bytes, for drivers that
+    /// cannot take the whole buffer at once. Writes are always made up of whole
+    /// rows, so at least one row (`2 * W` bytes) is written at a time.
+    pub fn set_max_write_len(&mut self, len: usize) {
+        self.max_write_len = len;
+    }
+
+    /// Returns the color of the pixel at (`x`, `y`), or `None` if it is outside
+    /// the buffer.
+    pub fn pixel(&self, x: usize, y: usize) -> Option<u16> {
+        let pixel = self.rows.get(y)?.get(x)?;
+        Some(u16::from_be_bytes(*pixel))
+    }
+
+    /// Sets the pixel at (`x`, `y`) to `color`. Pixels outside the buffer are
+    /// ignored, so shapes that run off the edge are clipped.
+    pub fn set_pixel(&mut self, x: usize, y: usize, color: u16) {
+        match self.rows.get_mut(y).and_then(|row| row.get_mut(x)) {
+            Some(pixel) if *pixel != color.to_be_bytes() => *pixel = color.to_be_bytes(),
+            _ => return,
+        }
+        self.mark_dirty(y, y + 1);
+    }
+
+    /// Sets every pixel to `color`.
+    pub fn fill(&mut self, color: u16) {
+        let mut changed = None;
+        for (y, row) in self.rows.iter_mut().enumerate() {
+            if row.iter().any(|pixel| *pixel != color.to_be_bytes()) {
+                *row = [color.to_be_bytes(); W];
+                changed = Some(match changed {
+                    Some((first, _)) => (first, y + 1),
+                    None => (y, y + 1),
+                });
+            }
+        }
+        if let Some((first, end)) = changed {
+            self.mark_dirty(first, end);
+        }
+    }
+
+    /// Writes the rows that changed since the last `present` to the screen. If
+    /// nothing changed, this makes no system calls.
+    pub fn present(&mut self) -> Result<(), ErrorCode> {
+        let (first, end) = match self.dirty {
+            Some(dirty) => dirty,
+            None => return Ok(()),
+        };
+        let row_len = 2 * W;
+        let rows_per_write = (self.max_write_len / row_len.max(1)).max(1);
+        let mut y = first;
+        while y < end {
+            let count = rows_per_write.min(end - y);
+            Screen::<S>::set_write_frame(0, y as u16, W as u16, count as u16)?;
+            // Safety: rows is an array of arrays of bytes, so rows y..y+count
+            // are count * row_len contiguous, initialized bytes.
+            let pixels = unsafe {
+                core::slice::from_raw_parts(
+                    self.rows[y..y + count].as_ptr() as *const u8,
+                    count * row_len,
+                )
+            };
+            Screen::<S>::write(pixels)?;
+            y += count;
+            // Rows that have been written no longer need to be, even if a
+            // later write fails.
+            self.dirty = if y < end { Some((y, end)) } else { None };
+        }
+        Ok(())
+    }
+
+    fn mark_dirty(&mut self, first: usize, end: usize) {
+        self.dirty = Some(match self.dirty {
+            Some((dirty_first, dirty_end)) => (dirty_first.min(first), dirty_end.max(end)),
+            None => (first, end),
+        });
+    }
+}
+
+impl<S: Syscalls, const W: usize, const H: usize> Default for FrameBuffer<S, W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,220 @@
+#![no_std]
+
+use core::marker::PhantomData;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::share;
+use libtock_platform::subscribe::{StandardResult, StandardResultArg2, Subscribe, UpcallResult};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+use libtock_sensor_common::command_and_wait;
+
+mod frame_buffer;
+pub use frame_buffer::FrameBuffer;
+
+/// The screen driver, for framebuffer displays.
+///
+/// Pixels are written to a rectangular "write frame" on the screen, filling it
+/// row by row, in the screen's current pixel format. Screens support different
+/// sets of formats, so applications should pick one they can encode from
+/// `supported_formats`.
+///
+/// # Example
+/// ```ignore
+/// use libtock::screen::{PixelFormat, Screen};
+///
+/// if Screen::supported_formats()?.any(|f| f == PixelFormat::Rgb565) {
+///     Screen::set_pixel_format(PixelFormat::Rgb565)?;
+/// }
+/// let (width, height) = Screen::get_resolution()?;
+/// Screen::set_write_frame(0, 0, 2, 1)?;
+/// Screen::write(&[0xf8, 0x00, 0x07, 0xe0])?;
+/// ```
+pub struct Screen<S: Syscalls>(S);
+
+impl<S: Syscalls> Screen<S> {
+    /// Returns `Ok(())` if the screen driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Returns the current resolution of the screen as `(width, height)`, in
+    /// pixels.
+    pub fn get_resolution() -> Result<(u32, u32), ErrorCode> {
+        let done = StandardResultArg2::new(None);
+        command_and_wait::<S, _, _, DRIVER_NUM, { subscribe::DONE }>(
+            &done,
+            command::GET_RESOLUTION,
+            0,
+            0,
+        )
+        .map(|(width, height)| (width as u32, height as u32))
+    }
+
+    /// Sets the brightness of the screen. 0 turns the backlight off; the
+    /// meaning of other values depends on the screen.
+    pub fn set_brightness(brightness: u16) -> Result<(), ErrorCode> {
+        Self::command_done(command::SET_BRIGHTNESS, brightness as usize, 0)
+    }
+
+    /// Returns an iterator over the pixel formats the screen supports.
+    /// Formats this library doesn't know are skipped.
+    pub fn supported_formats() -> Result<SupportedFormats<S>, ErrorCode> {
+        let count = S::command(DRIVER_NUM, command::PIXEL_FORMAT_COUNT, 0, 0).to_result()?;
+        Ok(SupportedFormats {
+            index: 0,
+            count,
+            _syscalls: PhantomData,
+        })
+    }
+
+    /// Returns the pixel format the screen is using.
+    pub fn pixel_format() -> Result<PixelFormat, ErrorCode> {
+        let done = StandardResultArg2::new(None);
+        let (format, _) = command_and_wait::<S, _, _, DRIVER_NUM, { subscribe::DONE }>(
+            &done,
+            command::GET_PIXEL_FORMAT,
+            0,
+            0,
+        )?;
+        PixelFormat::from_u32(format as u32).ok_or(ErrorCode::Fail)
+    }
+
+    /// Switches the screen to `format`. Returns `ErrorCode::Invalid` if the
+    /// screen doesn't support it.
+    pub fn set_pixel_format(format: PixelFormat) -> Result<(), ErrorCode> {
+        Self::command_done(command::SET_PIXEL_FORMAT, format as usize, 0)
+    }
+
+    /// Sets the rectangle that following writes fill: `width` by `height`
+    /// pixels, with its top left corner at (`x`, `y`).
+    pub fn set_write_frame(x: u16, y: u16, width: u16, height: u16) -> Result<(), ErrorCode> {
+        Self::command_done(
+            command::SET_WRITE_FRAME,
+            (x as usize) << 16 | y as usize,
+            (width as usize) << 16 | height as usize,
+        )
+    }
+
+    /// Writes `pixels`, encoded in the current pixel format, to the write
+    /// frame. Blocks until the screen has been updated.
+    pub fn write(pixels: &[u8]) -> Result<(), ErrorCode> {
+        let done = StandardResult::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::PIXELS }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::PIXELS }>(allow_ro, pixels)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::WRITE, pixels.len(), 0).to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()
+        })
+    }
+
+    // Runs a command that completes with an upcall, and waits for it.
+    fn command_done(command_num: u32, argument0: usize, argument1: usize) -> Result<(), ErrorCode> {
+        let done = StandardResult::new(None);
+        command_and_wait::<S, _, _, DRIVER_NUM, { subscribe::DONE }>(
+            &done,
+            command_num,
+            argument0,
+            argument1,
+        )
+    }
+}
+
+/// The ways a screen can encode pixels.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PixelFormat {
+    /// One bit per pixel, packed eight to a byte.
+    Mono = 0,
+    /// 8 bits per pixel: 2 bits of red, 3 of green and 3 of blue.
+    Rgb233 = 1,
+    /// 16 bits per pixel: 5 bits of red, 6 of green and 5 of blue.
+    Rgb565 = 2,
+    /// 24 bits per pixel: 8 bits each of red, green and blue.
+    Rgb888 = 3,
+    /// 32 bits per pixel: 8 bits each of alpha, red, green and blue.
+    Argb8888 = 4,
+}
+
+impl PixelFormat {
+    /// Converts the driver's number for a pixel format, returning `None` for
+    /// formats this library doesn't know.
+    pub fn from_u32(value: u32) -> Option<PixelFormat> {
+        match value {
+            0 => Some(PixelFormat::Mono),
+            1 => Some(PixelFormat::Rgb233),
+            2 => Some(PixelFormat::Rgb565),
+            3 => Some(PixelFormat::Rgb888),
+            4 => Some(PixelFormat::Argb8888),
+            _ => None,
+        }
+    }
+}
+
+/// An iterator over the pixel formats a screen supports, returned by
+/// `Screen::supported_formats`. Each format is queried from the driver as the
+/// iterator advances.
+pub struct SupportedFormats<S: Syscalls> {
+    index: u32,
+    count: u32,
+    _syscalls: PhantomData<S>,
+}
+
+impl<S: Syscalls> Iterator for SupportedFormats<S> {
+    type Item = PixelFormat;
+
+    fn next(&mut self) -> Option<PixelFormat> {
+        while self.index < self.count {
+            let index = self.index;
+            self.index += 1;
+            let format = S::command(
+                DRIVER_NUM,
+                command::SUPPORTED_PIXEL_FORMAT,
+                index as usize,
+                0,
+            )
+            .to_result::<u32, ErrorCode>();
+            if let Some(format) = format.ok().and_then(PixelFormat::from_u32) {
+                return Some(format);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90001;
+
+// Command IDs
+#[allow(unused)]
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const SET_POWER: u32 = 2;
+    pub const SET_BRIGHTNESS: u32 = 3;
+    pub const PIXEL_FORMAT_COUNT: u32 = 13;
+    pub const SUPPORTED_PIXEL_FORMAT: u32 = 14;
+    pub const GET_RESOLUTION: u32 = 23;
+    pub const GET_PIXEL_FORMAT: u32 = 25;
+    pub const SET_PIXEL_FORMAT: u32 = 26;
+    pub const SET_WRITE_FRAME: u32 = 100;
+    pub const WRITE: u32 = 200;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const PIXELS: u32 = 0;
+}
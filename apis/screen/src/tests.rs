@@ -0,0 +1,123 @@
+extern crate std;
+
+use super::PixelFormat;
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+use std::vec;
+
+type FrameBuffer<const W: usize, const H: usize> = super::FrameBuffer<fake::Syscalls, W, H>;
+type Screen = super::Screen<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Screen::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn get_resolution() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(128, 64, &[0]);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Screen::exists(), Ok(()));
+    assert_eq!(Screen::get_resolution(), Ok((128, 64)));
+}
+
+#[test]
+fn set_brightness() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(128, 64, &[0]);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Screen::set_brightness(200), Ok(()));
+    assert_eq!(driver.brightness(), 200);
+}
+
+#[test]
+fn format_negotiation() {
+    let kernel = fake::Kernel::new();
+    // 7 is not a format this library knows, so it is skipped.
+    let driver = fake::Screen::new(240, 240, &[3, 7, 2]);
+    kernel.add_driver(&driver);
+
+    let mut formats = Screen::supported_formats().unwrap();
+    assert_eq!(formats.next(), Some(PixelFormat::Rgb888));
+    assert_eq!(formats.next(), Some(PixelFormat::Rgb565));
+    assert_eq!(formats.next(), None);
+
+    assert_eq!(Screen::pixel_format(), Ok(PixelFormat::Rgb888));
+    assert_eq!(Screen::set_pixel_format(PixelFormat::Rgb565), Ok(()));
+    assert_eq!(driver.pixel_format(), 2);
+    assert_eq!(Screen::pixel_format(), Ok(PixelFormat::Rgb565));
+    assert_eq!(
+        Screen::set_pixel_format(PixelFormat::Mono),
+        Err(ErrorCode::Invalid)
+    );
+    assert_eq!(driver.pixel_format(), 2);
+}
+
+#[test]
+fn write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(240, 240, &[2]);
+    kernel.add_driver(&driver);
+
+    assert_eq!(Screen::set_write_frame(10, 20, 2, 1), Ok(()));
+    assert_eq!(driver.write_frame(), (10, 20, 2, 1));
+    assert_eq!(Screen::write(&[0xf8, 0x00, 0x07, 0xe0]), Ok(()));
+    assert_eq!(driver.last_frame(), [0xf8, 0x00, 0x07, 0xe0]);
+}
+
+#[test]
+fn frame_buffer() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Screen::new(240, 240, &[2]);
+    kernel.add_driver(&driver);
+
+    // The first present sends the whole buffer.
+    let mut frame = FrameBuffer::<2, 3>::new();
+    frame.set_pixel(1, 0, 0xf800);
+    frame.set_pixel(0, 2, 0x07e0);
+    frame.set_pixel(2, 0, 0xffff);
+    assert_eq!(frame.pixel(1, 0), Some(0xf800));
+    assert_eq!(frame.pixel(2, 0), None);
+    assert_eq!(frame.present(), Ok(()));
+    assert_eq!(
+        driver.take_writes(),
+        [(
+            (0, 0, 2, 3),
+            vec![0x00, 0x00, 0xf8, 0x00, 0, 0, 0, 0, 0x07, 0xe0, 0x00, 0x00]
+        )]
+    );
+
+    // An unchanged buffer, including one whose pixels are set to the colors
+    // they already have, is not sent.
+    frame.set_pixel(1, 0, 0xf800);
+    assert_eq!(frame.present(), Ok(()));
+    assert_eq!(driver.take_writes(), []);
+
+    // Only the changed rows are sent.
+    frame.set_pixel(1, 1, 0x001f);
+    assert_eq!(frame.present(), Ok(()));
+    assert_eq!(
+        driver.take_writes(),
+        [((0, 1, 2, 1), vec![0x00, 0x00, 0x00, 0x1f])]
+    );
+
+    // Writes are split to fit the driver's limit, in whole rows.
+    frame.set_max_write_len(5);
+    frame.fill(0xffff);
+    assert_eq!(frame.present(), Ok(()));
+    assert_eq!(
+        driver.take_writes(),
+        [
+            ((0, 0, 2, 1), vec![0xff; 4]),
+            ((0, 1, 2, 1), vec![0xff; 4]),
+            ((0, 2, 2, 1), vec![0xff; 4]),
+        ]
+    );
+    frame.fill(0xffff);
+    assert_eq!(frame.present(), Ok(()));
+    assert_eq!(driver.take_writes(), []);
+}
@@ -0,0 +1,113 @@
+//! Code shared by the drivers for sensors that take a single reading at a
+//! time, such as the temperature, humidity, pressure and ambient light
+//! sensors.
+//!
+//! `command_and_wait` is also used by drivers for other devices (such as the
+//! buzzer and the screen) whose operations start with a command and finish
+//! with an upcall.
+
+#![no_std]
+
+use libtock_platform::subscribe::{SupportsId, Upcall, UpcallResult, ValueResult};
+use libtock_platform::{share, DefaultConfig, ErrorCode, Syscalls};
+
+// Used by single_value_sensor!, so that crates using it don't need to name
+// libtock_platform themselves.
+#[doc(hidden)]
+pub use libtock_platform;
+
+/// Performs a complete asynchronous operation: subscribes `result` to the
+/// driver's upcall, issues the command that starts the operation, and yields
+/// until the upcall arrives. The upcall is unsubscribed before returning, so
+/// the caller does not need a `share::scope` of its own.
+///
+/// Subscribing discards any upcall still queued from an earlier operation, so
+/// back-to-back calls never see each other's results.
+pub fn command_and_wait<
+    S: Syscalls,
+    IDS: SupportsId<DRIVER_NUM, SUBSCRIBE_NUM>,
+    R: Upcall<IDS> + UpcallResult,
+    const DRIVER_NUM: u32,
+    const SUBSCRIBE_NUM: u32,
+>(
+    result: &R,
+    command_num: u32,
+    argument0: usize,
+    argument1: usize,
+) -> Result<R::Arg, ErrorCode> {
+    result.reset();
+    share::scope(|subscribe| {
+        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_NUM>(subscribe, result)?;
+        S::command(DRIVER_NUM, command_num, argument0, argument1).to_result::<(), ErrorCode>()?;
+        result.upcall_result_yield::<S>()
+    })
+}
+
+/// Takes a reading from a sensor whose driver starts a reading with a command
+/// and reports it in the first argument of an upcall, as the temperature,
+/// humidity, pressure, moisture and ambient light drivers do. Blocks until the
+/// reading is available.
+pub fn read_single_value<S: Syscalls, const DRIVER_NUM: u32, const SUBSCRIBE_NUM: u32>(
+    command_num: u32,
+) -> Result<u32, ErrorCode> {
+    let reading = ValueResult::new(None);
+    command_and_wait::<S, _, _, DRIVER_NUM, SUBSCRIBE_NUM>(&reading, command_num, 0, 0)
+}
+
+/// Generates the driver type for a sensor whose driver starts a reading with
+/// a command and reports it in the first argument of an upcall. The generated
+/// type has an `exists` function and a blocking read function, which calls
+/// `read_single_value` and converts the reading
+/// to the given type with `as`.
+///
+/// If the kernel rejects the read command (for example, with
+/// `ErrorCode::Off` if the sensor is powered off), the read returns that error
+/// without waiting for an upcall.
+///
+/// # Example
+/// ```ignore
+/// libtock_sensor_common::single_value_sensor! {
+///     /// The humidity sensor driver.
+///     pub struct Humidity;
+///     driver_num: 0x60001;
+///     subscribe_num: 0;
+///     command_num: 1;
+///     /// Reads the humidity, blocking until the reading is available.
+///     fn read_humidity() -> u32;
+/// }
+/// ```
+#[macro_export]
+macro_rules! single_value_sensor {
+    (
+        $(#[$struct_attr:meta])*
+        $vis:vis struct $name:ident;
+        driver_num: $driver_num:expr;
+        subscribe_num: $subscribe_num:expr;
+        command_num: $command_num:expr;
+        $(#[$read_attr:meta])*
+        fn $read:ident() -> $reading:ty;
+    ) => {
+        $(#[$struct_attr])*
+        $vis struct $name<S: $crate::libtock_platform::Syscalls>(S);
+
+        impl<S: $crate::libtock_platform::Syscalls> $name<S> {
+            /// Returns `Ok(())` if the sensor's driver is present.
+            pub fn exists() -> Result<(), $crate::libtock_platform::ErrorCode> {
+                S::driver_exists($driver_num)
+            }
+
+            $(#[$read_attr])*
+            pub fn $read() -> Result<$reading, $crate::libtock_platform::ErrorCode> {
+                $crate::read_single_value::<
+                    S,
+                    { $driver_num },
+                    { $subscribe_num },
+                >($command_num)
+                .map(|reading| reading as $reading)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,115 @@
+extern crate std;
+
+use crate::read_single_value;
+use libtock_platform::{ErrorCode, Syscalls};
+use libtock_unittest::fake;
+
+// The humidity driver, as generated by single_value_sensor!.
+crate::single_value_sensor! {
+    struct MacroHumidity;
+    driver_num: DRIVER_NUM;
+    subscribe_num: 0;
+    command_num: 1;
+    fn read_humidity() -> u32;
+}
+
+// The humidity driver, as it was written by hand before single_value_sensor!.
+struct HandHumidity<S: Syscalls>(S);
+
+impl<S: Syscalls> HandHumidity<S> {
+    fn exists() -> Result<(), ErrorCode> {
+        S::driver_exists(DRIVER_NUM)
+    }
+
+    fn read_humidity() -> Result<u32, ErrorCode> {
+        read_single_value::<S, DRIVER_NUM, 0>(1)
+    }
+}
+
+// A signed reading, as the temperature driver reports.
+crate::single_value_sensor! {
+    struct MacroTemperature;
+    driver_num: 0x60000;
+    subscribe_num: 0;
+    command_num: 1;
+    fn read_temperature() -> i32;
+}
+
+const DRIVER_NUM: u32 = 0x60001;
+
+// Runs the same calls against both humidity drivers, and checks that they
+// return the same results and make the same system calls.
+fn compare(setup: impl Fn(&fake::Humidity), run: impl Fn(bool)) {
+    let mut logs = [std::vec![], std::vec![]];
+    for (macro_generated, log) in [true, false].into_iter().zip(&mut logs) {
+        let kernel = fake::Kernel::new();
+        let driver = fake::Humidity::new();
+        kernel.add_driver(&driver);
+        setup(&driver);
+        kernel.take_syscall_log();
+        run(macro_generated);
+        *log = kernel.take_syscall_log();
+    }
+    assert_eq!(logs[0], logs[1]);
+}
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(
+        MacroHumidity::<fake::Syscalls>::exists(),
+        HandHumidity::<fake::Syscalls>::exists()
+    );
+    assert_eq!(
+        MacroHumidity::<fake::Syscalls>::exists(),
+        Err(ErrorCode::NoDevice)
+    );
+}
+
+#[test]
+fn read() {
+    compare(
+        |driver| {
+            driver.push_reading(4250);
+            driver.push_reading(10000);
+        },
+        |macro_generated| {
+            let read = match macro_generated {
+                true => MacroHumidity::<fake::Syscalls>::read_humidity,
+                false => HandHumidity::<fake::Syscalls>::read_humidity,
+            };
+            assert_eq!(read(), Ok(4250));
+            assert_eq!(read(), Ok(10000));
+        },
+    );
+}
+
+#[test]
+fn busy() {
+    use fake::SyscallDriver;
+    compare(
+        // Start a read that never completes.
+        |driver| assert!(driver.command(1, 0, 0).is_success()),
+        |macro_generated| {
+            let read = match macro_generated {
+                true => MacroHumidity::<fake::Syscalls>::read_humidity,
+                false => HandHumidity::<fake::Syscalls>::read_humidity,
+            };
+            assert_eq!(read(), Err(ErrorCode::Busy));
+        },
+    );
+}
+
+#[test]
+fn signed_reading() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Temperature::new();
+    kernel.add_driver(&driver);
+
+    driver.set_value(-1250);
+    assert_eq!(MacroTemperature::<fake::Syscalls>::exists(), Ok(()));
+    assert_eq!(
+        MacroTemperature::<fake::Syscalls>::read_temperature(),
+        Ok(-1250)
+    );
+}
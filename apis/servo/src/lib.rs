@@ -0,0 +1,69 @@
+#![no_std]
+
+use libtock_platform::{ErrorCode, Syscalls};
+
+/// The servo motor driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock::servo::Servo;
+///
+/// // Center servo 0
+/// Servo::set_angle(0, 90)?;
+/// ```
+pub struct Servo<S: Syscalls>(S);
+
+impl<S: Syscalls> Servo<S> {
+    /// Returns `Ok(())` if the servo driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Returns the number of servos.
+    pub fn count() -> Result<u32, ErrorCode> {
+        S::command(DRIVER_NUM, command::COUNT, 0, 0).to_result()
+    }
+
+    /// Turns servo `index` to `degrees`, which must be at most 180. Returns
+    /// `Err(ErrorCode::NoDevice)` if there is no such servo.
+    pub fn set_angle(index: u32, degrees: u16) -> Result<(), ErrorCode> {
+        if degrees > MAX_ANGLE {
+            return Err(ErrorCode::Invalid);
+        }
+        S::command(
+            DRIVER_NUM,
+            command::SET_ANGLE,
+            index as usize,
+            degrees as usize,
+        )
+        .to_result()
+    }
+
+    /// Returns the angle of servo `index`, in degrees. Returns
+    /// `Err(ErrorCode::NoDevice)` if there is no such servo.
+    pub fn get_angle(index: u32) -> Result<u16, ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_ANGLE, index as usize, 0)
+            .to_result::<u32, ErrorCode>()
+            .map(|degrees| degrees as u16)
+    }
+}
+
+/// The largest angle a servo can be turned to, in degrees.
+pub const MAX_ANGLE: u16 = 180;
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90009;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const SET_ANGLE: u32 = 1;
+    pub const GET_ANGLE: u32 = 2;
+    pub const COUNT: u32 = 3;
+}
@@ -0,0 +1,54 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Servo = super::Servo<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Servo::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn count() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Servo::<3>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Servo::exists(), Ok(()));
+    assert_eq!(Servo::count(), Ok(3));
+}
+
+#[test]
+fn set_get_angle() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Servo::<3>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Servo::set_angle(1, 45), Ok(()));
+    assert_eq!(Servo::set_angle(2, 180), Ok(()));
+    assert_eq!(driver.get_angle(0), Some(0));
+    assert_eq!(driver.get_angle(1), Some(45));
+    assert_eq!(driver.get_angle(2), Some(180));
+    assert_eq!(Servo::get_angle(1), Ok(45));
+}
+
+#[test]
+fn invalid_angle() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Servo::<3>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Servo::set_angle(0, 181), Err(ErrorCode::Invalid));
+    assert_eq!(driver.get_angle(0), Some(0));
+}
+
+#[test]
+fn no_servo() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Servo::<3>::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Servo::set_angle(3, 90), Err(ErrorCode::NoDevice));
+    assert_eq!(Servo::get_angle(3), Err(ErrorCode::NoDevice));
+}
@@ -0,0 +1,141 @@
+#![no_std]
+
+use core::marker::PhantomData;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::{StandardResult, Subscribe, UpcallResult};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// A digest computation using the SHA driver.
+///
+/// The kernel keeps one digest in progress per process, so only one `Sha`
+/// should be in use at a time.
+///
+/// # Example
+/// ```ignore
+/// use libtock::sha::{HashAlgorithm, Sha};
+///
+/// let mut sha = Sha::new(HashAlgorithm::Sha256)?;
+/// sha.update(b"hello ")?;
+/// sha.update(b"world")?;
+/// let mut digest = [0; 32];
+/// sha.finalize(&mut digest)?;
+/// ```
+pub struct Sha<S: Syscalls> {
+    algorithm: HashAlgorithm,
+    _syscalls: PhantomData<S>,
+}
+
+impl<S: Syscalls> Sha<S> {
+    /// Starts a new digest computation using `algorithm`. Returns
+    /// `Err(ErrorCode::NoSupport)` if the kernel does not support the
+    /// algorithm.
+    pub fn new(algorithm: HashAlgorithm) -> Result<Self, ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_ALGORITHM, algorithm as usize, 0)
+            .to_result::<(), ErrorCode>()?;
+        Ok(Sha {
+            algorithm,
+            _syscalls: PhantomData,
+        })
+    }
+
+    /// Returns the algorithm the digest is computed with.
+    pub fn algorithm(&self) -> HashAlgorithm {
+        self.algorithm
+    }
+
+    /// Adds `data` to the digest, blocking until the kernel has processed it.
+    pub fn update(&mut self, data: &[u8]) -> Result<(), ErrorCode> {
+        let done = StandardResult::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::DATA }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::DATA }>(allow_ro, data)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::UPDATE, 0, 0).to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()
+        })
+    }
+
+    /// Completes the digest and writes it to the start of `digest`. Returns
+    /// the length of the digest.
+    ///
+    /// Returns `Err(ErrorCode::Size)`, without finishing the digest, if
+    /// `digest` is shorter than the algorithm's digest.
+    pub fn finalize(self, digest: &mut [u8]) -> Result<usize, ErrorCode> {
+        let len = self.algorithm.digest_len();
+        let digest = digest.get_mut(..len).ok_or(ErrorCode::Size)?;
+        let done = StandardResult::new(None);
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::DIGEST }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::DIGEST }>(allow_rw, digest)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::FINISH, 0, 0).to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()
+        })?;
+        Ok(len)
+    }
+}
+
+/// The hash algorithms the SHA driver may support.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HashAlgorithm {
+    Sha256 = 0,
+    Sha384 = 1,
+    Sha512 = 2,
+}
+
+impl HashAlgorithm {
+    /// Returns the length of the algorithm's digests, in bytes.
+    pub fn digest_len(self) -> usize {
+        match self {
+            HashAlgorithm::Sha256 => 32,
+            HashAlgorithm::Sha384 => 48,
+            HashAlgorithm::Sha512 => 64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40005;
+
+// Command IDs
+#[allow(unused)]
+mod command {
+    pub const SET_ALGORITHM: u32 = 0;
+    pub const RUN: u32 = 1;
+    pub const UPDATE: u32 = 2;
+    pub const FINISH: u32 = 3;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const DATA: u32 = 1;
+}
+
+mod allow_rw {
+    pub const DIGEST: u32 = 2;
+}
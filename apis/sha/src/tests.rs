@@ -0,0 +1,82 @@
+use super::HashAlgorithm;
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type Sha = super::Sha<fake::Syscalls>;
+
+// The SHA-256 digest of "abc", from FIPS 180-2.
+const ABC_DIGEST: [u8; 32] = [
+    0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae, 0x22, 0x23,
+    0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61, 0xf2, 0x00, 0x15, 0xad,
+];
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert!(matches!(
+        Sha::new(HashAlgorithm::Sha256),
+        Err(ErrorCode::NoDevice)
+    ));
+}
+
+#[test]
+fn unsupported_algorithm() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Sha::new();
+    kernel.add_driver(&driver);
+    assert!(matches!(
+        Sha::new(HashAlgorithm::Sha512),
+        Err(ErrorCode::NoSupport)
+    ));
+}
+
+#[test]
+fn digest() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Sha::new();
+    kernel.add_driver(&driver);
+
+    let mut sha = Sha::new(HashAlgorithm::Sha256).unwrap();
+    assert_eq!(sha.algorithm(), HashAlgorithm::Sha256);
+    assert_eq!(sha.update(b"abc"), Ok(()));
+    let mut digest = [0; 40];
+    assert_eq!(sha.finalize(&mut digest), Ok(32));
+    assert_eq!(digest[..32], ABC_DIGEST);
+    assert_eq!(digest[32..], [0; 8]);
+}
+
+#[test]
+fn streaming() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Sha::new();
+    kernel.add_driver(&driver);
+
+    // The digest of a message split across updates is the same as that of the
+    // whole message.
+    let mut sha = Sha::new(HashAlgorithm::Sha256).unwrap();
+    assert_eq!(sha.update(b"a"), Ok(()));
+    assert_eq!(sha.update(b""), Ok(()));
+    assert_eq!(sha.update(b"bc"), Ok(()));
+    let mut digest = [0; 32];
+    assert_eq!(sha.finalize(&mut digest), Ok(32));
+    assert_eq!(digest, ABC_DIGEST);
+
+    // Finalizing starts the next digest from scratch.
+    let mut sha = Sha::new(HashAlgorithm::Sha256).unwrap();
+    assert_eq!(sha.update(b"abc"), Ok(()));
+    assert_eq!(sha.finalize(&mut digest), Ok(32));
+    assert_eq!(digest, ABC_DIGEST);
+}
+
+#[test]
+fn digest_buffer_too_small() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Sha::new();
+    kernel.add_driver(&driver);
+
+    let mut sha = Sha::new(HashAlgorithm::Sha256).unwrap();
+    assert_eq!(sha.update(b"abc"), Ok(()));
+    let mut digest = [0; 31];
+    assert_eq!(sha.finalize(&mut digest), Err(ErrorCode::Size));
+    assert_eq!(digest, [0; 31]);
+}
@@ -0,0 +1,71 @@
+#![no_std]
+
+use libtock_platform::subscribe::StandardResultArg1;
+use libtock_platform::{ErrorCode, Syscalls};
+use libtock_sensor_common::command_and_wait;
+
+/// The sound pressure driver, which reports the sound level picked up by a
+/// microphone on a scale from 0 to 255.
+///
+/// # Example
+/// ```ignore
+/// use libtock::sound_pressure::SoundPressure;
+///
+/// SoundPressure::enable()?;
+/// let level = SoundPressure::read()?;
+/// SoundPressure::disable()?;
+/// ```
+pub struct SoundPressure<S: Syscalls>(S);
+
+impl<S: Syscalls> SoundPressure<S> {
+    /// Returns `Ok(())` if the sound pressure driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::driver_exists(DRIVER_NUM)
+    }
+
+    /// Powers on the microphone. The sensor must be enabled before reading.
+    pub fn enable() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::ENABLE, 0, 0).to_result()
+    }
+
+    /// Powers off the microphone.
+    pub fn disable() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::DISABLE, 0, 0).to_result()
+    }
+
+    /// Reads the sound level, blocking until the reading is available.
+    ///
+    /// Returns `Err(ErrorCode::Off)` if the sensor is disabled. The kernel
+    /// rejects the read command in that case, so this returns without waiting
+    /// for an upcall that would never come.
+    pub fn read() -> Result<u8, ErrorCode> {
+        let reading = StandardResultArg1::new(None);
+        command_and_wait::<S, _, _, DRIVER_NUM, { subscribe::READING }>(
+            &reading,
+            command::READ,
+            0,
+            0,
+        )
+        .map(|level| level as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60006;
+
+// Command IDs
+mod command {
+    pub const READ: u32 = 1;
+    pub const ENABLE: u32 = 2;
+    pub const DISABLE: u32 = 3;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
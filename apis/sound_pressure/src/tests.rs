@@ -0,0 +1,38 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type SoundPressure = super::SoundPressure<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(SoundPressure::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn read() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SoundPressure::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(SoundPressure::exists(), Ok(()));
+    assert_eq!(SoundPressure::enable(), Ok(()));
+    assert!(driver.is_enabled());
+    driver.set_level(87);
+    assert_eq!(SoundPressure::read(), Ok(87));
+    driver.set_level(255);
+    assert_eq!(SoundPressure::read(), Ok(255));
+    assert_eq!(SoundPressure::disable(), Ok(()));
+    assert!(!driver.is_enabled());
+}
+
+#[test]
+fn read_disabled() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SoundPressure::new();
+    kernel.add_driver(&driver);
+
+    // The read fails immediately rather than waiting for an upcall.
+    driver.set_level(87);
+    assert_eq!(SoundPressure::read(), Err(ErrorCode::Off));
+}
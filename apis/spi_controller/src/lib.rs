@@ -0,0 +1,144 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The SPI controller driver.
+///
+/// # Example
+/// ```ignore
+/// use libtock::spi_controller::SpiController;
+///
+/// SpiController::set_rate(1_000_000)?;
+/// let mut response = [0; 3];
+/// SpiController::read_write(&[0x9f, 0, 0], &mut response)?;
+/// ```
+pub struct SpiController<S: Syscalls>(S);
+
+impl<S: Syscalls> SpiController<S> {
+    /// Returns `Ok(())` if the SPI controller driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Performs a full-duplex transfer: `tx` is written while `rx` is read.
+    /// The two buffers must be the same length, or `ErrorCode::Size` is
+    /// returned.
+    pub fn read_write(tx: &[u8], rx: &mut [u8]) -> Result<(), ErrorCode> {
+        if tx.len() != rx.len() {
+            return Err(ErrorCode::Size);
+        }
+        let len = tx.len();
+        let done: Cell<Option<(u32,)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, allow_rw, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, tx)?;
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::READ }>(allow_rw, rx)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            Self::transfer(len, &done)
+        })
+    }
+
+    /// Writes `tx`, discarding the bytes read while it is written.
+    pub fn write(tx: &[u8]) -> Result<(), ErrorCode> {
+        let done: Cell<Option<(u32,)>> = Cell::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, tx)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            Self::transfer(tx.len(), &done)
+        })
+    }
+
+    /// Sets the clock rate, in hertz.
+    pub fn set_rate(rate_hz: u32) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_RATE, rate_hz as usize, 0).to_result()
+    }
+
+    pub fn set_polarity(polarity: ClockPolarity) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_POLARITY, polarity as usize, 0).to_result()
+    }
+
+    pub fn set_phase(phase: ClockPhase) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::SET_PHASE, phase as usize, 0).to_result()
+    }
+
+    // Starts a transfer of `len` bytes using the shared buffers, and waits for
+    // it to complete.
+    fn transfer(len: usize, done: &Cell<Option<(u32,)>>) -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::READ_WRITE, len, 0).to_result::<(), ErrorCode>()?;
+        while done.get().is_none() {
+            S::yield_wait();
+        }
+        Ok(())
+    }
+}
+
+/// The level of the clock line while it is idle.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClockPolarity {
+    IdleLow = 0,
+    IdleHigh = 1,
+}
+
+/// The clock edge on which data is sampled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ClockPhase {
+    SampleLeading = 0,
+    SampleTrailing = 1,
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20001;
+
+// Command IDs
+#[allow(unused)]
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const READ_WRITE: u32 = 2;
+    pub const SET_CHIP_SELECT: u32 = 3;
+    pub const GET_CHIP_SELECT: u32 = 4;
+    pub const SET_RATE: u32 = 5;
+    pub const GET_RATE: u32 = 6;
+    pub const SET_PHASE: u32 = 7;
+    pub const GET_PHASE: u32 = 8;
+    pub const SET_POLARITY: u32 = 9;
+    pub const GET_POLARITY: u32 = 10;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 0;
+}
+
+mod allow_rw {
+    pub const READ: u32 = 0;
+}
@@ -0,0 +1,65 @@
+use super::{ClockPhase, ClockPolarity};
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+type SpiController = super::SpiController<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(SpiController::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn configure() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SpiController::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(SpiController::set_rate(8_000_000), Ok(()));
+    assert_eq!(SpiController::set_polarity(ClockPolarity::IdleHigh), Ok(()));
+    assert_eq!(SpiController::set_phase(ClockPhase::SampleTrailing), Ok(()));
+    assert_eq!(driver.rate(), 8_000_000);
+    assert_eq!(driver.polarity(), 1);
+    assert_eq!(driver.phase(), 1);
+}
+
+#[test]
+fn read_write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SpiController::new();
+    kernel.add_driver(&driver);
+
+    let mut rx = [0; 3];
+    assert_eq!(SpiController::read_write(&[1, 2, 3], &mut rx), Ok(()));
+    assert_eq!(rx, [1, 2, 3]);
+
+    driver.set_response(&[0xef, 0x40, 0x18]);
+    assert_eq!(SpiController::read_write(&[0x9f, 0, 0], &mut rx), Ok(()));
+    assert_eq!(rx, [0xef, 0x40, 0x18]);
+    assert_eq!(driver.take_bytes(), [1, 2, 3, 0x9f, 0, 0]);
+}
+
+#[test]
+fn read_write_length_mismatch() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SpiController::new();
+    kernel.add_driver(&driver);
+
+    let mut rx = [0; 2];
+    assert_eq!(
+        SpiController::read_write(&[1, 2, 3], &mut rx),
+        Err(ErrorCode::Size)
+    );
+    assert_eq!(kernel.take_syscall_log(), []);
+}
+
+#[test]
+fn write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::SpiController::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(SpiController::write(b"hello"), Ok(()));
+    assert_eq!(driver.take_bytes(), b"hello");
+}
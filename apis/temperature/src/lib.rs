@@ -0,0 +1,74 @@
+#![no_std]
+
+use libtock_platform::share::Handle;
+use libtock_platform::subscribe::OneId;
+use libtock_platform::{DefaultConfig, ErrorCode, Subscribe, Syscalls, Upcall};
+
+libtock_sensor_common::single_value_sensor! {
+    /// The temperature sensor driver. Temperatures are in hundredths of a
+    /// degree Celsius.
+    ///
+    /// # Example
+    /// ```ignore
+    /// use libtock::temperature::Temperature;
+    ///
+    /// // Read the temperature, blocking until it is available
+    /// let centi_celsius = Temperature::read_temperature()?;
+    /// ```
+    pub struct Temperature;
+    driver_num: DRIVER_NUM;
+    subscribe_num: subscribe::READING;
+    command_num: command::READ_TEMPERATURE;
+    /// Reads the temperature, blocking until the reading is available.
+    fn read_temperature() -> i32;
+}
+
+impl<S: Syscalls> Temperature<S> {
+    /// Starts a temperature reading and returns without waiting for it.
+    /// `listener` is called with the temperature once it is available, from
+    /// within a later `yield_wait`. The listener remains subscribed until the
+    /// end of the `share::scope` that provided `subscribe`.
+    pub fn read_temperature_async<'share, F: Fn(i32)>(
+        listener: &'share TemperatureListener<F>,
+        subscribe: Handle<Subscribe<'share, S, DRIVER_NUM, { subscribe::READING }>>,
+    ) -> Result<(), ErrorCode> {
+        S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::READING }>(
+            subscribe, listener,
+        )?;
+        S::command(DRIVER_NUM, command::READ_TEMPERATURE, 0, 0).to_result()
+    }
+}
+
+/// A wrapper around a closure to be registered and called when a temperature
+/// reading is available.
+///
+/// ```ignore
+/// let listener = TemperatureListener(|centi_celsius| {
+///     // make use of the reading
+/// });
+/// ```
+pub struct TemperatureListener<F: Fn(i32)>(pub F);
+
+impl<F: Fn(i32)> Upcall<OneId<DRIVER_NUM, { subscribe::READING }>> for TemperatureListener<F> {
+    fn upcall(&self, temperature: usize, _arg1: usize, _arg2: usize) {
+        self.0(temperature as i32)
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60000;
+
+// Command IDs
+mod command {
+    pub const READ_TEMPERATURE: u32 = 1;
+}
+
+mod subscribe {
+    pub const READING: u32 = 0;
+}
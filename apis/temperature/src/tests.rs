@@ -0,0 +1,85 @@
+use core::cell::Cell;
+use libtock_platform::{share, ErrorCode, Syscalls};
+use libtock_unittest::{fake, SyscallLogEntry};
+
+use super::TemperatureListener;
+
+type Temperature = super::Temperature<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Temperature::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn exists() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Temperature::new();
+    kernel.add_driver(&driver);
+    assert_eq!(Temperature::exists(), Ok(()));
+}
+
+#[test]
+fn read_temperature() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Temperature::new();
+    kernel.add_driver(&driver);
+
+    driver.set_value(2150);
+    assert_eq!(Temperature::read_temperature(), Ok(2150));
+    driver.set_value(-400);
+    assert_eq!(Temperature::read_temperature(), Ok(-400));
+}
+
+#[test]
+fn read_temperature_off() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Temperature::new();
+    kernel.add_driver(&driver);
+
+    // A disabled sensor fails the read immediately, without waiting for an
+    // upcall, and the upcall is unsubscribed on the way out.
+    driver.set_enabled(false);
+    assert_eq!(Temperature::read_temperature(), Err(ErrorCode::Off));
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Subscribe {
+                driver_num: 0x60000,
+                subscribe_num: 0,
+            },
+            SyscallLogEntry::Command {
+                driver_id: 0x60000,
+                command_id: 1,
+                argument0: 0,
+                argument1: 0,
+            },
+            SyscallLogEntry::Subscribe {
+                driver_num: 0x60000,
+                subscribe_num: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn read_temperature_async() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Temperature::new();
+    kernel.add_driver(&driver);
+
+    let reading = Cell::new(None);
+    let listener = TemperatureListener(|centi_celsius| reading.set(Some(centi_celsius)));
+    driver.set_value(1875);
+    share::scope(|subscribe| {
+        assert_eq!(
+            Temperature::read_temperature_async(&listener, subscribe),
+            Ok(())
+        );
+        // The listener isn't called until the application yields.
+        assert_eq!(reading.get(), None);
+        fake::Syscalls::yield_wait();
+        assert_eq!(reading.get(), Some(1875));
+    });
+}
@@ -0,0 +1,118 @@
+#![no_std]
+
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::share;
+use libtock_platform::subscribe::{StandardResult, Subscribe, UpcallResult};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+use libtock_sensor_common::command_and_wait;
+
+/// The text screen driver, for character displays such as HD44780 LCDs.
+///
+/// # Example
+/// ```ignore
+/// use libtock::text_screen::TextScreen;
+///
+/// TextScreen::clear()?;
+/// TextScreen::set_cursor(0, 1)?;
+/// TextScreen::write(b"Hello")?;
+/// ```
+pub struct TextScreen<S: Syscalls>(S);
+
+impl<S: Syscalls> TextScreen<S> {
+    /// Returns `Ok(())` if the text screen driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Returns the size of the screen as `(columns, rows)`.
+    pub fn get_size() -> Result<(u32, u32), ErrorCode> {
+        S::command(DRIVER_NUM, command::GET_SIZE, 0, 0).to_result()
+    }
+
+    /// Turns the display on.
+    pub fn on() -> Result<(), ErrorCode> {
+        Self::command_done(command::ON, 0, 0)
+    }
+
+    /// Turns the display off. Its contents are kept, and shown again by `on`.
+    pub fn off() -> Result<(), ErrorCode> {
+        Self::command_done(command::OFF, 0, 0)
+    }
+
+    /// Clears the screen and moves the cursor to the top left corner.
+    pub fn clear() -> Result<(), ErrorCode> {
+        Self::command_done(command::CLEAR, 0, 0)
+    }
+
+    /// Moves the cursor to column `col` of row `row`, counting from 0.
+    pub fn set_cursor(col: u32, row: u32) -> Result<(), ErrorCode> {
+        Self::command_done(command::SET_CURSOR, col as usize, row as usize)
+    }
+
+    /// Writes `text` at the cursor, moving the cursor past it. Blocks until
+    /// the screen has been updated.
+    pub fn write(text: &[u8]) -> Result<(), ErrorCode> {
+        let done = StandardResult::new(None);
+        // Leaving the scope unallows the buffer, so the kernel can't read
+        // `text` once this returns.
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::DONE }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, text)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::DONE }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::WRITE, text.len(), 0).to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()
+        })
+    }
+
+    // Runs a command that completes with an upcall, and waits for it.
+    fn command_done(command_num: u32, argument0: usize, argument1: usize) -> Result<(), ErrorCode> {
+        let done = StandardResult::new(None);
+        command_and_wait::<S, _, _, DRIVER_NUM, { subscribe::DONE }>(
+            &done,
+            command_num,
+            argument0,
+            argument1,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90003;
+
+// Command IDs
+#[allow(unused)]
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const GET_SIZE: u32 = 1;
+    pub const ON: u32 = 2;
+    pub const OFF: u32 = 3;
+    pub const BLINK_ON: u32 = 4;
+    pub const BLINK_OFF: u32 = 5;
+    pub const SHOW_CURSOR: u32 = 6;
+    pub const HIDE_CURSOR: u32 = 7;
+    pub const WRITE: u32 = 8;
+    pub const CLEAR: u32 = 9;
+    pub const HOME: u32 = 10;
+    pub const SET_CURSOR: u32 = 11;
+}
+
+mod subscribe {
+    pub const DONE: u32 = 0;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 0;
+}
@@ -0,0 +1,69 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::{fake, SyscallLogEntry};
+
+type TextScreen = super::TextScreen<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(TextScreen::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn get_size() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::TextScreen::new(16, 2);
+    kernel.add_driver(&driver);
+
+    assert_eq!(TextScreen::exists(), Ok(()));
+    assert_eq!(TextScreen::get_size(), Ok((16, 2)));
+}
+
+#[test]
+fn on_off() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::TextScreen::new(16, 2);
+    kernel.add_driver(&driver);
+
+    assert!(!driver.is_on());
+    assert_eq!(TextScreen::on(), Ok(()));
+    assert!(driver.is_on());
+    assert_eq!(TextScreen::off(), Ok(()));
+    assert!(!driver.is_on());
+}
+
+#[test]
+fn write() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::TextScreen::new(8, 2);
+    kernel.add_driver(&driver);
+
+    assert_eq!(TextScreen::set_cursor(2, 1), Ok(()));
+    assert_eq!(TextScreen::write(b"Hi"), Ok(()));
+    assert_eq!(driver.rows(), ["        ", "  Hi    "]);
+    assert_eq!(driver.cursor(), (4, 1));
+
+    // The buffer is unallowed once the write is done.
+    assert!(kernel
+        .take_syscall_log()
+        .contains(&SyscallLogEntry::AllowRo {
+            driver_num: 0x90003,
+            buffer_num: 0,
+            len: 0,
+        }));
+
+    assert_eq!(TextScreen::clear(), Ok(()));
+    assert_eq!(driver.rows(), ["        ", "        "]);
+    assert_eq!(driver.cursor(), (0, 0));
+}
+
+#[test]
+fn set_cursor_out_of_range() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::TextScreen::new(8, 2);
+    kernel.add_driver(&driver);
+
+    assert_eq!(TextScreen::set_cursor(8, 0), Err(ErrorCode::Invalid));
+    assert_eq!(TextScreen::set_cursor(0, 2), Err(ErrorCode::Invalid));
+    assert_eq!(driver.cursor(), (0, 0));
+}
@@ -0,0 +1,176 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The touch driver, for touch panels.
+///
+/// Single-touch events report one point of contact. Panels that track several
+/// fingers at once also report multi-touch events, read with `read_touches`.
+///
+/// # Example
+/// ```ignore
+/// use libtock::touch::{Touch, TouchStatus};
+///
+/// let event = Touch::next_event()?;
+/// if event.status == TouchStatus::Pressed {
+///     // The panel was touched at (event.x, event.y).
+/// }
+/// ```
+pub struct Touch<S: Syscalls>(S);
+
+impl<S: Syscalls> Touch<S> {
+    /// Returns `Ok(())` if the touch driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Starts reporting single-touch events.
+    pub fn enable() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::ENABLE_SINGLE_TOUCH, 0, 0).to_result()
+    }
+
+    /// Stops reporting single-touch events, and unsubscribes from them so that
+    /// no event already queued is delivered.
+    pub fn disable() -> Result<(), ErrorCode> {
+        S::unsubscribe(DRIVER_NUM, subscribe::SINGLE_TOUCH);
+        S::command(DRIVER_NUM, command::DISABLE_SINGLE_TOUCH, 0, 0).to_result()
+    }
+
+    /// Blocks until the next single-touch event. Single-touch events are
+    /// enabled while this waits, and disabled again before it returns. Single
+    /// touches have ID 0.
+    pub fn next_event() -> Result<TouchEvent, ErrorCode> {
+        // A single-touch upcall reports the status, the position as
+        // x << 16 | y, and the size and pressure, which are ignored.
+        let event: Cell<Option<(u32, u32)>> = Cell::new(None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::SINGLE_TOUCH }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::SINGLE_TOUCH }>(
+                subscribe, &event,
+            )?;
+            Self::enable()?;
+            let (status, position) = S::yield_wait_for_result(|| event.get());
+            Self::disable()?;
+            Ok(TouchEvent {
+                id: 0,
+                status: TouchStatus::from_u32(status).ok_or(ErrorCode::Fail)?,
+                x: (position >> 16) as u16,
+                y: position as u16,
+            })
+        })
+    }
+
+    /// Blocks until the next multi-touch event, and stores the touches it
+    /// reports in `touches`. Returns the number of touches stored, which is at
+    /// most `MAX_TOUCHES`; touches that do not fit are dropped. Multi-touch
+    /// events are enabled while this waits, and disabled again before it
+    /// returns.
+    pub fn read_touches(touches: &mut [TouchEvent]) -> Result<usize, ErrorCode> {
+        let mut buffer = [0; MAX_TOUCHES * TOUCH_LEN];
+        // A multi-touch upcall reports the number of touches in the buffer.
+        let count: Cell<Option<(u32,)>> = Cell::new(None);
+        let reported = share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, { allow_rw::TOUCHES }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::MULTI_TOUCH }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::TOUCHES }>(allow_rw, &mut buffer)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::MULTI_TOUCH }>(
+                subscribe, &count,
+            )?;
+            S::command(DRIVER_NUM, command::ENABLE_MULTI_TOUCH, 0, 0)
+                .to_result::<(), ErrorCode>()?;
+            let (reported,) = S::yield_wait_for_result(|| count.get());
+            S::command(DRIVER_NUM, command::DISABLE_MULTI_TOUCH, 0, 0)
+                .to_result::<(), ErrorCode>()?;
+            Ok::<usize, ErrorCode>(reported as usize)
+        })?;
+        let stored = reported.min(MAX_TOUCHES).min(touches.len());
+        for (touch, bytes) in touches[..stored]
+            .iter_mut()
+            .zip(buffer.chunks_exact(TOUCH_LEN))
+        {
+            *touch = TouchEvent {
+                id: bytes[0],
+                status: TouchStatus::from_u32(bytes[1] as u32).ok_or(ErrorCode::Fail)?,
+                x: u16::from_be_bytes([bytes[2], bytes[3]]),
+                y: u16::from_be_bytes([bytes[4], bytes[5]]),
+            };
+        }
+        Ok(stored)
+    }
+}
+
+/// The most touches `Touch::read_touches` reports from one event.
+pub const MAX_TOUCHES: usize = 10;
+
+/// A touch on the panel.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TouchEvent {
+    /// Identifies the finger across events, for panels that track several.
+    pub id: u8,
+    pub status: TouchStatus,
+    /// The position of the touch, in pixels.
+    pub x: u16,
+    pub y: u16,
+}
+
+/// What happened to a touch.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TouchStatus {
+    #[default]
+    Released = 0,
+    Pressed = 1,
+    Moved = 2,
+}
+
+impl TouchStatus {
+    /// Converts the driver's number for a touch status, returning `None` for
+    /// statuses this library doesn't know.
+    pub fn from_u32(value: u32) -> Option<TouchStatus> {
+        match value {
+            0 => Some(TouchStatus::Released),
+            1 => Some(TouchStatus::Pressed),
+            2 => Some(TouchStatus::Moved),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90002;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const ENABLE_SINGLE_TOUCH: u32 = 1;
+    pub const DISABLE_SINGLE_TOUCH: u32 = 2;
+    pub const ENABLE_MULTI_TOUCH: u32 = 11;
+    pub const DISABLE_MULTI_TOUCH: u32 = 12;
+}
+
+mod subscribe {
+    pub const SINGLE_TOUCH: u32 = 0;
+    pub const MULTI_TOUCH: u32 = 2;
+}
+
+mod allow_rw {
+    pub const TOUCHES: u32 = 2;
+}
+
+// Each touch in the multi-touch buffer is 8 bytes: the ID, the status, x and y
+// (big-endian), the size, and the pressure.
+const TOUCH_LEN: usize = 8;
@@ -0,0 +1,97 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::{fake, SyscallLogEntry};
+
+use crate::{TouchEvent, TouchStatus};
+
+type Touch = super::Touch<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Touch::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn next_event() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Touch::new();
+    kernel.add_driver(&driver);
+    assert_eq!(Touch::exists(), Ok(()));
+
+    driver.push_event(1, 10, 20);
+    driver.push_event(2, 11, 21);
+    driver.push_event(0, 12, 22);
+    driver.push_event(3, 13, 23);
+    let event = |status, x, y| TouchEvent {
+        id: 0,
+        status,
+        x,
+        y,
+    };
+    assert_eq!(Touch::next_event(), Ok(event(TouchStatus::Pressed, 10, 20)));
+    assert_eq!(Touch::next_event(), Ok(event(TouchStatus::Moved, 11, 21)));
+    assert_eq!(
+        Touch::next_event(),
+        Ok(event(TouchStatus::Released, 12, 22))
+    );
+    assert_eq!(Touch::next_event(), Err(ErrorCode::Fail));
+    assert!(!driver.is_waiting());
+}
+
+#[test]
+fn disable_unsubscribes() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Touch::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(Touch::enable(), Ok(()));
+    assert!(driver.is_waiting());
+    kernel.take_syscall_log();
+    assert_eq!(Touch::disable(), Ok(()));
+    assert!(!driver.is_waiting());
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Subscribe {
+                driver_num: 0x90002,
+                subscribe_num: 0,
+            },
+            SyscallLogEntry::Command {
+                driver_id: 0x90002,
+                command_id: 2,
+                argument0: 0,
+                argument1: 0,
+            },
+        ]
+    );
+}
+
+#[test]
+fn read_touches() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Touch::new();
+    kernel.add_driver(&driver);
+
+    let pressed = TouchEvent {
+        id: 0,
+        status: TouchStatus::Pressed,
+        x: 100,
+        y: 200,
+    };
+    let moved = TouchEvent {
+        id: 1,
+        status: TouchStatus::Moved,
+        x: 300,
+        y: 400,
+    };
+    driver.push_touches(&[(0, 1, 100, 200), (1, 2, 300, 400)]);
+    let mut touches = [TouchEvent::default(); 3];
+    assert_eq!(Touch::read_touches(&mut touches), Ok(2));
+    assert_eq!(touches[..2], [pressed, moved]);
+
+    // Touches that don't fit are dropped.
+    driver.push_touches(&[(1, 2, 300, 400), (0, 1, 100, 200)]);
+    let mut touches = [TouchEvent::default(); 1];
+    assert_eq!(Touch::read_touches(&mut touches), Ok(1));
+    assert_eq!(touches, [moved]);
+}
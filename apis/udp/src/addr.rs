@@ -0,0 +1,87 @@
+use core::fmt;
+
+/// An IPv6 address. This is a minimal stand-in for `std::net::Ipv6Addr`,
+/// which is not available in `no_std` code.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Ipv6Addr([u8; 16]);
+
+impl Ipv6Addr {
+    /// The unspecified address, `::`.
+    pub const UNSPECIFIED: Ipv6Addr = Ipv6Addr([0; 16]);
+
+    /// The loopback address, `::1`.
+    pub const LOCALHOST: Ipv6Addr = Ipv6Addr::new(0, 0, 0, 0, 0, 0, 0, 1);
+
+    /// Creates an address from eight 16-bit segments, most significant first.
+    #[allow(clippy::too_many_arguments)]
+    pub const fn new(a: u16, b: u16, c: u16, d: u16, e: u16, f: u16, g: u16, h: u16) -> Self {
+        let segments = [a, b, c, d, e, f, g, h];
+        let mut octets = [0; 16];
+        let mut i = 0;
+        while i < 8 {
+            let [high, low] = segments[i].to_be_bytes();
+            octets[2 * i] = high;
+            octets[2 * i + 1] = low;
+            i += 1;
+        }
+        Ipv6Addr(octets)
+    }
+
+    /// Returns the eight 16-bit segments of the address.
+    pub fn segments(&self) -> [u16; 8] {
+        core::array::from_fn(|i| u16::from_be_bytes([self.0[2 * i], self.0[2 * i + 1]]))
+    }
+
+    pub const fn octets(&self) -> [u8; 16] {
+        self.0
+    }
+}
+
+impl From<[u8; 16]> for Ipv6Addr {
+    fn from(octets: [u8; 16]) -> Self {
+        Ipv6Addr(octets)
+    }
+}
+
+impl From<[u16; 8]> for Ipv6Addr {
+    fn from(segments: [u16; 8]) -> Self {
+        let [a, b, c, d, e, f, g, h] = segments;
+        Ipv6Addr::new(a, b, c, d, e, f, g, h)
+    }
+}
+
+/// Writes the segments in hexadecimal, separated by colons. Unlike
+/// `std::net::Ipv6Addr`, runs of zeros are not compressed to `::`.
+impl fmt::Display for Ipv6Addr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, segment) in self.segments().iter().enumerate() {
+            if i > 0 {
+                f.write_str(":")?;
+            }
+            write!(f, "{:x}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+/// An IP address. Tock's networking stack only supports IPv6, so this has a
+/// single variant; it exists so that IPv4 can be added without changing the
+/// socket API.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IpAddr {
+    V6(Ipv6Addr),
+}
+
+impl From<Ipv6Addr> for IpAddr {
+    fn from(addr: Ipv6Addr) -> Self {
+        IpAddr::V6(addr)
+    }
+}
+
+impl fmt::Display for IpAddr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IpAddr::V6(addr) => addr.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,196 @@
+#![no_std]
+
+use core::marker::PhantomData;
+use libtock_platform::allow_ro::AllowRo;
+use libtock_platform::allow_rw::AllowRw;
+use libtock_platform::share;
+use libtock_platform::subscribe::{StandardResult, Subscribe, UpcallResult, ValueResult};
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+mod addr;
+
+pub use addr::{IpAddr, Ipv6Addr};
+
+/// A UDP socket, bound to a local port.
+///
+/// Buffers are only shared with the kernel during `send_to` and `recv_from`,
+/// so datagrams that arrive while no `recv_from` is in progress are dropped.
+///
+/// # Example
+/// ```ignore
+/// use libtock::udp::{IpAddr, Ipv6Addr, UdpSocket};
+///
+/// let socket = UdpSocket::bind(1000)?;
+/// socket.send_to(IpAddr::V6(Ipv6Addr::LOCALHOST), 2000, b"ping")?;
+/// let mut buffer = [0; 64];
+/// let (len, addr, port) = socket.recv_from(&mut buffer)?;
+/// ```
+pub struct UdpSocket<S: Syscalls> {
+    port: u16,
+    _syscalls: PhantomData<S>,
+}
+
+impl<S: Syscalls> UdpSocket<S> {
+    /// Returns `Ok(())` if the UDP driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Binds a socket to `port` on all interfaces. Port 0 is not a valid
+    /// port.
+    pub fn bind(port: u16) -> Result<Self, ErrorCode> {
+        if port == 0 {
+            return Err(ErrorCode::Invalid);
+        }
+        let mut config = [0; 2 * SOCKADDR_LEN];
+        encode_sockaddr(&mut config[SOCKADDR_LEN..], Ipv6Addr::UNSPECIFIED, port);
+        share::scope::<AllowRw<_, DRIVER_NUM, { allow_rw::RX_CFG }>, _, _>(|allow_rw| {
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::RX_CFG }>(allow_rw, &mut config)?;
+            S::command(DRIVER_NUM, command::BIND, 0, 0).to_result::<(), ErrorCode>()
+        })?;
+        Ok(UdpSocket {
+            port,
+            _syscalls: PhantomData,
+        })
+    }
+
+    /// Returns the local port the socket is bound to.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Sends `data` as one datagram to `port` at `addr`, blocking until it has
+    /// been sent. Returns the number of bytes sent.
+    pub fn send_to(&self, addr: IpAddr, port: u16, data: &[u8]) -> Result<usize, ErrorCode> {
+        let IpAddr::V6(addr) = addr;
+        // The source address, followed by the destination address.
+        let mut config = [0; 2 * SOCKADDR_LEN];
+        encode_sockaddr(
+            &mut config[..SOCKADDR_LEN],
+            Ipv6Addr::UNSPECIFIED,
+            self.port,
+        );
+        encode_sockaddr(&mut config[SOCKADDR_LEN..], addr, port);
+        let done = StandardResult::new(None);
+        share::scope::<
+            (
+                AllowRo<_, DRIVER_NUM, { allow_ro::WRITE }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::TX_CFG }>,
+                Subscribe<_, DRIVER_NUM, { subscribe::TX }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_ro, allow_rw, subscribe) = handle.split();
+            S::allow_ro::<DefaultConfig, DRIVER_NUM, { allow_ro::WRITE }>(allow_ro, data)?;
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::TX_CFG }>(allow_rw, &mut config)?;
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::TX }>(subscribe, &done)?;
+            S::command(DRIVER_NUM, command::SEND, 0, 0).to_result::<(), ErrorCode>()?;
+            done.upcall_result_yield::<S>()
+        })?;
+        Ok(data.len())
+    }
+
+    /// Blocks until a datagram arrives for this socket, copies it into
+    /// `buffer`, and returns its length along with the address and port it
+    /// was sent from. Returns `Err(ErrorCode::Size)` if the datagram is
+    /// longer than `buffer`, in which case `buffer` holds its start.
+    pub fn recv_from(&self, buffer: &mut [u8]) -> Result<(usize, IpAddr, u16), ErrorCode> {
+        // The kernel writes the source address into the first half, and
+        // checks the second half against the socket's binding.
+        let mut config = [0; 2 * SOCKADDR_LEN];
+        encode_sockaddr(
+            &mut config[SOCKADDR_LEN..],
+            Ipv6Addr::UNSPECIFIED,
+            self.port,
+        );
+        let received = ValueResult::new(None);
+        let buffer_len = buffer.len();
+        let len = share::scope::<
+            (
+                Subscribe<_, DRIVER_NUM, { subscribe::RX }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::RX_CFG }>,
+                AllowRw<_, DRIVER_NUM, { allow_rw::READ }>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (subscribe, rx_cfg, read) = handle.split();
+            // Subscribe first, so a datagram the kernel delivers as soon as
+            // the buffers are shared is not missed.
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::RX }>(
+                subscribe, &received,
+            )?;
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::RX_CFG }>(rx_cfg, &mut config)?;
+            S::allow_rw::<DefaultConfig, DRIVER_NUM, { allow_rw::READ }>(read, buffer)?;
+            received.upcall_result_yield::<S>()
+        })? as usize;
+        if len > buffer_len {
+            return Err(ErrorCode::Size);
+        }
+        let (addr, port) = decode_sockaddr(&config[..SOCKADDR_LEN]);
+        Ok((len, IpAddr::V6(addr), port))
+    }
+}
+
+impl<S: Syscalls> Drop for UdpSocket<S> {
+    fn drop(&mut self) {
+        // Make sure the kernel holds none of the socket's buffers once it is
+        // gone. Each call already unallows its own buffers when it returns,
+        // so normally there is nothing left to unallow.
+        let _ = S::unallow_ro(DRIVER_NUM, allow_ro::WRITE);
+        let _ = S::unallow_rw(DRIVER_NUM, allow_rw::READ);
+        let _ = S::unallow_rw(DRIVER_NUM, allow_rw::TX_CFG);
+        let _ = S::unallow_rw(DRIVER_NUM, allow_rw::RX_CFG);
+    }
+}
+
+// The kernel's socket address layout: the IPv6 address, followed by the port
+// in native byte order.
+const SOCKADDR_LEN: usize = 18;
+
+fn encode_sockaddr(buffer: &mut [u8], addr: Ipv6Addr, port: u16) {
+    buffer[..16].copy_from_slice(&addr.octets());
+    buffer[16..SOCKADDR_LEN].copy_from_slice(&port.to_ne_bytes());
+}
+
+fn decode_sockaddr(buffer: &[u8]) -> (Ipv6Addr, u16) {
+    let mut octets = [0; 16];
+    octets.copy_from_slice(&buffer[..16]);
+    let port = u16::from_ne_bytes([buffer[16], buffer[17]]);
+    (Ipv6Addr::from(octets), port)
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x30002;
+
+// Command IDs
+#[allow(unused)]
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const GET_INTERFACES: u32 = 1;
+    pub const SEND: u32 = 2;
+    pub const BIND: u32 = 3;
+    pub const GET_MAX_TX_LEN: u32 = 4;
+}
+
+mod subscribe {
+    pub const RX: u32 = 0;
+    pub const TX: u32 = 1;
+}
+
+mod allow_ro {
+    pub const WRITE: u32 = 0;
+}
+
+mod allow_rw {
+    pub const READ: u32 = 0;
+    pub const TX_CFG: u32 = 1;
+    pub const RX_CFG: u32 = 2;
+}
@@ -0,0 +1,94 @@
+use super::{IpAddr, Ipv6Addr};
+use libtock_platform::ErrorCode;
+use libtock_unittest::{fake, SyscallLogEntry};
+
+type UdpSocket = super::UdpSocket<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(UdpSocket::exists(), Err(ErrorCode::NoDevice));
+    assert!(UdpSocket::bind(1000).is_err());
+}
+
+#[test]
+fn bind() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::UdpDriver::new();
+    kernel.add_driver(&driver);
+
+    assert_eq!(UdpSocket::exists(), Ok(()));
+    let socket = UdpSocket::bind(1000).unwrap();
+    assert_eq!(socket.port(), 1000);
+    assert!(driver.is_bound(1000));
+    assert_eq!(UdpSocket::bind(0).err(), Some(ErrorCode::Invalid));
+}
+
+#[test]
+fn send_recv() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::UdpDriver::new();
+    kernel.add_driver(&driver);
+
+    let client = UdpSocket::bind(1000).unwrap();
+    let server = UdpSocket::bind(2000).unwrap();
+    let localhost = IpAddr::V6(Ipv6Addr::LOCALHOST);
+
+    assert_eq!(client.send_to(localhost, 2000, b"ping"), Ok(4));
+    assert_eq!(driver.pending(2000), 1);
+    let mut buffer = [0; 16];
+    assert_eq!(server.recv_from(&mut buffer), Ok((4, localhost, 1000)));
+    assert_eq!(&buffer[..4], b"ping");
+
+    assert_eq!(server.send_to(localhost, 1000, b"pong"), Ok(4));
+    assert_eq!(client.recv_from(&mut buffer), Ok((4, localhost, 2000)));
+    assert_eq!(&buffer[..4], b"pong");
+}
+
+#[test]
+fn recv_too_long() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::UdpDriver::new();
+    kernel.add_driver(&driver);
+
+    let socket = UdpSocket::bind(1000).unwrap();
+    let localhost = IpAddr::V6(Ipv6Addr::LOCALHOST);
+    assert_eq!(socket.send_to(localhost, 1000, b"too long"), Ok(8));
+    let mut buffer = [0; 3];
+    assert_eq!(socket.recv_from(&mut buffer), Err(ErrorCode::Size));
+    assert_eq!(&buffer, b"too");
+}
+
+#[test]
+fn drop_unallows() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::UdpDriver::new();
+    kernel.add_driver(&driver);
+
+    let socket = UdpSocket::bind(1000).unwrap();
+    kernel.take_syscall_log();
+    drop(socket);
+    let log = kernel.take_syscall_log();
+    for buffer_num in [0, 1, 2] {
+        assert!(log.contains(&SyscallLogEntry::AllowRw {
+            driver_num: 0x30002,
+            buffer_num,
+            len: 0,
+        }));
+    }
+    assert!(log.contains(&SyscallLogEntry::AllowRo {
+        driver_num: 0x30002,
+        buffer_num: 0,
+        len: 0,
+    }));
+}
+
+#[test]
+fn ipv6_addr() {
+    let addr = Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0xab, 0x1234);
+    assert_eq!(addr.segments(), [0xfe80, 0, 0, 0, 0, 0, 0xab, 0x1234]);
+    assert_eq!(addr.octets()[..2], [0xfe, 0x80]);
+    assert_eq!(Ipv6Addr::from(addr.octets()), addr);
+    assert_eq!(Ipv6Addr::from(addr.segments()), addr);
+    assert_eq!(Ipv6Addr::LOCALHOST.octets()[15], 1);
+}
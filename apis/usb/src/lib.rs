@@ -0,0 +1,123 @@
+#![no_std]
+
+use core::cell::Cell;
+use libtock_platform::share;
+use libtock_platform::subscribe::Subscribe;
+use libtock_platform::{DefaultConfig, ErrorCode, Syscalls};
+
+/// The USB controller driver, which connects the device to a USB host. This is
+/// the low-level driver that manages the connection itself; USB classes such as
+/// CTAP have their own drivers.
+///
+/// Upstream Tock's USB user driver only has a combined enable-and-attach
+/// command. This expects a version of it with separate enable, attach, and
+/// detach commands, which reports the controller's state changes as the host
+/// enumerates the device.
+///
+/// # Example
+/// ```ignore
+/// use libtock::usb::{Usb, UsbState};
+///
+/// Usb::enable()?;
+/// Usb::attach()?;
+/// while Usb::wait_for_state()? != UsbState::Configured {}
+/// ```
+pub struct Usb<S: Syscalls>(S);
+
+impl<S: Syscalls> Usb<S> {
+    /// Returns `Ok(())` if the USB controller driver is present.
+    pub fn exists() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::EXISTS, 0, 0).to_result()
+    }
+
+    /// Powers on the USB controller. Enabling a controller that is already
+    /// enabled does nothing.
+    pub fn enable() -> Result<(), ErrorCode> {
+        match S::command(DRIVER_NUM, command::ENABLE, 0, 0).to_result() {
+            Err(ErrorCode::Already) => Ok(()),
+            result => result,
+        }
+    }
+
+    /// Connects to the bus, so the host sees the device and starts
+    /// enumerating it. The controller must be enabled.
+    pub fn attach() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::ATTACH, 0, 0).to_result()
+    }
+
+    /// Disconnects from the bus.
+    pub fn detach() -> Result<(), ErrorCode> {
+        S::command(DRIVER_NUM, command::DETACH, 0, 0).to_result()
+    }
+
+    /// Blocks until the controller's state next changes, and returns the new
+    /// state. Returns `ErrorCode::Fail` if the driver reports a state this
+    /// library doesn't know.
+    pub fn wait_for_state() -> Result<UsbState, ErrorCode> {
+        let state: Cell<Option<(u32,)>> = Cell::new(None);
+        share::scope::<Subscribe<_, DRIVER_NUM, { subscribe::STATE }>, _, _>(|subscribe| {
+            S::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::STATE }>(
+                subscribe, &state,
+            )?;
+            // The driver reports one state change per request.
+            S::command(DRIVER_NUM, command::NOTIFY_STATE, 0, 0).to_result::<(), ErrorCode>()?;
+            let (state,) = S::yield_wait_for_result(|| state.get());
+            UsbState::from_u32(state).ok_or(ErrorCode::Fail)
+        })
+    }
+}
+
+/// The state of the USB controller, as the host enumerates the device.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UsbState {
+    /// Not connected to the bus.
+    Detached = 0,
+    /// Connected, but not yet reset by the host.
+    Attached = 1,
+    /// Reset by the host, which starts enumeration.
+    Reset = 2,
+    /// Given an address by the host.
+    Addressed = 3,
+    /// Configured by the host, and ready for use.
+    Configured = 4,
+    /// Suspended because the bus has been idle.
+    Suspended = 5,
+}
+
+impl UsbState {
+    /// Converts the driver's number for a state, returning `None` for states
+    /// this library doesn't know.
+    pub fn from_u32(value: u32) -> Option<UsbState> {
+        match value {
+            0 => Some(UsbState::Detached),
+            1 => Some(UsbState::Attached),
+            2 => Some(UsbState::Reset),
+            3 => Some(UsbState::Addressed),
+            4 => Some(UsbState::Configured),
+            5 => Some(UsbState::Suspended),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Driver number and command IDs
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20005;
+
+// Command IDs
+mod command {
+    pub const EXISTS: u32 = 0;
+    pub const ENABLE: u32 = 1;
+    pub const ATTACH: u32 = 2;
+    pub const DETACH: u32 = 3;
+    pub const NOTIFY_STATE: u32 = 4;
+}
+
+mod subscribe {
+    pub const STATE: u32 = 0;
+}
@@ -0,0 +1,49 @@
+use libtock_platform::ErrorCode;
+use libtock_unittest::fake;
+
+use crate::UsbState;
+
+type Usb = super::Usb<fake::Syscalls>;
+
+#[test]
+fn no_driver() {
+    let _kernel = fake::Kernel::new();
+    assert_eq!(Usb::exists(), Err(ErrorCode::NoDevice));
+}
+
+#[test]
+fn enable_attach_detach() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Usb::new();
+    kernel.add_driver(&driver);
+    assert_eq!(Usb::exists(), Ok(()));
+
+    assert_eq!(Usb::attach(), Err(ErrorCode::Off));
+    assert_eq!(Usb::enable(), Ok(()));
+    assert!(driver.is_enabled());
+    // Enabling again is a no-op.
+    assert_eq!(Usb::enable(), Ok(()));
+    assert!(driver.is_enabled());
+
+    assert_eq!(Usb::attach(), Ok(()));
+    assert!(driver.is_attached());
+    assert_eq!(Usb::detach(), Ok(()));
+    assert!(!driver.is_attached());
+}
+
+#[test]
+fn wait_for_state() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Usb::new();
+    kernel.add_driver(&driver);
+
+    driver.push_states(&[1, 2, 3, 4, 5, 4, 0, 9]);
+    assert_eq!(Usb::wait_for_state(), Ok(UsbState::Attached));
+    assert_eq!(Usb::wait_for_state(), Ok(UsbState::Reset));
+    assert_eq!(Usb::wait_for_state(), Ok(UsbState::Addressed));
+    assert_eq!(Usb::wait_for_state(), Ok(UsbState::Configured));
+    assert_eq!(Usb::wait_for_state(), Ok(UsbState::Suspended));
+    assert_eq!(Usb::wait_for_state(), Ok(UsbState::Configured));
+    assert_eq!(Usb::wait_for_state(), Ok(UsbState::Detached));
+    assert_eq!(Usb::wait_for_state(), Err(ErrorCode::Fail));
+}
@@ -0,0 +1,77 @@
+//! `libtock_bump_alloc` provides `BumpAllocator`, a global allocator that
+//! hands out memory from a fixed-size arena and never frees it. It is much
+//! smaller than libc's malloc, which makes it a good fit for processes that
+//! allocate a little memory at startup and keep it. `libtock_runtime` uses it
+//! when its `bump_alloc` feature is enabled; the arena is declared with
+//! `libtock_runtime::heap_size!`.
+
+#![no_std]
+#![deny(unsafe_op_in_unsafe_fn)]
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::{Cell, UnsafeCell};
+use core::ptr;
+
+/// An allocator backed by an arena of `SIZE` bytes. Memory is allocated from
+/// the start of the arena upwards, and deallocation does nothing. Once the
+/// arena is full, allocations return null, which makes Rust call the
+/// allocation error handler.
+pub struct BumpAllocator<const SIZE: usize> {
+    arena: UnsafeCell<[u8; SIZE]>,
+    // The offset of the first unallocated byte in the arena.
+    next: Cell<usize>,
+}
+
+// Safety: Tock processes are single-threaded, and upcalls only run while the
+// process is in a yield system call, which the allocator never makes. Therefore
+// the allocator can never be accessed concurrently.
+unsafe impl<const SIZE: usize> Sync for BumpAllocator<SIZE> {}
+
+impl<const SIZE: usize> BumpAllocator<SIZE> {
+    pub const fn new() -> Self {
+        BumpAllocator {
+            arena: UnsafeCell::new([0; SIZE]),
+            next: Cell::new(0),
+        }
+    }
+
+    /// Returns the number of bytes that have been allocated, including any
+    /// padding added for alignment.
+    pub fn used(&self) -> usize {
+        self.next.get()
+    }
+}
+
+impl<const SIZE: usize> Default for BumpAllocator<SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+unsafe impl<const SIZE: usize> GlobalAlloc for BumpAllocator<SIZE> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let base = self.arena.get() as *mut u8;
+        // Layout guarantees the alignment is a power of two.
+        let misalignment = (base as usize + self.next.get()) & (layout.align() - 1);
+        let padding = match misalignment {
+            0 => 0,
+            misalignment => layout.align() - misalignment,
+        };
+        let start = self.next.get() + padding;
+        match start.checked_add(layout.size()) {
+            Some(end) if end <= SIZE => {
+                self.next.set(end);
+                // Safety: start <= end <= SIZE, so the result is within (or
+                // one past the end of) the arena. Deriving it from base keeps
+                // the arena's provenance, which on CHERI includes its bounds.
+                unsafe { base.add(start) }
+            }
+            _ => ptr::null_mut(),
+        }
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,90 @@
+use crate::BumpAllocator;
+use core::alloc::{GlobalAlloc, Layout};
+
+fn layout(size: usize, align: usize) -> Layout {
+    Layout::from_size_align(size, align).unwrap()
+}
+
+#[test]
+fn alignment() {
+    let allocator = BumpAllocator::<64>::new();
+    let a = unsafe { allocator.alloc(layout(1, 1)) };
+    let b = unsafe { allocator.alloc(layout(4, 4)) };
+    let c = unsafe { allocator.alloc(layout(8, 8)) };
+    assert!(!a.is_null() && !b.is_null() && !c.is_null());
+    assert_eq!(b as usize % 4, 0);
+    assert_eq!(c as usize % 8, 0);
+    assert!((a as usize) < (b as usize) && (b as usize) < (c as usize));
+
+    // The allocations are usable and don't overlap.
+    unsafe {
+        a.write(1);
+        (b as *mut u32).write(2);
+        (c as *mut u64).write(3);
+        assert_eq!(a.read(), 1);
+        assert_eq!((b as *mut u32).read(), 2);
+        assert_eq!((c as *mut u64).read(), 3);
+    }
+}
+
+#[test]
+fn alignment_padding() {
+    let allocator = BumpAllocator::<64>::new();
+    let a = unsafe { allocator.alloc(layout(1, 1)) } as usize;
+    let b = unsafe { allocator.alloc(layout(4, 8)) } as usize;
+    // b starts at the first 8-byte aligned address after a, and the padding
+    // between them counts as used.
+    assert_eq!(b, (a + 1 + 7) & !7);
+    assert_eq!(allocator.used(), b + 4 - a);
+    // An allocation that needs no padding follows straight on.
+    let c = unsafe { allocator.alloc(layout(1, 1)) } as usize;
+    assert_eq!(c, b + 4);
+}
+
+#[test]
+fn padding_exhausts_arena() {
+    let allocator = BumpAllocator::<24>::new();
+    let a = unsafe { allocator.alloc(layout(1, 1)) } as usize;
+    let padding = ((a + 1 + 7) & !7) - (a + 1);
+    let remaining = 23 - padding;
+    // The allocation fits without its padding, but not with it.
+    assert!(unsafe { allocator.alloc(layout(remaining + 1, 8)) }.is_null());
+    assert_eq!(allocator.used(), 1);
+    assert!(!unsafe { allocator.alloc(layout(remaining, 8)) }.is_null());
+    assert_eq!(allocator.used(), 24);
+}
+
+#[test]
+fn capacity() {
+    let allocator = BumpAllocator::<32>::new();
+    for _ in 0..4 {
+        assert!(!unsafe { allocator.alloc(layout(8, 1)) }.is_null());
+    }
+    assert_eq!(allocator.used(), 32);
+
+    // Once the arena is full, allocations return null, which the alloc crate
+    // turns into a call to the allocation error handler.
+    assert!(unsafe { allocator.alloc(layout(1, 1)) }.is_null());
+
+    // Zero-sized allocations still succeed.
+    assert!(!unsafe { allocator.alloc(layout(0, 1)) }.is_null());
+}
+
+#[test]
+fn dealloc_does_not_free() {
+    let allocator = BumpAllocator::<16>::new();
+    let ptr = unsafe { allocator.alloc(layout(16, 1)) };
+    assert!(!ptr.is_null());
+    unsafe { allocator.dealloc(ptr, layout(16, 1)) };
+    assert!(unsafe { allocator.alloc(layout(1, 1)) }.is_null());
+}
+
+#[test]
+fn oversized() {
+    let allocator = BumpAllocator::<16>::new();
+    assert!(unsafe { allocator.alloc(layout(17, 1)) }.is_null());
+    assert!(unsafe { allocator.alloc(layout(usize::MAX / 2, 1)) }.is_null());
+    // A failed allocation doesn't use any of the arena.
+    assert_eq!(allocator.used(), 0);
+    assert!(!unsafe { allocator.alloc(layout(16, 1)) }.is_null());
+}
@@ -0,0 +1,138 @@
+//! `libtock_mem` provides the memory routines that the compiler expects to be
+//! able to call (`memcpy`, `memmove`, `memset`, `memcmp` and `bcmp`). They are
+//! normally provided by libc; this crate exists for process binaries that are
+//! built without it. `libtock_runtime` links it in when its `no_libc` feature
+//! is enabled.
+//!
+//! Copies are done a pointer-sized word at a time when the source and
+//! destination are both word-aligned. Besides being faster, this is required on
+//! CHERI: a capability that is copied byte-by-byte loses its tag.
+
+#![no_std]
+// Stops LLVM from recognizing the loops below as memcpy/memset idioms and
+// replacing them with calls to the very functions they implement.
+#![no_builtins]
+#![deny(unsafe_op_in_unsafe_fn)]
+
+use core::mem::{align_of, size_of};
+use core::ptr;
+
+// The unit of word-sized copies. Using a pointer type (rather than usize)
+// means capabilities are copied whole, tag included.
+type Word = *const ();
+
+const WORD_SIZE: usize = size_of::<Word>();
+
+fn word_aligned(dest: *const u8, src: *const u8) -> bool {
+    let mask = align_of::<Word>() - 1;
+    (dest as usize) & mask == 0 && (src as usize) & mask == 0
+}
+
+// The host test binary links against the host's libc, so the symbols are only
+// exported outside of tests. The tests call these functions directly.
+
+/// # Safety
+/// `src` must be valid for reads of `n` bytes, `dest` must be valid for writes
+/// of `n` bytes, and the two regions must not overlap.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn memcpy(dest: *mut u8, src: *const u8, n: usize) -> *mut u8 {
+    unsafe { copy_forward(dest, src, n) };
+    dest
+}
+
+/// # Safety
+/// `src` must be valid for reads of `n` bytes and `dest` must be valid for
+/// writes of `n` bytes. The two regions may overlap.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn memmove(dest: *mut u8, src: *const u8, n: usize) -> *mut u8 {
+    // Copying front-to-back is only a problem if the start of the destination
+    // lies inside the source, in which case we copy back-to-front instead.
+    if (dest as usize).wrapping_sub(src as usize) >= n {
+        unsafe { copy_forward(dest, src, n) };
+    } else {
+        unsafe { copy_backward(dest, src, n) };
+    }
+    dest
+}
+
+/// # Safety
+/// `dest` must be valid for writes of `n` bytes.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn memset(dest: *mut u8, c: i32, n: usize) -> *mut u8 {
+    // As in C, only the low byte of `c` is used.
+    let byte = c as u8;
+    for i in 0..n {
+        unsafe { ptr::write(dest.add(i), byte) };
+    }
+    dest
+}
+
+/// # Safety
+/// `a` and `b` must both be valid for reads of `n` bytes.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn memcmp(a: *const u8, b: *const u8, n: usize) -> i32 {
+    for i in 0..n {
+        let (a, b) = unsafe { (ptr::read(a.add(i)), ptr::read(b.add(i))) };
+        if a != b {
+            return a as i32 - b as i32;
+        }
+    }
+    0
+}
+
+/// Like `memcmp`, but only reports whether the regions differ. LLVM emits calls
+/// to `bcmp` for equality comparisons, such as comparing slices.
+///
+/// # Safety
+/// `a` and `b` must both be valid for reads of `n` bytes.
+#[cfg_attr(not(test), no_mangle)]
+pub unsafe extern "C" fn bcmp(a: *const u8, b: *const u8, n: usize) -> i32 {
+    unsafe { memcmp(a, b, n) }
+}
+
+// Copies from the lowest address up. Safe to use for overlapping regions as
+// long as `dest` is not inside the source region.
+unsafe fn copy_forward(dest: *mut u8, src: *const u8, n: usize) {
+    let mut i = 0;
+    if word_aligned(dest, src) {
+        while n - i >= WORD_SIZE {
+            unsafe {
+                let word = ptr::read(src.add(i) as *const Word);
+                ptr::write(dest.add(i) as *mut Word, word);
+            }
+            i += WORD_SIZE;
+        }
+    }
+    while i < n {
+        unsafe { ptr::write(dest.add(i), ptr::read(src.add(i))) };
+        i += 1;
+    }
+}
+
+// Copies from the highest address down. Safe to use for overlapping regions
+// as long as `src` is not inside the destination region.
+unsafe fn copy_backward(dest: *mut u8, src: *const u8, n: usize) {
+    let mut i = n;
+    if word_aligned(dest, src) {
+        // Copy the trailing partial word first, so that the remaining words
+        // start at aligned offsets.
+        while i % WORD_SIZE != 0 {
+            i -= 1;
+            unsafe { ptr::write(dest.add(i), ptr::read(src.add(i))) };
+        }
+        while i >= WORD_SIZE {
+            i -= WORD_SIZE;
+            unsafe {
+                let word = ptr::read(src.add(i) as *const Word);
+                ptr::write(dest.add(i) as *mut Word, word);
+            }
+        }
+    }
+    while i > 0 {
+        i -= 1;
+        unsafe { ptr::write(dest.add(i), ptr::read(src.add(i))) };
+    }
+}
+
+#[cfg(test)]
+mod tests;
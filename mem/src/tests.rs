@@ -0,0 +1,136 @@
+use crate::{bcmp, memcmp, memcpy, memmove, memset, WORD_SIZE};
+
+// Word-aligned scratch space, so the tests control exactly which copies take
+// the word-at-a-time path.
+#[repr(C, align(16))]
+struct Buffer([u8; 64]);
+
+impl Buffer {
+    fn counting() -> Buffer {
+        let mut buffer = Buffer([0; 64]);
+        for (i, byte) in buffer.0.iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        buffer
+    }
+}
+
+// Compares memmove on `buffer` against core::ptr::copy, which has the same
+// semantics, for every length up to `max_len`.
+fn check_memmove(dest_offset: usize, src_offset: usize, max_len: usize) {
+    for n in 0..=max_len {
+        let mut actual = Buffer::counting();
+        let mut expected = Buffer::counting();
+        let base = actual.0.as_mut_ptr();
+        let returned = unsafe { memmove(base.add(dest_offset), base.add(src_offset), n) };
+        assert_eq!(returned, unsafe { base.add(dest_offset) });
+        let base = expected.0.as_mut_ptr();
+        unsafe { core::ptr::copy(base.add(src_offset), base.add(dest_offset), n) };
+        assert_eq!(
+            actual.0, expected.0,
+            "dest {} src {} n {}",
+            dest_offset, src_offset, n
+        );
+    }
+}
+
+#[test]
+fn memcpy_aligned_and_unaligned() {
+    for (dest_offset, src_offset) in [(0, 0), (0, 1), (3, 0), (5, 5)] {
+        for n in 0..=(64 - 8) {
+            let src = Buffer::counting();
+            let mut dest = Buffer([0xff; 64]);
+            unsafe {
+                memcpy(
+                    dest.0.as_mut_ptr().add(dest_offset),
+                    src.0.as_ptr().add(src_offset),
+                    n,
+                );
+            }
+            let copied = &dest.0[dest_offset..dest_offset + n];
+            assert_eq!(copied, &src.0[src_offset..src_offset + n]);
+            // Nothing outside the destination range was touched.
+            assert!(dest.0[..dest_offset].iter().all(|&b| b == 0xff));
+            assert!(dest.0[dest_offset + n..].iter().all(|&b| b == 0xff));
+        }
+    }
+}
+
+#[test]
+fn memcpy_copies_pointers() {
+    let values = [10u32, 20, 30, 40];
+    let src: [*const u32; 4] = [&values[0], &values[1], &values[2], &values[3]];
+    let mut dest: [*const u32; 4] = [core::ptr::null(); 4];
+    unsafe {
+        memcpy(
+            dest.as_mut_ptr() as *mut u8,
+            src.as_ptr() as *const u8,
+            core::mem::size_of_val(&src),
+        );
+    }
+    // The copied pointers must remain usable, which on CHERI requires their
+    // tags to have been preserved.
+    for (i, &pointer) in dest.iter().enumerate() {
+        assert_eq!(unsafe { *pointer }, values[i]);
+    }
+}
+
+#[test]
+fn memmove_forward_overlap() {
+    // Destination below the source.
+    check_memmove(0, 1, 63);
+    check_memmove(0, WORD_SIZE, 64 - WORD_SIZE);
+    check_memmove(3, 7, 57);
+}
+
+#[test]
+fn memmove_backward_overlap() {
+    // Destination above the source, inside it.
+    check_memmove(1, 0, 63);
+    check_memmove(WORD_SIZE, 0, 64 - WORD_SIZE);
+    check_memmove(7, 3, 57);
+}
+
+#[test]
+fn memmove_disjoint_and_identical() {
+    check_memmove(32, 0, 32);
+    check_memmove(0, 32, 32);
+    check_memmove(0, 0, 64);
+    check_memmove(17, 17, 47);
+}
+
+#[test]
+fn memset_fill() {
+    let mut buffer = Buffer::counting();
+    unsafe { memset(buffer.0.as_mut_ptr().add(3), 0xab, 10) };
+    assert_eq!(buffer.0[2], 2);
+    assert!(buffer.0[3..13].iter().all(|&b| b == 0xab));
+    assert_eq!(buffer.0[13], 13);
+
+    // Only the low byte of the fill value is used.
+    unsafe { memset(buffer.0.as_mut_ptr(), 0x1234, 2) };
+    assert_eq!(buffer.0[..3], [0x34, 0x34, 2]);
+
+    // A zero-length memset does nothing.
+    unsafe { memset(buffer.0.as_mut_ptr(), 0, 0) };
+    assert_eq!(buffer.0[0], 0x34);
+}
+
+#[test]
+fn memcmp_ordering() {
+    let a = [1u8, 2, 3, 4];
+    let b = [1u8, 2, 5, 0];
+    unsafe {
+        assert_eq!(memcmp(a.as_ptr(), b.as_ptr(), 0), 0);
+        assert_eq!(memcmp(a.as_ptr(), b.as_ptr(), 2), 0);
+        assert!(memcmp(a.as_ptr(), b.as_ptr(), 4) < 0);
+        assert!(memcmp(b.as_ptr(), a.as_ptr(), 4) > 0);
+        assert_eq!(bcmp(a.as_ptr(), a.as_ptr(), 4), 0);
+        assert_ne!(bcmp(a.as_ptr(), b.as_ptr(), 4), 0);
+    }
+
+    // Bytes compare as unsigned values.
+    let high = [0x80u8];
+    let low = [0x7fu8];
+    assert!(unsafe { memcmp(high.as_ptr(), low.as_ptr(), 1) } > 0);
+}
@@ -0,0 +1,72 @@
+#![cfg_attr(not(test), no_std)]
+use core::fmt::{self, Write};
+use libtock_console::Console;
+use libtock_platform::Syscalls;
+
+/// The maximum number of bytes of panic message (including the location) that
+/// are written to the console. Longer messages are truncated and end in "...".
+pub const MAX_MESSAGE_LEN: usize = 128;
+
+/// Formats `info` into a fixed-size buffer on the stack and writes it to the
+/// console, followed by a newline. This does not allocate, as the panic may
+/// have been caused by running out of heap.
+pub fn report<S: Syscalls>(info: &dyn fmt::Display) {
+    let mut message = MessageBuffer {
+        buf: [0; MAX_MESSAGE_LEN + 1],
+        len: 0,
+        truncated: false,
+    };
+    // MessageBuffer only returns an error once it is full, in which case we
+    // still want to print what fit.
+    let _ = write!(message, "{}", info);
+    if message.truncated {
+        message.buf[MAX_MESSAGE_LEN - 3..MAX_MESSAGE_LEN].copy_from_slice(b"...");
+    }
+    message.buf[message.len] = b'\n';
+    // If this printing fails, we can't panic harder, and we can't print it either.
+    let _ = Console::<S>::write(&message.buf[..=message.len]);
+}
+
+// Collects formatted output into buf, dropping everything past the first
+// MAX_MESSAGE_LEN bytes. The last byte of buf is reserved for the newline.
+struct MessageBuffer {
+    buf: [u8; MAX_MESSAGE_LEN + 1],
+    len: usize,
+    truncated: bool,
+}
+
+impl Write for MessageBuffer {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let count = core::cmp::min(s.len(), MAX_MESSAGE_LEN - self.len);
+        self.buf[self.len..self.len + count].copy_from_slice(&s.as_bytes()[..count]);
+        self.len += count;
+        if count < s.len() {
+            self.truncated = true;
+            // Stop formatting, as nothing else will fit.
+            return Err(fmt::Error);
+        }
+        Ok(())
+    }
+}
+
+// This handler requires some 0x100 bytes of stack.
+#[cfg(all(target_os = "none", not(test)))]
+#[panic_handler]
+fn panic_handler(info: &core::panic::PanicInfo) -> ! {
+    use libtock_low_level_debug::{AlertCode, LowLevelDebug};
+    use libtock_platform::ErrorCode;
+    use libtock_runtime::TockSyscalls;
+
+    // Signal a panic using the LowLevelDebug capsule (if available).
+    LowLevelDebug::<TockSyscalls>::print_alert_code(AlertCode::Panic);
+    // Also signal if the panic follows a stack overflow (if stack_guard is
+    // enabled).
+    libtock_runtime::stack_guard::report_overflow();
+
+    report::<TockSyscalls>(info);
+    // Exit with a non-zero exit code to indicate failure.
+    TockSyscalls::exit_terminate(ErrorCode::Fail as u32);
+}
+
+#[cfg(test)]
+mod tests;
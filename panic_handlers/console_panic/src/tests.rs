@@ -0,0 +1,42 @@
+use super::*;
+use libtock_unittest::fake;
+
+#[test]
+fn short_message() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    report::<fake::Syscalls>(&format_args!(
+        "panicked at {}:\n{}",
+        "src/main.rs:7", "oops"
+    ));
+    assert_eq!(driver.take_bytes(), b"panicked at src/main.rs:7:\noops\n");
+}
+
+#[test]
+fn truncated_message() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    let long = "x".repeat(2 * MAX_MESSAGE_LEN);
+    report::<fake::Syscalls>(&format_args!("panicked at src/main.rs:7:\n{}", long));
+    let output = driver.take_bytes();
+    assert_eq!(output.len(), MAX_MESSAGE_LEN + 1);
+    assert!(output.starts_with(b"panicked at src/main.rs:7:\nxxx"));
+    assert!(output.ends_with(b"xxx...\n"));
+}
+
+#[test]
+fn exact_length_message() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Console::new();
+    kernel.add_driver(&driver);
+
+    let message = "y".repeat(MAX_MESSAGE_LEN);
+    report::<fake::Syscalls>(&message);
+    let mut expected = message.into_bytes();
+    expected.push(b'\n');
+    assert_eq!(driver.take_bytes(), expected);
+}
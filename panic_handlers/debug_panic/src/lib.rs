@@ -11,6 +11,9 @@ use libtock_runtime::TockSyscalls;
 fn panic_handler(info: &core::panic::PanicInfo) -> ! {
     // Signal a panic using the LowLevelDebug capsule (if available).
     LowLevelDebug::<TockSyscalls>::print_alert_code(AlertCode::Panic);
+    // Also signal if the panic follows a stack overflow (if stack_guard is
+    // enabled).
+    libtock_runtime::stack_guard::report_overflow();
 
     let mut writer = Console::<TockSyscalls>::writer();
     // If this printing fails, we can't panic harder, and we can't print it either.
@@ -0,0 +1,46 @@
+#![cfg_attr(not(test), no_std)]
+use libtock_leds::Leds;
+use libtock_platform::Syscalls;
+
+/// The LED that is blinked on panic.
+pub const PANIC_LED: u32 = 0;
+
+/// The number of busy-wait iterations between toggles of the LED. This is
+/// meant to give a slow blink, around a second per toggle on a typical
+/// microcontroller.
+pub const SPINS_PER_TOGGLE: u32 = 4_000_000;
+
+/// Toggles the panic LED, then busy-waits before returning. The panic handler
+/// calls this forever.
+///
+/// This busy-waits rather than using the alarm driver, as the panic may have
+/// happened in the middle of an alarm operation (or in an upcall), so the
+/// alarm cannot be relied upon. It does not allocate.
+pub fn blink_once<S: Syscalls>() {
+    // If the LED is missing, there is nothing better to do than keep trying.
+    let _ = Leds::<S>::toggle(PANIC_LED);
+    for i in 0..SPINS_PER_TOGGLE {
+        // black_box keeps the loop from being optimized away.
+        core::hint::black_box(i);
+    }
+}
+
+#[cfg(all(target_os = "none", not(test)))]
+#[panic_handler]
+fn panic_handler(_info: &core::panic::PanicInfo) -> ! {
+    use libtock_low_level_debug::{AlertCode, LowLevelDebug};
+    use libtock_runtime::TockSyscalls;
+
+    // Signal a panic using the LowLevelDebug capsule (if available).
+    LowLevelDebug::<TockSyscalls>::print_alert_code(AlertCode::Panic);
+    // Also signal if the panic follows a stack overflow (if stack_guard is
+    // enabled).
+    libtock_runtime::stack_guard::report_overflow();
+
+    loop {
+        blink_once::<TockSyscalls>();
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,23 @@
+use super::*;
+use libtock_unittest::fake;
+
+#[test]
+fn blink() {
+    let kernel = fake::Kernel::new();
+    let driver = fake::Leds::<2>::new();
+    kernel.add_driver(&driver);
+
+    blink_once::<fake::Syscalls>();
+    assert_eq!(driver.get_led(PANIC_LED), Some(true));
+    blink_once::<fake::Syscalls>();
+    assert_eq!(driver.get_led(PANIC_LED), Some(false));
+    // Only the panic LED blinks.
+    assert_eq!(driver.get_led(1), Some(false));
+}
+
+#[test]
+fn no_led() {
+    let _kernel = fake::Kernel::new();
+    // Without an LED, blinking does nothing, but still returns.
+    blink_once::<fake::Syscalls>();
+}
@@ -8,6 +8,9 @@ use libtock_runtime::TockSyscalls;
 fn panic_handler(_info: &core::panic::PanicInfo) -> ! {
     // Signal a panic using the LowLevelDebug capsule (if available).
     LowLevelDebug::<TockSyscalls>::print_alert_code(AlertCode::Panic);
+    // Also signal if the panic follows a stack overflow (if stack_guard is
+    // enabled).
+    libtock_runtime::stack_guard::report_overflow();
 
     // Exit with a non-zero exit code to indicate failure.
     // TODO(kupiakos@google.com): Make this logic consistent with tock/tock#2914
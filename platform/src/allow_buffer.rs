@@ -0,0 +1,71 @@
+use crate::{share, AllowRo, AllowRw, DefaultConfig, ErrorCode, Syscalls};
+
+/// A buffer that is shared with driver `DRIVER_NUM` as its Allow buffer
+/// `BUFFER_NUM`. Because the buffer's type carries its driver and buffer
+/// number, `share_ro` and `share_rw` only accept a `share::Handle` for the
+/// same Allow, so sharing it as the wrong buffer fails to compile.
+///
+/// `B` is the underlying storage, such as an array, `&[u8]`, or `&mut [u8]`.
+/// Read-Only Allow needs `B: AsRef<[u8]>`, and Read-Write Allow needs
+/// `B: AsMut<[u8]>`.
+///
+/// # Example
+/// ```ignore
+/// use libtock_platform::{share, AllowBuffer};
+///
+/// type WriteBuffer<B> = AllowBuffer<B, 0x1, 1>;
+///
+/// let message = WriteBuffer::new(*b"Hello");
+/// share::scope(|handle| {
+///     message.share_ro::<S>(handle)?;
+///     // ...
+/// })?;
+/// ```
+pub struct AllowBuffer<B, const DRIVER_NUM: u32, const BUFFER_NUM: u32> {
+    buffer: B,
+}
+
+impl<B, const DRIVER_NUM: u32, const BUFFER_NUM: u32> AllowBuffer<B, DRIVER_NUM, BUFFER_NUM> {
+    pub const fn new(buffer: B) -> Self {
+        AllowBuffer { buffer }
+    }
+
+    /// Returns the underlying storage.
+    pub fn get(&self) -> &B {
+        &self.buffer
+    }
+
+    /// Returns the underlying storage, for modifying it while it is not
+    /// shared.
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.buffer
+    }
+
+    pub fn into_inner(self) -> B {
+        self.buffer
+    }
+
+    /// Shares the buffer with the kernel through Read-Only Allow, using
+    /// `DefaultConfig`. The buffer stays shared until `handle`'s scope ends.
+    pub fn share_ro<'share, S: Syscalls>(
+        &'share self,
+        handle: share::Handle<AllowRo<'share, S, DRIVER_NUM, BUFFER_NUM>>,
+    ) -> Result<(), ErrorCode>
+    where
+        B: AsRef<[u8]>,
+    {
+        S::allow_ro::<DefaultConfig, DRIVER_NUM, BUFFER_NUM>(handle, self.buffer.as_ref())
+    }
+
+    /// Shares the buffer with the kernel through Read-Write Allow, using
+    /// `DefaultConfig`. The buffer stays shared until `handle`'s scope ends.
+    pub fn share_rw<'share, S: Syscalls>(
+        &'share mut self,
+        handle: share::Handle<AllowRw<'share, S, DRIVER_NUM, BUFFER_NUM>>,
+    ) -> Result<(), ErrorCode>
+    where
+        B: AsMut<[u8]>,
+    {
+        S::allow_rw::<DefaultConfig, DRIVER_NUM, BUFFER_NUM>(handle, self.buffer.as_mut())
+    }
+}
@@ -0,0 +1,38 @@
+//! A minimal single-task executor.
+
+use crate::Syscalls;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+/// Runs `future` to completion and returns its output.
+///
+/// The only way a Tock process learns that something has happened is by
+/// receiving an upcall, which it can only do while yielding. Therefore the
+/// future is polled once, and then again each time `yield_wait` returns, until
+/// it is ready. Wakers are ignored: every upcall is treated as a wakeup.
+pub fn block_on<S: Syscalls, F: Future>(mut future: F) -> F::Output {
+    // Safety: future is shadowed, so it can't be moved again after being
+    // pinned.
+    let mut future = unsafe { Pin::new_unchecked(&mut future) };
+    // Safety: the vtable's functions do nothing, and never touch the data
+    // pointer, so they are trivially thread-safe and uphold RawWaker's
+    // contract.
+    let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+    let mut context = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+        S::yield_wait();
+    }
+}
+
+fn noop_raw_waker() -> RawWaker {
+    fn clone(_: *const ()) -> RawWaker {
+        noop_raw_waker()
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+    RawWaker::new(core::ptr::null(), &VTABLE)
+}
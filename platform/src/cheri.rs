@@ -0,0 +1,78 @@
+//! CHERI support: the kernel's `cheri` module (which provides `cptr`), plus
+//! helpers for sealing capabilities.
+//!
+//! A sealed capability cannot be dereferenced or modified; it can only be
+//! copied around or unsealed by code holding the authority it was sealed
+//! with. That makes sealed capabilities unforgeable tokens, such as handles
+//! a service gives to its clients. The sealing helpers only exist on CHERI.
+
+pub use kernel::cheri::*;
+
+#[cfg(target_feature = "xcheri")]
+use crate::Register;
+
+/// Seals `capability` with the object type given by the address of
+/// `authority`, which must be a capability with permission to seal that
+/// object type. If it is not, the result is untagged and cannot be used.
+#[cfg(target_feature = "xcheri")]
+pub fn seal(capability: Register, authority: Register) -> Register {
+    let mut sealed = capability;
+    // Safety: sealing only restricts what the capability can be used for, and
+    // the hardware checks that authority permits it.
+    unsafe {
+        core::arch::asm!(
+            "lc    ca2, 0(a0)",
+            "lc    ca3, 0(a1)",
+            "cseal ca2, ca2, ca3",
+            "sc    ca2, 0(a0)",
+            in("a0") &mut sealed.0 as *mut cptr,
+            in("a1") &authority.0 as *const cptr,
+            out("a2") _,
+            out("a3") _,
+            options(preserves_flags, nostack),
+        );
+    }
+    sealed
+}
+
+/// Unseals `capability`, which must have been sealed with the object type
+/// given by the address of `authority`. `authority` must have permission to
+/// unseal that object type. If either does not hold, the result is untagged
+/// and cannot be used.
+#[cfg(target_feature = "xcheri")]
+pub fn unseal(capability: Register, authority: Register) -> Register {
+    let mut unsealed = capability;
+    // Safety: the hardware only unseals the capability if authority permits
+    // it, so this cannot grant access to anything the caller was not given.
+    unsafe {
+        core::arch::asm!(
+            "lc      ca2, 0(a0)",
+            "lc      ca3, 0(a1)",
+            "cunseal ca2, ca2, ca3",
+            "sc      ca2, 0(a0)",
+            in("a0") &mut unsealed.0 as *mut cptr,
+            in("a1") &authority.0 as *const cptr,
+            out("a2") _,
+            out("a3") _,
+            options(preserves_flags, nostack),
+        );
+    }
+    unsealed
+}
+
+/// Returns true if `capability` is sealed.
+#[cfg(target_feature = "xcheri")]
+pub fn is_sealed(capability: Register) -> bool {
+    let sealed: usize;
+    // Safety: this only reads the capability's metadata.
+    unsafe {
+        core::arch::asm!(
+            "lc    ca1, 0(a0)",
+            "cgetsealed a0, ca1",
+            inlateout("a0") &capability.0 as *const cptr => sealed,
+            out("a1") _,
+            options(preserves_flags, nostack, readonly),
+        );
+    }
+    sealed != 0
+}
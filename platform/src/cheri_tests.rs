@@ -0,0 +1,32 @@
+use crate::cheri::{is_sealed, seal, unseal};
+use crate::Register;
+
+// The CHERI permissions to seal and to unseal.
+const PERMIT_SEAL: u32 = 1 << 7;
+const PERMIT_UNSEAL: u32 = 1 << 9;
+
+#[test]
+fn not_sealed() {
+    let buffer = [0u8; 16];
+    let register = Register::from_slice(buffer.as_ptr(), buffer.len());
+    assert!(register.cheri_tag());
+    assert!(!is_sealed(register));
+}
+
+// A capability to data carries no permission to seal or unseal, so it cannot
+// be used as an authority: the results are untagged.
+#[test]
+fn no_authority() {
+    let buffer = [0u8; 16];
+    let register = Register::from_slice(buffer.as_ptr(), buffer.len());
+    assert_eq!(
+        register.cheri_permissions() & (PERMIT_SEAL | PERMIT_UNSEAL),
+        0
+    );
+
+    let sealed = seal(register, register);
+    assert!(!sealed.cheri_tag());
+    assert!(!is_sealed(sealed));
+    let unsealed = unseal(register, register);
+    assert!(!unsealed.cheri_tag());
+}
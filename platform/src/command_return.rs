@@ -73,20 +73,50 @@ pub struct CommandReturn {
 /// On 32-bit platforms, lsb are the 32 lsbs and msb are the 32 msbs.
 /// on 64-bit platforms, lsb is all of the 64-bit field, and msb is ignored.
 pub fn get_u64_from_usizes(lsb: usize, msb: usize) -> u64 {
-    if core::mem::size_of::<usize>() == 4 {
-        lsb as u64 + ((msb as u64) << 32)
-    } else {
-        lsb as u64
-    }
+    u64_from_command_args(lsb, msb)
 }
 
 /// On 32-bit platforms, return (lsb, msb) of val
 /// On 64-bit platforms, return (val, 0)
 pub fn get_usizes_from_u64(val: u64) -> (usize, usize) {
-    if core::mem::size_of::<usize>() == 4 {
-        (val as usize, (val >> 32) as usize)
-    } else {
-        (val as usize, 0)
+    command_u64_args(val)
+}
+
+/// Splits `value` into the two arguments of a command (or the two registers
+/// of a `CommandReturn`), the way Tock passes 64-bit values. On 32-bit
+/// targets, the first argument holds the low 32 bits and the second the high
+/// 32 bits. On 64-bit targets, the whole value fits in the first argument and
+/// the second is 0.
+///
+/// ```
+/// use libtock_platform::{command_u64_args, u64_from_command_args};
+///
+/// let (argument0, argument1) = command_u64_args(0x1234_5678_9abc_def0);
+/// assert_eq!(u64_from_command_args(argument0, argument1), 0x1234_5678_9abc_def0);
+/// ```
+pub fn command_u64_args(value: u64) -> (usize, usize) {
+    #[cfg(target_pointer_width = "32")]
+    {
+        (value as usize, (value >> 32) as usize)
+    }
+    #[cfg(not(target_pointer_width = "32"))]
+    {
+        (value as usize, 0)
+    }
+}
+
+/// The inverse of `command_u64_args`: reassembles a 64-bit value from two
+/// command arguments or `CommandReturn` registers. On 64-bit targets,
+/// `argument1` is ignored.
+pub fn u64_from_command_args(argument0: usize, argument1: usize) -> u64 {
+    #[cfg(target_pointer_width = "32")]
+    {
+        argument0 as u64 | (argument1 as u64) << 32
+    }
+    #[cfg(not(target_pointer_width = "32"))]
+    {
+        let _ = argument1;
+        argument0 as u64
     }
 }
 
@@ -247,6 +277,38 @@ impl CommandReturn {
         self.return_variant
     }
 
+    /// Decodes this `CommandReturn` into a `CommandResult`, which has one
+    /// variant per return variant. This is useful for code that does not know
+    /// in advance which variant a command returns, as it can `match` on every
+    /// possibility.
+    pub fn decode(&self) -> CommandResult {
+        // The accessors return None unless the return variant matches, so
+        // exactly one of them succeeds for a recognized return variant.
+        if self.is_success() {
+            CommandResult::Success
+        } else if let Some(value) = self.get_success_u32() {
+            CommandResult::SuccessU32(value)
+        } else if let Some((value0, value1)) = self.get_success_2_u32() {
+            CommandResult::Success2U32(value0, value1)
+        } else if let Some(value) = self.get_success_u64() {
+            CommandResult::SuccessU64(value)
+        } else if let Some((value0, value1, value2)) = self.get_success_3_u32() {
+            CommandResult::Success3U32(value0, value1, value2)
+        } else if let Some((value0, value1)) = self.get_success_u32_u64() {
+            CommandResult::SuccessU32U64(value0, value1)
+        } else if let Some(error_code) = self.get_failure() {
+            CommandResult::Failure(error_code)
+        } else if let Some((error_code, value)) = self.get_failure_u32() {
+            CommandResult::FailureU32(error_code, value)
+        } else if let Some((error_code, value0, value1)) = self.get_failure_2_u32() {
+            CommandResult::Failure2U32(error_code, value0, value1)
+        } else if let Some((error_code, value)) = self.get_failure_u64() {
+            CommandResult::FailureU64(error_code, value)
+        } else {
+            CommandResult::Unrecognized(self.return_variant)
+        }
+    }
+
     /// Interprets this `CommandReturn` as a `Result`, checking the success and
     /// failure variants, as well as extracting the relevant data.
     ///
@@ -278,6 +340,28 @@ impl CommandReturn {
     }
 }
 
+/// A decoded [`CommandReturn`], returned by [`CommandReturn::decode`]. Each
+/// variant corresponds to a return variant from [TRD 104][trd-104], and carries
+/// that return variant's data.
+///
+/// [trd-104]: https://github.com/tock/tock/blob/master/doc/reference/trd104-syscalls.md#32-return-values
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandResult {
+    Failure(ErrorCode),
+    FailureU32(ErrorCode, u32),
+    Failure2U32(ErrorCode, u32, u32),
+    FailureU64(ErrorCode, u64),
+    Success,
+    SuccessU32(u32),
+    Success2U32(u32, u32),
+    SuccessU64(u64),
+    Success3U32(u32, u32, u32),
+    SuccessU32U64(u32, u64),
+
+    /// The kernel returned a return variant that is not valid for `command`.
+    Unrecognized(ReturnVariant),
+}
+
 mod sealed {
     pub trait Sealed {}
 }
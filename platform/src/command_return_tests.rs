@@ -1,4 +1,8 @@
-use crate::{return_variant, CommandReturn, ErrorCode};
+use crate::command_return::get_u64_from_usizes;
+use crate::{
+    command_u64_args, return_variant, u64_from_command_args, CommandResult, CommandReturn,
+    ErrorCode,
+};
 
 #[test]
 fn failure() {
@@ -57,7 +61,7 @@ fn failure_u32() {
     let command_return = unsafe {
         CommandReturn::new(
             return_variant::FAILURE_U32,
-            ErrorCode::Off as u32,
+            ErrorCode::Off as usize,
             1002,
             1003,
         )
@@ -551,3 +555,92 @@ fn success_u32_u64() {
         Ok((1001, 0x0000_1003_0000_1002))
     );
 }
+
+#[test]
+fn decode() {
+    let decode = |return_variant, r1, r2, r3| {
+        unsafe { CommandReturn::new(return_variant, r1, r2, r3) }.decode()
+    };
+    let fail = ErrorCode::Fail as usize;
+    assert_eq!(
+        decode(return_variant::FAILURE, fail, 1002, 1003),
+        CommandResult::Failure(ErrorCode::Fail)
+    );
+    assert_eq!(
+        decode(return_variant::FAILURE_U32, fail, 1002, 1003),
+        CommandResult::FailureU32(ErrorCode::Fail, 1002)
+    );
+    assert_eq!(
+        decode(return_variant::FAILURE_2_U32, fail, 1002, 1003),
+        CommandResult::Failure2U32(ErrorCode::Fail, 1002, 1003)
+    );
+    assert_eq!(
+        decode(return_variant::FAILURE_U64, fail, 0x1002, 0x1003),
+        CommandResult::FailureU64(ErrorCode::Fail, get_u64_from_usizes(0x1002, 0x1003))
+    );
+    assert_eq!(
+        decode(return_variant::SUCCESS, 1001, 1002, 1003),
+        CommandResult::Success
+    );
+    assert_eq!(
+        decode(return_variant::SUCCESS_U32, 1001, 1002, 1003),
+        CommandResult::SuccessU32(1001)
+    );
+    assert_eq!(
+        decode(return_variant::SUCCESS_2_U32, 1001, 1002, 1003),
+        CommandResult::Success2U32(1001, 1002)
+    );
+    assert_eq!(
+        decode(return_variant::SUCCESS_U64, 0x1001, 0x1002, 0x1003),
+        CommandResult::SuccessU64(get_u64_from_usizes(0x1001, 0x1003))
+    );
+    assert_eq!(
+        decode(return_variant::SUCCESS_3_U32, 1001, 1002, 1003),
+        CommandResult::Success3U32(1001, 1002, 1003)
+    );
+    assert_eq!(
+        decode(return_variant::SUCCESS_U32_U64, 1001, 0x1002, 0x1003),
+        CommandResult::SuccessU32U64(1001, get_u64_from_usizes(0x1002, 0x1003))
+    );
+    assert_eq!(
+        decode(7.into(), 1001, 1002, 1003),
+        CommandResult::Unrecognized(7.into())
+    );
+}
+
+#[cfg(target_pointer_width = "32")]
+#[test]
+fn u64_args_32_bit() {
+    assert_eq!(
+        command_u64_args(0x1234_5678_9abc_def0),
+        (0x9abc_def0, 0x1234_5678)
+    );
+    assert_eq!(
+        u64_from_command_args(0x9abc_def0, 0x1234_5678),
+        0x1234_5678_9abc_def0
+    );
+    assert_eq!(command_u64_args(u64::MAX), (usize::MAX, usize::MAX));
+}
+
+#[cfg(target_pointer_width = "64")]
+#[test]
+fn u64_args_64_bit() {
+    assert_eq!(
+        command_u64_args(0x1234_5678_9abc_def0),
+        (0x1234_5678_9abc_def0, 0)
+    );
+    assert_eq!(
+        u64_from_command_args(0x1234_5678_9abc_def0, 0),
+        0x1234_5678_9abc_def0
+    );
+    // The second argument is unused.
+    assert_eq!(u64_from_command_args(1, 2), 1);
+}
+
+#[test]
+fn u64_args_round_trip() {
+    for value in [0, 1, u32::MAX as u64, 1 << 32, u64::MAX] {
+        let (argument0, argument1) = command_u64_args(value);
+        assert_eq!(u64_from_command_args(argument0, argument1), value);
+    }
+}
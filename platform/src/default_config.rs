@@ -6,6 +6,40 @@ impl crate::allow_ro::Config for DefaultConfig {}
 impl crate::allow_rw::Config for DefaultConfig {}
 impl crate::subscribe::Config for DefaultConfig {}
 
+/// A syscall configuration for debugging, which panics if a Subscribe or Allow
+/// call returns a non-null upcall or non-zero buffer. That usually means two
+/// pieces of code are using the same upcall or buffer at once, such as an
+/// accidental double subscribe. To opt in, pass it as the `CONFIG` type
+/// parameter in place of `DefaultConfig`.
+pub struct PanicOnReentrance;
+
+impl crate::allow_ro::Config for PanicOnReentrance {
+    fn returned_nonzero_buffer(driver_num: u32, buffer_num: u32) {
+        panic!(
+            "Read-Only Allow returned a non-zero buffer (driver {:#x}, buffer {})",
+            driver_num, buffer_num
+        );
+    }
+}
+
+impl crate::allow_rw::Config for PanicOnReentrance {
+    fn returned_nonzero_buffer(driver_num: u32, buffer_num: u32) {
+        panic!(
+            "Read-Write Allow returned a non-zero buffer (driver {:#x}, buffer {})",
+            driver_num, buffer_num
+        );
+    }
+}
+
+impl crate::subscribe::Config for PanicOnReentrance {
+    fn returned_nonnull_upcall(driver_num: u32, subscribe_num: u32) {
+        panic!(
+            "Subscribe returned a non-null upcall (driver {:#x}, subscribe {})",
+            driver_num, subscribe_num
+        );
+    }
+}
+
 /// Combo trait for all configs
 pub trait AllConfig:
     crate::allow_ro::Config + crate::allow_rw::Config + crate::subscribe::Config
@@ -241,7 +241,20 @@ pub enum ErrorCode {
 pub struct NotAnErrorCode;
 
 impl ErrorCode {
-    /// Represent this error code as a string, if defined.
+    /// Converts an error code returned by the kernel into an `ErrorCode`.
+    /// Values outside the TRD 104 range, which a correctly-functioning kernel
+    /// never returns, become `ErrorCode::Fail` instead of undefined behavior.
+    ///
+    /// Paths where TRD 104 guarantees a valid error code and code size matters
+    /// (such as the Command and Subscribe return values) transmute instead.
+    pub fn from_kernel(value: u32) -> ErrorCode {
+        value.try_into().unwrap_or(ErrorCode::Fail)
+    }
+
+    /// Represent this error code as its TRD 104 name, if defined. This match is
+    /// exhaustive so that naming one of the reserved error codes fails to
+    /// compile until it is given a string.
+    #[rustfmt::skip]
     fn as_str(self) -> Option<&'static str> {
         match self {
             Self::Fail => Some("FAIL"),
@@ -258,7 +271,209 @@ impl ErrorCode {
             Self::Uninstalled => Some("UNINSTALLED"),
             Self::NoAck => Some("NOACK"),
             Self::BadRVal => Some("BADRVAL"),
-            _ => None,
+            Self::N00014 | Self::N00015 |
+            Self::N00016 | Self::N00017 | Self::N00018 | Self::N00019 | Self::N00020 |
+            Self::N00021 | Self::N00022 | Self::N00023 | Self::N00024 | Self::N00025 |
+            Self::N00026 | Self::N00027 | Self::N00028 | Self::N00029 | Self::N00030 |
+            Self::N00031 | Self::N00032 | Self::N00033 | Self::N00034 | Self::N00035 |
+            Self::N00036 | Self::N00037 | Self::N00038 | Self::N00039 | Self::N00040 |
+            Self::N00041 | Self::N00042 | Self::N00043 | Self::N00044 | Self::N00045 |
+            Self::N00046 | Self::N00047 | Self::N00048 | Self::N00049 | Self::N00050 |
+            Self::N00051 | Self::N00052 | Self::N00053 | Self::N00054 | Self::N00055 |
+            Self::N00056 | Self::N00057 | Self::N00058 | Self::N00059 | Self::N00060 |
+            Self::N00061 | Self::N00062 | Self::N00063 | Self::N00064 | Self::N00065 |
+            Self::N00066 | Self::N00067 | Self::N00068 | Self::N00069 | Self::N00070 |
+            Self::N00071 | Self::N00072 | Self::N00073 | Self::N00074 | Self::N00075 |
+            Self::N00076 | Self::N00077 | Self::N00078 | Self::N00079 | Self::N00080 |
+            Self::N00081 | Self::N00082 | Self::N00083 | Self::N00084 | Self::N00085 |
+            Self::N00086 | Self::N00087 | Self::N00088 | Self::N00089 | Self::N00090 |
+            Self::N00091 | Self::N00092 | Self::N00093 | Self::N00094 | Self::N00095 |
+            Self::N00096 | Self::N00097 | Self::N00098 | Self::N00099 | Self::N00100 |
+            Self::N00101 | Self::N00102 | Self::N00103 | Self::N00104 | Self::N00105 |
+            Self::N00106 | Self::N00107 | Self::N00108 | Self::N00109 | Self::N00110 |
+            Self::N00111 | Self::N00112 | Self::N00113 | Self::N00114 | Self::N00115 |
+            Self::N00116 | Self::N00117 | Self::N00118 | Self::N00119 | Self::N00120 |
+            Self::N00121 | Self::N00122 | Self::N00123 | Self::N00124 | Self::N00125 |
+            Self::N00126 | Self::N00127 | Self::N00128 | Self::N00129 | Self::N00130 |
+            Self::N00131 | Self::N00132 | Self::N00133 | Self::N00134 | Self::N00135 |
+            Self::N00136 | Self::N00137 | Self::N00138 | Self::N00139 | Self::N00140 |
+            Self::N00141 | Self::N00142 | Self::N00143 | Self::N00144 | Self::N00145 |
+            Self::N00146 | Self::N00147 | Self::N00148 | Self::N00149 | Self::N00150 |
+            Self::N00151 | Self::N00152 | Self::N00153 | Self::N00154 | Self::N00155 |
+            Self::N00156 | Self::N00157 | Self::N00158 | Self::N00159 | Self::N00160 |
+            Self::N00161 | Self::N00162 | Self::N00163 | Self::N00164 | Self::N00165 |
+            Self::N00166 | Self::N00167 | Self::N00168 | Self::N00169 | Self::N00170 |
+            Self::N00171 | Self::N00172 | Self::N00173 | Self::N00174 | Self::N00175 |
+            Self::N00176 | Self::N00177 | Self::N00178 | Self::N00179 | Self::N00180 |
+            Self::N00181 | Self::N00182 | Self::N00183 | Self::N00184 | Self::N00185 |
+            Self::N00186 | Self::N00187 | Self::N00188 | Self::N00189 | Self::N00190 |
+            Self::N00191 | Self::N00192 | Self::N00193 | Self::N00194 | Self::N00195 |
+            Self::N00196 | Self::N00197 | Self::N00198 | Self::N00199 | Self::N00200 |
+            Self::N00201 | Self::N00202 | Self::N00203 | Self::N00204 | Self::N00205 |
+            Self::N00206 | Self::N00207 | Self::N00208 | Self::N00209 | Self::N00210 |
+            Self::N00211 | Self::N00212 | Self::N00213 | Self::N00214 | Self::N00215 |
+            Self::N00216 | Self::N00217 | Self::N00218 | Self::N00219 | Self::N00220 |
+            Self::N00221 | Self::N00222 | Self::N00223 | Self::N00224 | Self::N00225 |
+            Self::N00226 | Self::N00227 | Self::N00228 | Self::N00229 | Self::N00230 |
+            Self::N00231 | Self::N00232 | Self::N00233 | Self::N00234 | Self::N00235 |
+            Self::N00236 | Self::N00237 | Self::N00238 | Self::N00239 | Self::N00240 |
+            Self::N00241 | Self::N00242 | Self::N00243 | Self::N00244 | Self::N00245 |
+            Self::N00246 | Self::N00247 | Self::N00248 | Self::N00249 | Self::N00250 |
+            Self::N00251 | Self::N00252 | Self::N00253 | Self::N00254 | Self::N00255 |
+            Self::N00256 | Self::N00257 | Self::N00258 | Self::N00259 | Self::N00260 |
+            Self::N00261 | Self::N00262 | Self::N00263 | Self::N00264 | Self::N00265 |
+            Self::N00266 | Self::N00267 | Self::N00268 | Self::N00269 | Self::N00270 |
+            Self::N00271 | Self::N00272 | Self::N00273 | Self::N00274 | Self::N00275 |
+            Self::N00276 | Self::N00277 | Self::N00278 | Self::N00279 | Self::N00280 |
+            Self::N00281 | Self::N00282 | Self::N00283 | Self::N00284 | Self::N00285 |
+            Self::N00286 | Self::N00287 | Self::N00288 | Self::N00289 | Self::N00290 |
+            Self::N00291 | Self::N00292 | Self::N00293 | Self::N00294 | Self::N00295 |
+            Self::N00296 | Self::N00297 | Self::N00298 | Self::N00299 | Self::N00300 |
+            Self::N00301 | Self::N00302 | Self::N00303 | Self::N00304 | Self::N00305 |
+            Self::N00306 | Self::N00307 | Self::N00308 | Self::N00309 | Self::N00310 |
+            Self::N00311 | Self::N00312 | Self::N00313 | Self::N00314 | Self::N00315 |
+            Self::N00316 | Self::N00317 | Self::N00318 | Self::N00319 | Self::N00320 |
+            Self::N00321 | Self::N00322 | Self::N00323 | Self::N00324 | Self::N00325 |
+            Self::N00326 | Self::N00327 | Self::N00328 | Self::N00329 | Self::N00330 |
+            Self::N00331 | Self::N00332 | Self::N00333 | Self::N00334 | Self::N00335 |
+            Self::N00336 | Self::N00337 | Self::N00338 | Self::N00339 | Self::N00340 |
+            Self::N00341 | Self::N00342 | Self::N00343 | Self::N00344 | Self::N00345 |
+            Self::N00346 | Self::N00347 | Self::N00348 | Self::N00349 | Self::N00350 |
+            Self::N00351 | Self::N00352 | Self::N00353 | Self::N00354 | Self::N00355 |
+            Self::N00356 | Self::N00357 | Self::N00358 | Self::N00359 | Self::N00360 |
+            Self::N00361 | Self::N00362 | Self::N00363 | Self::N00364 | Self::N00365 |
+            Self::N00366 | Self::N00367 | Self::N00368 | Self::N00369 | Self::N00370 |
+            Self::N00371 | Self::N00372 | Self::N00373 | Self::N00374 | Self::N00375 |
+            Self::N00376 | Self::N00377 | Self::N00378 | Self::N00379 | Self::N00380 |
+            Self::N00381 | Self::N00382 | Self::N00383 | Self::N00384 | Self::N00385 |
+            Self::N00386 | Self::N00387 | Self::N00388 | Self::N00389 | Self::N00390 |
+            Self::N00391 | Self::N00392 | Self::N00393 | Self::N00394 | Self::N00395 |
+            Self::N00396 | Self::N00397 | Self::N00398 | Self::N00399 | Self::N00400 |
+            Self::N00401 | Self::N00402 | Self::N00403 | Self::N00404 | Self::N00405 |
+            Self::N00406 | Self::N00407 | Self::N00408 | Self::N00409 | Self::N00410 |
+            Self::N00411 | Self::N00412 | Self::N00413 | Self::N00414 | Self::N00415 |
+            Self::N00416 | Self::N00417 | Self::N00418 | Self::N00419 | Self::N00420 |
+            Self::N00421 | Self::N00422 | Self::N00423 | Self::N00424 | Self::N00425 |
+            Self::N00426 | Self::N00427 | Self::N00428 | Self::N00429 | Self::N00430 |
+            Self::N00431 | Self::N00432 | Self::N00433 | Self::N00434 | Self::N00435 |
+            Self::N00436 | Self::N00437 | Self::N00438 | Self::N00439 | Self::N00440 |
+            Self::N00441 | Self::N00442 | Self::N00443 | Self::N00444 | Self::N00445 |
+            Self::N00446 | Self::N00447 | Self::N00448 | Self::N00449 | Self::N00450 |
+            Self::N00451 | Self::N00452 | Self::N00453 | Self::N00454 | Self::N00455 |
+            Self::N00456 | Self::N00457 | Self::N00458 | Self::N00459 | Self::N00460 |
+            Self::N00461 | Self::N00462 | Self::N00463 | Self::N00464 | Self::N00465 |
+            Self::N00466 | Self::N00467 | Self::N00468 | Self::N00469 | Self::N00470 |
+            Self::N00471 | Self::N00472 | Self::N00473 | Self::N00474 | Self::N00475 |
+            Self::N00476 | Self::N00477 | Self::N00478 | Self::N00479 | Self::N00480 |
+            Self::N00481 | Self::N00482 | Self::N00483 | Self::N00484 | Self::N00485 |
+            Self::N00486 | Self::N00487 | Self::N00488 | Self::N00489 | Self::N00490 |
+            Self::N00491 | Self::N00492 | Self::N00493 | Self::N00494 | Self::N00495 |
+            Self::N00496 | Self::N00497 | Self::N00498 | Self::N00499 | Self::N00500 |
+            Self::N00501 | Self::N00502 | Self::N00503 | Self::N00504 | Self::N00505 |
+            Self::N00506 | Self::N00507 | Self::N00508 | Self::N00509 | Self::N00510 |
+            Self::N00511 | Self::N00512 | Self::N00513 | Self::N00514 | Self::N00515 |
+            Self::N00516 | Self::N00517 | Self::N00518 | Self::N00519 | Self::N00520 |
+            Self::N00521 | Self::N00522 | Self::N00523 | Self::N00524 | Self::N00525 |
+            Self::N00526 | Self::N00527 | Self::N00528 | Self::N00529 | Self::N00530 |
+            Self::N00531 | Self::N00532 | Self::N00533 | Self::N00534 | Self::N00535 |
+            Self::N00536 | Self::N00537 | Self::N00538 | Self::N00539 | Self::N00540 |
+            Self::N00541 | Self::N00542 | Self::N00543 | Self::N00544 | Self::N00545 |
+            Self::N00546 | Self::N00547 | Self::N00548 | Self::N00549 | Self::N00550 |
+            Self::N00551 | Self::N00552 | Self::N00553 | Self::N00554 | Self::N00555 |
+            Self::N00556 | Self::N00557 | Self::N00558 | Self::N00559 | Self::N00560 |
+            Self::N00561 | Self::N00562 | Self::N00563 | Self::N00564 | Self::N00565 |
+            Self::N00566 | Self::N00567 | Self::N00568 | Self::N00569 | Self::N00570 |
+            Self::N00571 | Self::N00572 | Self::N00573 | Self::N00574 | Self::N00575 |
+            Self::N00576 | Self::N00577 | Self::N00578 | Self::N00579 | Self::N00580 |
+            Self::N00581 | Self::N00582 | Self::N00583 | Self::N00584 | Self::N00585 |
+            Self::N00586 | Self::N00587 | Self::N00588 | Self::N00589 | Self::N00590 |
+            Self::N00591 | Self::N00592 | Self::N00593 | Self::N00594 | Self::N00595 |
+            Self::N00596 | Self::N00597 | Self::N00598 | Self::N00599 | Self::N00600 |
+            Self::N00601 | Self::N00602 | Self::N00603 | Self::N00604 | Self::N00605 |
+            Self::N00606 | Self::N00607 | Self::N00608 | Self::N00609 | Self::N00610 |
+            Self::N00611 | Self::N00612 | Self::N00613 | Self::N00614 | Self::N00615 |
+            Self::N00616 | Self::N00617 | Self::N00618 | Self::N00619 | Self::N00620 |
+            Self::N00621 | Self::N00622 | Self::N00623 | Self::N00624 | Self::N00625 |
+            Self::N00626 | Self::N00627 | Self::N00628 | Self::N00629 | Self::N00630 |
+            Self::N00631 | Self::N00632 | Self::N00633 | Self::N00634 | Self::N00635 |
+            Self::N00636 | Self::N00637 | Self::N00638 | Self::N00639 | Self::N00640 |
+            Self::N00641 | Self::N00642 | Self::N00643 | Self::N00644 | Self::N00645 |
+            Self::N00646 | Self::N00647 | Self::N00648 | Self::N00649 | Self::N00650 |
+            Self::N00651 | Self::N00652 | Self::N00653 | Self::N00654 | Self::N00655 |
+            Self::N00656 | Self::N00657 | Self::N00658 | Self::N00659 | Self::N00660 |
+            Self::N00661 | Self::N00662 | Self::N00663 | Self::N00664 | Self::N00665 |
+            Self::N00666 | Self::N00667 | Self::N00668 | Self::N00669 | Self::N00670 |
+            Self::N00671 | Self::N00672 | Self::N00673 | Self::N00674 | Self::N00675 |
+            Self::N00676 | Self::N00677 | Self::N00678 | Self::N00679 | Self::N00680 |
+            Self::N00681 | Self::N00682 | Self::N00683 | Self::N00684 | Self::N00685 |
+            Self::N00686 | Self::N00687 | Self::N00688 | Self::N00689 | Self::N00690 |
+            Self::N00691 | Self::N00692 | Self::N00693 | Self::N00694 | Self::N00695 |
+            Self::N00696 | Self::N00697 | Self::N00698 | Self::N00699 | Self::N00700 |
+            Self::N00701 | Self::N00702 | Self::N00703 | Self::N00704 | Self::N00705 |
+            Self::N00706 | Self::N00707 | Self::N00708 | Self::N00709 | Self::N00710 |
+            Self::N00711 | Self::N00712 | Self::N00713 | Self::N00714 | Self::N00715 |
+            Self::N00716 | Self::N00717 | Self::N00718 | Self::N00719 | Self::N00720 |
+            Self::N00721 | Self::N00722 | Self::N00723 | Self::N00724 | Self::N00725 |
+            Self::N00726 | Self::N00727 | Self::N00728 | Self::N00729 | Self::N00730 |
+            Self::N00731 | Self::N00732 | Self::N00733 | Self::N00734 | Self::N00735 |
+            Self::N00736 | Self::N00737 | Self::N00738 | Self::N00739 | Self::N00740 |
+            Self::N00741 | Self::N00742 | Self::N00743 | Self::N00744 | Self::N00745 |
+            Self::N00746 | Self::N00747 | Self::N00748 | Self::N00749 | Self::N00750 |
+            Self::N00751 | Self::N00752 | Self::N00753 | Self::N00754 | Self::N00755 |
+            Self::N00756 | Self::N00757 | Self::N00758 | Self::N00759 | Self::N00760 |
+            Self::N00761 | Self::N00762 | Self::N00763 | Self::N00764 | Self::N00765 |
+            Self::N00766 | Self::N00767 | Self::N00768 | Self::N00769 | Self::N00770 |
+            Self::N00771 | Self::N00772 | Self::N00773 | Self::N00774 | Self::N00775 |
+            Self::N00776 | Self::N00777 | Self::N00778 | Self::N00779 | Self::N00780 |
+            Self::N00781 | Self::N00782 | Self::N00783 | Self::N00784 | Self::N00785 |
+            Self::N00786 | Self::N00787 | Self::N00788 | Self::N00789 | Self::N00790 |
+            Self::N00791 | Self::N00792 | Self::N00793 | Self::N00794 | Self::N00795 |
+            Self::N00796 | Self::N00797 | Self::N00798 | Self::N00799 | Self::N00800 |
+            Self::N00801 | Self::N00802 | Self::N00803 | Self::N00804 | Self::N00805 |
+            Self::N00806 | Self::N00807 | Self::N00808 | Self::N00809 | Self::N00810 |
+            Self::N00811 | Self::N00812 | Self::N00813 | Self::N00814 | Self::N00815 |
+            Self::N00816 | Self::N00817 | Self::N00818 | Self::N00819 | Self::N00820 |
+            Self::N00821 | Self::N00822 | Self::N00823 | Self::N00824 | Self::N00825 |
+            Self::N00826 | Self::N00827 | Self::N00828 | Self::N00829 | Self::N00830 |
+            Self::N00831 | Self::N00832 | Self::N00833 | Self::N00834 | Self::N00835 |
+            Self::N00836 | Self::N00837 | Self::N00838 | Self::N00839 | Self::N00840 |
+            Self::N00841 | Self::N00842 | Self::N00843 | Self::N00844 | Self::N00845 |
+            Self::N00846 | Self::N00847 | Self::N00848 | Self::N00849 | Self::N00850 |
+            Self::N00851 | Self::N00852 | Self::N00853 | Self::N00854 | Self::N00855 |
+            Self::N00856 | Self::N00857 | Self::N00858 | Self::N00859 | Self::N00860 |
+            Self::N00861 | Self::N00862 | Self::N00863 | Self::N00864 | Self::N00865 |
+            Self::N00866 | Self::N00867 | Self::N00868 | Self::N00869 | Self::N00870 |
+            Self::N00871 | Self::N00872 | Self::N00873 | Self::N00874 | Self::N00875 |
+            Self::N00876 | Self::N00877 | Self::N00878 | Self::N00879 | Self::N00880 |
+            Self::N00881 | Self::N00882 | Self::N00883 | Self::N00884 | Self::N00885 |
+            Self::N00886 | Self::N00887 | Self::N00888 | Self::N00889 | Self::N00890 |
+            Self::N00891 | Self::N00892 | Self::N00893 | Self::N00894 | Self::N00895 |
+            Self::N00896 | Self::N00897 | Self::N00898 | Self::N00899 | Self::N00900 |
+            Self::N00901 | Self::N00902 | Self::N00903 | Self::N00904 | Self::N00905 |
+            Self::N00906 | Self::N00907 | Self::N00908 | Self::N00909 | Self::N00910 |
+            Self::N00911 | Self::N00912 | Self::N00913 | Self::N00914 | Self::N00915 |
+            Self::N00916 | Self::N00917 | Self::N00918 | Self::N00919 | Self::N00920 |
+            Self::N00921 | Self::N00922 | Self::N00923 | Self::N00924 | Self::N00925 |
+            Self::N00926 | Self::N00927 | Self::N00928 | Self::N00929 | Self::N00930 |
+            Self::N00931 | Self::N00932 | Self::N00933 | Self::N00934 | Self::N00935 |
+            Self::N00936 | Self::N00937 | Self::N00938 | Self::N00939 | Self::N00940 |
+            Self::N00941 | Self::N00942 | Self::N00943 | Self::N00944 | Self::N00945 |
+            Self::N00946 | Self::N00947 | Self::N00948 | Self::N00949 | Self::N00950 |
+            Self::N00951 | Self::N00952 | Self::N00953 | Self::N00954 | Self::N00955 |
+            Self::N00956 | Self::N00957 | Self::N00958 | Self::N00959 | Self::N00960 |
+            Self::N00961 | Self::N00962 | Self::N00963 | Self::N00964 | Self::N00965 |
+            Self::N00966 | Self::N00967 | Self::N00968 | Self::N00969 | Self::N00970 |
+            Self::N00971 | Self::N00972 | Self::N00973 | Self::N00974 | Self::N00975 |
+            Self::N00976 | Self::N00977 | Self::N00978 | Self::N00979 | Self::N00980 |
+            Self::N00981 | Self::N00982 | Self::N00983 | Self::N00984 | Self::N00985 |
+            Self::N00986 | Self::N00987 | Self::N00988 | Self::N00989 | Self::N00990 |
+            Self::N00991 | Self::N00992 | Self::N00993 | Self::N00994 | Self::N00995 |
+            Self::N00996 | Self::N00997 | Self::N00998 | Self::N00999 | Self::N01000 |
+            Self::N01001 | Self::N01002 | Self::N01003 | Self::N01004 | Self::N01005 |
+            Self::N01006 | Self::N01007 | Self::N01008 | Self::N01009 | Self::N01010 |
+            Self::N01011 | Self::N01012 | Self::N01013 | Self::N01014 | Self::N01015 |
+            Self::N01016 | Self::N01017 | Self::N01018 | Self::N01019 | Self::N01020 |
+            Self::N01021 | Self::N01022 | Self::N01023 => None,
         }
     }
 }
@@ -272,6 +487,24 @@ impl fmt::Debug for ErrorCode {
     }
 }
 
+/// Renders the TRD 104 name of the error code (e.g. `BUSY` or `NOACK`), or
+/// `code <n>` for codes reserved for future use.
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for ErrorCode {
+    fn format(&self, f: defmt::Formatter) {
+        match self.as_str() {
+            Some(s) => defmt::write!(f, "{=str}", s),
+            None => defmt::write!(f, "code {=u32}", *self as u32),
+        }
+    }
+}
+
 impl TryFrom<u32> for ErrorCode {
     type Error = NotAnErrorCode;
 
@@ -20,3 +20,25 @@ fn error_code_try_into() {
     }
     assert_eq!(TryInto::<ErrorCode>::try_into(1025u32), Err(NotAnErrorCode));
 }
+
+#[test]
+fn error_code_from_kernel() {
+    assert_eq!(ErrorCode::from_kernel(2), ErrorCode::Busy);
+    assert_eq!(ErrorCode::from_kernel(14), ErrorCode::N00014);
+    assert_eq!(ErrorCode::from_kernel(1024), ErrorCode::BadRVal);
+    for value in 1..=1024u32 {
+        assert_eq!(ErrorCode::from_kernel(value) as u32, value);
+    }
+    // Values that are not error codes become Fail.
+    assert_eq!(ErrorCode::from_kernel(0), ErrorCode::Fail);
+    assert_eq!(ErrorCode::from_kernel(1025), ErrorCode::Fail);
+    assert_eq!(ErrorCode::from_kernel(u32::MAX), ErrorCode::Fail);
+}
+
+#[test]
+fn error_code_display() {
+    assert_eq!(format!("{}", ErrorCode::Busy), "BUSY");
+    assert_eq!(format!("{}", ErrorCode::NoAck), "NOACK");
+    assert_eq!(format!("{}", ErrorCode::BadRVal), "BADRVAL");
+    assert_eq!(format!("{}", ErrorCode::N00014), "code 14");
+}
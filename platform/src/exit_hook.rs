@@ -0,0 +1,39 @@
+//! Hook for running code when a process returns from `main`.
+//!
+//! Exit is abrupt: output that is still buffered when `main` returns is lost.
+//! A process binary that keeps buffered output alive past `main` (for example,
+//! a console writer in a `static`) can install a hook with `set_hook` to flush
+//! it. The `Termination` implementations run the hook immediately before they
+//! call Exit:
+//!
+//! ```
+//! fn flush_console() {
+//!     // Flush the process' buffered console writer here.
+//! }
+//!
+//! libtock_platform::exit_hook::set_hook(flush_console);
+//! ```
+
+use crate::hook_cell::HookCell;
+
+/// The signature of an exit hook.
+pub type Hook = fn();
+
+static HOOK: HookCell<Hook> = HookCell::new();
+
+/// Installs `hook` as the exit hook, replacing any previously-installed hook.
+pub fn set_hook(hook: Hook) {
+    HOOK.set(hook);
+}
+
+/// Returns the installed exit hook, if any.
+pub fn hook() -> Option<Hook> {
+    HOOK.get()
+}
+
+/// Calls the installed exit hook, if any.
+pub fn run_hook() {
+    if let Some(hook) = hook() {
+        hook();
+    }
+}
@@ -0,0 +1,59 @@
+//! Storage for the hooks that `oom`, `exit_hook`, and `yield_watchdog` let a
+//! process binary install.
+
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// A function pointer type that can be stored in a `HookCell`.
+pub(crate) trait HookFn: Copy {
+    fn into_ptr(self) -> *mut ();
+}
+
+// HookFn must only be implemented for function pointer types, as HookCell::get
+// relies on converting the pointer back into the function pointer.
+macro_rules! impl_hook_fn {
+    ($($hook:ty),*) => {$(
+        impl HookFn for $hook {
+            fn into_ptr(self) -> *mut () {
+                self as *mut ()
+            }
+        }
+    )*};
+}
+
+impl_hook_fn!(fn(), fn(core::alloc::Layout) -> !);
+
+/// A hook that may be installed at runtime, suitable for use in a `static`.
+pub(crate) struct HookCell<F: HookFn> {
+    // Stored as a pointer (rather than an integer) so that it keeps its
+    // capability on CHERI. Null means no hook is installed.
+    hook: AtomicPtr<()>,
+    _hook: PhantomData<F>,
+}
+
+impl<F: HookFn> HookCell<F> {
+    pub const fn new() -> Self {
+        Self {
+            hook: AtomicPtr::new(core::ptr::null_mut()),
+            _hook: PhantomData,
+        }
+    }
+
+    /// Installs `hook`, replacing any previously-installed hook.
+    pub fn set(&self, hook: F) {
+        self.hook.store(hook.into_ptr(), Ordering::Relaxed);
+    }
+
+    /// Returns the installed hook, if any.
+    pub fn get(&self) -> Option<F> {
+        let hook = self.hook.load(Ordering::Relaxed);
+        if hook.is_null() {
+            return None;
+        }
+        // Safety: The only non-null values stored in self.hook come from set,
+        // which converts an F into a pointer with HookFn::into_ptr. HookFn is
+        // only implemented for function pointer types, which have the same
+        // representation as the pointer, so this gives back that F.
+        Some(unsafe { core::mem::transmute_copy::<*mut (), F>(&hook) })
+    }
+}
@@ -1,41 +1,71 @@
 #![cfg_attr(not(test), no_std)]
 #![warn(unsafe_op_in_unsafe_fn)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod allow_buffer;
 pub mod allow_ro;
 pub mod allow_rw;
+mod block_on;
+pub mod cheri;
 pub mod command_return;
 mod constants;
 mod default_config;
 mod error_code;
+pub mod exit_hook;
 pub mod exit_on_drop;
+mod hook_cell;
+pub mod memop;
+pub mod oom;
 mod raw_syscalls;
 mod register;
 pub mod return_variant;
 pub mod share;
 pub mod subscribe;
+#[cfg(feature = "alloc")]
+pub mod subscribe_boxed;
 mod syscalls;
 mod syscalls_impl;
 mod termination;
+mod write_buffer;
 mod yield_types;
+#[cfg(feature = "yield_watchdog")]
+pub mod yield_watchdog;
 
+pub use allow_buffer::AllowBuffer;
 pub use allow_ro::AllowRo;
 pub use allow_rw::AllowRw;
-pub use command_return::CommandReturn;
+pub use block_on::block_on;
+pub use command_return::{command_u64_args, u64_from_command_args, CommandResult, CommandReturn};
 pub use constants::{exit_id, syscall_class, yield_id};
 pub use default_config::AllConfig;
 pub use default_config::DefaultConfig;
+pub use default_config::PanicOnReentrance;
 pub use error_code::ErrorCode;
-pub use kernel::cheri;
 pub use raw_syscalls::RawSyscalls;
 pub use register::Register;
 pub use return_variant::ReturnVariant;
-pub use subscribe::{Subscribe, Upcall};
-pub use syscalls::Syscalls;
+pub use subscribe::{Subscribe, SubscriptionGuard, Upcall};
+pub use syscalls::{Syscalls, DRAIN_UPCALLS_LIMIT};
 pub use termination::Termination;
+pub use write_buffer::WriteBuffer;
 pub use yield_types::YieldNoWaitReturn;
 
+#[cfg(all(test, target_feature = "xcheri"))]
+mod cheri_tests;
+
 #[cfg(test)]
 mod command_return_tests;
 
 #[cfg(test)]
 mod error_code_tests;
+
+#[cfg(test)]
+mod register_tests;
+
+#[cfg(test)]
+mod syscalls_impl_tests;
+
+#[cfg(test)]
+mod write_buffer_tests;
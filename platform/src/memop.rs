@@ -0,0 +1,142 @@
+//! Typed wrappers around the Memop system call. The operation numbers are
+//! specified in [TRD 104][memop].
+//!
+//! [memop]: https://github.com/tock/tock/blob/master/doc/reference/trd104-syscalls.md#46-memop-class-id-5
+
+use crate::{ErrorCode, Syscalls};
+use kernel::cheri::cptr;
+
+/// Memop operation numbers.
+pub mod op {
+    pub const BRK: u32 = 0;
+    pub const SBRK: u32 = 1;
+    pub const MEMORY_START: u32 = 2;
+    pub const MEMORY_END: u32 = 3;
+    pub const FLASH_START: u32 = 4;
+    pub const FLASH_END: u32 = 5;
+    pub const GRANT_START: u32 = 6;
+    pub const WRITEABLE_FLASH_REGIONS: u32 = 7;
+    pub const WRITEABLE_FLASH_REGION_START: u32 = 8;
+    pub const WRITEABLE_FLASH_REGION_END: u32 = 9;
+    pub const SPECIFY_STACK_TOP: u32 = 10;
+    pub const SPECIFY_HEAP_START: u32 = 11;
+}
+
+/// Sets the process break (the end of the memory the process may access) to
+/// `addr`.
+pub fn memop_brk<S: Syscalls>(addr: usize) -> Result<cptr, ErrorCode> {
+    S::memop(op::BRK, addr)
+}
+
+/// Returns the start of the process's RAM.
+pub fn memory_start<S: Syscalls>() -> Result<cptr, ErrorCode> {
+    S::memop(op::MEMORY_START, 0)
+}
+
+/// Returns the end of the process's RAM.
+pub fn memory_end<S: Syscalls>() -> Result<cptr, ErrorCode> {
+    S::memop(op::MEMORY_END, 0)
+}
+
+/// Returns the start of the process's flash region.
+pub fn flash_start<S: Syscalls>() -> Result<cptr, ErrorCode> {
+    S::memop(op::FLASH_START, 0)
+}
+
+/// Returns the end of the process's flash region.
+pub fn flash_end<S: Syscalls>() -> Result<cptr, ErrorCode> {
+    S::memop(op::FLASH_END, 0)
+}
+
+/// Returns the lowest address of the kernel-owned grant region at the top of
+/// the process's RAM.
+pub fn grant_start<S: Syscalls>() -> Result<cptr, ErrorCode> {
+    S::memop(op::GRANT_START, 0)
+}
+
+/// Tells the kernel where the top of the process's stack is. This is only
+/// used for debugging output.
+pub fn specify_stack_top<S: Syscalls>(addr: usize) -> Result<cptr, ErrorCode> {
+    S::memop(op::SPECIFY_STACK_TOP, addr)
+}
+
+/// Tells the kernel where the process's heap starts. This is only used for
+/// debugging output.
+pub fn specify_heap_start<S: Syscalls>(addr: usize) -> Result<cptr, ErrorCode> {
+    S::memop(op::SPECIFY_HEAP_START, addr)
+}
+
+/// The process's memory layout, as reported by the kernel. Returned by
+/// `AppLayout::current`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct AppLayout {
+    /// The start of the process's RAM.
+    pub memory_start: usize,
+    /// The end of the process's RAM, including the kernel-owned grant region.
+    pub memory_end: usize,
+    /// The start of the process's flash region.
+    pub flash_start: usize,
+    /// The end of the process's flash region.
+    pub flash_end: usize,
+    /// The current process break (the end of the memory the process may
+    /// access), which is where the heap ends.
+    pub heap_break: usize,
+    /// The bounds of the capability the kernel returns for the start of RAM.
+    #[cfg(target_feature = "xcheri")]
+    pub memory_bounds: CheriBounds,
+    /// The bounds of the capability the kernel returns for the start of flash.
+    #[cfg(target_feature = "xcheri")]
+    pub flash_bounds: CheriBounds,
+}
+
+/// The range of addresses a capability grants access to.
+#[cfg(target_feature = "xcheri")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CheriBounds {
+    pub base: usize,
+    pub length: usize,
+}
+
+#[cfg(target_feature = "xcheri")]
+impl From<cptr> for CheriBounds {
+    fn from(capability: cptr) -> CheriBounds {
+        let register = crate::Register(capability);
+        CheriBounds {
+            base: register.cheri_base(),
+            length: register.cheri_length(),
+        }
+    }
+}
+
+impl AppLayout {
+    /// Queries the kernel for the process's memory layout.
+    pub fn current<S: Syscalls>() -> Result<AppLayout, ErrorCode> {
+        let memory_start = memory_start::<S>()?;
+        let flash_start = flash_start::<S>()?;
+        Ok(AppLayout {
+            memory_start: memory_start.into(),
+            memory_end: memory_end::<S>()?.into(),
+            flash_start: flash_start.into(),
+            flash_end: flash_end::<S>()?.into(),
+            // Sbrk by 0 returns the break without moving it. This calls Memop
+            // directly rather than S::sbrk, which also updates DDC on CHERI.
+            heap_break: S::memop(op::SBRK, 0)?.into(),
+            #[cfg(target_feature = "xcheri")]
+            memory_bounds: memory_start.into(),
+            #[cfg(target_feature = "xcheri")]
+            flash_bounds: flash_start.into(),
+        })
+    }
+
+    /// Returns the number of bytes of RAM the process has, or 0 if the kernel
+    /// reported its end before its start.
+    pub fn memory_len(&self) -> usize {
+        self.memory_end.saturating_sub(self.memory_start)
+    }
+
+    /// Returns the number of bytes of flash the process has, or 0 if the
+    /// kernel reported its end before its start.
+    pub fn flash_len(&self) -> usize {
+        self.flash_end.saturating_sub(self.flash_start)
+    }
+}
@@ -0,0 +1,48 @@
+//! Hook for customizing what happens when a heap allocation fails.
+//!
+//! By default, `libtock_runtime`'s allocation error handler reports
+//! `AlertCode::HeapOOM` and terminates the process. A process binary can
+//! replace that behavior by installing a hook with `set_hook`, for example to
+//! restart instead of terminating:
+//!
+//! ```
+//! use core::alloc::Layout;
+//! use libtock_platform::{ErrorCode, Syscalls};
+//!
+//! fn restart_on_oom<S: Syscalls>(_layout: Layout) -> ! {
+//!     S::exit_restart(ErrorCode::NoMem as u32)
+//! }
+//!
+//! # fn install<S: Syscalls>() {
+//! libtock_platform::oom::set_hook(restart_on_oom::<S>);
+//! # }
+//! ```
+
+use crate::hook_cell::HookCell;
+use core::alloc::Layout;
+
+/// The signature of an OOM hook. The hook receives the layout of the
+/// allocation that failed, and must diverge -- typically by calling one of the
+/// Exit system calls. Returning from an allocation error handler is not
+/// possible, which is enforced by the `!` return type.
+pub type Hook = fn(Layout) -> !;
+
+static HOOK: HookCell<Hook> = HookCell::new();
+
+/// Installs `hook` as the OOM hook, replacing any previously-installed hook.
+pub fn set_hook(hook: Hook) {
+    HOOK.set(hook);
+}
+
+/// Returns the installed OOM hook, if any.
+pub fn hook() -> Option<Hook> {
+    HOOK.get()
+}
+
+/// Calls the installed OOM hook, if any. Returns only if no hook is installed,
+/// in which case the caller should perform its default OOM handling.
+pub fn run_hook(layout: Layout) {
+    if let Some(hook) = hook() {
+        hook(layout);
+    }
+}
@@ -70,6 +70,43 @@ impl Register {
         as_usize as u32
     }
 
+    /// Converts a pointer to `len` bytes into a register, as passed to Allow.
+    /// Unlike `From<*const T>`, on CHERI the resulting capability is bounded
+    /// to the `len` bytes starting at `ptr`, so the kernel cannot use it to
+    /// reach memory outside the buffer. Bounds that cannot be represented
+    /// exactly (which only happens for large, unaligned buffers) are rounded
+    /// outwards to the nearest representable bounds.
+    pub fn from_slice(ptr: *const u8, len: usize) -> Register {
+        #[cfg(not(target_feature = "xcheri"))]
+        {
+            let _ = len;
+            ptr.into()
+        }
+        #[cfg(target_feature = "xcheri")]
+        {
+            let mut register: Register = ptr.into();
+            // A zero-length slice's pointer may dangle, and there is nothing
+            // to bound anyway.
+            if len != 0 {
+                // Safety: this only narrows the capability stored in
+                // `register`, which cannot grant access to anything it did not
+                // already.
+                unsafe {
+                    core::arch::asm!(
+                        "lc    ca2, 0(a0)",
+                        "csetbounds ca2, ca2, a1",
+                        "sc    ca2, 0(a0)",
+                        in("a0") &mut register.0 as *mut cptr,
+                        in("a1") len,
+                        out("a2") _,
+                        options(preserves_flags, nostack),
+                    );
+                }
+            }
+            register
+        }
+    }
+
     /// Similar to From<*const ()> but on CHERI will derive from PCC
     pub fn from_function(fnptr: *const ()) -> Register {
         let mut v: cptr = Default::default();
@@ -78,6 +115,84 @@ impl Register {
     }
 }
 
+// -----------------------------------------------------------------------------
+// CHERI capability introspection
+// -----------------------------------------------------------------------------
+
+// These read the metadata of the capability held in a register, which helps
+// explain why the kernel rejected it (for example, an Allow that fails with
+// ErrorCode::Invalid because the buffer lies outside the capability's bounds).
+// They only exist on CHERI, as other targets have no capability to inspect.
+#[cfg(target_feature = "xcheri")]
+impl Register {
+    /// Returns the lowest address the capability grants access to.
+    pub fn cheri_base(self) -> usize {
+        let base: usize;
+        // Safety: this only reads the capability's metadata.
+        unsafe {
+            core::arch::asm!(
+                "lc    ca1, 0(a0)",
+                "cgetbase a0, ca1",
+                inlateout("a0") &self.0 as *const cptr => base,
+                out("a1") _,
+                options(preserves_flags, nostack, readonly),
+            );
+        }
+        base
+    }
+
+    /// Returns the number of bytes, starting at `cheri_base`, that the
+    /// capability grants access to.
+    pub fn cheri_length(self) -> usize {
+        let length: usize;
+        // Safety: this only reads the capability's metadata.
+        unsafe {
+            core::arch::asm!(
+                "lc    ca1, 0(a0)",
+                "cgetlen a0, ca1",
+                inlateout("a0") &self.0 as *const cptr => length,
+                out("a1") _,
+                options(preserves_flags, nostack, readonly),
+            );
+        }
+        length
+    }
+
+    /// Returns true if the capability is valid. An untagged capability cannot
+    /// be used to access memory at all.
+    pub fn cheri_tag(self) -> bool {
+        let tag: usize;
+        // Safety: this only reads the capability's metadata.
+        unsafe {
+            core::arch::asm!(
+                "lc    ca1, 0(a0)",
+                "cgettag a0, ca1",
+                inlateout("a0") &self.0 as *const cptr => tag,
+                out("a1") _,
+                options(preserves_flags, nostack, readonly),
+            );
+        }
+        tag != 0
+    }
+
+    /// Returns the capability's permission bits, as defined by the CHERI ISA
+    /// (for example, bit 2 permits loads and bit 3 permits stores).
+    pub fn cheri_permissions(self) -> u32 {
+        let permissions: usize;
+        // Safety: this only reads the capability's metadata.
+        unsafe {
+            core::arch::asm!(
+                "lc    ca1, 0(a0)",
+                "cgetperm a0, ca1",
+                inlateout("a0") &self.0 as *const cptr => permissions,
+                out("a1") _,
+                options(preserves_flags, nostack, readonly),
+            );
+        }
+        permissions as u32
+    }
+}
+
 impl From<Register> for usize {
     fn from(register: Register) -> usize {
         register.0.into()
@@ -0,0 +1,52 @@
+use crate::Register;
+
+#[test]
+fn from_slice() {
+    let buffer = [0u8; 16];
+    let register = Register::from_slice(buffer.as_ptr(), buffer.len());
+    assert_eq!(usize::from(register), buffer.as_ptr() as usize);
+
+    let empty: &[u8] = &[];
+    let register = Register::from_slice(empty.as_ptr(), 0);
+    assert_eq!(usize::from(register), empty.as_ptr() as usize);
+}
+
+#[cfg(target_feature = "xcheri")]
+#[test]
+fn from_slice_bounds() {
+    let buffer = [0u8; 16];
+    let register = Register::from_slice(buffer.as_ptr(), buffer.len());
+    assert_eq!(register.cheri_base(), buffer.as_ptr() as usize);
+    assert_eq!(register.cheri_length(), 16);
+
+    let register = Register::from_slice(buffer[4..].as_ptr(), 8);
+    assert_eq!(register.cheri_base(), buffer.as_ptr() as usize + 4);
+    assert_eq!(register.cheri_length(), 8);
+}
+
+// Plain pointer conversions are left unbounded, as callers may not know the
+// size of what they point to.
+#[cfg(target_feature = "xcheri")]
+#[test]
+fn from_pointer_unbounded() {
+    let buffer = [0u8; 16];
+    let register: Register = buffer.as_ptr().into();
+    assert!(register.cheri_length() > buffer.len());
+}
+
+#[cfg(target_feature = "xcheri")]
+#[test]
+fn introspection() {
+    let mut buffer = [0u8; 32];
+    let register = Register::from_slice(buffer.as_mut_ptr(), buffer.len());
+    assert!(register.cheri_tag());
+    assert_eq!(register.cheri_base(), buffer.as_ptr() as usize);
+    assert_eq!(register.cheri_length(), 32);
+    // Bounding a capability does not remove its load and store permissions.
+    const LOAD: u32 = 1 << 2;
+    const STORE: u32 = 1 << 3;
+    assert_eq!(register.cheri_permissions() & (LOAD | STORE), LOAD | STORE);
+
+    // An integer converted to a register carries no capability.
+    assert!(!Register::from(0x1000usize).cheri_tag());
+}
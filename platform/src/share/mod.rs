@@ -6,19 +6,46 @@ mod tuple_impls;
 
 pub use handle::{Handle, SplittableHandle};
 
+use core::mem::ManuallyDrop;
+
 /// Creates a scope in which objects may safely be shared with the kernel.
+///
+/// When the scope ends, the shares in `L` are cleaned up in the reverse of the
+/// order they are listed in, so a tuple such as `(AllowRo, AllowRw, Subscribe)`
+/// unsubscribes before it revokes its buffers.
 pub fn scope<L: List, Output, F: FnOnce(Handle<L>) -> Output>(fcn: F) -> Output {
-    let list = Default::default();
+    let list = ScopeList(ManuallyDrop::new(Default::default()));
     // Safety: We do not move the L out of the `list` variable. The `list`
     // variable will be dropped at the end of the scope, immediately before the
     // L becomes invalid.
-    fcn(unsafe { Handle::new(&list) })
+    fcn(unsafe { Handle::new(&list.0) })
 }
 
 /// A list of objects that may be shared with the kernel. `List` is implemented
 /// for system call types such as `Subscribe`, as well as (potentially-nested)
 /// tuples of such types.
-pub trait List: Default {}
+pub trait List: Default {
+    /// Drops `list` in place. Tuples override this to drop their elements in
+    /// reverse order; everything else is dropped normally.
+    ///
+    /// # Safety
+    /// `list` must not be used or dropped again after this call.
+    unsafe fn drop_list(list: &mut Self) {
+        // Safety: The caller guarantees list is not used again.
+        unsafe { core::ptr::drop_in_place(list) }
+    }
+}
+
+// Owns the List created by `scope`, and drops it using `List::drop_list`.
+struct ScopeList<L: List>(ManuallyDrop<L>);
+
+impl<L: List> Drop for ScopeList<L> {
+    fn drop(&mut self) {
+        // Safety: The ManuallyDrop prevents the L from being dropped again, and
+        // ScopeList does not use it after this.
+        unsafe { L::drop_list(&mut self.0) }
+    }
+}
 
 #[cfg(test)]
 mod tests;
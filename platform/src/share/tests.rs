@@ -100,3 +100,39 @@ fn tuple_impls() {
         },
     );
 }
+
+std::thread_local! {static DROP_ORDER: core::cell::RefCell<Vec<u8>> = Default::default()}
+
+// DropRecorder<ID> appends ID to DROP_ORDER when it is dropped.
+#[derive(Default)]
+struct DropRecorder<const ID: u8>;
+
+impl<const ID: u8> Drop for DropRecorder<ID> {
+    fn drop(&mut self) {
+        DROP_ORDER.with(|order| order.borrow_mut().push(ID));
+    }
+}
+
+impl<const ID: u8> List for DropRecorder<ID> {}
+
+#[test]
+fn drop_order() {
+    DROP_ORDER.with(|order| order.borrow_mut().clear());
+
+    #[allow(clippy::type_complexity)]
+    scope(
+        |list: Handle<(
+            DropRecorder<1>,
+            (DropRecorder<2>, DropRecorder<3>),
+            DropRecorder<4>,
+            DropRecorder<5>,
+        )>| {
+            let (_one, pair, _four, _five) = list.split();
+            let (_two, _three) = pair.split();
+            assert_eq!(DROP_ORDER.with(|order| order.borrow().len()), 0);
+        },
+    );
+
+    // Elements are dropped last-to-first, including within nested tuples.
+    assert_eq!(DROP_ORDER.with(|order| order.take()), [5, 4, 3, 2, 1]);
+}
@@ -18,7 +18,17 @@ impl<'handle> SplittableHandle<'handle> for () {
 // generate impls for tuples of the given length.
 macro_rules! tuple_impls {
     ($($name:ident),*) => {
-        impl<$($name: List),*> List for ($($name),*,) {}
+        impl<$($name: List),*> List for ($($name),*,) {
+            #[allow(non_snake_case, unused_variables)]
+            unsafe fn drop_list(list: &mut Self) {
+                let ($($name),*,) = list;
+                // Local variables are dropped in the reverse of the order they
+                // are declared in, which is the order we want to drop the
+                // elements in. Dropping continues even if one of the elements
+                // panics.
+                $(let $name = DropGuard($name);)*
+            }
+        }
 
         impl<'handle, $($name: List + 'handle),*> SplittableHandle<'handle> for ($($name),*,) {
             type SplitHandles = ($(Handle<'handle, $name>),*,);
@@ -35,6 +45,18 @@ macro_rules! tuple_impls {
     }
 }
 
+// Drops the List it references, using `List::drop_list`, when dropped.
+struct DropGuard<'l, L: List>(&'l mut L);
+
+impl<'l, L: List> Drop for DropGuard<'l, L> {
+    fn drop(&mut self) {
+        // Safety: DropGuard is only constructed by List::drop_list for tuples,
+        // whose caller guarantees that the tuple, and therefore this element
+        // of it, is not used or dropped again.
+        unsafe { L::drop_list(self.0) }
+    }
+}
+
 // Recursively calls tuple_impls for all tuples of a given length or shorter
 // (except the empty tuple, which tuple_impls doesn't support).
 macro_rules! impl_recursive {
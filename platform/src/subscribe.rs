@@ -1,5 +1,5 @@
-use crate::ErrorCode;
 use crate::share::List;
+use crate::ErrorCode;
 use crate::Syscalls;
 
 // -----------------------------------------------------------------------------
@@ -53,10 +53,70 @@ impl<'share, S: Syscalls, const DRIVER_NUM: u32, const SUBSCRIBE_NUM: u32> List
 {
 }
 
+// -----------------------------------------------------------------------------
+// `SubscriptionGuard` struct
+// -----------------------------------------------------------------------------
+
+/// Keeps a `'static` upcall registered by `Syscalls::subscribe_owned`, and
+/// unsubscribes it when dropped.
+///
+/// A `Subscribe` handle only exists within a `share::scope`, which lets the
+/// upcall borrow from the stack but means the subscription cannot outlive the
+/// function that created it. A `SubscriptionGuard` is an ordinary value that
+/// can be returned or stored in a struct field, so a state machine can arm an
+/// upcall in one method and disarm it in another. In exchange, the upcall must
+/// be `'static`, as nothing guarantees the guard is ever dropped. Leaking the
+/// guard is sound: the upcall simply stays registered.
+///
+/// Dropping the guard unsubscribes whatever upcall is registered with its ID at
+/// that point, even if it has been replaced since.
+pub struct SubscriptionGuard<S: Syscalls, const DRIVER_NUM: u32, const SUBSCRIBE_NUM: u32> {
+    _syscalls: core::marker::PhantomData<S>,
+}
+
+impl<S: Syscalls, const DRIVER_NUM: u32, const SUBSCRIBE_NUM: u32>
+    SubscriptionGuard<S, DRIVER_NUM, SUBSCRIBE_NUM>
+{
+    // Only called by Syscalls::subscribe_owned, once the upcall is registered.
+    pub(crate) fn new() -> Self {
+        Self {
+            _syscalls: Default::default(),
+        }
+    }
+}
+
+impl<S: Syscalls, const DRIVER_NUM: u32, const SUBSCRIBE_NUM: u32> Drop
+    for SubscriptionGuard<S, DRIVER_NUM, SUBSCRIBE_NUM>
+{
+    fn drop(&mut self) {
+        S::unsubscribe(DRIVER_NUM, SUBSCRIBE_NUM);
+    }
+}
+
 // -----------------------------------------------------------------------------
 // `Upcall` trait
 // -----------------------------------------------------------------------------
 
+/// The upcall that was registered before a call to
+/// `Syscalls::subscribe_return` replaced it, as returned by the kernel. The
+/// registers are exactly what the kernel returned, so they can be passed back
+/// to a raw Subscribe system call to restore the previous upcall. Doing so is
+/// only sound if the previous upcall's data is still valid.
+#[derive(Clone, Copy, Debug)]
+pub struct PreviousUpcall {
+    pub function: crate::Register,
+    pub data: crate::Register,
+}
+
+impl PreviousUpcall {
+    /// Returns true if the previous upcall was the null upcall, i.e. no upcall
+    /// was registered. TRD 104 specifies that the null upcall has address 0,
+    /// which is not necessarily a null pointer.
+    pub fn is_null(&self) -> bool {
+        usize::from(self.function) == 0
+    }
+}
+
 /// A Tock kernel upcall. Upcalls are registered using the Subscribe system
 /// call, and are invoked during Yield calls.
 ///
@@ -159,12 +219,7 @@ pub trait UpcallResult {
     /// Get the upcall result as a Result<> type, yielding if the upcall has not been called.
     #[inline]
     fn upcall_result_yield<S: Syscalls>(&self) -> Result<Self::Arg, ErrorCode> {
-        loop {
-            if let Some(result) = self.upcall_result() {
-                return result;
-            }
-            S::yield_wait();
-        }
+        S::yield_wait_for_result(|| self.upcall_result())
     }
 
     #[inline]
@@ -202,6 +257,25 @@ implement_result!(StandardResult, (), {}, ());
 implement_result!(StandardResultArg1, usize, { y }, y);
 implement_result!(StandardResultArg2, (usize, usize), {y, z}, (y, z));
 
+/// The result of an upcall that reports a value in its first argument rather
+/// than a status code, as the sensor drivers' upcalls do. Those drivers report
+/// failures through the command that starts the operation instead.
+pub type ValueResult = core::cell::Cell<Option<(u32,)>>;
+
+impl UpcallResult for ValueResult {
+    type Arg = u32;
+
+    #[inline]
+    fn upcall_result(&self) -> Option<Result<u32, ErrorCode>> {
+        self.get().map(|(value,)| Ok(value))
+    }
+
+    #[inline]
+    fn reset(&self) {
+        self.set(None);
+    }
+}
+
 #[cfg(test)]
 #[test]
 fn upcall_impls() {
@@ -213,15 +287,15 @@ fn upcall_impls() {
     cell_empty.upcall(1, 2, 3);
     assert_eq!(cell_empty.get(), Some(()));
 
-    let cell_one = core::cell::Cell::new(None);
+    let cell_one: core::cell::Cell<Option<(u32,)>> = core::cell::Cell::new(None);
     cell_one.upcall(1, 2, 3);
     assert_eq!(cell_one.get(), Some((1,)));
 
-    let cell_two = core::cell::Cell::new(None);
+    let cell_two: core::cell::Cell<Option<(u32, u32)>> = core::cell::Cell::new(None);
     cell_two.upcall(1, 2, 3);
     assert_eq!(cell_two.get(), Some((1, 2)));
 
-    let cell_three = core::cell::Cell::new(None);
+    let cell_three: core::cell::Cell<Option<(u32, u32, u32)>> = core::cell::Cell::new(None);
     cell_three.upcall(1, 2, 3);
     assert_eq!(cell_three.get(), Some((1, 2, 3)));
 }
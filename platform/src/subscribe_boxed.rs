@@ -0,0 +1,96 @@
+//! Subscribing heap-allocated closures as upcalls.
+//!
+//! `Syscalls::subscribe` needs an `Upcall` that outlives a `share::scope`,
+//! which usually means declaring it on the stack before the scope. Processes
+//! that have a heap can instead pass a boxed closure to `subscribe_boxed`,
+//! which keeps the closure alive for as long as the returned `BoxedSubscription`
+//! exists:
+//!
+//! ```ignore
+//! let presses = Rc::new(Cell::new(0));
+//! let counter = presses.clone();
+//! let _subscription = subscribe_boxed::<TockSyscalls>(
+//!     3,
+//!     0,
+//!     Box::new(move |_, _, _| counter.set(counter.get() + 1)),
+//! )?;
+//! ```
+
+use crate::{
+    exit_on_drop, return_variant, syscall_class, ErrorCode, Register, ReturnVariant, Syscalls,
+};
+use alloc::boxed::Box;
+
+/// The type of closure accepted by `subscribe_boxed`. It is called with the
+/// upcall's three arguments.
+pub type BoxedUpcall = Box<dyn Fn(usize, usize, usize)>;
+
+/// Keeps a closure registered by `subscribe_boxed` alive. Dropping it
+/// unsubscribes the upcall and then frees the closure.
+pub struct BoxedSubscription<S: Syscalls> {
+    driver_num: u32,
+    subscribe_num: u32,
+    // Boxed again so that the kernel can be given a thin pointer to it.
+    _upcall: Box<BoxedUpcall>,
+    _syscalls: core::marker::PhantomData<S>,
+}
+
+impl<S: Syscalls> Drop for BoxedSubscription<S> {
+    fn drop(&mut self) {
+        S::unsubscribe(self.driver_num, self.subscribe_num);
+    }
+}
+
+/// Registers `upcall` as the upcall for `driver_num` and `subscribe_num`. The
+/// upcall stays registered until the returned `BoxedSubscription` is dropped.
+///
+/// Leaking the `BoxedSubscription` (e.g. with `core::mem::forget`) is sound: it
+/// leaves the upcall registered and leaks the closure.
+pub fn subscribe_boxed<S: Syscalls>(
+    driver_num: u32,
+    subscribe_num: u32,
+    upcall: BoxedUpcall,
+) -> Result<BoxedSubscription<S>, ErrorCode> {
+    // The upcall function passed to the Tock kernel.
+    //
+    // Safety: data must point to a valid BoxedUpcall.
+    unsafe extern "C" fn kernel_upcall<S: Syscalls>(
+        arg0: usize,
+        arg1: usize,
+        arg2: usize,
+        data: Register,
+    ) {
+        let exit: exit_on_drop::ExitOnDrop<S> = Default::default();
+        #[cfg(feature = "yield_watchdog")]
+        crate::yield_watchdog::upcall_ran();
+        let upcall: *const BoxedUpcall = data.into();
+        (unsafe { &*upcall })(arg0, arg1, arg2);
+        core::mem::forget(exit);
+    }
+
+    let upcall = Box::new(upcall);
+    let upcall_fcn = Register::from_function(kernel_upcall::<S> as *const ());
+    let upcall_data = (&*upcall as *const BoxedUpcall).into();
+    // Safety: syscall4's documentation indicates it can be used to call
+    // Subscribe. These arguments follow TRD104. kernel_upcall has the required
+    // signature, and upcall_data points to a BoxedUpcall that is only freed
+    // after BoxedSubscription's Drop implementation unsubscribes it.
+    let [r0, r1, _, _] = unsafe {
+        S::syscall4::<{ syscall_class::SUBSCRIBE }>([
+            driver_num.into(),
+            subscribe_num.into(),
+            upcall_fcn,
+            upcall_data,
+        ])
+    };
+    let variant: ReturnVariant = r0.as_u32().into();
+    if variant == return_variant::FAILURE_2_U32 {
+        return Err(ErrorCode::from_kernel(r1.as_u32()));
+    }
+    Ok(BoxedSubscription {
+        driver_num,
+        subscribe_num,
+        _upcall: upcall,
+        _syscalls: core::marker::PhantomData,
+    })
+}
@@ -1,9 +1,12 @@
 use crate::{
     allow_ro, allow_rw, share, subscribe, AllowRo, AllowRw, CommandReturn, ErrorCode, RawSyscalls,
-    Subscribe, Upcall, YieldNoWaitReturn,
+    Subscribe, SubscriptionGuard, Upcall, YieldNoWaitReturn,
 };
 use kernel::cheri::cptr;
 
+/// The most callbacks `Syscalls::drain_upcalls` runs in one call.
+pub const DRAIN_UPCALLS_LIMIT: usize = 64;
+
 /// `Syscalls` provides safe abstractions over Tock's system calls. It is
 /// implemented for `libtock_runtime::TockSyscalls` and
 /// `libtock_unittest::fake::Kernel` (by way of `RawSyscalls`).
@@ -21,6 +24,53 @@ pub trait Syscalls: RawSyscalls + Sized {
     /// callback, then returns.
     fn yield_wait();
 
+    /// Calls `yield_wait` until `predicate` returns true. `predicate` is
+    /// checked before each yield, so this returns immediately if it is
+    /// already true.
+    fn yield_wait_for<F: Fn() -> bool>(predicate: F) {
+        while !predicate() {
+            Self::yield_wait();
+        }
+    }
+
+    /// Calls `yield_wait` until `f` returns `Some`, and returns the contained
+    /// value. `f` is checked before each yield.
+    fn yield_wait_for_result<T, F: Fn() -> Option<T>>(f: F) -> T {
+        loop {
+            if let Some(result) = f() {
+                return result;
+            }
+            Self::yield_wait();
+        }
+    }
+
+    /// Runs the next pending callback, if one is pending, without blocking.
+    /// Returns true if a callback ran. This suits superloops that poll their
+    /// drivers and cannot block.
+    fn poll_once() -> bool {
+        Self::yield_no_wait() == YieldNoWaitReturn::Upcall
+    }
+
+    /// Runs pending callbacks until none are left, without blocking, and
+    /// returns how many ran. Calling this at the top of a superloop handles
+    /// every queued event before the loop decides whether to sleep.
+    ///
+    /// A callback that causes another upcall to be queued could keep this
+    /// running forever, so it stops after `DRAIN_UPCALLS_LIMIT` callbacks. Use
+    /// `drain_upcalls_at_most` for a different limit.
+    fn drain_upcalls() -> usize {
+        Self::drain_upcalls_at_most(DRAIN_UPCALLS_LIMIT)
+    }
+
+    /// Like `drain_upcalls`, but stops after `limit` callbacks.
+    fn drain_upcalls_at_most(limit: usize) -> usize {
+        let mut count = 0;
+        while count < limit && Self::poll_once() {
+            count += 1;
+        }
+        count
+    }
+
     // -------------------------------------------------------------------------
     // Subscribe
     // -------------------------------------------------------------------------
@@ -38,6 +88,66 @@ pub trait Syscalls: RawSyscalls + Sized {
         upcall: &'share U,
     ) -> Result<(), ErrorCode>;
 
+    /// Registers an upcall with the kernel, like `subscribe`, and returns the
+    /// upcall that was previously registered with the same ID. This allows
+    /// advanced code to restore a prior handler using raw system calls.
+    fn subscribe_return<
+        'share,
+        IDS: subscribe::SupportsId<DRIVER_NUM, SUBSCRIBE_NUM>,
+        U: Upcall<IDS>,
+        CONFIG: subscribe::Config,
+        const DRIVER_NUM: u32,
+        const SUBSCRIBE_NUM: u32,
+    >(
+        subscribe: share::Handle<Subscribe<'share, Self, DRIVER_NUM, SUBSCRIBE_NUM>>,
+        upcall: &'share U,
+    ) -> Result<subscribe::PreviousUpcall, ErrorCode>;
+
+    /// Registers an upcall that lives for the rest of the program. Because the
+    /// upcall can never be freed, no `share::scope` is needed, and the upcall
+    /// stays registered until it is replaced or `unsubscribe` is called. This
+    /// is useful for state that outlives any one stack frame, such as a
+    /// single process-wide alarm shared by several futures.
+    fn subscribe_static<
+        IDS: subscribe::SupportsId<DRIVER_NUM, SUBSCRIBE_NUM>,
+        U: Upcall<IDS>,
+        CONFIG: subscribe::Config,
+        const DRIVER_NUM: u32,
+        const SUBSCRIBE_NUM: u32,
+    >(
+        upcall: &'static U,
+    ) -> Result<(), ErrorCode> {
+        let subscribe = Subscribe::<'static, Self, DRIVER_NUM, SUBSCRIBE_NUM>::default();
+        // Safety: Handle::new requires the Subscribe to be dropped, as dropping
+        // it is what unregisters the upcall before 'share ends. Here 'share is
+        // 'static, so the upcall remains valid forever and it is sound to
+        // forget the Subscribe instead.
+        let result = Self::subscribe::<IDS, U, CONFIG, DRIVER_NUM, SUBSCRIBE_NUM>(
+            unsafe { share::Handle::new(&subscribe) },
+            upcall,
+        );
+        core::mem::forget(subscribe);
+        result
+    }
+
+    /// Registers an upcall that lives for the rest of the program, like
+    /// `subscribe_static`, and returns a `SubscriptionGuard` that unsubscribes
+    /// it when dropped. The guard can be stored in a struct field, which suits
+    /// event-driven code that arms an upcall and returns; see
+    /// `SubscriptionGuard` for how this compares to `share::scope`.
+    fn subscribe_owned<
+        IDS: subscribe::SupportsId<DRIVER_NUM, SUBSCRIBE_NUM>,
+        U: Upcall<IDS>,
+        CONFIG: subscribe::Config,
+        const DRIVER_NUM: u32,
+        const SUBSCRIBE_NUM: u32,
+    >(
+        upcall: &'static U,
+    ) -> Result<SubscriptionGuard<Self, DRIVER_NUM, SUBSCRIBE_NUM>, ErrorCode> {
+        Self::subscribe_static::<IDS, U, CONFIG, DRIVER_NUM, SUBSCRIBE_NUM>(upcall)?;
+        Ok(SubscriptionGuard::new())
+    }
+
     /// Unregisters the upcall with the given ID. If no upcall is registered
     /// with the given ID, `unsubscribe` does nothing.
     fn unsubscribe(driver_num: u32, subscribe_num: u32);
@@ -53,6 +163,49 @@ pub trait Syscalls: RawSyscalls + Sized {
         argument1: usize,
     ) -> CommandReturn;
 
+    /// Returns `Ok(())` if the driver with number `driver_num` is present, by
+    /// issuing its existence check (command 0). Drivers may answer that command
+    /// with either Success or Success with u32. If it fails, the error the
+    /// kernel returned is passed on (`NoDevice` if the driver is absent).
+    fn driver_exists(driver_num: u32) -> Result<(), ErrorCode> {
+        let command_return = Self::command(driver_num, 0, 0, 0);
+        if command_return.is_success_u32() {
+            return Ok(());
+        }
+        command_return.to_result()
+    }
+
+    /// Returns the number of instances the driver with number `driver_num`
+    /// reports from command 0, for drivers (such as LEDs and buttons) whose
+    /// existence check returns a count. Fails with the error the kernel
+    /// returned, or `BadRVal` if the driver does not return a count.
+    fn driver_count(driver_num: u32) -> Result<u32, ErrorCode> {
+        Self::command(driver_num, 0, 0, 0).to_result()
+    }
+
+    /// Issues a command, and re-issues it up to `retries` times for as long as
+    /// the driver returns `ErrorCode::Busy`. Between attempts, this calls
+    /// `yield_no_wait`, which runs a pending upcall (such as the one that
+    /// completes the operation the driver is busy with) without blocking.
+    /// Returns the `CommandReturn` of the last attempt.
+    fn command_retry(
+        driver_id: u32,
+        command_id: u32,
+        argument0: usize,
+        argument1: usize,
+        retries: u32,
+    ) -> CommandReturn {
+        let mut command_return = Self::command(driver_id, command_id, argument0, argument1);
+        for _ in 0..retries {
+            if command_return.get_failure() != Some(ErrorCode::Busy) {
+                break;
+            }
+            Self::yield_no_wait();
+            command_return = Self::command(driver_id, command_id, argument0, argument1);
+        }
+        command_return
+    }
+
     // -------------------------------------------------------------------------
     // Read-Write Allow
     // -------------------------------------------------------------------------
@@ -63,6 +216,50 @@ pub trait Syscalls: RawSyscalls + Sized {
         buffer: &'share mut [u8],
     ) -> Result<(), ErrorCode>;
 
+    /// Shares a read-write buffer of `u16`s with the kernel, for drivers that
+    /// produce 16-bit values (e.g. ADC samples). The kernel sees the buffer as
+    /// bytes, in native byte order.
+    fn allow_rw_u16<
+        'share,
+        CONFIG: allow_rw::Config,
+        const DRIVER_NUM: u32,
+        const BUFFER_NUM: u32,
+    >(
+        allow_rw: share::Handle<AllowRw<'share, Self, DRIVER_NUM, BUFFER_NUM>>,
+        buffer: &'share mut [u16],
+    ) -> Result<(), ErrorCode> {
+        // Safety: every buffer of u16s is also a buffer of u8s, and any bytes
+        // the kernel writes into it form valid u16s.
+        Self::allow_rw::<CONFIG, DRIVER_NUM, BUFFER_NUM>(allow_rw, unsafe {
+            let len = core::mem::size_of::<u16>() * buffer.len();
+            let ptr = buffer.as_mut_ptr() as *mut u8;
+            core::slice::from_raw_parts_mut(ptr, len)
+        })
+    }
+
+    /// Shares a read-write buffer of `u32`s with the kernel, for drivers that
+    /// work with 32-bit words (e.g. DMA buffers). The kernel sees the buffer
+    /// as `4 * buffer.len()` bytes, in native byte order.
+    fn allow_rw_32<
+        'share,
+        CONFIG: allow_rw::Config,
+        const DRIVER_NUM: u32,
+        const BUFFER_NUM: u32,
+    >(
+        allow_rw: share::Handle<AllowRw<'share, Self, DRIVER_NUM, BUFFER_NUM>>,
+        buffer: &'share mut [u32],
+    ) -> Result<(), ErrorCode> {
+        // Safety: a u8 has no alignment requirement, so every buffer of u32s
+        // is also a valid buffer of u8s, and any bytes the kernel writes into
+        // it form valid u32s. The kernel cannot misalign the buffer, as it
+        // only writes through the pointer it was given.
+        Self::allow_rw::<CONFIG, DRIVER_NUM, BUFFER_NUM>(allow_rw, unsafe {
+            let len = core::mem::size_of::<u32>() * buffer.len();
+            let ptr = buffer.as_mut_ptr() as *mut u8;
+            core::slice::from_raw_parts_mut(ptr, len)
+        })
+    }
+
     /// Revokes the kernel's access to the buffer with the given ID, overwriting
     /// it with a zero buffer. If no buffer is shared with the given ID,
     /// `unallow_rw` does nothing.
@@ -100,7 +297,8 @@ pub trait Syscalls: RawSyscalls + Sized {
     /// `unallow_ro` does nothing.
     fn unallow_ro(driver_num: u32, buffer_num: u32) -> Result<(), ErrorCode>;
 
-    /// Perform a memory operation
+    /// Perform a memory operation. The `memop` module has wrappers for the
+    /// individual operations.
     fn memop(op_type: u32, arg1: usize) -> Result<cptr, ErrorCode>;
 
     /// Move the user/kernel break by offset bytes.
@@ -108,8 +306,6 @@ pub trait Syscalls: RawSyscalls + Sized {
     /// On CHERI: DDC will be automatically set to authorise at least up to the new break.
     fn sbrk(offset: usize) -> Result<usize, ErrorCode>;
 
-    /// TODO: wrap the other memops
-
     // -------------------------------------------------------------------------
     // Exit
     // -------------------------------------------------------------------------
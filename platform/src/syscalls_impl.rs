@@ -1,17 +1,18 @@
 //! Implements `Syscalls` for all types that implement `RawSyscalls`.
 
 use crate::{
-    allow_ro, allow_rw, exit_id, exit_on_drop, return_variant, share, subscribe, syscall_class,
-    yield_id, AllowRo, AllowRw, CommandReturn, ErrorCode, RawSyscalls, Register, ReturnVariant,
-    Subscribe, Syscalls, Upcall, YieldNoWaitReturn,
+    allow_ro, allow_rw, exit_id, exit_on_drop, memop, return_variant, share, subscribe,
+    syscall_class, yield_id, AllowRo, AllowRw, CommandReturn, ErrorCode, RawSyscalls, Register,
+    ReturnVariant, Subscribe, Syscalls, Upcall, YieldNoWaitReturn,
 };
 use kernel::cheri::cptr;
 
 #[inline]
 fn check_result(r0: Register, r1: Register) -> Result<(), ErrorCode> {
     let return_variant: ReturnVariant = r0.as_u32().into();
-    // TRD 104 guarantees that Subscribe returns either Successor Failure.
-    // We check the return variant by comparing against Failure for 2 reasons:
+    // TRD 104 guarantees that Subscribe and Allow return either Success
+    // with 2 U32 or Failure with 2 U32. We check the return variant by
+    // comparing against Failure with 2 U32 for 2 reasons:
     //
     //   1. On RISC-V with compressed instructions, it generates smaller
     //      code. FAILURE_2_U32 has value 2, which can be loaded into a
@@ -24,7 +25,7 @@ fn check_result(r0: Register, r1: Register) -> Result<(), ErrorCode> {
     //      Incorrectly assuming the call succeeded will not generate
     //      unsoundness, and will likely lead to the application
     //      hanging.
-    if return_variant == return_variant::FAILURE {
+    if return_variant == return_variant::FAILURE_2_U32 {
         // Safety: TRD 104 guarantees that if r0 is Failure with 2 U32,
         // then r1 will contain a valid error code. ErrorCode is
         // designed to be safely transmuted directly from a kernel error
@@ -34,6 +35,22 @@ fn check_result(r0: Register, r1: Register) -> Result<(), ErrorCode> {
     Ok(())
 }
 
+// TRD 104 requires a failed Allow to return the buffer it was passed (in r2
+// and r3). In debug builds, check that it did, to catch kernel or driver bugs
+// that hand back a different buffer. Successful Allows return the previous
+// buffer instead, which returned_nonzero_buffer checks.
+#[cfg(debug_assertions)]
+fn check_failed_allow_buffer(r0: Register, r2: Register, r3: Register, buffer: &[u8]) {
+    let return_variant: ReturnVariant = r0.as_u32().into();
+    if return_variant == return_variant::FAILURE_2_U32 {
+        assert_eq!(
+            (usize::from(r2), usize::from(r3)),
+            (buffer.as_ptr() as usize, buffer.len()),
+            "Failed Allow returned a different buffer than it was passed"
+        );
+    }
+}
+
 impl<S: RawSyscalls> Syscalls for S {
     // -------------------------------------------------------------------------
     // Yield
@@ -58,9 +75,13 @@ impl<S: RawSyscalls> Syscalls for S {
         // Safety: yield-wait does not return a value, which satisfies yield1's
         // requirement. The yield-wait system call cannot trigger undefined
         // behavior on its own in any other way.
+        #[cfg(feature = "yield_watchdog")]
+        crate::yield_watchdog::yield_starting();
         unsafe {
             Self::yield1([yield_id::WAIT.into()]);
         }
+        #[cfg(feature = "yield_watchdog")]
+        crate::yield_watchdog::yield_finished();
     }
 
     // -------------------------------------------------------------------------
@@ -75,9 +96,24 @@ impl<S: RawSyscalls> Syscalls for S {
         const DRIVER_NUM: u32,
         const SUBSCRIBE_NUM: u32,
     >(
-        _subscribe: share::Handle<Subscribe<'share, Self, DRIVER_NUM, SUBSCRIBE_NUM>>,
+        subscribe: share::Handle<Subscribe<'share, Self, DRIVER_NUM, SUBSCRIBE_NUM>>,
         upcall: &'share U,
     ) -> Result<(), ErrorCode> {
+        Self::subscribe_return::<IDS, U, CONFIG, DRIVER_NUM, SUBSCRIBE_NUM>(subscribe, upcall)
+            .map(|_| ())
+    }
+
+    fn subscribe_return<
+        'share,
+        IDS: subscribe::SupportsId<DRIVER_NUM, SUBSCRIBE_NUM>,
+        U: Upcall<IDS>,
+        CONFIG: subscribe::Config,
+        const DRIVER_NUM: u32,
+        const SUBSCRIBE_NUM: u32,
+    >(
+        _subscribe: share::Handle<Subscribe<'share, Self, DRIVER_NUM, SUBSCRIBE_NUM>>,
+        upcall: &'share U,
+    ) -> Result<subscribe::PreviousUpcall, ErrorCode> {
         // The upcall function passed to the Tock kernel.
         //
         // Safety: data must be a reference to a valid instance of U.
@@ -88,6 +124,8 @@ impl<S: RawSyscalls> Syscalls for S {
             data: Register,
         ) {
             let exit: exit_on_drop::ExitOnDrop<S> = Default::default();
+            #[cfg(feature = "yield_watchdog")]
+            crate::yield_watchdog::upcall_ran();
             let upcall: *const U = data.into();
             unsafe { &*upcall }.upcall(arg0, arg1, arg2);
             core::mem::forget(exit);
@@ -106,7 +144,7 @@ impl<S: RawSyscalls> Syscalls for S {
             subscribe_num: u32,
             upcall_fcn: Register,
             upcall_data: Register,
-        ) -> Result<(), ErrorCode> {
+        ) -> Result<subscribe::PreviousUpcall, ErrorCode> {
             // Safety: syscall4's documentation indicates it can be used to call
             // Subscribe. These arguments follow TRD104. kernel_upcall has the
             // required signature. This function's preconditions mean that
@@ -116,7 +154,7 @@ impl<S: RawSyscalls> Syscalls for S {
             // that if this Subscribe succeeds then the upcall will be cleaned
             // up before the 'scope lifetime ends, guaranteeing that upcall is
             // still alive when kernel_upcall is invoked.
-            let [r0, r1, _, _] = unsafe {
+            let [r0, r1, r2, _] = unsafe {
                 S::syscall4::<{ syscall_class::SUBSCRIBE }>([
                     driver_num.into(),
                     subscribe_num.into(),
@@ -137,7 +175,10 @@ impl<S: RawSyscalls> Syscalls for S {
             if returned_upcall != 0usize {
                 CONFIG::returned_nonnull_upcall(driver_num, subscribe_num);
             }
-            Ok(())
+            Ok(subscribe::PreviousUpcall {
+                function: r1,
+                data: r2,
+            })
         }
 
         let upcall_fcn = Register::from_function(kernel_upcall::<S, IDS, U> as *const ());
@@ -212,15 +253,18 @@ impl<S: RawSyscalls> Syscalls for S {
         ) -> Result<(), ErrorCode> {
             // Safety: syscall4's documentation indicates it can be used to call
             // Read-Write Allow. These arguments follow TRD104.
-            let [r0, r1, r2, _] = unsafe {
+            let [r0, r1, r2, _r3] = unsafe {
                 S::syscall4::<{ syscall_class::ALLOW_RW }>([
                     driver_num.into(),
                     buffer_num.into(),
-                    buffer.as_mut_ptr().into(),
+                    Register::from_slice(buffer.as_mut_ptr(), buffer.len()),
                     buffer.len().into(),
                 ])
             };
 
+            #[cfg(debug_assertions)]
+            check_failed_allow_buffer(r0, r2, _r3, buffer);
+
             check_result(r0, r1)?;
 
             // r0 indicates Success with 2 u32s. Confirm a zero buffer was
@@ -278,15 +322,18 @@ impl<S: RawSyscalls> Syscalls for S {
         ) -> Result<(), ErrorCode> {
             // Safety: syscall4's documentation indicates it can be used to call
             // Read-Only Allow. These arguments follow TRD104.
-            let [r0, r1, r2, _] = unsafe {
+            let [r0, r1, r2, _r3] = unsafe {
                 S::syscall4::<{ syscall_class::ALLOW_RO }>([
                     driver_num.into(),
                     buffer_num.into(),
-                    buffer.as_ptr().into(),
+                    Register::from_slice(buffer.as_ptr(), buffer.len()),
                     buffer.len().into(),
                 ])
             };
 
+            #[cfg(debug_assertions)]
+            check_failed_allow_buffer(r0, r2, _r3, buffer);
+
             check_result(r0, r1)?;
 
             // r0 indicates Success. Confirm a zero buffer was
@@ -353,20 +400,24 @@ impl<S: RawSyscalls> Syscalls for S {
     }
 
     fn memop(op_type: u32, arg1: usize) -> Result<cptr, ErrorCode> {
-        unsafe {
-            let [r0, r1] =
-                Self::syscall2::<{ syscall_class::MEMOP }>([op_type.into(), arg1.into()]);
-            let return_variant: ReturnVariant = r0.as_u32().into();
-            if return_variant == return_variant::FAILURE {
-                Err(core::mem::transmute(r1.as_u32()))
-            } else {
-                Ok(r1.0)
-            }
+        // Safety: syscall2's documentation indicates it can be used to call
+        // Memop.
+        let [r0, r1] =
+            unsafe { Self::syscall2::<{ syscall_class::MEMOP }>([op_type.into(), arg1.into()]) };
+        let return_variant: ReturnVariant = r0.as_u32().into();
+        if return_variant == return_variant::FAILURE {
+            // Memop's errors are less rigorously specified than Command's and
+            // Subscribe's, so this does not rely on the kernel returning a
+            // valid error code. from_kernel is a single range check, so this
+            // adds almost nothing to sbrk, which the allocator calls.
+            Err(ErrorCode::from_kernel(r1.as_u32()))
+        } else {
+            Ok(r1.0)
         }
     }
 
     fn sbrk(offset: usize) -> Result<usize, ErrorCode> {
-        Self::memop(1, offset).map(|ptr: cptr| {
+        Self::memop(memop::op::SBRK, offset).map(|ptr: cptr| {
             // On CHERI, sbrk should change DDC
             #[cfg(target_feature = "xcheri")]
             unsafe {
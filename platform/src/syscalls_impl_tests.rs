@@ -0,0 +1,39 @@
+use crate::{memop, return_variant, syscall_class, ErrorCode, RawSyscalls, Register, Syscalls};
+
+// A RawSyscalls implementation whose Memop calls fail with a value that is not
+// a TRD 104 error code. libtock_unittest's fake kernel can only return valid
+// error codes, so this test uses its own.
+struct BadMemop;
+
+unsafe impl RawSyscalls for BadMemop {
+    unsafe fn yield1(_: [Register; 1]) {
+        unreachable!("BadMemop only implements Memop")
+    }
+
+    unsafe fn yield2(_: [Register; 2]) {
+        unreachable!("BadMemop only implements Memop")
+    }
+
+    unsafe fn syscall1<const CLASS: usize>(_: [Register; 1]) -> [Register; 2] {
+        unreachable!("BadMemop only implements Memop")
+    }
+
+    unsafe fn syscall2<const CLASS: usize>(_: [Register; 2]) -> [Register; 2] {
+        assert_eq!(CLASS, syscall_class::MEMOP);
+        let r0: u32 = return_variant::FAILURE.into();
+        [r0.into(), 5000u32.into()]
+    }
+
+    unsafe fn syscall4<const CLASS: usize>(_: [Register; 4]) -> [Register; 4] {
+        unreachable!("BadMemop only implements Memop")
+    }
+}
+
+#[test]
+fn memop_invalid_error_code() {
+    assert_eq!(
+        BadMemop::memop(memop::op::BRK, 0).err(),
+        Some(ErrorCode::Fail)
+    );
+    assert_eq!(BadMemop::sbrk(16), Err(ErrorCode::Fail));
+}
@@ -1,5 +1,8 @@
 //! Definition of the Termination trait. The main() function (set using set_main!())
 //! must return a type that implements Termination.
+//!
+//! The implementations below run the hook installed with
+//! `exit_hook::set_hook`, if any, before calling Exit.
 
 use crate::{ErrorCode, Syscalls};
 
@@ -9,16 +12,32 @@ pub trait Termination {
 
 impl Termination for () {
     fn complete<S: Syscalls>(self) -> ! {
+        crate::exit_hook::run_hook();
         S::exit_terminate(0)
     }
 }
 
-impl Termination for Result<(), ErrorCode> {
+/// Returning `Ok(value)` completes `value`. Returning `Err(error)` prints the
+/// error code using the LowLevelDebug driver (if present), then terminates with
+/// the error code as the completion code.
+impl<T: Termination, E: Into<ErrorCode>> Termination for Result<T, E> {
     fn complete<S: Syscalls>(self) -> ! {
-        let exit_code = match self {
-            Ok(()) => 0,
-            Err(ec) => ec as u32,
+        let error_code: ErrorCode = match self {
+            Ok(value) => value.complete::<S>(),
+            Err(error) => error.into(),
         };
-        S::exit_terminate(exit_code);
+        crate::exit_hook::run_hook();
+        // LowLevelDebug's "print one number" command. libtock_low_level_debug
+        // depends on this crate, so the command is issued directly.
+        let _ = S::command(
+            LOW_LEVEL_DEBUG_DRIVER_NUM,
+            LOW_LEVEL_DEBUG_PRINT_1,
+            error_code as usize,
+            0,
+        );
+        S::exit_terminate(error_code as u32);
     }
 }
+
+const LOW_LEVEL_DEBUG_DRIVER_NUM: u32 = 8;
+const LOW_LEVEL_DEBUG_PRINT_1: u32 = 2;
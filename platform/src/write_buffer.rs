@@ -0,0 +1,110 @@
+use core::fmt;
+
+/// A fixed-capacity buffer that text can be formatted into with `write!`,
+/// for `no_std` code that wants to format a message on the stack and then pass
+/// it to the kernel in a single Allow.
+///
+/// By default, a write that does not fit fails with `fmt::Error` and leaves the
+/// buffer as it was. A buffer created with `truncating` instead keeps as much
+/// of the write as fits. Either way, the buffer only ever holds whole UTF-8
+/// characters, and `overflowed` reports whether anything was left out.
+///
+/// # Example
+/// ```
+/// use core::fmt::Write;
+/// use libtock_platform::WriteBuffer;
+///
+/// let mut message = WriteBuffer::<32>::new();
+/// write!(message, "{} + {} = {}", 1, 2, 1 + 2).unwrap();
+/// assert_eq!(message.as_str(), "1 + 2 = 3");
+/// ```
+pub struct WriteBuffer<const N: usize> {
+    buf: [u8; N],
+    len: usize,
+    truncating: bool,
+    overflowed: bool,
+}
+
+impl<const N: usize> WriteBuffer<N> {
+    /// Creates an empty buffer in which writes that don't fit fail.
+    pub const fn new() -> Self {
+        WriteBuffer {
+            buf: [0; N],
+            len: 0,
+            truncating: false,
+            overflowed: false,
+        }
+    }
+
+    /// Creates an empty buffer in which writes that don't fit are truncated
+    /// (at a character boundary) rather than failing.
+    pub const fn truncating() -> Self {
+        WriteBuffer {
+            truncating: true,
+            ..Self::new()
+        }
+    }
+
+    /// Returns the text written so far.
+    pub fn as_str(&self) -> &str {
+        // Safety: write_str only copies whole characters from a str into buf,
+        // so buf[..len] is always valid UTF-8.
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+
+    /// Returns the text written so far, as bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if nothing has been written.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns `true` if any write did not fit, i.e. if some text is missing.
+    pub fn overflowed(&self) -> bool {
+        self.overflowed
+    }
+
+    /// Empties the buffer and resets `overflowed`.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.overflowed = false;
+    }
+}
+
+impl<const N: usize> Default for WriteBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> fmt::Write for WriteBuffer<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        let space = N - self.len;
+        let count = if s.len() <= space {
+            s.len()
+        } else {
+            self.overflowed = true;
+            if !self.truncating {
+                return Err(fmt::Error);
+            }
+            // Back up to the last character boundary that fits. 0 is always
+            // a boundary.
+            let mut count = space;
+            while !s.is_char_boundary(count) {
+                count -= 1;
+            }
+            count
+        };
+        self.buf[self.len..self.len + count].copy_from_slice(&s.as_bytes()[..count]);
+        self.len += count;
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use core::fmt::Write;
+
+use crate::WriteBuffer;
+
+#[test]
+fn exact_fit() {
+    let mut buffer = WriteBuffer::<8>::new();
+    assert!(buffer.is_empty());
+    assert_eq!(write!(buffer, "{}-{}", 123, "abcd"), Ok(()));
+    assert_eq!(buffer.as_str(), "123-abcd");
+    assert_eq!(buffer.as_bytes(), b"123-abcd");
+    assert_eq!(buffer.len(), 8);
+    assert!(!buffer.overflowed());
+
+    // An empty write still fits in a full buffer.
+    assert_eq!(buffer.write_str(""), Ok(()));
+    assert!(!buffer.overflowed());
+}
+
+#[test]
+fn overflow() {
+    let mut buffer = WriteBuffer::<8>::new();
+    assert_eq!(buffer.write_str("hello"), Ok(()));
+    // The write that doesn't fit fails and leaves the buffer unchanged.
+    assert_eq!(buffer.write_str(" world"), Err(core::fmt::Error));
+    assert_eq!(buffer.as_str(), "hello");
+    assert!(buffer.overflowed());
+    // Later writes that fit still succeed, but overflowed stays set.
+    assert_eq!(buffer.write_str("!"), Ok(()));
+    assert_eq!(buffer.as_str(), "hello!");
+    assert!(buffer.overflowed());
+
+    buffer.clear();
+    assert_eq!(buffer.as_str(), "");
+    assert!(!buffer.overflowed());
+
+    let mut empty = WriteBuffer::<0>::new();
+    assert_eq!(empty.write_str(""), Ok(()));
+    assert_eq!(empty.write_str("a"), Err(core::fmt::Error));
+}
+
+#[test]
+fn truncating() {
+    let mut buffer = WriteBuffer::<8>::truncating();
+    assert_eq!(write!(buffer, "{} world", "hello"), Ok(()));
+    assert_eq!(buffer.as_str(), "hello wo");
+    assert!(buffer.overflowed());
+    assert_eq!(buffer.write_str("more"), Ok(()));
+    assert_eq!(buffer.as_str(), "hello wo");
+}
+
+#[test]
+fn utf8_boundary() {
+    // "é" is 2 bytes and "€" is 3, so neither fits in the last 1 or 2 bytes.
+    let mut buffer = WriteBuffer::<4>::truncating();
+    assert_eq!(buffer.write_str("abcé"), Ok(()));
+    assert_eq!(buffer.as_str(), "abc");
+    assert!(buffer.overflowed());
+
+    let mut buffer = WriteBuffer::<4>::truncating();
+    assert_eq!(buffer.write_str("a€b"), Ok(()));
+    assert_eq!(buffer.as_str(), "a€");
+
+    let mut buffer = WriteBuffer::<4>::truncating();
+    assert_eq!(buffer.write_str("ab"), Ok(()));
+    assert_eq!(buffer.write_str("€"), Ok(()));
+    assert_eq!(buffer.as_str(), "ab");
+
+    // Without truncation, a multi-byte character that would straddle the end
+    // fails the write as a whole.
+    let mut buffer = WriteBuffer::<4>::new();
+    assert_eq!(buffer.write_str("abc"), Ok(()));
+    assert_eq!(buffer.write_str("é"), Err(core::fmt::Error));
+    assert_eq!(buffer.as_str(), "abc");
+}
@@ -0,0 +1,55 @@
+//! Instrumentation for catching processes that wait for an upcall that never
+//! comes.
+//!
+//! With the `yield_watchdog` feature enabled, every Yield-Wait is counted, and
+//! the count is reset whenever an upcall runs. Once `limit` consecutive
+//! Yield-Waits have completed without running an upcall, the hook installed
+//! with `set_watchdog` is called (and the count starts over). The hook may,
+//! for example, print an alert with `LowLevelDebug` or terminate the process.
+
+use crate::hook_cell::HookCell;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// The signature of a yield watchdog hook.
+pub type Hook = fn();
+
+// The number of consecutive Yield-Waits after which the hook is called. 0
+// disables the watchdog.
+static LIMIT: AtomicUsize = AtomicUsize::new(0);
+
+// The number of Yield-Waits since an upcall last ran.
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+static HOOK: HookCell<Hook> = HookCell::new();
+
+/// Calls `hook` once `limit` consecutive Yield-Waits have completed without
+/// running an upcall, replacing any previously-installed watchdog. A `limit`
+/// of 0 disables the watchdog.
+pub fn set_watchdog(limit: usize, hook: Hook) {
+    HOOK.set(hook);
+    COUNT.store(0, Ordering::Relaxed);
+    LIMIT.store(limit, Ordering::Relaxed);
+}
+
+// Called by yield_wait before each Yield-Wait. If an upcall runs during the
+// Yield-Wait, it resets the count again.
+pub(crate) fn yield_starting() {
+    COUNT.fetch_add(1, Ordering::Relaxed);
+}
+
+// Called by yield_wait after each Yield-Wait.
+pub(crate) fn yield_finished() {
+    let limit = LIMIT.load(Ordering::Relaxed);
+    if limit == 0 || COUNT.load(Ordering::Relaxed) < limit {
+        return;
+    }
+    COUNT.store(0, Ordering::Relaxed);
+    if let Some(hook) = HOOK.get() {
+        hook();
+    }
+}
+
+// Called by the upcall trampolines whenever an upcall runs.
+pub(crate) fn upcall_ran() {
+    COUNT.store(0, Ordering::Relaxed);
+}
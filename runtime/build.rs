@@ -33,11 +33,14 @@ fn auto_layout() {
         .expect("Unable to copy layout_generic.ld into OUT_DIR");
 
     // Link in libc. Only needed for malloc.
-    println!(
-        "cargo:rustc-link-search=native={}/lib",
-        std::env::var("CHERI_LIBC").expect("CHERI_LIBC not set"),
-    );
-    println!("cargo:rustc-link-lib=static=c");
+    #[cfg(not(feature = "no_libc"))]
+    {
+        println!(
+            "cargo:rustc-link-search=native={}/lib",
+            std::env::var("CHERI_LIBC").expect("CHERI_LIBC not set"),
+        );
+        println!("cargo:rustc-link-lib=static=c");
+    }
 
     // Tell rustc where to search for the layout file.
     println!("cargo:rustc-link-search={}", out_dir);
@@ -22,10 +22,24 @@
 #![warn(unsafe_op_in_unsafe_fn)]
 #![feature(alloc_error_handler)]
 
+pub mod stack_guard;
 pub mod startup;
 pub mod std;
 mod tock_alloc;
 
+// Nothing in the runtime refers to libtock_mem directly, so it must be named
+// here for its memcpy/memset/etc. symbols to be linked in.
+#[cfg(feature = "no_libc")]
+extern crate libtock_mem;
+
+#[cfg(feature = "bump_alloc")]
+pub use libtock_bump_alloc::BumpAllocator;
+
+#[cfg(feature = "stack_guard")]
+pub use stack_guard::check_stack_guard;
+
+pub use libtock_platform::memop::AppLayout;
+
 /// TockSyscalls implements `libtock_platform::Syscalls`.
 pub struct TockSyscalls;
 
@@ -34,3 +48,22 @@ mod syscalls_impl_riscv;
 
 #[cfg(target_arch = "arm")]
 mod syscalls_impl_arm;
+
+/// Queries the kernel for the process's memory layout. See
+/// `libtock_platform::memop::AppLayout::current`.
+pub fn app_layout() -> Result<AppLayout, libtock_platform::ErrorCode> {
+    AppLayout::current::<TockSyscalls>()
+}
+
+/// Calls `hook` once `limit` consecutive Yield-Waits have completed without
+/// running an upcall. See `libtock_platform::yield_watchdog`.
+#[cfg(feature = "yield_watchdog")]
+pub fn set_yield_watchdog(limit: usize, hook: libtock_platform::yield_watchdog::Hook) {
+    libtock_platform::yield_watchdog::set_watchdog(limit, hook)
+}
+
+/// Runs `future` to completion, yielding to the kernel whenever it is pending.
+/// See `libtock_platform::block_on`.
+pub fn block_on<F: core::future::Future>(future: F) -> F::Output {
+    libtock_platform::block_on::<TockSyscalls, F>(future)
+}
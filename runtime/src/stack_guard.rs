@@ -0,0 +1,55 @@
+//! Best-effort stack overflow detection, enabled by the `stack_guard` feature.
+//!
+//! With the feature enabled, `rust_start` paints a canary over the lowest
+//! bytes of the stack, which starts at the `_stack_start` symbol defined by the
+//! linker script, before calling `main`.
+//! The stack grows downward, so a stack that overflows clobbers the canary on
+//! its way out of the stack region. `check_stack_guard` reports whether the
+//! canary is still intact, and the libtock panic handlers check it
+//! automatically. A clobbered canary means the stack overflowed at some point,
+//! but an overflow that jumps over the canary without writing to it goes
+//! unnoticed.
+
+#[cfg(feature = "stack_guard")]
+extern "C" {
+    // The bottom of the .stack section, defined by libtock_layout_pie.ld (and
+    // required of custom linker scripts). Only its address is used, so its
+    // declared type does not matter.
+    static mut _stack_start: u8;
+}
+
+// Returns a pointer to the lowest byte of the stack.
+#[cfg(feature = "stack_guard")]
+fn stack_bottom() -> *mut u8 {
+    // Safety: This only takes the address of _stack_start, it does not access
+    // it.
+    unsafe { core::ptr::addr_of_mut!(_stack_start) }
+}
+
+// Paints the canary. Called by rust_start, while the stack is nearly empty.
+#[cfg(feature = "stack_guard")]
+pub(crate) fn paint() {
+    // Safety: _stack_start is the bottom of the stack, which is far larger
+    // than GUARD_LEN bytes. Its lowest bytes are not in use this early in the
+    // process' execution, as the stack grows downward from its top.
+    unsafe { libtock_stack_guard::paint(stack_bottom()) }
+}
+
+/// Returns `true` if the stack canary is intact, i.e. no stack overflow has
+/// been detected.
+#[cfg(feature = "stack_guard")]
+pub fn check_stack_guard() -> bool {
+    // Safety: The stack above _stack_start is at least GUARD_LEN bytes long.
+    unsafe { libtock_stack_guard::is_intact(stack_bottom()) }
+}
+
+/// Prints `AlertCode::StackOverflow` using LowLevelDebug if the stack canary
+/// has been clobbered. Used by the panic handlers before they terminate the
+/// process. Does nothing if the `stack_guard` feature is disabled.
+pub fn report_overflow() {
+    #[cfg(feature = "stack_guard")]
+    if !check_stack_guard() {
+        use libtock_low_level_debug::{AlertCode, LowLevelDebug};
+        LowLevelDebug::<crate::TockSyscalls>::print_alert_code(AlertCode::StackOverflow);
+    }
+}
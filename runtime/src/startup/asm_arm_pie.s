@@ -0,0 +1,143 @@
+/* rt_header is defined by the PIE linker script (libtock_layout_pie.ld). Its
+ * addresses are offsets from the start of the process's RAM. It has the
+ * following layout:
+ *
+ *     Field                       | Offset
+ *     ------------------------------------
+ *     Start of the stack          |      0
+ *     Size of the stack           |      4
+ *     Start of .bss               |      8
+ *     Size of .bss                |     12
+ *     Start of relocations        |     16
+ *     Size of relocations         |     20
+ */
+
+.set STACK_START, 0
+.set STACK_SIZE,  4
+.set BSS_START,   8
+.set BSS_SIZE,   12
+.set REL_START,  16
+.set REL_SIZE,   20
+
+/* The only relocation type a position-independent ARM binary should need. */
+.set R_ARM_RELATIVE, 23
+
+/* _start is the entry point -- the first code executed by the kernel. The
+ * kernel passes arguments through 4 registers:
+ *
+ *     r0  Pointer to rt_header.
+ *
+ *     r1  Address of the beginning of the process's usable memory region.
+ *     r2  Size of the process' allocated memory region (including grant region)
+ *     r3  Process break provided by the kernel.
+ *
+ * r0-r3 are needed to invoke system calls, so the values that are used after
+ * the first system call are kept in:
+ *
+ *     r4  Start of the process's memory region
+ *     r5  rt_header
+ *     r6  Start of .bss
+ *     r7  End of .bss, which is the initial process break
+ *     r8  Top of the stack
+ *
+ * This follows asm_riscv.s, and like asm_arm.s only uses instructions that
+ * are available in ARMv6 Thumb-1 so that it runs on Cortex-M0+ processors.
+ */
+.section .start, "ax"
+.global _start
+.thumb_func
+_start:
+	mov r5, r0                 /* r5 = rt_header */
+	mov r4, r1                 /* r4 = mem_start */
+
+	/* stack_top = mem_start + stack start + stack size */
+	ldr r0, [r5, #STACK_START]
+	ldr r1, [r5, #STACK_SIZE]
+	adds r0, r0, r1
+	adds r0, r0, r4
+	mov sp, r0
+	mov r8, r0                 /* r8 = stack_top */
+
+	ldr r6, [r5, #BSS_START]
+	adds r6, r6, r4            /* r6 = mem_start + bss_start */
+	ldr r7, [r5, #BSS_SIZE]
+	adds r7, r7, r6            /* r7 = bss end = app_brk */
+
+	/* The relocations overlap the stack and .bss. If they extend past the end
+	 * of .bss, the break has to cover them until they have been processed. */
+	ldr r0, [r5, #REL_START]
+	ldr r1, [r5, #REL_SIZE]
+	adds r0, r0, r4            /* r0 = reloc_start */
+	adds r1, r1, r0            /* r1 = reloc_end */
+	cmp r1, r7
+	bhi .Lrelocs_larger_than_bss
+	mov r1, r7
+.Lrelocs_larger_than_bss:
+	/* r1 is now the larger of the two. If the kernel's break is already past
+	 * it, there is no need to call brk at all. */
+	cmp r1, r3
+	bls .Lskip_brk
+	movs r0, #0                /* memop(0, r1): set break */
+	svc 5
+.Lskip_brk:
+
+	/* Debug support, tell the kernel the stack location */
+	movs r0, #10               /* memop(10, stack_top) */
+	mov r1, r8
+	svc 5
+	/* Debug support, tell the kernel the heap location */
+	movs r0, #11               /* memop(11, app_brk) */
+	mov r1, r7
+	svc 5
+
+	/* Process the relocations, which are Elf32_Rel entries. Each one's addend
+	 * is stored at the location it relocates. */
+	ldr r0, [r5, #REL_START]
+	ldr r1, [r5, #REL_SIZE]
+	adds r0, r0, r4            /* r0 = reloc_start */
+	adds r1, r1, r0            /* r1 = reloc_end */
+	cmp r0, r1
+	beq .Lrelocs_done
+.Lreloc_loop:
+	ldr r2, [r0, #4]           /* r2 = r_info */
+	cmp r2, #R_ARM_RELATIVE
+	bne .Lpanic
+	ldr r2, [r0, #0]           /* r2 = r_offset */
+	adds r2, r2, r4            /* r2 = mem_start + r_offset */
+	ldr r3, [r2]               /* r3 = addend */
+	adds r3, r3, r4            /* r3 = mem_start + addend */
+	str r3, [r2]
+	adds r0, #8
+	cmp r0, r1
+	bne .Lreloc_loop
+.Lrelocs_done:
+
+	/* If the break was moved past .bss to cover the relocations, move it
+	 * back. r1 still holds reloc_end. */
+	cmp r7, r1
+	bhs .Lskip_second_brk
+	movs r0, #0                /* memop(0, app_brk): set break */
+	mov r1, r7
+	svc 5
+.Lskip_second_brk:
+
+	/* Always zero .bss, as the relocations may have overlapped it. */
+	movs r0, #0
+	mov r1, r6
+	cmp r1, r7
+	beq .Lcall_rust_start
+.Lzero_loop:
+	str r0, [r1]
+	adds r1, #4
+	cmp r1, r7
+	blo .Lzero_loop
+
+.Lcall_rust_start:
+	/* Note: rust_start must be a diverging function (i.e. return `!`) */
+	bl rust_start
+
+.Lpanic:
+	/* An unexpected relocation: exit, as there is no way to report it. */
+	movs r0, #0                /* Operation: exit-terminate */
+	movs r1, #1                /* Completion code: FAIL */
+	svc 6                      /* Execute `exit` */
@@ -35,7 +35,6 @@ macro_rules! asm_prelude {
 core::arch::global_asm!(
     asm_prelude!(),
     asm_prelude_bits!(),
-    ".set CONTIGOUS, 1",
     include_str!("asm_arm_pie.s")
 );
 
@@ -106,6 +105,9 @@ extern "C" fn rust_start() -> ! {
         fn libtock_unsafe_main() -> !;
     }
 
+    #[cfg(feature = "stack_guard")]
+    crate::stack_guard::paint();
+
     // Safety: libtock_unsafe_main is defined by the set_main! macro, and its
     // signature matches the signature in the `extern` block in this function.
     unsafe {
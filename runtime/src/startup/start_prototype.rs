@@ -45,13 +45,7 @@ extern "C" fn start_prototype(
     }
 
     // Set the app break.
-    // TODO: Replace with Syscalls::memop_brk() when that is implemented.
-    unsafe {
-        TockSyscalls::syscall2::<{ syscall_class::MEMOP }>([
-            0u32.into(),
-            rt_header.initial_break.into(),
-        ]);
-    }
+    let _ = libtock_platform::memop::memop_brk::<TockSyscalls>(rt_header.initial_break);
 
     // Set the stack pointer.
     unsafe {
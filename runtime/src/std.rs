@@ -21,3 +21,12 @@ macro_rules! print {
       }
     };
 }
+
+/// Print to the console, returning the `ErrorCode` of a failed write instead of
+/// panicking
+#[macro_export]
+macro_rules! print_result {
+    ($($ts: tt)*) => {
+        libtock::console::Console::try_write_fmt(core::format_args!($($ts)*))
+    };
+}
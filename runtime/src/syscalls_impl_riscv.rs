@@ -11,8 +11,8 @@ use libtock_platform::{RawSyscalls, Register};
  */
 
 unsafe impl RawSyscalls for crate::TockSyscalls {
-    // This yield implementation is currently limited to RISC-V versions without
-    // floating-point registers, as it does not mark them clobbered.
+    // This yield implementation is for RISC-V versions without floating-point
+    // registers. The version for those with them follows.
     #[cfg(not(any(target_feature = "d", target_feature = "f")))]
     unsafe fn yield1([Register(r0)]: [Register; 1]) {
         // Safety: This matches the invariants required by the documentation on
@@ -44,8 +44,26 @@ unsafe impl RawSyscalls for crate::TockSyscalls {
         }
     }
 
-    // This yield implementation is currently limited to RISC-V versions without
-    // floating-point registers, as it does not mark them clobbered.
+    // Upcalls invoked during yield may use the floating-point registers, so on
+    // RISC-V versions that have them the caller-saved ones (ft0-ft11 and
+    // fa0-fa7) must be marked clobbered too. clobber_abi("C") does exactly
+    // that, along with the caller-saved integer registers listed explicitly
+    // above. The callee-saved registers are preserved by the upcall.
+    #[cfg(any(target_feature = "d", target_feature = "f"))]
+    unsafe fn yield1([Register(r0)]: [Register; 1]) {
+        // Safety: This matches the invariants required by the documentation on
+        // RawSyscalls::yield1
+        unsafe {
+            asm!("ecall",
+                 inlateout("x10") <cptr as Into<usize>>::into(r0) => _, // a0
+                 inlateout("x14") 0 => _, // a4
+                 clobber_abi("C"),
+            );
+        }
+    }
+
+    // This yield implementation is for RISC-V versions without floating-point
+    // registers. The version for those with them follows.
     #[cfg(not(any(target_feature = "d", target_feature = "f")))]
     unsafe fn yield2([Register(r0), Register(r1)]: [Register; 2]) {
         // Safety: This matches the invariants required by the documentation on
@@ -106,6 +124,33 @@ unsafe impl RawSyscalls for crate::TockSyscalls {
         }
     }
 
+    // See yield1 for why clobber_abi("C") is used here.
+    #[cfg(any(target_feature = "d", target_feature = "f"))]
+    unsafe fn yield2([Register(r0), Register(r1)]: [Register; 2]) {
+        // Safety: This matches the invariants required by the documentation on
+        // RawSyscalls::yield2
+        unsafe {
+            #[cfg(not(target_feature = "xcheri"))]
+            asm!("ecall",
+                 inlateout("x10") r0 => _, // a0
+                 inlateout("x11") r1 => _, // a1
+                 inlateout("x14") 0 => _,  // a4
+                 clobber_abi("C"),
+            );
+            #[cfg(target_feature = "xcheri")]
+            {
+                let mut r1 = r1;
+                asm!("lc c11, (x11)",
+                     "ecall",
+                     inlateout("x10") <cptr as Into<usize>>::into(r0) => _, // a0
+                     inlateout("x11") &mut r1 => _, // a1
+                     inlateout("x14") 0 => _,       // a4
+                     clobber_abi("C"),
+                );
+            }
+        }
+    }
+
     unsafe fn syscall1<const CLASS: usize>([Register(r0)]: [Register; 1]) -> [Register; 2] {
         let mut r0 = <cptr as Into<usize>>::into(r0);
         let r1: usize;
@@ -4,14 +4,17 @@
 extern crate alloc;
 
 use crate::TockSyscalls;
+#[cfg(not(feature = "no_libc"))]
 use core::alloc::{GlobalAlloc, Layout};
 use libtock_low_level_debug::{AlertCode, LowLevelDebug};
 use libtock_platform::{ErrorCode, Syscalls};
 
 /// An allocator that defers to malloc in libc. This adds libc as a dependency.
+#[cfg(not(feature = "no_libc"))]
 struct TockAllocatorMalloc;
 
 // The functions we pull in from malloc in libc
+#[cfg(not(feature = "no_libc"))]
 extern "C" {
     fn free(ptr: *mut u8);
     fn memalign(align: usize, nbytes: usize) -> *mut u8;
@@ -35,6 +38,7 @@ pub extern "C" fn sbrk(nbytes: usize) -> *mut u8 {
     _sbrk(nbytes)
 }
 
+#[cfg(not(feature = "no_libc"))]
 unsafe impl GlobalAlloc for TockAllocatorMalloc {
     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
         unsafe { memalign(layout.align(), layout.size()) }
@@ -45,11 +49,33 @@ unsafe impl GlobalAlloc for TockAllocatorMalloc {
     }
 }
 
+// Defers to the hook installed with `libtock_platform::oom::set_hook`, if there
+// is one.
 #[alloc_error_handler]
-fn oom_handler(_layout: core::alloc::Layout) -> ! {
+fn oom_handler(layout: core::alloc::Layout) -> ! {
+    libtock_platform::oom::run_hook(layout);
     LowLevelDebug::<TockSyscalls>::print_alert_code(AlertCode::HeapOOM);
     TockSyscalls::exit_terminate(ErrorCode::NoMem as u32);
 }
 
+#[cfg(not(feature = "no_libc"))]
 #[global_allocator]
 static GLOBAL: TockAllocatorMalloc = TockAllocatorMalloc;
+
+/// With the `bump_alloc` feature, executables that allocate must specify the
+/// size of their heap by using the `heap_size!` macro. It takes a single
+/// argument, the heap size in bytes, and declares a `BumpAllocator` of that
+/// size as the global allocator. Example:
+/// ```
+/// heap_size!{0x800}
+/// ```
+/// Running out of heap calls the same handler as the malloc-backed allocator,
+/// which reports `AlertCode::HeapOOM` and terminates the process.
+#[cfg(feature = "bump_alloc")]
+#[macro_export]
+macro_rules! heap_size {
+    {$size:expr} => {
+        #[global_allocator]
+        static HEAP: $crate::BumpAllocator<{ $size }> = $crate::BumpAllocator::new();
+    }
+}
@@ -1,7 +1,15 @@
 #![forbid(unsafe_code)]
 #![no_std]
 
+#[cfg(all(feature = "panic_console", feature = "panic_led"))]
+compile_error!("Only one of the panic_console and panic_led features can be enabled");
+
+#[cfg(feature = "panic_console")]
+extern crate libtock_console_panic;
+#[cfg(not(any(feature = "panic_console", feature = "panic_led")))]
 extern crate libtock_debug_panic;
+#[cfg(feature = "panic_led")]
+extern crate libtock_led_panic;
 
 pub extern crate alloc;
 
@@ -12,6 +20,7 @@ pub mod prelude {
     pub type Vec<T> = alloc::vec::Vec<T>;
     pub type String = alloc::string::String;
     pub use libtock_runtime::print;
+    pub use libtock_runtime::print_result;
     pub use libtock_runtime::println;
 
     // Not a part of the std predule, but used by so many tock applications that we should also have
@@ -23,18 +32,103 @@ pub mod prelude {
 pub use libtock_platform as platform;
 pub use libtock_runtime as runtime;
 
+pub mod adc {
+    use libtock_adc as adc;
+    pub type Adc = adc::Adc<super::runtime::TockSyscalls>;
+    pub use adc::{ContinuousListener, ContinuousSampling, ContinuousShares};
+}
+pub mod aes {
+    use libtock_aes as aes;
+    pub type Aes<'k> = aes::Aes<'k, super::runtime::TockSyscalls>;
+    pub use aes::{AesMode, BLOCK_LEN, CCM_NONCE_LEN, KEY_LEN};
+}
 pub mod alarm {
     use libtock_alarm as alarm;
     pub type Alarm = alarm::Alarm<super::runtime::TockSyscalls>;
-    pub use alarm::{Convert, Hz, Milliseconds, Ticks};
+    pub type Sleep = alarm::Sleep<super::runtime::TockSyscalls>;
+    pub use alarm::{AlarmListener, Convert, Hz, Milliseconds, Ticks};
+}
+pub mod ambient_light {
+    use libtock_ambient_light as ambient_light;
+    pub type AmbientLight = ambient_light::AmbientLight<super::runtime::TockSyscalls>;
+}
+pub mod analog_comparator {
+    use libtock_analog_comparator as analog_comparator;
+    pub type AnalogComparator = analog_comparator::AnalogComparator<super::runtime::TockSyscalls>;
+    pub use analog_comparator::InterruptListener;
 }
 pub mod buttons {
     use libtock_buttons as buttons;
     pub type Buttons = buttons::Buttons<super::runtime::TockSyscalls>;
 }
+pub mod buzzer {
+    use libtock_buzzer as buzzer;
+    pub type Buzzer = buzzer::Buzzer<super::runtime::TockSyscalls>;
+}
+pub mod can {
+    use libtock_can as can;
+    pub type Can = can::Can<super::runtime::TockSyscalls>;
+    pub use can::MAX_DATA_LEN;
+}
 pub mod console {
     use libtock_console as console;
     pub type Console = console::Console<super::runtime::TockSyscalls>;
+    pub type BufferedWriter<'b> = console::BufferedWriter<'b, super::runtime::TockSyscalls>;
+    pub use console::write_console;
+}
+pub mod crc {
+    use libtock_crc as crc;
+    pub type Crc = crc::Crc<super::runtime::TockSyscalls>;
+    pub use crc::CrcAlgorithm;
+}
+pub mod ctap {
+    use libtock_ctap as ctap;
+    pub type Ctap = ctap::Ctap<super::runtime::TockSyscalls>;
+    pub use ctap::PACKET_LEN;
+}
+pub mod dac {
+    use libtock_dac as dac;
+    pub type Dac = dac::Dac<super::runtime::TockSyscalls>;
+}
+pub mod gpio {
+    use libtock_gpio as gpio;
+    pub type Gpio = gpio::Gpio<super::runtime::TockSyscalls>;
+    pub use gpio::{
+        GpioInterruptListener, GpioState, PinInterruptEdge, PullDown, PullMode, PullNone, PullUp,
+    };
+}
+pub mod hmac {
+    use libtock_hmac as hmac;
+    pub type Hmac = hmac::Hmac<super::runtime::TockSyscalls>;
+    pub use hmac::HmacAlgorithm;
+}
+pub mod humidity {
+    use libtock_humidity as humidity;
+    pub type Humidity = humidity::Humidity<super::runtime::TockSyscalls>;
+}
+pub mod i2c_master {
+    use libtock_i2c_master as i2c_master;
+    pub type I2cMaster = i2c_master::I2cMaster<super::runtime::TockSyscalls>;
+}
+pub mod ieee802154 {
+    use libtock_ieee802154 as ieee802154;
+    pub type Radio = ieee802154::Radio<super::runtime::TockSyscalls>;
+    pub use ieee802154::MAX_FRAME_LEN;
+}
+pub mod ipc {
+    use libtock_ipc as ipc;
+    pub type IpcClient = ipc::IpcClient<super::runtime::TockSyscalls>;
+    pub type IpcService = ipc::IpcService<super::runtime::TockSyscalls>;
+    pub use ipc::{IpcListener, IpcNotification};
+}
+pub mod kv {
+    use libtock_kv as kv;
+    pub type KvStore = kv::KvStore<super::runtime::TockSyscalls>;
+}
+pub mod led_matrix {
+    use libtock_led_matrix as led_matrix;
+    pub type LedMatrix = led_matrix::LedMatrix<super::runtime::TockSyscalls>;
+    pub use led_matrix::frame_len;
 }
 pub mod leds {
     use libtock_leds as leds;
@@ -43,5 +137,90 @@ pub mod leds {
 pub mod low_level_debug {
     use libtock_low_level_debug as lldb;
     pub type LowLevelDebug = lldb::LowLevelDebug<super::runtime::TockSyscalls>;
-    pub use lldb::AlertCode;
+    pub use lldb::{AlertCode, NotAnAlertCode, CUSTOM_ALERT_MIN};
+}
+pub mod moisture {
+    use libtock_moisture as moisture;
+    pub type Moisture = moisture::Moisture<super::runtime::TockSyscalls>;
+}
+pub mod ninedof {
+    use libtock_ninedof as ninedof;
+    pub type NineDof = ninedof::NineDof<super::runtime::TockSyscalls>;
+    pub use ninedof::Vec3;
+}
+pub mod nonvolatile_storage {
+    use libtock_nonvolatile_storage as nonvolatile_storage;
+    pub type NonvolatileStorage =
+        nonvolatile_storage::NonvolatileStorage<super::runtime::TockSyscalls>;
+}
+pub mod pressure {
+    use libtock_pressure as pressure;
+    pub type Pressure = pressure::Pressure<super::runtime::TockSyscalls>;
+}
+pub mod proximity {
+    use libtock_proximity as proximity;
+    pub type Proximity = proximity::Proximity<super::runtime::TockSyscalls>;
+    pub use proximity::{ThresholdInterrupt, ThresholdListener};
+}
+pub mod pwm {
+    use libtock_pwm as pwm;
+    pub type Pwm = pwm::Pwm<super::runtime::TockSyscalls>;
+}
+pub mod rng {
+    use libtock_rng as rng;
+    pub type Rng = rng::Rng<super::runtime::TockSyscalls>;
+}
+pub mod rtc {
+    use libtock_rtc as rtc;
+    pub type Rtc = rtc::Rtc<super::runtime::TockSyscalls>;
+    pub use rtc::DateTime;
+}
+pub mod screen {
+    use libtock_screen as screen;
+    pub type Screen = screen::Screen<super::runtime::TockSyscalls>;
+    pub type FrameBuffer<const W: usize, const H: usize> =
+        screen::FrameBuffer<super::runtime::TockSyscalls, W, H>;
+    pub use screen::PixelFormat;
+}
+pub mod servo {
+    use libtock_servo as servo;
+    pub type Servo = servo::Servo<super::runtime::TockSyscalls>;
+}
+pub mod sha {
+    use libtock_sha as sha;
+    pub type Sha = sha::Sha<super::runtime::TockSyscalls>;
+    pub use sha::HashAlgorithm;
+}
+pub mod sound_pressure {
+    use libtock_sound_pressure as sound_pressure;
+    pub type SoundPressure = sound_pressure::SoundPressure<super::runtime::TockSyscalls>;
+}
+pub mod spi_controller {
+    use libtock_spi_controller as spi_controller;
+    pub type SpiController = spi_controller::SpiController<super::runtime::TockSyscalls>;
+    pub use spi_controller::{ClockPhase, ClockPolarity};
+}
+pub mod temperature {
+    use libtock_temperature as temperature;
+    pub type Temperature = temperature::Temperature<super::runtime::TockSyscalls>;
+    pub use temperature::TemperatureListener;
+}
+pub mod text_screen {
+    use libtock_text_screen as text_screen;
+    pub type TextScreen = text_screen::TextScreen<super::runtime::TockSyscalls>;
+}
+pub mod touch {
+    use libtock_touch as touch;
+    pub type Touch = touch::Touch<super::runtime::TockSyscalls>;
+    pub use touch::{TouchEvent, TouchStatus, MAX_TOUCHES};
+}
+pub mod udp {
+    use libtock_udp as udp;
+    pub type UdpSocket = udp::UdpSocket<super::runtime::TockSyscalls>;
+    pub use udp::{IpAddr, Ipv6Addr};
+}
+pub mod usb {
+    use libtock_usb as usb;
+    pub type Usb = usb::Usb<super::runtime::TockSyscalls>;
+    pub use usb::UsbState;
 }
@@ -0,0 +1,48 @@
+//! `libtock_stack_guard` paints and checks the canary behind
+//! `libtock_runtime`'s `stack_guard` feature. The runtime paints the canary
+//! over the lowest bytes of the stack at startup; as the stack grows downward,
+//! a stack that overflows writes over the canary before it leaves the stack
+//! region.
+//!
+//! Detection is best-effort: a frame that skips over the canary without
+//! writing to it (e.g. a large, mostly-unused local array) is not detected, and
+//! by the time the canary is checked the overflow may already have corrupted
+//! other memory.
+
+#![no_std]
+#![deny(unsafe_op_in_unsafe_fn)]
+
+/// The number of bytes the canary occupies.
+pub const GUARD_LEN: usize = CANARY.len();
+
+// The canary pattern. It is recognizable in a memory dump, and unlikely to be
+// written to the stack by accident.
+const CANARY: [u8; 16] = *b"libtock-rs stack";
+
+/// Writes the canary to the `GUARD_LEN` bytes starting at `guard`.
+///
+/// # Safety
+/// `guard` must be valid for writes of `GUARD_LEN` bytes, and those bytes must
+/// not be in use (in particular, they must not be part of a live stack frame).
+pub unsafe fn paint(guard: *mut u8) {
+    for (i, &byte) in CANARY.iter().enumerate() {
+        // Volatile, as nothing in the program reads these bytes back other
+        // than is_intact, which the compiler cannot see the connection to.
+        unsafe { guard.add(i).write_volatile(byte) };
+    }
+}
+
+/// Returns true if the `GUARD_LEN` bytes starting at `guard` still hold the
+/// canary written by `paint`.
+///
+/// # Safety
+/// `guard` must be valid for reads of `GUARD_LEN` bytes.
+pub unsafe fn is_intact(guard: *const u8) -> bool {
+    CANARY
+        .iter()
+        .enumerate()
+        .all(|(i, &byte)| unsafe { guard.add(i).read_volatile() } == byte)
+}
+
+#[cfg(test)]
+mod tests;
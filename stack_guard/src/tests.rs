@@ -0,0 +1,36 @@
+use crate::{is_intact, paint, GUARD_LEN};
+
+#[test]
+fn intact() {
+    let mut stack = [0u8; 64];
+    assert!(!unsafe { is_intact(stack.as_ptr()) });
+    unsafe { paint(stack.as_mut_ptr()) };
+    assert!(unsafe { is_intact(stack.as_ptr()) });
+
+    // Writes above the canary (i.e. in the in-use part of the stack) do not
+    // affect it.
+    stack[GUARD_LEN..].fill(0xff);
+    assert!(unsafe { is_intact(stack.as_ptr()) });
+}
+
+#[test]
+fn overflow_detected() {
+    let mut stack = [0u8; 64];
+    unsafe { paint(stack.as_mut_ptr()) };
+
+    // Simulate a stack that grew down past its end, clobbering the top byte of
+    // the canary.
+    stack[GUARD_LEN - 1] = 0;
+    assert!(!unsafe { is_intact(stack.as_ptr()) });
+
+    // Every byte of the canary is checked.
+    for i in 0..GUARD_LEN {
+        unsafe { paint(stack.as_mut_ptr()) };
+        stack[i] ^= 1;
+        assert!(
+            !unsafe { is_intact(stack.as_ptr()) },
+            "byte {} not checked",
+            i
+        );
+    }
+}
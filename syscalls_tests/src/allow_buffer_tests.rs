@@ -0,0 +1,68 @@
+//! Tests for `libtock_platform::AllowBuffer`, using `fake::Console` (driver 1),
+//! which shares its write buffer as Read-Only Allow buffer 1 and its read
+//! buffer as Read-Write Allow buffer 1.
+
+use libtock_platform::{share, AllowBuffer, Syscalls};
+use libtock_unittest::{fake, SyscallLogEntry};
+
+#[test]
+fn share_ro() {
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new();
+    kernel.add_driver(&console);
+
+    let message = AllowBuffer::<_, 1, 1>::new(*b"Hello");
+    share::scope(|handle| {
+        message.share_ro::<fake::Syscalls>(handle).unwrap();
+        assert_eq!(
+            kernel.take_syscall_log(),
+            [SyscallLogEntry::AllowRo {
+                driver_num: 1,
+                buffer_num: 1,
+                len: 5,
+            }]
+        );
+        assert!(fake::Syscalls::command(1, 1, 5, 0).is_success());
+    });
+    assert_eq!(console.take_bytes(), b"Hello");
+}
+
+#[test]
+fn share_rw() {
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new_with_input(b"abc");
+    kernel.add_driver(&console);
+
+    let mut storage = [0; 3];
+    let mut input = AllowBuffer::<&mut [u8], 1, 1>::new(&mut storage);
+    share::scope(|handle| {
+        input.share_rw::<fake::Syscalls>(handle).unwrap();
+        assert_eq!(
+            kernel.take_syscall_log(),
+            [SyscallLogEntry::AllowRw {
+                driver_num: 1,
+                buffer_num: 1,
+                len: 3,
+            }]
+        );
+        assert!(fake::Syscalls::command(1, 2, 3, 0).is_success());
+    });
+    assert_eq!(input.get(), b"abc");
+    // The scope unallowed the buffer it shared.
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::Command {
+                driver_id: 1,
+                command_id: 2,
+                argument0: 3,
+                argument1: 0,
+            },
+            SyscallLogEntry::AllowRw {
+                driver_num: 1,
+                buffer_num: 1,
+                len: 0,
+            }
+        ]
+    );
+}
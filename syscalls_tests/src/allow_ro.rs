@@ -14,7 +14,7 @@ impl fake::SyscallDriver for TestDriver {
         DriverInfo::new(42)
     }
 
-    fn command(&self, _command_num: usize, _argument0: usize, _argument1: usize) -> CommandReturn {
+    fn command(&self, _command_num: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
         command_return::failure(ErrorCode::NoSupport)
     }
 
@@ -114,3 +114,48 @@ fn allow_ro() {
         }]
     );
 }
+
+// A driver that, when an Allow fails, returns the buffer it previously held
+// rather than the one it was passed.
+#[cfg(debug_assertions)]
+#[derive(Default)]
+struct SwappingDriver {
+    buffer: Cell<RoAllowBuffer>,
+}
+
+#[cfg(debug_assertions)]
+impl fake::SyscallDriver for SwappingDriver {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(42)
+    }
+
+    fn command(&self, _command_num: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
+        command_return::failure(ErrorCode::NoSupport)
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        match buffer_num {
+            0 => Ok(self.buffer.replace(buffer)),
+            _ => Err((self.buffer.replace(buffer), ErrorCode::NoSupport)),
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic = "Failed Allow returned a different buffer than it was passed"]
+fn allow_ro_wrong_buffer() {
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&Rc::new(SwappingDriver::default()));
+    let buffer1 = [1, 2, 3, 4];
+    let buffer2 = [5, 6];
+    share::scope::<(allow_ro::AllowRo<_, 42, 0>, allow_ro::AllowRo<_, 42, 1>), _, _>(|handle| {
+        let (allow_0, allow_1) = handle.split();
+        fake::Syscalls::allow_ro::<TestConfig, 42, 0>(allow_0, &buffer1).unwrap();
+        let _ = fake::Syscalls::allow_ro::<TestConfig, 42, 1>(allow_1, &buffer2);
+    });
+}
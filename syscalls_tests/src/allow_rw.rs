@@ -1,4 +1,4 @@
-use libtock_platform::{allow_rw, share, CommandReturn, ErrorCode, Syscalls};
+use libtock_platform::{allow_rw, share, CommandReturn, DefaultConfig, ErrorCode, Syscalls};
 use libtock_unittest::{command_return, fake, DriverInfo, RwAllowBuffer, SyscallLogEntry};
 use std::cell::Cell;
 use std::rc::Rc;
@@ -14,7 +14,7 @@ impl fake::SyscallDriver for TestDriver {
         DriverInfo::new(42)
     }
 
-    fn command(&self, _command_num: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+    fn command(&self, _command_num: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
         command_return::failure(ErrorCode::NoSupport)
     }
 
@@ -123,3 +123,59 @@ fn allow_rw() {
     // Verify the buffer write occurred.
     assert_eq!(buffer2, [5, 31]);
 }
+
+#[test]
+fn allow_rw_u16() {
+    let kernel = fake::Kernel::new();
+    let driver = Rc::new(TestDriver::default());
+    kernel.add_driver(&driver);
+    let mut buffer = [0x0102u16, 0x0304, 0x0506];
+    share::scope(|allow_rw| {
+        let result = fake::Syscalls::allow_rw_u16::<DefaultConfig, 42, 0>(allow_rw, &mut buffer);
+        assert_eq!(result, Ok(()));
+        // The kernel sees the buffer's length in bytes.
+        assert_eq!(
+            kernel.take_syscall_log(),
+            [SyscallLogEntry::AllowRw {
+                driver_num: 42,
+                buffer_num: 0,
+                len: 6,
+            }]
+        );
+
+        let mut shared = driver.buffer_0.take();
+        assert_eq!(shared.len(), 6);
+        shared[2..4].copy_from_slice(&0xabcdu16.to_ne_bytes());
+        driver.buffer_0.set(shared);
+    });
+
+    assert_eq!(buffer, [0x0102, 0xabcd, 0x0506]);
+}
+
+#[test]
+fn allow_rw_32() {
+    let kernel = fake::Kernel::new();
+    let driver = Rc::new(TestDriver::default());
+    kernel.add_driver(&driver);
+    let mut buffer = [0x01020304u32, 0x05060708];
+    share::scope(|allow_rw| {
+        let result = fake::Syscalls::allow_rw_32::<DefaultConfig, 42, 0>(allow_rw, &mut buffer);
+        assert_eq!(result, Ok(()));
+        // The kernel sees the buffer's length in bytes.
+        assert_eq!(
+            kernel.take_syscall_log(),
+            [SyscallLogEntry::AllowRw {
+                driver_num: 42,
+                buffer_num: 0,
+                len: 8,
+            }]
+        );
+
+        let mut shared = driver.buffer_0.take();
+        assert_eq!(shared.len(), 8);
+        shared[4..8].copy_from_slice(&0xdeadbeefu32.to_ne_bytes());
+        driver.buffer_0.set(shared);
+    });
+
+    assert_eq!(buffer, [0x01020304, 0xdeadbeef]);
+}
@@ -10,6 +10,9 @@
 //! Mixing types from the two `libtock_platform` instantiations in tests results
 //! in confusing error messages, so instead those tests live in this crate.
 
+#[cfg(test)]
+mod allow_buffer_tests;
+
 #[cfg(test)]
 mod allow_ro;
 
@@ -26,8 +29,14 @@ mod exit_on_drop;
 
 // TODO: Add Memop.
 
+#[cfg(test)]
+mod oom_tests;
+
 #[cfg(test)]
 mod subscribe_tests;
 
 #[cfg(test)]
 mod yield_tests;
+
+#[cfg(test)]
+mod yield_watchdog_tests;
@@ -0,0 +1,47 @@
+use core::alloc::{GlobalAlloc, Layout};
+use libtock_bump_alloc::BumpAllocator;
+use libtock_platform::{oom, Syscalls};
+use libtock_unittest::fake;
+
+fn restart_hook(layout: Layout) -> ! {
+    fake::Syscalls::exit_restart(layout.size() as u32)
+}
+
+#[cfg(not(miri))]
+#[test]
+fn hook_runs() {
+    let exit = libtock_unittest::exit_test("oom_tests::hook_runs", || {
+        let _kernel = fake::Kernel::new();
+        oom::set_hook(restart_hook);
+        oom::run_hook(Layout::from_size_align(24, 4).unwrap());
+    });
+    assert_eq!(exit, libtock_unittest::ExitCall::Restart(24));
+}
+
+// Running out of a bump allocator's arena reaches the hook, with the layout of
+// the allocation that failed. libtock_runtime's allocation error handler does
+// not build for the host, so this makes the run_hook call the handler starts
+// with.
+#[cfg(not(miri))]
+#[test]
+fn bump_alloc_exhausted() {
+    let exit = libtock_unittest::exit_test("oom_tests::bump_alloc_exhausted", || {
+        let _kernel = fake::Kernel::new();
+        oom::set_hook(restart_hook);
+        let allocator = BumpAllocator::<32>::new();
+        let layout = Layout::from_size_align(24, 4).unwrap();
+        assert!(!unsafe { allocator.alloc(layout) }.is_null());
+        if unsafe { allocator.alloc(layout) }.is_null() {
+            oom::run_hook(layout);
+        }
+    });
+    assert_eq!(exit, libtock_unittest::ExitCall::Restart(24));
+}
+
+#[test]
+fn no_hook() {
+    // Without a hook installed, run_hook returns so the caller can fall back
+    // to its default handling.
+    assert!(oom::hook().is_none());
+    oom::run_hook(Layout::from_size_align(24, 4).unwrap());
+}
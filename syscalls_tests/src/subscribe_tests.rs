@@ -1,5 +1,6 @@
 use libtock_platform::{
-    share, subscribe, CommandReturn, DefaultConfig, ErrorCode, Syscalls, YieldNoWaitReturn,
+    share, subscribe, CommandReturn, DefaultConfig, ErrorCode, PanicOnReentrance, Syscalls,
+    YieldNoWaitReturn,
 };
 use libtock_unittest::{command_return, fake, DriverInfo, DriverShareRef, SyscallLogEntry};
 use std::rc::Rc;
@@ -56,6 +57,22 @@ fn config() {
     });
 }
 
+#[test]
+#[should_panic = "Subscribe returned a non-null upcall (driver 0x1, subscribe 0)"]
+fn panic_on_reentrance() {
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&std::rc::Rc::new(MockDriver::default()));
+    let called = core::cell::Cell::new(false);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, PanicOnReentrance, 1, 0>(subscribe, &called),
+            Ok(())
+        );
+        // The second subscribe replaces the first, which the kernel returns.
+        let _ = fake::Syscalls::subscribe::<_, _, PanicOnReentrance, 1, 0>(subscribe, &called);
+    });
+}
+
 #[test]
 fn failed() {
     let _kernel = fake::Kernel::new();
@@ -73,7 +90,7 @@ fn success() {
     let driver = Rc::new(MockDriver::default());
     let kernel = fake::Kernel::new();
     kernel.add_driver(&driver);
-    let called = core::cell::Cell::new(None);
+    let called: core::cell::Cell<Option<(u32, u32, u32)>> = core::cell::Cell::new(None);
     share::scope(|subscribe| {
         assert_eq!(
             fake::Syscalls::subscribe::<_, _, DefaultConfig, 1, 0>(subscribe, &called),
@@ -131,3 +148,66 @@ fn unwinding_upcall() {
     });
     assert_eq!(exit, libtock_unittest::ExitCall::Terminate(0));
 }
+
+#[test]
+fn subscribe_return() {
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&Rc::new(MockDriver::default()));
+    let first = core::cell::Cell::new(false);
+    let second = core::cell::Cell::new(false);
+    share::scope(|subscribe| {
+        let previous =
+            fake::Syscalls::subscribe_return::<_, _, DefaultConfig, 1, 0>(subscribe, &first)
+                .unwrap();
+        assert!(previous.is_null());
+
+        // The second subscribe returns the first upcall.
+        let previous =
+            fake::Syscalls::subscribe_return::<_, _, DefaultConfig, 1, 0>(subscribe, &second)
+                .unwrap();
+        assert!(!previous.is_null());
+        assert_eq!(usize::from(previous.data), &first as *const _ as usize);
+    });
+}
+
+#[test]
+fn subscribe_owned() {
+    // Holds a subscription past the function that created it, as an
+    // event-driven state machine would.
+    struct Listener {
+        _subscription: libtock_platform::SubscriptionGuard<fake::Syscalls, 1, 0>,
+    }
+
+    impl Listener {
+        fn start(called: &'static core::cell::Cell<Option<(u32,)>>) -> Listener {
+            Listener {
+                _subscription: fake::Syscalls::subscribe_owned::<_, _, DefaultConfig, 1, 0>(called)
+                    .unwrap(),
+            }
+        }
+    }
+
+    let driver = Rc::new(MockDriver::default());
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&driver);
+    let called = Box::leak(Box::new(core::cell::Cell::new(None)));
+
+    let listener = Listener::start(called);
+    driver.share_ref.schedule_upcall(0, (2, 3, 4)).unwrap();
+    assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+    assert_eq!(called.get(), Some((2,)));
+    kernel.take_syscall_log();
+
+    // Dropping the listener unsubscribes the upcall.
+    drop(listener);
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [SyscallLogEntry::Subscribe {
+            driver_num: 1,
+            subscribe_num: 0
+        }]
+    );
+    driver.share_ref.schedule_upcall(0, (5, 6, 7)).unwrap();
+    assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    assert_eq!(called.get(), Some((2,)));
+}
@@ -28,6 +28,55 @@ fn no_wait_no_upcall() {
     assert_eq!(kernel.take_syscall_log(), [SyscallLogEntry::YieldNoWait]);
 }
 
+// Tests poll_once, which reports whether yield_no_wait ran an upcall.
+#[test]
+fn poll_once() {
+    use YieldNoWaitReturn::{NoUpcall, Upcall};
+    let kernel = fake::Kernel::new();
+    kernel.add_expected_syscall(ExpectedSyscall::YieldNoWait {
+        override_return: Some(Upcall),
+    });
+    kernel.add_expected_syscall(ExpectedSyscall::YieldNoWait {
+        override_return: Some(NoUpcall),
+    });
+    assert!(fake::Syscalls::poll_once());
+    assert!(!fake::Syscalls::poll_once());
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [SyscallLogEntry::YieldNoWait, SyscallLogEntry::YieldNoWait]
+    );
+}
+
+// Tests drain_upcalls with three upcalls queued.
+#[test]
+fn drain_upcalls() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig};
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&fake::Buttons::<1>::new());
+    let upcall: Cell<Option<(u32, u32, u32)>> = Cell::new(None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, 3, 0>(subscribe, &upcall).unwrap();
+        assert_eq!(fake::Syscalls::drain_upcalls(), 0);
+        for i in 0..3 {
+            kernel.schedule_upcall(3, 0, (i, 0, 0)).unwrap();
+        }
+        kernel.take_syscall_log();
+        assert_eq!(fake::Syscalls::drain_upcalls(), 3);
+        assert_eq!(upcall.get(), Some((2, 0, 0)));
+        // Three yields ran upcalls, and a fourth found none.
+        assert_eq!(kernel.take_syscall_log().len(), 4);
+
+        // The limit stops draining early, leaving the rest queued.
+        for i in 0..3 {
+            kernel.schedule_upcall(3, 0, (i, 0, 0)).unwrap();
+        }
+        assert_eq!(fake::Syscalls::drain_upcalls_at_most(2), 2);
+        assert_eq!(upcall.get(), Some((1, 0, 0)));
+        assert_eq!(fake::Syscalls::drain_upcalls(), 1);
+    });
+}
+
 // Tests yield_wait.
 #[test]
 fn wait() {
@@ -36,3 +85,49 @@ fn wait() {
     fake::Syscalls::yield_wait();
     assert_eq!(kernel.take_syscall_log(), [SyscallLogEntry::YieldWait]);
 }
+
+// Tests yield_wait_for with a predicate that becomes true after 3 yields.
+#[test]
+fn wait_for() {
+    let kernel = fake::Kernel::new();
+    for _ in 0..3 {
+        kernel.add_expected_syscall(ExpectedSyscall::YieldWait { skip_upcall: true });
+    }
+    let checks = core::cell::Cell::new(0);
+    fake::Syscalls::yield_wait_for(|| {
+        checks.set(checks.get() + 1);
+        checks.get() > 3
+    });
+    assert_eq!(checks.get(), 4);
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [
+            SyscallLogEntry::YieldWait,
+            SyscallLogEntry::YieldWait,
+            SyscallLogEntry::YieldWait
+        ]
+    );
+
+    // A predicate that is already true does not yield.
+    fake::Syscalls::yield_wait_for(|| true);
+    assert_eq!(kernel.take_syscall_log(), []);
+}
+
+// Tests yield_wait_for_result with a result that is available after 2 yields.
+#[test]
+fn wait_for_result() {
+    let kernel = fake::Kernel::new();
+    for _ in 0..2 {
+        kernel.add_expected_syscall(ExpectedSyscall::YieldWait { skip_upcall: true });
+    }
+    let checks = core::cell::Cell::new(0);
+    let result = fake::Syscalls::yield_wait_for_result(|| {
+        checks.set(checks.get() + 1);
+        (checks.get() == 3).then_some("done")
+    });
+    assert_eq!(result, "done");
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [SyscallLogEntry::YieldWait, SyscallLogEntry::YieldWait]
+    );
+}
@@ -0,0 +1,28 @@
+use core::sync::atomic::{AtomicU32, Ordering};
+use libtock_platform::{yield_watchdog, Syscalls};
+use libtock_unittest::{fake, ExpectedSyscall};
+
+// The number of Yield-Waits that have returned.
+static YIELDS: AtomicU32 = AtomicU32::new(0);
+
+fn terminate_hook() {
+    fake::Syscalls::exit_terminate(YIELDS.load(Ordering::Relaxed))
+}
+
+#[cfg(not(miri))]
+#[test]
+fn hook_runs() {
+    let exit = libtock_unittest::exit_test("yield_watchdog_tests::hook_runs", || {
+        let kernel = fake::Kernel::new();
+        yield_watchdog::set_watchdog(3, terminate_hook);
+        for _ in 0..5 {
+            kernel.add_expected_syscall(ExpectedSyscall::YieldWait { skip_upcall: true });
+        }
+        loop {
+            fake::Syscalls::yield_wait();
+            YIELDS.fetch_add(1, Ordering::Relaxed);
+        }
+    });
+    // The hook runs at the end of the third Yield-Wait, before yield_wait returns.
+    assert_eq!(exit, libtock_unittest::ExitCall::Terminate(2));
+}
@@ -33,7 +33,7 @@ impl DriverInfo {
     ///     fn info(&self) -> DriverInfo {
     ///         DriverInfo::new(3).upcall_count(2)
     ///     }
-    ///     fn command(&self, _: u32, _: u32, _: u32) -> CommandReturn {
+    ///     fn command(&self, _: u32, _: usize, _: usize) -> CommandReturn {
     ///         unimplemented!("Example code");
     ///     }
     /// }
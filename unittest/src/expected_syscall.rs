@@ -70,7 +70,18 @@ pub enum ExpectedSyscall {
         // invoked and the provided error will be returned instead.
         return_error: Option<libtock_platform::ErrorCode>,
     },
-    // TODO: Add Memop.
+
+    // -------------------------------------------------------------------------
+    // Memop
+    // -------------------------------------------------------------------------
+    Memop {
+        memop_num: u32,
+        argument0: usize,
+
+        // If not None, the memop will return the given result instead of
+        // succeeding with the value set by `fake::Kernel::set_memop_value`.
+        override_return: Option<Result<usize, libtock_platform::ErrorCode>>,
+    },
     // TODO: Add Exit.
 }
 
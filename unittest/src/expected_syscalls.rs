@@ -0,0 +1,100 @@
+use crate::SyscallLogEntry;
+
+/// `ExpectedSyscalls` describes the exact sequence of system calls a unit test
+/// expects code under test to make. It is created by `fake::Kernel::expect`,
+/// built up by chaining calls to its methods (one per expected system call, in
+/// order), and compared against `fake::Kernel`'s system call log by `check`.
+///
+/// # Example
+/// ```
+/// use libtock_platform::Syscalls;
+/// use libtock_unittest::fake;
+///
+/// let kernel = fake::Kernel::new();
+/// let _ = fake::Syscalls::command(2, 1, 3, 0);
+/// fake::Syscalls::yield_no_wait();
+/// kernel.expect().command(2, 1, 3, 0).yield_no_wait().check();
+/// ```
+#[must_use = "the expected system calls are only compared with the log by check()"]
+pub struct ExpectedSyscalls<'k> {
+    kernel: &'k crate::fake::Kernel,
+    syscalls: Vec<SyscallLogEntry>,
+}
+
+impl<'k> ExpectedSyscalls<'k> {
+    pub(crate) fn new(kernel: &'k crate::fake::Kernel) -> ExpectedSyscalls<'k> {
+        ExpectedSyscalls {
+            kernel,
+            syscalls: Vec::new(),
+        }
+    }
+
+    pub fn yield_no_wait(self) -> Self {
+        self.push(SyscallLogEntry::YieldNoWait)
+    }
+
+    pub fn yield_wait(self) -> Self {
+        self.push(SyscallLogEntry::YieldWait)
+    }
+
+    pub fn subscribe(self, driver_num: u32, subscribe_num: u32) -> Self {
+        self.push(SyscallLogEntry::Subscribe {
+            driver_num,
+            subscribe_num,
+        })
+    }
+
+    pub fn command(
+        self,
+        driver_id: u32,
+        command_id: u32,
+        argument0: usize,
+        argument1: usize,
+    ) -> Self {
+        self.push(SyscallLogEntry::Command {
+            driver_id,
+            command_id,
+            argument0,
+            argument1,
+        })
+    }
+
+    pub fn allow_ro(self, driver_num: u32, buffer_num: u32, len: usize) -> Self {
+        self.push(SyscallLogEntry::AllowRo {
+            driver_num,
+            buffer_num,
+            len,
+        })
+    }
+
+    pub fn allow_rw(self, driver_num: u32, buffer_num: u32, len: usize) -> Self {
+        self.push(SyscallLogEntry::AllowRw {
+            driver_num,
+            buffer_num,
+            len,
+        })
+    }
+
+    pub fn memop(self, memop_num: u32, argument0: usize) -> Self {
+        self.push(SyscallLogEntry::Memop {
+            memop_num,
+            argument0,
+        })
+    }
+
+    /// Takes the kernel's system call log (as `fake::Kernel::take_syscall_log`
+    /// does) and panics unless it exactly matches the expected system calls.
+    #[track_caller]
+    pub fn check(self) {
+        let log = self.kernel.take_syscall_log();
+        assert_eq!(
+            log, self.syscalls,
+            "The system calls made (left) do not match the expected system calls (right)."
+        );
+    }
+
+    fn push(mut self, syscall: SyscallLogEntry) -> Self {
+        self.syscalls.push(syscall);
+        self
+    }
+}
@@ -0,0 +1,211 @@
+//! Fake implementation of the ADC API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/00005_adc.md
+//!
+//! Like the real API, `Adc` takes samples from a set of channels. Tests provide
+//! the sample values by queueing them with `push_sample`. A sample request is
+//! answered with the next queued value; if the queue is empty, the request
+//! stays outstanding (and further requests fail with `BUSY`) until a value is
+//! pushed.
+//!
+//! Continuous buffered sampling is also supported. While it is running, each
+//! pushed value is written to the shared buffer, and an upcall is scheduled
+//! each time the buffer fills up.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::{DriverInfo, DriverShareRef, RwAllowBuffer};
+
+pub struct Adc<const NUM_CHANNELS: usize> {
+    samples: RefCell<VecDeque<u16>>,
+    // The channel of the outstanding single-sample request, if any.
+    pending: Cell<Option<u32>>,
+    // The (channel, frequency) of the continuous sampling run, if any.
+    continuous: Cell<Option<(u32, u32)>>,
+    // The shared sample buffer, and how many samples have been written to it
+    // since it was last reported full.
+    buffer: RefCell<RwAllowBuffer>,
+    buffered_samples: Cell<usize>,
+    resolution_bits: Cell<u32>,
+    reference_voltage_mv: Cell<u32>,
+    share_ref: DriverShareRef,
+}
+
+impl<const NUM_CHANNELS: usize> Adc<NUM_CHANNELS> {
+    pub fn new() -> std::rc::Rc<Adc<NUM_CHANNELS>> {
+        std::rc::Rc::new(Adc {
+            samples: Default::default(),
+            pending: Cell::new(None),
+            continuous: Cell::new(None),
+            buffer: Default::default(),
+            buffered_samples: Cell::new(0),
+            resolution_bits: Cell::new(12),
+            reference_voltage_mv: Cell::new(3300),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Queues a sample value to be returned by a future sample request. If a
+    /// request is outstanding, it is completed immediately. During continuous
+    /// sampling, the value is written to the shared buffer instead.
+    pub fn push_sample(&self, value: u16) {
+        if self.continuous.get().is_some() {
+            self.write_buffered(value);
+            return;
+        }
+        self.samples.borrow_mut().push_back(value);
+        self.deliver();
+    }
+
+    /// Returns true if a sample request is waiting for a value, or continuous
+    /// sampling is running.
+    pub fn is_busy(&self) -> bool {
+        self.pending.get().is_some() || self.continuous.get().is_some()
+    }
+
+    /// Returns the (channel, frequency) of the continuous sampling run, if one
+    /// is in progress.
+    pub fn continuous_sampling(&self) -> Option<(u32, u32)> {
+        self.continuous.get()
+    }
+
+    pub fn set_resolution_bits(&self, bits: u32) {
+        self.resolution_bits.set(bits);
+    }
+
+    pub fn set_reference_voltage_mv(&self, millivolts: u32) {
+        self.reference_voltage_mv.set(millivolts);
+    }
+
+    fn deliver(&self) {
+        let channel = match self.pending.get() {
+            None => return,
+            Some(channel) => channel,
+        };
+        if let Some(value) = self.samples.borrow_mut().pop_front() {
+            self.pending.set(None);
+            self.share_ref
+                .schedule_upcall(
+                    SUBSCRIBE_SAMPLE,
+                    (MODE_SINGLE_SAMPLE, channel, value as u32),
+                )
+                .expect("Unable to schedule upcall");
+        }
+    }
+
+    fn write_buffered(&self, value: u16) {
+        let mut buffer = self.buffer.borrow_mut();
+        let index = self.buffered_samples.get();
+        buffer[2 * index..2 * index + 2].copy_from_slice(&value.to_ne_bytes());
+        self.buffered_samples.set(index + 1);
+        if index + 1 == buffer.len() / 2 {
+            self.report_buffered();
+        }
+    }
+
+    fn report_buffered(&self) {
+        self.share_ref
+            .schedule_upcall(
+                SUBSCRIBE_SAMPLE,
+                (
+                    MODE_CONTINUOUS_BUFFERED,
+                    self.buffered_samples.replace(0) as u32,
+                    0,
+                ),
+            )
+            .expect("Unable to schedule upcall");
+    }
+}
+
+impl<const NUM_CHANNELS: usize> crate::fake::SyscallDriver for Adc<NUM_CHANNELS> {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_BUFFER {
+            Ok(self.buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, argument1: usize) -> CommandReturn {
+        match command_num {
+            COUNT => crate::command_return::success_u32(NUM_CHANNELS as u32),
+            SINGLE_SAMPLE => {
+                if argument0 >= NUM_CHANNELS {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                if self.is_busy() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                self.pending.set(Some(argument0 as u32));
+                self.deliver();
+                crate::command_return::success()
+            }
+            CONTINUOUS_BUFFERED_SAMPLE => {
+                if argument0 >= NUM_CHANNELS {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                if self.is_busy() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                if self.buffer.borrow().len() < 2 {
+                    return crate::command_return::failure(ErrorCode::NoMem);
+                }
+                self.continuous
+                    .set(Some((argument0 as u32, argument1 as u32)));
+                self.buffered_samples.set(0);
+                crate::command_return::success()
+            }
+            STOP => {
+                if self.continuous.take().is_some() {
+                    // Report whatever was sampled since the last full buffer.
+                    self.report_buffered();
+                } else if self.pending.take().is_none() {
+                    return crate::command_return::failure(ErrorCode::Off);
+                }
+                crate::command_return::success()
+            }
+            RESOLUTION_BITS => crate::command_return::success_u32(self.resolution_bits.get()),
+            REFERENCE_VOLTAGE => {
+                crate::command_return::success_u32(self.reference_voltage_mv.get())
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x5;
+
+// Command numbers
+const COUNT: u32 = 0;
+const SINGLE_SAMPLE: u32 = 1;
+const CONTINUOUS_BUFFERED_SAMPLE: u32 = 4;
+const STOP: u32 = 5;
+const RESOLUTION_BITS: u32 = 101;
+const REFERENCE_VOLTAGE: u32 = 102;
+
+const SUBSCRIBE_SAMPLE: u32 = 0;
+const ALLOW_BUFFER: u32 = 0;
+
+// The first upcall argument identifies the kind of sampling that completed.
+const MODE_SINGLE_SAMPLE: u32 = 0;
+const MODE_CONTINUOUS_BUFFERED: u32 = 3;
@@ -0,0 +1,148 @@
+use crate::fake;
+use fake::adc::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let adc = Adc::<2>::new();
+
+    assert_eq!(adc.command(COUNT, 0, 0).get_success_u32(), Some(2));
+    assert_eq!(
+        adc.command(RESOLUTION_BITS, 0, 0).get_success_u32(),
+        Some(12)
+    );
+    assert_eq!(
+        adc.command(REFERENCE_VOLTAGE, 0, 0).get_success_u32(),
+        Some(3300)
+    );
+    adc.set_resolution_bits(10);
+    adc.set_reference_voltage_mv(1800);
+    assert_eq!(
+        adc.command(RESOLUTION_BITS, 0, 0).get_success_u32(),
+        Some(10)
+    );
+    assert_eq!(
+        adc.command(REFERENCE_VOLTAGE, 0, 0).get_success_u32(),
+        Some(1800)
+    );
+
+    assert_eq!(
+        adc.command(SINGLE_SAMPLE, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(adc.command(SINGLE_SAMPLE, 1, 0).is_success());
+    assert!(adc.is_busy());
+    assert_eq!(
+        adc.command(SINGLE_SAMPLE, 0, 0).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    adc.push_sample(7);
+    assert!(!adc.is_busy());
+
+    // Continuous sampling needs a buffer.
+    assert_eq!(
+        adc.command(CONTINUOUS_BUFFERED_SAMPLE, 0, 1000)
+            .get_failure(),
+        Some(ErrorCode::NoMem)
+    );
+    assert_eq!(adc.command(STOP, 0, 0).get_failure(), Some(ErrorCode::Off));
+}
+
+// Integration test that verifies Adc works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let adc = Adc::<2>::new();
+    kernel.add_driver(&adc);
+
+    assert_eq!(
+        fake::Syscalls::command(DRIVER_NUM, COUNT, 0, 0).get_success_u32(),
+        Some(2)
+    );
+
+    adc.push_sample(100);
+    adc.push_sample(200);
+    let sample: Cell<Option<(u32, u32, u32)>> = Cell::new(None);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_SAMPLE>(
+                subscribe, &sample
+            ),
+            Ok(())
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, SINGLE_SAMPLE, 1, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(sample.get(), Some((MODE_SINGLE_SAMPLE, 1, 100)));
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, SINGLE_SAMPLE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(sample.get(), Some((MODE_SINGLE_SAMPLE, 0, 200)));
+
+        // With no queued samples, the request stays outstanding until one
+        // is pushed.
+        assert!(fake::Syscalls::command(DRIVER_NUM, SINGLE_SAMPLE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        adc.push_sample(300);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(sample.get(), Some((MODE_SINGLE_SAMPLE, 0, 300)));
+    });
+}
+
+#[test]
+fn continuous_buffered() {
+    use core::cell::Cell;
+    use libtock_platform::{share, AllowRw, DefaultConfig, Subscribe, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let adc = Adc::<2>::new();
+    kernel.add_driver(&adc);
+
+    let mut buffer = [0u16; 2];
+    let upcall: Cell<Option<(u32, u32, u32)>> = Cell::new(None);
+    share::scope::<
+        (
+            AllowRw<_, DRIVER_NUM, ALLOW_BUFFER>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_SAMPLE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_rw_u16::<DefaultConfig, DRIVER_NUM, ALLOW_BUFFER>(
+            allow_rw,
+            &mut buffer,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_SAMPLE>(
+            subscribe, &upcall,
+        )
+        .unwrap();
+        assert!(
+            fake::Syscalls::command(DRIVER_NUM, CONTINUOUS_BUFFERED_SAMPLE, 1, 500).is_success()
+        );
+        assert_eq!(adc.continuous_sampling(), Some((1, 500)));
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, SINGLE_SAMPLE, 0, 0).get_failure(),
+            Some(ErrorCode::Busy)
+        );
+
+        adc.push_sample(10);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        adc.push_sample(20);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(upcall.get(), Some((MODE_CONTINUOUS_BUFFERED, 2, 0)));
+
+        // Sampling wraps around to the start of the buffer, and stopping
+        // reports the partially-filled buffer.
+        adc.push_sample(30);
+        assert!(fake::Syscalls::command(DRIVER_NUM, STOP, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(upcall.get(), Some((MODE_CONTINUOUS_BUFFERED, 1, 0)));
+        assert!(!adc.is_busy());
+    });
+    assert_eq!(buffer, [30, 20]);
+}
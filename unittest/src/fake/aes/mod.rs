@@ -0,0 +1,152 @@
+//! Fake implementation of the AES API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/40006_aes.md
+//!
+//! `Aes` computes AES-128-CTR in software, so tests can check results against
+//! known test vectors. It does not support the other modes. The counter block
+//! is the IV, incremented as a big-endian integer after each block.
+//! Operations complete immediately.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+mod aes128;
+
+use aes128::{encrypt_block, BLOCK_LEN, KEY_LEN};
+
+pub struct Aes {
+    // Whether SET_ALGORITHM has selected CTR mode.
+    configured: Cell<bool>,
+    key: RefCell<RoAllowBuffer>,
+    iv: RefCell<RoAllowBuffer>,
+    source: RefCell<RoAllowBuffer>,
+    dest: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Aes {
+    pub fn new() -> std::rc::Rc<Aes> {
+        std::rc::Rc::new(Aes {
+            configured: Cell::new(false),
+            key: Default::default(),
+            iv: Default::default(),
+            source: Default::default(),
+            dest: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    // Encrypts (or, equivalently, decrypts) the source buffer into the
+    // destination buffer in CTR mode, returning the number of bytes written.
+    fn crypt(&self) -> Result<u32, ErrorCode> {
+        let key: [u8; KEY_LEN] = self.key.borrow()[..]
+            .try_into()
+            .map_err(|_| ErrorCode::Size)?;
+        let mut counter: [u8; BLOCK_LEN] = self.iv.borrow()[..]
+            .try_into()
+            .map_err(|_| ErrorCode::Size)?;
+        let source = self.source.borrow();
+        let mut dest = self.dest.borrow_mut();
+        if dest.len() < source.len() {
+            return Err(ErrorCode::Size);
+        }
+        for (input, output) in source.chunks(BLOCK_LEN).zip(dest.chunks_mut(BLOCK_LEN)) {
+            let mut keystream = counter;
+            encrypt_block(&key, &mut keystream);
+            for (i, byte) in input.iter().enumerate() {
+                output[i] = byte ^ keystream[i];
+            }
+            counter = (u128::from_be_bytes(counter).wrapping_add(1)).to_be_bytes();
+        }
+        Ok(source.len() as u32)
+    }
+
+    fn report(&self, result: Result<u32, ErrorCode>) {
+        let args = match result {
+            Ok(len) => (0, len, 0),
+            Err(error) => (error as u32, 0, 0),
+        };
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_DONE, args)
+            .expect("Unable to schedule upcall");
+    }
+}
+
+impl crate::fake::SyscallDriver for Aes {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        match buffer_num {
+            ALLOW_KEY => Ok(self.key.replace(buffer)),
+            ALLOW_IV => Ok(self.iv.replace(buffer)),
+            ALLOW_SOURCE => Ok(self.source.replace(buffer)),
+            _ => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_DEST {
+            Ok(self.dest.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => {}
+            SET_ALGORITHM => {
+                if argument0 != AES128_CTR {
+                    return crate::command_return::failure(ErrorCode::NoSupport);
+                }
+                self.configured.set(true);
+            }
+            CRYPT => {
+                if !self.configured.get() {
+                    return crate::command_return::failure(ErrorCode::Reserve);
+                }
+                self.report(self.crypt());
+            }
+            _ => return crate::command_return::failure(ErrorCode::NoSupport),
+        }
+        crate::command_return::success()
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40006;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const SET_ALGORITHM: u32 = 1;
+const CRYPT: u32 = 2;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_KEY: u32 = 0;
+const ALLOW_IV: u32 = 1;
+const ALLOW_SOURCE: u32 = 2;
+const ALLOW_DEST: u32 = 0;
+
+// Algorithm IDs
+const AES128_CTR: usize = 0;
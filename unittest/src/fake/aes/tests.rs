@@ -0,0 +1,112 @@
+use crate::fake;
+use fake::aes::*;
+
+// The key, initial counter block, plaintext and ciphertext of the
+// CTR-AES128.Encrypt test vector in NIST SP 800-38A, section F.5.1.
+const CTR_KEY: [u8; 16] = [
+    0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf, 0x4f, 0x3c,
+];
+const CTR_COUNTER: [u8; 16] = [
+    0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd, 0xfe, 0xff,
+];
+const CTR_PLAINTEXT: [u8; 32] = [
+    0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93, 0x17, 0x2a,
+    0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac, 0x45, 0xaf, 0x8e, 0x51,
+];
+const CTR_CIPHERTEXT: [u8; 32] = [
+    0x87, 0x4d, 0x61, 0x91, 0xb6, 0x20, 0xe3, 0x26, 0x1b, 0xef, 0x68, 0x64, 0x99, 0x0d, 0xb6, 0xce,
+    0x98, 0x06, 0xf6, 0x6b, 0x79, 0x70, 0xfd, 0xff, 0x86, 0x17, 0x18, 0x7b, 0xb9, 0xff, 0xfd, 0xff,
+];
+
+// Test vector from FIPS 197, appendix C.1.
+#[test]
+fn aes128_vector() {
+    let key = [
+        0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d, 0x0e,
+        0x0f,
+    ];
+    let mut block = [
+        0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee,
+        0xff,
+    ];
+    encrypt_block(&key, &mut block);
+    assert_eq!(
+        block,
+        [
+            0x69, 0xc4, 0xe0, 0xd8, 0x6a, 0x7b, 0x04, 0x30, 0xd8, 0xcd, 0xb7, 0x80, 0x70, 0xb4,
+            0xc5, 0x5a,
+        ]
+    );
+}
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let aes = Aes::new();
+
+    assert!(aes.command(EXISTS, 0, 0).is_success());
+    // Crypting before an algorithm is selected fails.
+    assert_eq!(
+        aes.command(CRYPT, 0, 0).get_failure(),
+        Some(ErrorCode::Reserve)
+    );
+    assert_eq!(
+        aes.command(SET_ALGORITHM, 1, 1).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+    assert!(aes.command(SET_ALGORITHM, AES128_CTR, 1).is_success());
+}
+
+// Integration test that verifies Aes works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    let kernel = fake::Kernel::new();
+    let aes = Aes::new();
+    kernel.add_driver(&aes);
+
+    let done: Cell<Option<(u32, u32)>> = Cell::new(None);
+    // Leave out the end of the last block, to check partial blocks.
+    let mut output = [0; 30];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_KEY>,
+            AllowRo<_, DRIVER_NUM, ALLOW_IV>,
+            AllowRo<_, DRIVER_NUM, ALLOW_SOURCE>,
+            AllowRw<_, DRIVER_NUM, ALLOW_DEST>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_key, allow_iv, allow_source, allow_dest, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_KEY>(allow_key, &CTR_KEY)
+            .unwrap();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_IV>(allow_iv, &CTR_COUNTER)
+            .unwrap();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_SOURCE>(
+            allow_source,
+            &CTR_PLAINTEXT[..30],
+        )
+        .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_DEST>(allow_dest, &mut output)
+            .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, SET_ALGORITHM, AES128_CTR, 1).is_success());
+        assert!(fake::Syscalls::command(DRIVER_NUM, CRYPT, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 30)));
+    });
+    assert_eq!(output, CTR_CIPHERTEXT[..30]);
+}
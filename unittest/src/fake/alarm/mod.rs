@@ -1,9 +1,16 @@
 //! Fake implementation of the Alarm API.
 //!
-//! Supports frequency and set_relative.
-//! Will schedule the upcall immediately.
+//! Supports frequency, time, set_relative, set_absolute and stop.
+//!
+//! An `Alarm` created with `new` schedules the upcall immediately, moving the
+//! tick counter forward to the deadline. One created with
+//! `with_virtual_clock` instead keeps the counter still until the test calls
+//! `advance`, which fires the alarms that come due in deadline order. Unlike
+//! the kernel, which keeps one alarm per process, a virtual clock keeps every
+//! alarm that is set until it fires or `stop` is called, so tests can arm
+//! several at once.
 
-use core::cell::Cell;
+use core::cell::{Cell, RefCell};
 use core::num::Wrapping;
 use libtock_platform::{CommandReturn, ErrorCode};
 
@@ -12,6 +19,9 @@ use crate::{DriverInfo, DriverShareRef};
 pub struct Alarm {
     frequency_hz: u32,
     now: Cell<Wrapping<u32>>,
+    virtual_clock: bool,
+    // The deadlines of the alarms waiting for advance.
+    pending: RefCell<Vec<Wrapping<u32>>>,
     share_ref: DriverShareRef,
 }
 
@@ -20,9 +30,52 @@ impl Alarm {
         std::rc::Rc::new(Alarm {
             frequency_hz,
             now: Cell::new(Wrapping(0)),
+            virtual_clock: false,
+            pending: Default::default(),
             share_ref: Default::default(),
         })
     }
+
+    /// Creates an `Alarm` whose tick counter only moves when `advance` (or
+    /// `set_now`) is called, and whose alarms only fire once it reaches them.
+    pub fn with_virtual_clock(frequency_hz: u32) -> std::rc::Rc<Alarm> {
+        std::rc::Rc::new(Alarm {
+            frequency_hz,
+            now: Cell::new(Wrapping(0)),
+            virtual_clock: true,
+            pending: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Moves the tick counter forward by `ticks`, firing the alarms whose
+    /// deadlines it reaches, earliest first.
+    pub fn advance(&self, ticks: u32) {
+        let start = self.now.get();
+        let mut pending = self.pending.take();
+        pending.sort_by_key(|&deadline| deadline - start);
+        let due = pending
+            .iter()
+            .take_while(|&&deadline| (deadline - start).0 <= ticks)
+            .count();
+        for deadline in pending.drain(..due) {
+            self.fire(deadline);
+        }
+        self.pending.replace(pending);
+        self.now.set(start + Wrapping(ticks));
+    }
+
+    /// Sets the current value of the tick counter, for example to test
+    /// behavior near the point where it wraps around.
+    pub fn set_now(&self, ticks: u32) {
+        self.now.set(Wrapping(ticks));
+    }
+
+    fn fire(&self, deadline: Wrapping<u32>) {
+        self.share_ref
+            .schedule_upcall(subscribe::CALLBACK, (deadline.0, 0, 0))
+            .expect("schedule_upcall failed");
+    }
 }
 
 impl crate::fake::SyscallDriver for Alarm {
@@ -34,22 +87,48 @@ impl crate::fake::SyscallDriver for Alarm {
         self.share_ref.replace(share_ref);
     }
 
-    fn command(&self, command_number: u32, argument0: usize, _argument1: usize) -> CommandReturn {
+    fn command(&self, command_number: u32, argument0: usize, argument1: usize) -> CommandReturn {
         match command_number {
             command::FREQUENCY => crate::command_return::success_u32(self.frequency_hz),
+            command::TIME => crate::command_return::success_u32(self.now.get().0),
             command::SET_RELATIVE => {
-                // We're not actually sleeping, just ticking the timer.
-                // The semantics of sleeping aren't clear,
-                // so we're assuming that all future times are equal,
-                // and waking immediately.
                 let relative = argument0 as u32;
                 let wake = self.now.get() + Wrapping(relative);
-                self.share_ref
-                    .schedule_upcall(subscribe::CALLBACK, (wake.0, 0, 0))
-                    .expect("schedule_upcall failed");
-                self.now.set(wake);
+                if self.virtual_clock && relative != 0 {
+                    self.pending.borrow_mut().push(wake);
+                } else {
+                    // We're not actually sleeping, just ticking the timer.
+                    // The semantics of sleeping aren't clear,
+                    // so we're assuming that all future times are equal,
+                    // and waking immediately.
+                    self.fire(wake);
+                    self.now.set(wake);
+                }
+                crate::command_return::success_u32(wake.0)
+            }
+            command::SET_ABSOLUTE => {
+                let reference = Wrapping(argument0 as u32);
+                let dt = Wrapping(argument1 as u32);
+                let wake = reference + dt;
+                // As with the kernel, a deadline that has already passed fires
+                // without waiting for the counter to wrap around to it.
+                let in_future = self.now.get() - reference < dt;
+                if self.virtual_clock && in_future {
+                    self.pending.borrow_mut().push(wake);
+                } else {
+                    self.fire(wake);
+                    if in_future {
+                        self.now.set(wake);
+                    }
+                }
                 crate::command_return::success_u32(wake.0)
             }
+            // Without a virtual clock, alarms fire as soon as they are set, so
+            // there is never anything left to cancel.
+            command::STOP => {
+                self.pending.borrow_mut().clear();
+                crate::command_return::success()
+            }
             _ => crate::command_return::failure(ErrorCode::NoSupport),
         }
     }
@@ -11,4 +11,115 @@ fn command() {
         alarm.command(command::FREQUENCY, 1, 2).get_success_u32(),
         Some(10)
     );
+    assert!(alarm.command(command::STOP, 0, 0).is_success());
+}
+
+#[test]
+fn time() {
+    use fake::SyscallDriver;
+    let kernel = fake::Kernel::new();
+    let alarm = Alarm::new(10);
+    kernel.add_driver(&alarm);
+
+    assert_eq!(
+        alarm.command(command::TIME, 0, 0).get_success_u32(),
+        Some(0)
+    );
+    assert_eq!(
+        alarm.command(command::SET_RELATIVE, 7, 0).get_success_u32(),
+        Some(7)
+    );
+    assert_eq!(
+        alarm.command(command::TIME, 0, 0).get_success_u32(),
+        Some(7)
+    );
+}
+
+#[test]
+fn set_absolute() {
+    use fake::SyscallDriver;
+    let kernel = fake::Kernel::new();
+    let alarm = Alarm::new(10);
+    kernel.add_driver(&alarm);
+
+    alarm.set_now(u32::MAX - 1);
+    // The deadline wraps around past 0.
+    assert_eq!(
+        alarm
+            .command(command::SET_ABSOLUTE, u32::MAX as usize - 2, 5)
+            .get_success_u32(),
+        Some(2)
+    );
+    assert_eq!(
+        alarm.command(command::TIME, 0, 0).get_success_u32(),
+        Some(2)
+    );
+    // A deadline that has already passed doesn't move the counter backwards.
+    assert_eq!(
+        alarm.command(command::SET_ABSOLUTE, 0, 1).get_success_u32(),
+        Some(1)
+    );
+    assert_eq!(
+        alarm.command(command::TIME, 0, 0).get_success_u32(),
+        Some(2)
+    );
+}
+
+// Arms two alarms on a virtual clock and checks that they fire in deadline
+// order once the clock reaches them.
+#[test]
+fn advance() {
+    use core::cell::RefCell;
+    use libtock_platform::subscribe::{AnyId, Upcall};
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    struct Fired(RefCell<Vec<usize>>);
+    impl Upcall<AnyId> for Fired {
+        fn upcall(&self, now: usize, _: usize, _: usize) {
+            self.0.borrow_mut().push(now);
+        }
+    }
+
+    let kernel = fake::Kernel::new();
+    let alarm = Alarm::with_virtual_clock(10);
+    kernel.add_driver(&alarm);
+
+    let fired = Fired(RefCell::new(Vec::new()));
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, { subscribe::CALLBACK }>(
+            subscribe, &fired,
+        )
+        .unwrap();
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, command::SET_RELATIVE, 200, 0).get_success_u32(),
+            Some(200)
+        );
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, command::SET_ABSOLUTE, 0, 100).get_success_u32(),
+            Some(100)
+        );
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        alarm.advance(99);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, command::TIME, 0, 0).get_success_u32(),
+            Some(99)
+        );
+
+        alarm.advance(150);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert_eq!(*fired.0.borrow(), [100, 200]);
+
+        // Stopping cancels the armed alarms.
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, command::SET_RELATIVE, 10, 0).get_success_u32(),
+            Some(259)
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, command::STOP, 0, 0).is_success());
+        alarm.advance(20);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
 }
@@ -0,0 +1,68 @@
+//! Fake implementation of the ambient light API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/60002_luminance.md
+//!
+//! Like the real API, `AmbientLight` reports light intensity in lux. Tests set
+//! the value to report with `set_value`; each read request is answered
+//! immediately with that value.
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef};
+
+pub struct AmbientLight {
+    value: Cell<u32>,
+    share_ref: DriverShareRef,
+}
+
+impl AmbientLight {
+    pub fn new() -> std::rc::Rc<AmbientLight> {
+        std::rc::Rc::new(AmbientLight {
+            value: Cell::new(0),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Sets the intensity, in lux, returned by future reads.
+    pub fn set_value(&self, lux: u32) {
+        self.value.set(lux);
+    }
+}
+
+impl crate::fake::SyscallDriver for AmbientLight {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn command(&self, command_num: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            READ_INTENSITY => {
+                self.share_ref
+                    .schedule_upcall(SUBSCRIBE_READING, (self.value.get(), 0, 0))
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60002;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const READ_INTENSITY: u32 = 1;
+
+const SUBSCRIBE_READING: u32 = 0;
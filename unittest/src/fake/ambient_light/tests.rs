@@ -0,0 +1,40 @@
+use crate::fake;
+use fake::ambient_light::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let ambient_light = AmbientLight::new();
+
+    assert!(ambient_light.command(EXISTS, 0, 0).is_success());
+    assert!(ambient_light.command(READ_INTENSITY, 0, 0).is_success());
+    assert!(ambient_light.command(2, 0, 0).is_failure());
+}
+
+// Integration test that verifies AmbientLight works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let ambient_light = AmbientLight::new();
+    kernel.add_driver(&ambient_light);
+
+    assert!(fake::Syscalls::command(DRIVER_NUM, EXISTS, 0, 0).is_success());
+
+    let reading: Cell<Option<(u32,)>> = Cell::new(None);
+    ambient_light.set_value(850);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_READING>(
+                subscribe, &reading
+            ),
+            Ok(())
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_INTENSITY, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((850,)));
+    });
+}
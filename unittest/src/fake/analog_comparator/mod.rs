@@ -0,0 +1,97 @@
+//! Fake implementation of the analog comparator API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/00007_analog_comparator.md
+//!
+//! Tests set each channel's comparison result with `set_comparison`. If
+//! interrupts are enabled on a channel, every change to its result fires an
+//! interrupt.
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef};
+
+pub struct AnalogComparator {
+    comparisons: Vec<Cell<bool>>,
+    interrupts: Vec<Cell<bool>>,
+    share_ref: DriverShareRef,
+}
+
+impl AnalogComparator {
+    /// Creates a comparator with `count` channels, all of which compare low.
+    pub fn new(count: u32) -> std::rc::Rc<AnalogComparator> {
+        std::rc::Rc::new(AnalogComparator {
+            comparisons: (0..count).map(|_| Cell::new(false)).collect(),
+            interrupts: (0..count).map(|_| Cell::new(false)).collect(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Sets whether the first input of `channel` is higher than the second.
+    /// If this flips the comparison of a channel with interrupts enabled, the
+    /// interrupt fires.
+    pub fn set_comparison(&self, channel: u32, higher: bool) {
+        let index = channel as usize;
+        if self.comparisons[index].replace(higher) != higher && self.interrupts[index].get() {
+            self.share_ref
+                .schedule_upcall(SUBSCRIBE_INTERRUPT, (channel, 0, 0))
+                .expect("Unable to schedule upcall");
+        }
+    }
+
+    pub fn interrupts_enabled(&self, channel: u32) -> bool {
+        self.interrupts[channel as usize].get()
+    }
+}
+
+impl crate::fake::SyscallDriver for AnalogComparator {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, _argument1: usize) -> CommandReturn {
+        if command_num == COUNT {
+            return crate::command_return::success_u32(self.comparisons.len() as u32);
+        }
+        let (comparison, interrupts) = match (
+            self.comparisons.get(argument0),
+            self.interrupts.get(argument0),
+        ) {
+            (Some(comparison), Some(interrupts)) => (comparison, interrupts),
+            _ => return crate::command_return::failure(ErrorCode::Invalid),
+        };
+        match command_num {
+            COMPARISON => crate::command_return::success_u32(comparison.get() as u32),
+            START_INTERRUPTS => {
+                interrupts.set(true);
+                crate::command_return::success()
+            }
+            STOP_INTERRUPTS => {
+                interrupts.set(false);
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x7;
+
+// Command numbers
+const COUNT: u32 = 0;
+const COMPARISON: u32 = 1;
+const START_INTERRUPTS: u32 = 2;
+const STOP_INTERRUPTS: u32 = 3;
+
+// Upcall numbers
+const SUBSCRIBE_INTERRUPT: u32 = 0;
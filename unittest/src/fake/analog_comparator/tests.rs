@@ -0,0 +1,57 @@
+use crate::fake;
+use fake::analog_comparator::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let comparator = AnalogComparator::new(2);
+
+    assert_eq!(comparator.command(COUNT, 0, 0).get_success_u32(), Some(2));
+    assert_eq!(
+        comparator.command(COMPARISON, 1, 0).get_success_u32(),
+        Some(0)
+    );
+    comparator.set_comparison(1, true);
+    assert_eq!(
+        comparator.command(COMPARISON, 1, 0).get_success_u32(),
+        Some(1)
+    );
+    assert_eq!(
+        comparator.command(COMPARISON, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(comparator.command(START_INTERRUPTS, 0, 0).is_success());
+    assert!(comparator.interrupts_enabled(0));
+    assert!(comparator.command(STOP_INTERRUPTS, 0, 0).is_success());
+    assert!(!comparator.interrupts_enabled(0));
+}
+
+// Integration test that verifies AnalogComparator works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let comparator = AnalogComparator::new(2);
+    kernel.add_driver(&comparator);
+
+    let interrupt: Cell<Option<(u32,)>> = Cell::new(None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_INTERRUPT>(
+            subscribe, &interrupt,
+        )
+        .unwrap();
+        comparator.set_comparison(1, true);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, START_INTERRUPTS, 1, 0).is_success());
+        // Setting the same result again is not a flip.
+        comparator.set_comparison(1, true);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        comparator.set_comparison(1, false);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(interrupt.get(), Some((1,)));
+    });
+}
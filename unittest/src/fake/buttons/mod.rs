@@ -5,7 +5,9 @@
 //! a function `get_button_state` used to retrieve the state and interrupt
 //! status of a button.
 //!
-//! It also provides the function `set_pressed` that set the button's state.
+//! It also provides the function `set_pressed` that set the button's state,
+//! `set_pressed_mask` that sets every button's state at once, and `press` that
+//! simulates a press and release.
 
 use core::cell::Cell;
 use libtock_platform::{CommandReturn, ErrorCode};
@@ -20,6 +22,9 @@ pub struct ButtonState {
 
 pub struct Buttons<const NUM_BUTTONS: usize> {
     buttons: [Cell<ButtonState>; NUM_BUTTONS],
+    // Bitmask of buttons pressed by `press` while their interrupts were
+    // disabled.
+    pending_presses: Cell<u32>,
     share_ref: DriverShareRef,
 }
 
@@ -32,6 +37,7 @@ impl<const NUM_BUTTONS: usize> Buttons<NUM_BUTTONS> {
         });
         std::rc::Rc::new(Buttons {
             buttons: [OFF; NUM_BUTTONS],
+            pending_presses: Cell::new(0),
             share_ref: Default::default(),
         })
     }
@@ -56,6 +62,38 @@ impl<const NUM_BUTTONS: usize> Buttons<NUM_BUTTONS> {
             .ok_or(ErrorCode::Invalid)
     }
 
+    /// Sets the state of every button: button `n` is pressed if bit `n` of
+    /// `mask` is set.
+    pub fn set_pressed_mask(&self, mask: u32) {
+        for button in 0..NUM_BUTTONS.min(u32::BITS as usize) as u32 {
+            let _ = self.set_pressed(button, mask & 1 << button != 0);
+        }
+    }
+
+    /// Simulates pressing and releasing `button`, which leaves its state
+    /// unchanged but fires an upcall for each edge. If the button's interrupts
+    /// are disabled, the press is held back until they are enabled, as though
+    /// the user pressed the button just after. This lets a test press a button
+    /// before calling code that enables interrupts and then waits.
+    pub fn press(&self, button: u32) -> Result<(), ErrorCode> {
+        let state = self.get_button_state(button).ok_or(ErrorCode::Invalid)?;
+        if state.interrupt_enabled {
+            self.fire_press(button);
+        } else {
+            self.pending_presses
+                .set(self.pending_presses.get() | 1 << button);
+        }
+        Ok(())
+    }
+
+    fn fire_press(&self, button: u32) {
+        for pressed in [true, false] {
+            self.share_ref
+                .schedule_upcall(0, (button, pressed as u32, 0))
+                .expect("Unable to schedule upcall {}");
+        }
+    }
+
     pub fn get_button_state(&self, button: u32) -> Option<ButtonState> {
         self.buttons.get(button as usize).map(|button| button.get())
     }
@@ -80,6 +118,11 @@ impl<const NUM_BUTTONS: usize> crate::fake::SyscallDriver for Buttons<NUM_BUTTON
                         interrupt_enabled: true,
                         ..button
                     });
+                    let pending = self.pending_presses.get();
+                    if pending & 1 << argument0 != 0 {
+                        self.pending_presses.set(pending & !(1 << argument0));
+                        self.fire_press(argument0 as u32);
+                    }
                     crate::command_return::success()
                 } else {
                     crate::command_return::failure(ErrorCode::Invalid)
@@ -40,7 +40,7 @@ fn command() {
         );
 
         assert!(buttons
-            .command(BUTTONS_ENABLE_INTERRUPTS, button_index, 0)
+            .command(BUTTONS_ENABLE_INTERRUPTS, button_index as usize, 0)
             .is_success());
         assert_eq!(
             buttons.get_button_state(button_index),
@@ -51,7 +51,7 @@ fn command() {
         );
 
         assert!(buttons
-            .command(BUTTONS_DISABLE_INTERRUPTS, button_index, 0)
+            .command(BUTTONS_DISABLE_INTERRUPTS, button_index as usize, 0)
             .is_success());
         assert_eq!(
             buttons.get_button_state(button_index),
@@ -110,3 +110,38 @@ fn kernel_integration() {
     );
     assert!(fake::Syscalls::command(DRIVER_NUM, BUTTONS_DISABLE_INTERRUPTS, 0, 0).is_success());
 }
+
+#[test]
+fn press() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let buttons = Buttons::<4>::new();
+    kernel.add_driver(&buttons);
+
+    buttons.set_pressed_mask(0b1010);
+    assert!(buttons.get_button_state(1).unwrap().pressed);
+    assert!(!buttons.get_button_state(2).unwrap().pressed);
+    assert!(buttons.get_button_state(3).unwrap().pressed);
+    assert_eq!(buttons.press(4), Err(ErrorCode::Invalid));
+
+    let upcall: Cell<Option<(u32, u32)>> = Cell::new(None);
+    share::scope(|subscribe| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, &upcall)
+            .unwrap();
+        // With interrupts disabled, the press waits for them to be enabled.
+        assert_eq!(buttons.press(2), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert!(fake::Syscalls::command(DRIVER_NUM, BUTTONS_ENABLE_INTERRUPTS, 2, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(upcall.get(), Some((2, 1)));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(upcall.get(), Some((2, 0)));
+        assert!(!buttons.get_button_state(2).unwrap().pressed);
+
+        // With interrupts enabled, it fires immediately.
+        assert_eq!(buttons.press(2), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(upcall.get(), Some((2, 1)));
+    });
+}
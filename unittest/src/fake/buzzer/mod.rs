@@ -0,0 +1,104 @@
+//! Fake implementation of the buzzer API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/90000_buzzer.md
+//!
+//! Like the real API, `Buzzer` plays one tone at a time, and rejects requests
+//! for another tone with `BUSY` until it finishes. Tests end the tone with
+//! `finish_tone`, or use `set_auto_finish` to have tones end as soon as they
+//! start. The most recently requested tone is available from `last_tone`.
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef};
+
+pub struct Buzzer {
+    // The (frequency, duration) of the most recently started tone.
+    last_tone: Cell<Option<(u32, u32)>>,
+    playing: Cell<bool>,
+    auto_finish: Cell<bool>,
+    share_ref: DriverShareRef,
+}
+
+impl Buzzer {
+    pub fn new() -> std::rc::Rc<Buzzer> {
+        std::rc::Rc::new(Buzzer {
+            last_tone: Cell::new(None),
+            playing: Cell::new(false),
+            auto_finish: Cell::new(false),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Returns the (frequency in Hz, duration in ms) of the most recently
+    /// started tone.
+    pub fn last_tone(&self) -> Option<(u32, u32)> {
+        self.last_tone.get()
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.playing.get()
+    }
+
+    /// Ends the tone that is playing, scheduling the tone-finished upcall.
+    pub fn finish_tone(&self) {
+        if self.playing.replace(false) {
+            self.share_ref
+                .schedule_upcall(SUBSCRIBE_FINISHED, (0, 0, 0))
+                .expect("Unable to schedule upcall");
+        }
+    }
+
+    /// If `auto_finish` is true, tones finish as soon as they are started.
+    pub fn set_auto_finish(&self, auto_finish: bool) {
+        self.auto_finish.set(auto_finish);
+    }
+}
+
+impl crate::fake::SyscallDriver for Buzzer {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            TONE => {
+                if self.playing.get() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                self.last_tone
+                    .set(Some((argument0 as u32, argument1 as u32)));
+                self.playing.set(true);
+                if self.auto_finish.get() {
+                    self.finish_tone();
+                }
+                crate::command_return::success()
+            }
+            STOP => {
+                self.playing.set(false);
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90000;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const TONE: u32 = 1;
+const STOP: u32 = 2;
+
+const SUBSCRIBE_FINISHED: u32 = 0;
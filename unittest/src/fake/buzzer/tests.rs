@@ -0,0 +1,59 @@
+use crate::fake;
+use fake::buzzer::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let buzzer = Buzzer::new();
+
+    assert!(buzzer.command(EXISTS, 0, 0).is_success());
+    assert_eq!(buzzer.last_tone(), None);
+    assert!(buzzer.command(TONE, 440, 100).is_success());
+    assert_eq!(buzzer.last_tone(), Some((440, 100)));
+    assert!(buzzer.is_playing());
+    assert_eq!(
+        buzzer.command(TONE, 880, 100).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    buzzer.finish_tone();
+    assert!(!buzzer.is_playing());
+
+    assert!(buzzer.command(TONE, 880, 100).is_success());
+    assert!(buzzer.command(STOP, 0, 0).is_success());
+    assert!(!buzzer.is_playing());
+    assert_eq!(buzzer.last_tone(), Some((880, 100)));
+}
+
+// Integration test that verifies Buzzer works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let buzzer = Buzzer::new();
+    kernel.add_driver(&buzzer);
+
+    let finished = Cell::new(false);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_FINISHED>(
+                subscribe, &finished
+            ),
+            Ok(())
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, TONE, 440, 100).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        buzzer.finish_tone();
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert!(finished.get());
+
+        buzzer.set_auto_finish(true);
+        finished.set(false);
+        assert!(fake::Syscalls::command(DRIVER_NUM, TONE, 440, 100).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert!(finished.get());
+    });
+}
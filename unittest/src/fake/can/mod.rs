@@ -0,0 +1,180 @@
+//! Fake implementation of the CAN API.
+//!
+//! `Can` is a loopback: every frame sent is queued to be received, so tests
+//! can exercise sending and receiving without a second node. Tests can also
+//! queue frames from other nodes with `push_frame`. Each time receiving is
+//! started, the next queued frame is reported; if none is queued, the next one
+//! queued is reported as soon as it arrives.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+pub struct Can {
+    bitrate: Cell<Option<u32>>,
+    enabled: Cell<bool>,
+    receiving: Cell<bool>,
+    frames: RefCell<VecDeque<(u32, Vec<u8>)>>,
+    tx: RefCell<RoAllowBuffer>,
+    rx: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Can {
+    pub fn new() -> std::rc::Rc<Can> {
+        std::rc::Rc::new(Can {
+            bitrate: Cell::new(None),
+            enabled: Cell::new(false),
+            receiving: Cell::new(false),
+            frames: Default::default(),
+            tx: Default::default(),
+            rx: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Returns the bitrate last set, if any.
+    pub fn bitrate(&self) -> Option<u32> {
+        self.bitrate.get()
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+
+    /// Queues a frame with identifier `id` and `data` to be received, as if
+    /// it was sent by another node.
+    pub fn push_frame(&self, id: u32, data: &[u8]) {
+        self.frames.borrow_mut().push_back((id, data.to_vec()));
+        self.deliver();
+    }
+
+    fn deliver(&self) {
+        if !self.receiving.get() {
+            return;
+        }
+        let (id, data) = match self.frames.borrow_mut().pop_front() {
+            Some(frame) => frame,
+            None => return,
+        };
+        self.receiving.set(false);
+        // Like the real driver, data that doesn't fit in the buffer is
+        // dropped, but the upcall reports the frame's full length.
+        let mut rx = self.rx.borrow_mut();
+        let len = data.len().min(rx.len());
+        rx[..len].copy_from_slice(&data[..len]);
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_RECEIVED, (data.len() as u32, id, 0))
+            .expect("Unable to schedule upcall");
+    }
+}
+
+impl crate::fake::SyscallDriver for Can {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(3)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_TX {
+            Ok(self.tx.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_RX {
+            Ok(self.rx.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            SET_BITRATE => {
+                if self.enabled.get() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                self.bitrate.set(Some(argument0 as u32));
+                crate::command_return::success()
+            }
+            ENABLE => {
+                if self.bitrate.get().is_none() {
+                    return crate::command_return::failure(ErrorCode::Off);
+                }
+                self.enabled.set(true);
+                crate::command_return::success()
+            }
+            SEND => {
+                if !self.enabled.get() {
+                    return crate::command_return::failure(ErrorCode::Off);
+                }
+                let tx = self.tx.borrow();
+                if argument1 > MAX_DATA_LEN || argument1 > tx.len() {
+                    return crate::command_return::failure(ErrorCode::Size);
+                }
+                let data = tx[..argument1].to_vec();
+                drop(tx);
+                self.share_ref
+                    .schedule_upcall(SUBSCRIBE_SENT, (0, 0, 0))
+                    .expect("Unable to schedule upcall");
+                self.push_frame(argument0 as u32, &data);
+                crate::command_return::success()
+            }
+            START_RECEIVING => {
+                if !self.enabled.get() {
+                    return crate::command_return::failure(ErrorCode::Off);
+                }
+                self.receiving.set(true);
+                self.deliver();
+                crate::command_return::success()
+            }
+            STOP_RECEIVING => {
+                self.receiving.set(false);
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20007;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const SET_BITRATE: u32 = 1;
+const ENABLE: u32 = 3;
+const SEND: u32 = 5;
+const START_RECEIVING: u32 = 7;
+const STOP_RECEIVING: u32 = 8;
+
+const SUBSCRIBE_SENT: u32 = 1;
+const SUBSCRIBE_RECEIVED: u32 = 2;
+const ALLOW_TX: u32 = 0;
+const ALLOW_RX: u32 = 0;
+
+// The most data bytes a classic CAN frame holds.
+const MAX_DATA_LEN: usize = 8;
@@ -0,0 +1,92 @@
+use crate::fake;
+use fake::can::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let can = Can::new();
+
+    assert!(can.command(EXISTS, 0, 0).is_success());
+    assert_eq!(
+        can.command(ENABLE, 0, 0).get_failure(),
+        Some(ErrorCode::Off)
+    );
+    assert_eq!(can.command(SEND, 1, 0).get_failure(), Some(ErrorCode::Off));
+    assert!(can.command(SET_BITRATE, 250_000, 0).is_success());
+    assert_eq!(can.bitrate(), Some(250_000));
+    assert!(can.command(ENABLE, 0, 0).is_success());
+    assert!(can.is_enabled());
+    assert_eq!(
+        can.command(SET_BITRATE, 500_000, 0).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    // Nothing is shared, so there is no data to send.
+    assert_eq!(can.command(SEND, 1, 1).get_failure(), Some(ErrorCode::Size));
+    assert!(can.command(START_RECEIVING, 0, 0).is_success());
+    assert!(can.command(STOP_RECEIVING, 0, 0).is_success());
+    assert!(can.command(2, 0, 0).is_failure());
+}
+
+// Integration test that verifies Can works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let can = Can::new();
+    kernel.add_driver(&can);
+    assert!(fake::Syscalls::command(DRIVER_NUM, SET_BITRATE, 125_000, 0).is_success());
+    assert!(fake::Syscalls::command(DRIVER_NUM, ENABLE, 0, 0).is_success());
+
+    let tx = [1, 2, 3];
+    let mut rx = [0; 2];
+    let sent: Cell<Option<(u32,)>> = Cell::new(None);
+    let received: Cell<Option<(u32, u32)>> = Cell::new(None);
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_TX>,
+            AllowRw<_, DRIVER_NUM, ALLOW_RX>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_SENT>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_RECEIVED>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe_sent, subscribe_received) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_TX>(allow_ro, &tx).unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_RX>(allow_rw, &mut rx).unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_SENT>(
+            subscribe_sent,
+            &sent,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_RECEIVED>(
+            subscribe_received,
+            &received,
+        )
+        .unwrap();
+
+        // Sent frames are looped back, and received when receiving starts.
+        assert!(fake::Syscalls::command(DRIVER_NUM, SEND, 0x42, 3).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(sent.get(), Some((0,)));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert!(fake::Syscalls::command(DRIVER_NUM, START_RECEIVING, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(received.get(), Some((3, 0x42)));
+
+        // With nothing queued, the next frame is received when it arrives.
+        assert!(fake::Syscalls::command(DRIVER_NUM, START_RECEIVING, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        can.push_frame(0x7ff, &[9]);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(received.get(), Some((1, 0x7ff)));
+    });
+    assert_eq!(rx, [9, 2]);
+}
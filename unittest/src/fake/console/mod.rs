@@ -3,7 +3,10 @@
 //!
 //! Like the real API, `Console` stores each message written to it.
 //! The resulting byte stream can be retrieved via `take_bytes`
-//! for use in unit tests.
+//! for use in unit tests. Input for reads is queued with `new_with_input` or
+//! `push_input`, and each read drains as much of it as fits. A read that finds
+//! no input completes empty, unless `set_blocking_reads` is used to make it
+//! wait for input (or an abort) like the real driver.
 
 use core::cell::{Cell, RefCell};
 use core::cmp;
@@ -14,10 +17,15 @@ use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
 pub struct Console {
     messages: Cell<Vec<u8>>,
     buffer: Cell<RoAllowBuffer>,
+    /// The most bytes a single write accepts
+    write_limit: Cell<usize>,
 
     read_buffer: RefCell<RwAllowBuffer>,
     /// To be returned on read
     input: Cell<Vec<u8>>,
+    blocking_reads: Cell<bool>,
+    /// The length of the read waiting for input, if any
+    pending_read: Cell<Option<usize>>,
 
     share_ref: DriverShareRef,
 }
@@ -31,17 +39,56 @@ impl Console {
         std::rc::Rc::new(Console {
             messages: Default::default(),
             buffer: Default::default(),
+            write_limit: Cell::new(usize::MAX),
             read_buffer: Default::default(),
             input: Cell::new(Vec::from(inputs)),
+            blocking_reads: Cell::new(false),
+            pending_read: Cell::new(None),
             share_ref: Default::default(),
         })
     }
 
+    /// Appends `bytes` to the input that reads are served from.
+    pub fn push_input(&self, bytes: &[u8]) {
+        let mut input = self.input.take();
+        input.extend_from_slice(bytes);
+        self.input.set(input);
+        if let Some(count_wanted) = self.pending_read.take() {
+            self.complete_read(count_wanted);
+        }
+    }
+
+    /// Sets whether a read that finds no input waits for `push_input` or an
+    /// abort, rather than completing with no bytes.
+    pub fn set_blocking_reads(&self, blocking: bool) {
+        self.blocking_reads.set(blocking);
+    }
+
+    /// Limits how many bytes each write accepts, to simulate a kernel that
+    /// performs partial writes.
+    pub fn set_write_limit(&self, limit: usize) {
+        self.write_limit.set(limit);
+    }
+
     /// Returns the bytes that have been submitted so far,
     /// and clears them.
     pub fn take_bytes(&self) -> Vec<u8> {
         self.messages.take()
     }
+
+    fn complete_read(&self, count_wanted: usize) {
+        let bytes = self.input.take();
+        let count_wanted = cmp::min(count_wanted, bytes.len());
+        let to_send = &bytes[..count_wanted];
+        let to_keep = &bytes[count_wanted..];
+        self.input.set(Vec::from(to_keep));
+
+        let count_available = to_send.len();
+        self.read_buffer.borrow_mut()[..count_wanted].copy_from_slice(to_send);
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_READ, (0, count_available as u32, 0))
+            .expect("Unable to schedule upcall {}");
+    }
 }
 
 impl crate::fake::SyscallDriver for Console {
@@ -84,6 +131,7 @@ impl crate::fake::SyscallDriver for Console {
                 let mut bytes = self.messages.take();
                 let buffer = self.buffer.take();
                 let size = cmp::min(buffer.len(), argument0 as usize);
+                let size = cmp::min(size, self.write_limit.get());
                 bytes.extend_from_slice(&(*buffer)[..size]);
                 self.buffer.set(buffer);
                 self.messages.set(bytes);
@@ -92,18 +140,23 @@ impl crate::fake::SyscallDriver for Console {
                     .expect("Unable to schedule upcall {}");
             }
             READ => {
-                let count_wanted = argument0 as usize;
-                let bytes = self.input.take();
-                let count_wanted = cmp::min(count_wanted, bytes.len());
-                let to_send = &bytes[..count_wanted];
-                let to_keep = &bytes[count_wanted..];
-                self.input.set(Vec::from(to_keep));
-
-                let count_available = to_send.len();
-                self.read_buffer.borrow_mut()[..count_wanted].copy_from_slice(to_send);
-                self.share_ref
-                    .schedule_upcall(SUBSCRIBE_READ, (0, count_available as u32, 0))
-                    .expect("Unable to schedule upcall {}");
+                let input = self.input.take();
+                let empty = input.is_empty();
+                self.input.set(input);
+                if self.blocking_reads.get() && empty {
+                    self.pending_read.set(Some(argument0));
+                } else {
+                    self.complete_read(argument0);
+                }
+            }
+            ABORT => {
+                // Like the real driver, an aborted read reports what it had
+                // received so far, which is always nothing here.
+                if self.pending_read.take().is_some() {
+                    self.share_ref
+                        .schedule_upcall(SUBSCRIBE_READ, (ErrorCode::Cancel as u32, 0, 0))
+                        .expect("Unable to schedule upcall {}");
+                }
             }
             _ => return crate::command_return::failure(ErrorCode::NoSupport),
         }
@@ -124,7 +177,7 @@ const DRIVER_NUM: u32 = 1;
 const DRIVER_CHECK: u32 = 0;
 const WRITE: u32 = 1;
 const READ: u32 = 2;
-//const ABORT: u32 = 3;
+const ABORT: u32 = 3;
 const SUBSCRIBE_WRITE: u32 = 1;
 const SUBSCRIBE_READ: u32 = 2;
 const ALLOW_WRITE: u32 = 1;
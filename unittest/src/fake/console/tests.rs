@@ -2,6 +2,8 @@ use crate::fake;
 use crate::{RoAllowBuffer, RwAllowBuffer};
 use libtock_platform::share;
 use libtock_platform::DefaultConfig;
+use libtock_platform::ErrorCode;
+use libtock_platform::Syscalls;
 
 // Tests the command implementation.
 #[test]
@@ -24,7 +26,6 @@ fn command() {
 // libtock_platform::Syscalls.
 #[test]
 fn kernel_integration() {
-    use libtock_platform::Syscalls;
     let kernel = fake::Kernel::new();
     let console = fake::Console::new();
     kernel.add_driver(&console);
@@ -62,3 +63,117 @@ fn kernel_integration() {
         );
     });
 }
+
+#[test]
+fn push_input() {
+    use fake::SyscallDriver;
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new_with_input(b"ab");
+    kernel.add_driver(&console);
+    console.push_input(b"cd");
+
+    let mut buf = [0; 3];
+    share::scope(|allow_rw| {
+        fake::Syscalls::allow_rw::<
+            DefaultConfig,
+            { fake::console::DRIVER_NUM },
+            { fake::console::ALLOW_READ },
+        >(allow_rw, &mut buf)
+        .unwrap();
+        assert!(console.command(fake::console::READ, 3, 0).is_success());
+    });
+    assert_eq!(&buf, b"abc");
+    assert_eq!(console.input.take(), b"d");
+}
+
+#[test]
+fn blocking_reads() {
+    use core::cell::Cell;
+    use fake::console::{ABORT, ALLOW_READ, DRIVER_NUM, READ, SUBSCRIBE_READ};
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::YieldNoWaitReturn;
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new();
+    kernel.add_driver(&console);
+    console.set_blocking_reads(true);
+
+    let called: Cell<Option<(u32, u32)>> = Cell::new(None);
+    let mut buf = [0; 3];
+    share::scope::<
+        (
+            AllowRw<_, DRIVER_NUM, ALLOW_READ>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_READ>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_READ>(allow_rw, &mut buf)
+            .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_READ>(
+            subscribe, &called,
+        )
+        .unwrap();
+
+        // The read waits for input.
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ, 3, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        console.push_input(b"ab");
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(called.get(), Some((0, 2)));
+
+        // Aborting a waiting read completes it with ErrorCode::Cancel.
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ, 3, 0).is_success());
+        assert!(fake::Syscalls::command(DRIVER_NUM, ABORT, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(called.get(), Some((ErrorCode::Cancel as u32, 0)));
+        assert!(fake::Syscalls::command(DRIVER_NUM, ABORT, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+    assert_eq!(&buf[..2], b"ab");
+}
+
+// Writes "hello" through the allow, command, and upcall sequence the console
+// API uses, and checks it was captured.
+#[test]
+fn write_hello() {
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::subscribe::Subscribe;
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new();
+    kernel.add_driver(&console);
+
+    let written: core::cell::Cell<Option<(u32,)>> = core::cell::Cell::new(None);
+    share::scope::<
+        (
+            AllowRo<_, { fake::console::DRIVER_NUM }, { fake::console::ALLOW_WRITE }>,
+            Subscribe<_, { fake::console::DRIVER_NUM }, { fake::console::SUBSCRIBE_WRITE }>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<
+            DefaultConfig,
+            { fake::console::DRIVER_NUM },
+            { fake::console::ALLOW_WRITE },
+        >(allow_ro, b"hello")
+        .unwrap();
+        fake::Syscalls::subscribe::<
+            _,
+            _,
+            DefaultConfig,
+            { fake::console::DRIVER_NUM },
+            { fake::console::SUBSCRIBE_WRITE },
+        >(subscribe, &written)
+        .unwrap();
+        assert!(
+            fake::Syscalls::command(fake::console::DRIVER_NUM, fake::console::WRITE, 5, 0)
+                .is_success()
+        );
+        fake::Syscalls::yield_wait();
+    });
+    assert_eq!(written.get(), Some((5,)));
+    assert_eq!(console.take_bytes(), b"hello");
+}
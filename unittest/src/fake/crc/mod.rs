@@ -0,0 +1,172 @@
+//! Fake implementation of the CRC API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/40002_crc.md
+//!
+//! `Crc` computes CRCs in software, using the same parameters as the real
+//! driver, so tests can check results against known values. Like a hardware
+//! CRC unit fed from a small kernel buffer, it processes the input in chunks
+//! (see `set_chunk_size`), and reports only the final CRC. Requests complete
+//! immediately.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer};
+
+pub struct Crc {
+    chunk_size: Cell<usize>,
+    input: RefCell<RoAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Crc {
+    pub fn new() -> std::rc::Rc<Crc> {
+        std::rc::Rc::new(Crc {
+            chunk_size: Cell::new(64),
+            input: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Sets how many bytes are processed at a time. `chunk_size` must be
+    /// nonzero.
+    pub fn set_chunk_size(&self, chunk_size: usize) {
+        assert_ne!(chunk_size, 0, "chunks must not be empty");
+        self.chunk_size.set(chunk_size);
+    }
+}
+
+impl crate::fake::SyscallDriver for Crc {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_INPUT {
+            Ok(self.input.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            COMPUTE => {
+                let algorithm = match Algorithm::from_id(argument0) {
+                    Some(algorithm) => algorithm,
+                    None => return crate::command_return::failure(ErrorCode::Invalid),
+                };
+                let input = self.input.borrow();
+                if argument1 > input.len() {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                let mut state = algorithm.init;
+                for chunk in input[..argument1].chunks(self.chunk_size.get()) {
+                    state = algorithm.update(state, chunk);
+                }
+                self.share_ref
+                    .schedule_upcall(SUBSCRIBE_DONE, (0, state ^ algorithm.xor_out, 0))
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+// The parameters of a CRC algorithm, in the usual Rocksoft model. Reflected
+// algorithms take their polynomial reversed.
+struct Algorithm {
+    width: u32,
+    poly: u32,
+    init: u32,
+    reflected: bool,
+    xor_out: u32,
+}
+
+impl Algorithm {
+    fn from_id(id: usize) -> Option<Algorithm> {
+        match id {
+            CRC32 => Some(Algorithm {
+                width: 32,
+                poly: 0xedb88320,
+                init: 0xffffffff,
+                reflected: true,
+                xor_out: 0xffffffff,
+            }),
+            CRC32C => Some(Algorithm {
+                width: 32,
+                poly: 0x82f63b78,
+                init: 0xffffffff,
+                reflected: true,
+                xor_out: 0xffffffff,
+            }),
+            CRC16_CCITT => Some(Algorithm {
+                width: 16,
+                poly: 0x1021,
+                init: 0xffff,
+                reflected: false,
+                xor_out: 0,
+            }),
+            _ => None,
+        }
+    }
+
+    // Feeds `data` into the CRC register `state`, a bit at a time.
+    fn update(&self, mut state: u32, data: &[u8]) -> u32 {
+        let top_bit = 1 << (self.width - 1);
+        let mask = u32::MAX >> (32 - self.width);
+        for &byte in data {
+            if self.reflected {
+                state ^= byte as u32;
+                for _ in 0..8 {
+                    state = if state & 1 != 0 {
+                        (state >> 1) ^ self.poly
+                    } else {
+                        state >> 1
+                    };
+                }
+            } else {
+                state ^= (byte as u32) << (self.width - 8);
+                for _ in 0..8 {
+                    state = if state & top_bit != 0 {
+                        (state << 1) ^ self.poly
+                    } else {
+                        state << 1
+                    };
+                }
+                state &= mask;
+            }
+        }
+        state
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40002;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const COMPUTE: u32 = 1;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_INPUT: u32 = 0;
+
+// Algorithm IDs
+const CRC32: usize = 0;
+const CRC32C: usize = 1;
+const CRC16_CCITT: usize = 2;
@@ -0,0 +1,65 @@
+use crate::fake;
+use fake::crc::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let crc = Crc::new();
+
+    assert!(crc.command(EXISTS, 0, 0).is_success());
+    assert!(crc.command(COMPUTE, CRC32, 0).is_success());
+    assert_eq!(
+        crc.command(COMPUTE, 3, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    // Without an input buffer, only empty inputs are valid.
+    assert_eq!(
+        crc.command(COMPUTE, CRC32, 1).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+}
+
+// Integration test that verifies Crc works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    let kernel = fake::Kernel::new();
+    let crc = Crc::new();
+    kernel.add_driver(&crc);
+    crc.set_chunk_size(2);
+
+    let done: Cell<Option<(u32, u32)>> = Cell::new(None);
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_INPUT>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_INPUT>(allow_ro, b"123456789")
+            .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+        for (algorithm, expected) in [
+            (CRC32, 0xcbf43926),
+            (CRC32C, 0xe3069283),
+            (CRC16_CCITT, 0x29b1),
+        ] {
+            assert!(fake::Syscalls::command(DRIVER_NUM, COMPUTE, algorithm, 9).is_success());
+            // Only one upcall is delivered, however many chunks were processed.
+            assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+            assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+            assert_eq!(done.get(), Some((0, expected)));
+        }
+    });
+}
@@ -0,0 +1,166 @@
+//! Fake implementation of the USB CTAP API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/20009_usb_ctap.md
+//!
+//! `Ctap` stands in for the host. It records each packet the process sends,
+//! and also queues it to be received, so a test can round-trip packets through
+//! the send and receive buffers. Packets from the host can be queued with
+//! `push_packet`. Packets are received in order, once a receive is started.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+pub struct Ctap {
+    receiving: Cell<bool>,
+    queue: RefCell<VecDeque<Vec<u8>>>,
+    sent: RefCell<Vec<Vec<u8>>>,
+    send_buffer: RefCell<RoAllowBuffer>,
+    receive_buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Ctap {
+    pub fn new() -> std::rc::Rc<Ctap> {
+        std::rc::Rc::new(Ctap {
+            receiving: Cell::new(false),
+            queue: Default::default(),
+            sent: Default::default(),
+            send_buffer: Default::default(),
+            receive_buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Returns true if a receive has been started and not yet completed or
+    /// cancelled.
+    pub fn is_receiving(&self) -> bool {
+        self.receiving.get()
+    }
+
+    /// Queues a packet from the host.
+    pub fn push_packet(&self, packet: &[u8]) {
+        self.queue.borrow_mut().push_back(packet.to_vec());
+        self.deliver();
+    }
+
+    /// Returns the packets sent so far, and clears them.
+    pub fn take_sent(&self) -> Vec<Vec<u8>> {
+        self.sent.take()
+    }
+
+    // Completes the pending receive, if there is one and a packet is queued.
+    fn deliver(&self) {
+        if !self.receiving.get() {
+            return;
+        }
+        let packet = match self.queue.borrow_mut().pop_front() {
+            Some(packet) => packet,
+            None => return,
+        };
+        self.receiving.set(false);
+        self.receive_buffer.borrow_mut()[..packet.len()].copy_from_slice(&packet);
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_RECEIVED, (0, packet.len() as u32, 0))
+            .expect("Unable to schedule upcall");
+    }
+
+    fn send(&self) -> CommandReturn {
+        let packet = self.send_buffer.borrow().to_vec();
+        if packet.is_empty() {
+            return crate::command_return::failure(ErrorCode::Reserve);
+        }
+        if packet.len() > PACKET_LEN {
+            return crate::command_return::failure(ErrorCode::Size);
+        }
+        self.sent.borrow_mut().push(packet.clone());
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_SENT, (0, 0, 0))
+            .expect("Unable to schedule upcall");
+        self.push_packet(&packet);
+        crate::command_return::success()
+    }
+
+    fn receive(&self) -> CommandReturn {
+        if self.receiving.get() {
+            return crate::command_return::failure(ErrorCode::Busy);
+        }
+        if self.receive_buffer.borrow().len() < PACKET_LEN {
+            return crate::command_return::failure(ErrorCode::Reserve);
+        }
+        self.receiving.set(true);
+        self.deliver();
+        crate::command_return::success()
+    }
+}
+
+impl crate::fake::SyscallDriver for Ctap {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(2)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_SEND {
+            Ok(self.send_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_RECEIVE {
+            Ok(self.receive_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            SEND => self.send(),
+            RECEIVE => self.receive(),
+            CANCEL if self.receiving.replace(false) => crate::command_return::success(),
+            CANCEL => crate::command_return::failure(ErrorCode::Already),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20009;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const SEND: u32 = 1;
+const RECEIVE: u32 = 2;
+const CANCEL: u32 = 4;
+
+// Upcall numbers
+const SUBSCRIBE_RECEIVED: u32 = 0;
+const SUBSCRIBE_SENT: u32 = 1;
+
+// Allow numbers
+const ALLOW_SEND: u32 = 0;
+const ALLOW_RECEIVE: u32 = 0;
+
+const PACKET_LEN: usize = 64;
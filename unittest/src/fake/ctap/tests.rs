@@ -0,0 +1,93 @@
+use crate::fake;
+use fake::ctap::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let ctap = Ctap::new();
+
+    assert!(ctap.command(EXISTS, 0, 0).is_success());
+    // Sending and receiving need buffers.
+    assert_eq!(
+        ctap.command(SEND, 0, 0).get_failure(),
+        Some(ErrorCode::Reserve)
+    );
+    assert_eq!(
+        ctap.command(RECEIVE, 0, 0).get_failure(),
+        Some(ErrorCode::Reserve)
+    );
+    assert_eq!(
+        ctap.command(CANCEL, 0, 0).get_failure(),
+        Some(ErrorCode::Already)
+    );
+}
+
+// Integration test that verifies Ctap works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    let kernel = fake::Kernel::new();
+    let ctap = Ctap::new();
+    kernel.add_driver(&ctap);
+
+    let sent: Cell<Option<(u32,)>> = Cell::new(None);
+    let received: Cell<Option<(u32, u32)>> = Cell::new(None);
+    let packet = [0xab; PACKET_LEN];
+    let mut receive_buffer = [0; PACKET_LEN];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_SEND>,
+            AllowRw<_, DRIVER_NUM, ALLOW_RECEIVE>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_SENT>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_RECEIVED>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, sub_sent, sub_received) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_SEND>(allow_ro, &packet)
+            .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_RECEIVE>(
+            allow_rw,
+            &mut receive_buffer,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_SENT>(
+            sub_sent, &sent,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_RECEIVED>(
+            sub_received,
+            &received,
+        )
+        .unwrap();
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, SEND, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(sent.get(), Some((0,)));
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, RECEIVE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(received.get(), Some((0, PACKET_LEN as u32)));
+        assert!(!ctap.is_receiving());
+
+        // With nothing queued, the receive stays pending until cancelled.
+        assert!(fake::Syscalls::command(DRIVER_NUM, RECEIVE, 0, 0).is_success());
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, RECEIVE, 0, 0).get_failure(),
+            Some(ErrorCode::Busy)
+        );
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert!(fake::Syscalls::command(DRIVER_NUM, CANCEL, 0, 0).is_success());
+        assert!(!ctap.is_receiving());
+    });
+    assert_eq!(receive_buffer, packet);
+    assert_eq!(ctap.take_sent(), [packet.to_vec()]);
+}
@@ -0,0 +1,88 @@
+//! Fake implementation of the DAC API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/00006_dac.md
+//!
+//! Like the real API, `Dac` must be initialized before it accepts values. It
+//! records the last value written, which tests can retrieve with `value`, and
+//! can be made busy with `set_busy` to simulate a conversion in progress.
+
+use crate::DriverInfo;
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+pub struct Dac {
+    max_value: u16,
+    initialized: Cell<bool>,
+    busy: Cell<bool>,
+    value: Cell<Option<u16>>,
+}
+
+impl Dac {
+    /// Creates an uninitialized DAC that accepts values up to `max_value`.
+    pub fn new(max_value: u16) -> std::rc::Rc<Dac> {
+        std::rc::Rc::new(Dac {
+            max_value,
+            initialized: Cell::new(false),
+            busy: Cell::new(false),
+            value: Cell::new(None),
+        })
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.initialized.get()
+    }
+
+    pub fn set_busy(&self, busy: bool) {
+        self.busy.set(busy);
+    }
+
+    /// Returns the last value written, or `None` if none has been.
+    pub fn value(&self) -> Option<u16> {
+        self.value.get()
+    }
+}
+
+impl crate::fake::SyscallDriver for Dac {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM)
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            INITIALIZE => {
+                self.initialized.set(true);
+                crate::command_return::success()
+            }
+            SET_VALUE => {
+                if !self.initialized.get() {
+                    return crate::command_return::failure(ErrorCode::Off);
+                }
+                if self.busy.get() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                if argument0 > self.max_value as usize {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                self.value.set(Some(argument0 as u16));
+                crate::command_return::success()
+            }
+            MAX_VALUE => crate::command_return::success_u32(self.max_value as u32),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x6;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const INITIALIZE: u32 = 1;
+const SET_VALUE: u32 = 2;
+const MAX_VALUE: u32 = 3;
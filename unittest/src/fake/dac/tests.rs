@@ -0,0 +1,43 @@
+use crate::fake;
+use fake::dac::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let dac = Dac::new(1023);
+
+    assert!(dac.command(EXISTS, 0, 0).is_success());
+    assert_eq!(dac.command(MAX_VALUE, 0, 0).get_success_u32(), Some(1023));
+    assert_eq!(
+        dac.command(SET_VALUE, 5, 0).get_failure(),
+        Some(ErrorCode::Off)
+    );
+    assert!(dac.command(INITIALIZE, 0, 0).is_success());
+    assert!(dac.is_initialized());
+    assert!(dac.command(SET_VALUE, 1023, 0).is_success());
+    assert_eq!(dac.value(), Some(1023));
+    assert_eq!(
+        dac.command(SET_VALUE, 1024, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    dac.set_busy(true);
+    assert_eq!(
+        dac.command(SET_VALUE, 5, 0).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    assert_eq!(dac.value(), Some(1023));
+}
+
+// Integration test that verifies Dac works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let dac = Dac::new(255);
+    kernel.add_driver(&dac);
+    assert!(fake::Syscalls::command(DRIVER_NUM, INITIALIZE, 0, 0).is_success());
+    assert!(fake::Syscalls::command(DRIVER_NUM, SET_VALUE, 42, 0).is_success());
+    assert_eq!(dac.value(), Some(42));
+}
@@ -6,6 +6,7 @@
 //! status of a button.
 //!
 //! It also provides the function `set_pressed` that set the button's state.
+//! `pulse` briefly inverts a pin, producing an edge in each direction.
 
 use core::cell::Cell;
 use libtock_platform::{CommandReturn, ErrorCode};
@@ -69,6 +70,8 @@ pub struct GpioState {
 
 pub struct Gpio<const NUM_GPIOS: usize> {
     gpios: [Cell<Option<GpioState>>; NUM_GPIOS],
+    // Pulses of each pin that are held back until its interrupts are enabled.
+    pending_pulses: [Cell<u32>; NUM_GPIOS],
     share_ref: DriverShareRef,
 }
 
@@ -80,8 +83,11 @@ impl<const NUM_GPIOS: usize> Gpio<NUM_GPIOS> {
             mode: GpioMode::Input(PullMode::PullNone),
             interrupt_enabled: None,
         }));
+        #[allow(clippy::declare_interior_mutable_const)]
+        const NO_PULSES: Cell<u32> = Cell::new(0);
         std::rc::Rc::new(Gpio {
             gpios: [OFF; NUM_GPIOS],
+            pending_pulses: [NO_PULSES; NUM_GPIOS],
             share_ref: Default::default(),
         })
     }
@@ -128,6 +134,28 @@ impl<const NUM_GPIOS: usize> Gpio<NUM_GPIOS> {
             .and_then(|value| value)
     }
 
+    /// Simulates a pulse on `pin`: its value is inverted and then restored,
+    /// which fires an upcall for each edge the pin's interrupts are enabled
+    /// for. If the pin's interrupts are disabled, the pulse is held back until
+    /// they are enabled, as though it happened just after. This lets a test
+    /// pulse a pin before calling code that enables interrupts and then waits.
+    pub fn pulse(&self, pin: u32) -> Result<(), ErrorCode> {
+        let state = self.get_gpio_state(pin).ok_or(ErrorCode::Invalid)?;
+        match state.interrupt_enabled {
+            Some(_) => self.fire_pulse(pin, state.value),
+            None => {
+                let pending = &self.pending_pulses[pin as usize];
+                pending.set(pending.get() + 1);
+                Ok(())
+            }
+        }
+    }
+
+    fn fire_pulse(&self, pin: u32, value: bool) -> Result<(), ErrorCode> {
+        self.set_value(pin, !value)?;
+        self.set_value(pin, value)
+    }
+
     pub fn get_gpio_state(&self, button: u32) -> Option<GpioState> {
         self.gpios
             .get(button as usize)
@@ -214,6 +242,9 @@ impl<const NUM_GPIOS: usize> crate::fake::SyscallDriver for Gpio<NUM_GPIOS> {
                                     interrupt_enabled: Some(interrupt_edge),
                                     ..gpio
                                 }));
+                                for _ in 0..self.pending_pulses[argument0].take() {
+                                    let _ = self.fire_pulse(argument0 as u32, gpio.value);
+                                }
                                 crate::command_return::success()
                             }
                             Err(error) => crate::command_return::failure(error),
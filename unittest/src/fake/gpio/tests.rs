@@ -401,3 +401,36 @@ fn kernel_integration() {
     assert!(fake::Syscalls::command(DRIVER_NUM, GPIO_DISABLE, 0, 0).is_success());
     assert_eq!(gpio.get_gpio_state(0).unwrap().mode, GpioMode::Disable);
 }
+
+#[test]
+fn pulse() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let gpio = Gpio::<2>::new();
+    kernel.add_driver(&gpio);
+
+    let edges = Cell::<Option<(u32, u32)>>::new(None);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, 0>(subscribe, &edges),
+            Ok(())
+        );
+
+        // Pulses are held back until interrupts are enabled.
+        assert_eq!(gpio.pulse(0), Ok(()));
+        assert_eq!(gpio.pulse(2), Err(ErrorCode::Invalid));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert!(fake::Syscalls::command(DRIVER_NUM, GPIO_ENABLE_INTERRUPTS, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(edges.get(), Some((0, 1)));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(edges.get(), Some((0, 0)));
+
+        // Only the edges interrupts are enabled for fire upcalls.
+        assert!(fake::Syscalls::command(DRIVER_NUM, GPIO_ENABLE_INTERRUPTS, 0, 2).is_success());
+        assert_eq!(gpio.pulse(0), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert!(!gpio.get_gpio_state(0).unwrap().value);
+    });
+}
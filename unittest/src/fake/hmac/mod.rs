@@ -0,0 +1,172 @@
+//! Fake implementation of the HMAC API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/40003_hmac.md
+//!
+//! `Hmac` computes HMAC-SHA-256 in software, so tests can check results
+//! against known test vectors. It does not support the other algorithms. Like
+//! the real driver, it copies the key when the algorithm is set, and it
+//! compares digests itself for the verify commands. Operations complete
+//! immediately.
+
+use core::cell::RefCell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::fake::sha::{sha256, BLOCK_LEN, DIGEST_LEN};
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+pub struct Hmac {
+    key: RefCell<Vec<u8>>,
+    // The data added to the HMAC in progress.
+    message: RefCell<Vec<u8>>,
+    key_buffer: RefCell<RoAllowBuffer>,
+    data: RefCell<RoAllowBuffer>,
+    compare: RefCell<RoAllowBuffer>,
+    digest: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Hmac {
+    pub fn new() -> std::rc::Rc<Hmac> {
+        std::rc::Rc::new(Hmac {
+            key: Default::default(),
+            message: Default::default(),
+            key_buffer: Default::default(),
+            data: Default::default(),
+            compare: Default::default(),
+            digest: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    // Computes the HMAC of the message so far, as specified in RFC 2104, and
+    // starts a new message.
+    fn compute(&self) -> [u8; DIGEST_LEN] {
+        let mut key = self.key.borrow().clone();
+        if key.len() > BLOCK_LEN {
+            key = sha256(&key).to_vec();
+        }
+        key.resize(BLOCK_LEN, 0);
+        let mut inner: Vec<u8> = key.iter().map(|byte| byte ^ 0x36).collect();
+        inner.extend_from_slice(&self.message.take());
+        let mut outer: Vec<u8> = key.iter().map(|byte| byte ^ 0x5c).collect();
+        outer.extend_from_slice(&sha256(&inner));
+        sha256(&outer)
+    }
+
+    fn update(&self) {
+        self.message
+            .borrow_mut()
+            .extend_from_slice(&self.data.borrow());
+    }
+
+    fn finish(&self) -> Result<u32, ErrorCode> {
+        let mut digest = self.digest.borrow_mut();
+        if digest.len() < DIGEST_LEN {
+            return Err(ErrorCode::Size);
+        }
+        digest[..DIGEST_LEN].copy_from_slice(&self.compute());
+        Ok(0)
+    }
+
+    fn verify(&self) -> Result<u32, ErrorCode> {
+        Ok((self.compute()[..] == self.compare.borrow()[..]) as u32)
+    }
+
+    fn report(&self, result: Result<u32, ErrorCode>) {
+        let args = match result {
+            Ok(value) => (0, value, 0),
+            Err(error) => (error as u32, 0, 0),
+        };
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_DONE, args)
+            .expect("Unable to schedule upcall");
+    }
+}
+
+impl crate::fake::SyscallDriver for Hmac {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        match buffer_num {
+            ALLOW_KEY => Ok(self.key_buffer.replace(buffer)),
+            ALLOW_DATA => Ok(self.data.replace(buffer)),
+            ALLOW_COMPARE => Ok(self.compare.replace(buffer)),
+            _ => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_DIGEST {
+            Ok(self.digest.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            SET_ALGORITHM => {
+                if argument0 != SHA256 {
+                    return crate::command_return::failure(ErrorCode::NoSupport);
+                }
+                *self.key.borrow_mut() = self.key_buffer.borrow().to_vec();
+                self.message.take();
+            }
+            RUN => {
+                self.update();
+                self.report(self.finish());
+            }
+            UPDATE => {
+                self.update();
+                self.report(Ok(0));
+            }
+            FINISH => self.report(self.finish()),
+            VERIFY => {
+                self.update();
+                self.report(self.verify());
+            }
+            VERIFY_FINISH => self.report(self.verify()),
+            _ => return crate::command_return::failure(ErrorCode::NoSupport),
+        }
+        crate::command_return::success()
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40003;
+
+// Command numbers
+const SET_ALGORITHM: u32 = 0;
+const RUN: u32 = 1;
+const UPDATE: u32 = 2;
+const FINISH: u32 = 3;
+const VERIFY: u32 = 4;
+const VERIFY_FINISH: u32 = 5;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_KEY: u32 = 0;
+const ALLOW_DATA: u32 = 1;
+const ALLOW_COMPARE: u32 = 2;
+const ALLOW_DIGEST: u32 = 2;
+
+// Algorithm IDs
+const SHA256: usize = 0;
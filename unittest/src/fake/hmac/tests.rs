@@ -0,0 +1,83 @@
+use crate::fake;
+use fake::hmac::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let hmac = Hmac::new();
+
+    assert!(hmac.command(SET_ALGORITHM, SHA256, 0).is_success());
+    assert_eq!(
+        hmac.command(SET_ALGORITHM, 1, 0).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+    // Errors are reported through the upcall.
+    assert!(hmac.command(FINISH, 0, 0).is_success());
+}
+
+// Integration test that verifies Hmac works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    let kernel = fake::Kernel::new();
+    let hmac = Hmac::new();
+    kernel.add_driver(&hmac);
+
+    // RFC 4231 test case 6, which uses a key longer than a block.
+    let key = [0xaa; 131];
+    let data = b"Test Using Larger Than Block-Size Key - Hash Key First";
+    let expected = [
+        0x60, 0xe4, 0x31, 0x59, 0x1e, 0xe0, 0xb6, 0x7f, 0x0d, 0x8a, 0x26, 0xaa, 0xcb, 0xf5, 0xb7,
+        0x7f, 0x8e, 0x0b, 0xc6, 0x21, 0x37, 0x28, 0xc5, 0x14, 0x05, 0x46, 0x04, 0x0f, 0x0e, 0xe3,
+        0x7f, 0x54,
+    ];
+    let done: Cell<Option<(u32, u32)>> = Cell::new(None);
+    let mut digest = [0; 32];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_KEY>,
+            AllowRo<_, DRIVER_NUM, ALLOW_DATA>,
+            AllowRo<_, DRIVER_NUM, ALLOW_COMPARE>,
+            AllowRw<_, DRIVER_NUM, ALLOW_DIGEST>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_key, allow_data, allow_compare, allow_digest, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_KEY>(allow_key, &key).unwrap();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_DATA>(allow_data, data)
+            .unwrap();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_COMPARE>(
+            allow_compare,
+            &expected,
+        )
+        .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_DIGEST>(
+            allow_digest,
+            &mut digest,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, SET_ALGORITHM, SHA256, 0).is_success());
+        assert!(fake::Syscalls::command(DRIVER_NUM, VERIFY, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 1)));
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, RUN, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 0)));
+    });
+    assert_eq!(digest, expected);
+}
@@ -0,0 +1,94 @@
+//! Fake implementation of the humidity API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/60001_humidity.md
+//!
+//! Like the real API, `Humidity` reports relative humidity in hundredths of a
+//! percent. Tests queue readings with `push_reading`; each read request is
+//! answered with the next queued reading. If none is queued, the request stays
+//! outstanding until one is pushed.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::{DriverInfo, DriverShareRef};
+
+pub struct Humidity {
+    readings: RefCell<VecDeque<u32>>,
+    // Whether a read request is waiting for a reading.
+    pending: Cell<bool>,
+    share_ref: DriverShareRef,
+}
+
+impl Humidity {
+    pub fn new() -> std::rc::Rc<Humidity> {
+        std::rc::Rc::new(Humidity {
+            readings: Default::default(),
+            pending: Cell::new(false),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Queues a reading, in hundredths of a percent, to be returned by a future
+    /// read request. If a request is outstanding, it is completed immediately.
+    pub fn push_reading(&self, centi_percent: u32) {
+        self.readings.borrow_mut().push_back(centi_percent);
+        self.deliver();
+    }
+
+    /// Returns true if a read request is waiting for a reading.
+    pub fn is_busy(&self) -> bool {
+        self.pending.get()
+    }
+
+    fn deliver(&self) {
+        if !self.pending.get() {
+            return;
+        }
+        if let Some(reading) = self.readings.borrow_mut().pop_front() {
+            self.pending.set(false);
+            self.share_ref
+                .schedule_upcall(SUBSCRIBE_READING, (reading, 0, 0))
+                .expect("Unable to schedule upcall");
+        }
+    }
+}
+
+impl crate::fake::SyscallDriver for Humidity {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn command(&self, command_num: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            READ_HUMIDITY => {
+                if self.pending.get() {
+                    return crate::command_return::failure(ErrorCode::Busy);
+                }
+                self.pending.set(true);
+                self.deliver();
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60001;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const READ_HUMIDITY: u32 = 1;
+
+const SUBSCRIBE_READING: u32 = 0;
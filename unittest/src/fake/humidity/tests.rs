@@ -0,0 +1,55 @@
+use crate::fake;
+use fake::humidity::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let humidity = Humidity::new();
+
+    assert!(humidity.command(EXISTS, 0, 0).is_success());
+    assert!(humidity.command(READ_HUMIDITY, 0, 0).is_success());
+    assert!(humidity.is_busy());
+    assert_eq!(
+        humidity.command(READ_HUMIDITY, 0, 0).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    humidity.push_reading(4500);
+    assert!(!humidity.is_busy());
+}
+
+// Integration test that verifies Humidity works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let humidity = Humidity::new();
+    kernel.add_driver(&humidity);
+
+    assert!(fake::Syscalls::command(DRIVER_NUM, EXISTS, 0, 0).is_success());
+
+    let reading: Cell<Option<(u32,)>> = Cell::new(None);
+    humidity.push_reading(3725);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_READING>(
+                subscribe, &reading
+            ),
+            Ok(())
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_HUMIDITY, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((3725,)));
+
+        // With no queued readings, the request stays outstanding until one is
+        // pushed.
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_HUMIDITY, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        humidity.push_reading(5010);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((5010,)));
+    });
+}
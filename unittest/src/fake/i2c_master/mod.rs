@@ -0,0 +1,157 @@
+//! Fake implementation of the I2C master API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/20003_i2c_master.md
+//!
+//! Tests script the devices on the bus by queueing one response per transfer
+//! with `push_response`: either the bytes the device returns (for transfers
+//! that read), or an error such as `NOACK`. A transfer that starts with no
+//! response queued stays outstanding, and further transfers fail with `BUSY`,
+//! until one is pushed. The data written by each transfer is recorded and can
+//! be retrieved with `take_writes`.
+
+use core::cell::{Cell, RefCell};
+use core::cmp;
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+pub struct I2cMaster {
+    responses: RefCell<VecDeque<Result<Vec<u8>, ErrorCode>>>,
+    // The number of bytes the outstanding transfer reads, if there is one.
+    pending: Cell<Option<usize>>,
+    writes: RefCell<Vec<(u8, Vec<u8>)>>,
+    write_buffer: RefCell<RoAllowBuffer>,
+    read_buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl I2cMaster {
+    pub fn new() -> std::rc::Rc<I2cMaster> {
+        std::rc::Rc::new(I2cMaster {
+            responses: Default::default(),
+            pending: Cell::new(None),
+            writes: Default::default(),
+            write_buffer: Default::default(),
+            read_buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Queues the response to a future transfer. If a transfer is outstanding,
+    /// it is completed immediately.
+    pub fn push_response(&self, response: Result<&[u8], ErrorCode>) {
+        self.responses
+            .borrow_mut()
+            .push_back(response.map(Vec::from));
+        self.deliver();
+    }
+
+    /// Returns the (address, data) of each write so far, and clears them.
+    pub fn take_writes(&self) -> Vec<(u8, Vec<u8>)> {
+        self.writes.take()
+    }
+
+    fn start(&self, address: usize, write_len: usize, read_len: usize) -> CommandReturn {
+        if self.pending.get().is_some() {
+            return crate::command_return::failure(ErrorCode::Busy);
+        }
+        let write_buffer = self.write_buffer.borrow();
+        if write_len > write_buffer.len() || read_len > self.read_buffer.borrow().len() {
+            return crate::command_return::failure(ErrorCode::Size);
+        }
+        if write_len > 0 {
+            self.writes
+                .borrow_mut()
+                .push((address as u8, Vec::from(&write_buffer[..write_len])));
+        }
+        self.pending.set(Some(read_len));
+        self.deliver();
+        crate::command_return::success()
+    }
+
+    fn deliver(&self) {
+        let read_len = match self.pending.get() {
+            None => return,
+            Some(read_len) => read_len,
+        };
+        let response = match self.responses.borrow_mut().pop_front() {
+            None => return,
+            Some(response) => response,
+        };
+        self.pending.set(None);
+        let args = match response {
+            Err(error) => (error as u32, 0, 0),
+            Ok(data) => {
+                let len = cmp::min(data.len(), read_len);
+                self.read_buffer.borrow_mut()[..len].copy_from_slice(&data[..len]);
+                (0, len as u32, 0)
+            }
+        };
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_DONE, args)
+            .expect("Unable to schedule upcall");
+    }
+}
+
+impl crate::fake::SyscallDriver for I2cMaster {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_WRITE {
+            Ok(self.write_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_READ {
+            Ok(self.read_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            WRITE => self.start(argument0, argument1, 0),
+            READ => self.start(argument0, 0, argument1),
+            WRITE_READ => self.start(argument0, argument1 & 0xffff, argument1 >> 16),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20003;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const WRITE: u32 = 1;
+const READ: u32 = 2;
+const WRITE_READ: u32 = 3;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_WRITE: u32 = 0;
+const ALLOW_READ: u32 = 0;
@@ -0,0 +1,87 @@
+use crate::fake;
+use crate::{RoAllowBuffer, RwAllowBuffer};
+use fake::i2c_master::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let i2c = I2cMaster::new();
+
+    assert!(i2c.command(EXISTS, 0, 0).is_success());
+    assert!(i2c.allow_readonly(1, RoAllowBuffer::default()).is_err());
+    assert!(i2c.allow_readwrite(1, RwAllowBuffer::default()).is_err());
+
+    // Without any buffers, only empty transfers fit.
+    assert_eq!(
+        i2c.command(WRITE, 0x10, 1).get_failure(),
+        Some(ErrorCode::Size)
+    );
+    assert_eq!(
+        i2c.command(READ, 0x10, 1).get_failure(),
+        Some(ErrorCode::Size)
+    );
+    assert!(i2c.command(WRITE, 0x10, 0).is_success());
+    assert_eq!(
+        i2c.command(WRITE, 0x10, 0).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    i2c.push_response(Err(ErrorCode::NoAck));
+    assert!(i2c.command(READ, 0x10, 0).is_success());
+}
+
+// Integration test that verifies I2cMaster works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let i2c = I2cMaster::new();
+    kernel.add_driver(&i2c);
+
+    let done: Cell<Option<(u32, u32)>> = Cell::new(None);
+    let mut read_buffer = [0; 4];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_WRITE>,
+            AllowRw<_, DRIVER_NUM, ALLOW_READ>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_WRITE>(allow_ro, b"\x0f\x10")
+            .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_READ>(
+            allow_rw,
+            &mut read_buffer,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, WRITE_READ, 0x18, 1 | 3 << 16).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        i2c.push_response(Ok(&[1, 2, 3, 4, 5]));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 3)));
+
+        i2c.push_response(Err(ErrorCode::NoAck));
+        assert!(fake::Syscalls::command(DRIVER_NUM, WRITE, 0x19, 2).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((ErrorCode::NoAck as u32, 0)));
+    });
+    assert_eq!(read_buffer, [1, 2, 3, 0]);
+    assert_eq!(
+        i2c.take_writes(),
+        [(0x18, vec![0x0f]), (0x19, vec![0x0f, 0x10])]
+    );
+}
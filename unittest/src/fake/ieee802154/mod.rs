@@ -0,0 +1,224 @@
+//! Fake implementation of the IEEE 802.15.4 raw radio API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/30001_ieee802154.md
+//!
+//! `Radio` is a loopback radio: every frame it transmits is also queued for
+//! reception, so tests can send a frame and then receive it. Frames can also
+//! be queued directly with `push_frame`. A queued frame is delivered as soon
+//! as a receive buffer is shared. Configuration changes only take effect once
+//! committed, as on real hardware.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+pub struct Radio {
+    on: Cell<bool>,
+    // (address, pan, channel), as set and as committed.
+    pending_config: Cell<(u16, u16, u8)>,
+    config: Cell<(u16, u16, u8)>,
+    transmitted: RefCell<Vec<Vec<u8>>>,
+    rx_queue: RefCell<VecDeque<Vec<u8>>>,
+    tx_buffer: RefCell<RoAllowBuffer>,
+    rx_buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Radio {
+    /// Creates a radio that is on, with address 0 on PAN 0 and channel 26.
+    pub fn new() -> std::rc::Rc<Radio> {
+        std::rc::Rc::new(Radio {
+            on: Cell::new(true),
+            pending_config: Cell::new((0, 0, 26)),
+            config: Cell::new((0, 0, 26)),
+            transmitted: Default::default(),
+            rx_queue: Default::default(),
+            tx_buffer: Default::default(),
+            rx_buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on.get()
+    }
+
+    pub fn set_on(&self, on: bool) {
+        self.on.set(on);
+    }
+
+    /// Returns the committed short address.
+    pub fn address(&self) -> u16 {
+        self.config.get().0
+    }
+
+    /// Returns the committed PAN ID.
+    pub fn pan(&self) -> u16 {
+        self.config.get().1
+    }
+
+    /// Returns the committed channel.
+    pub fn channel(&self) -> u8 {
+        self.config.get().2
+    }
+
+    /// Returns the frames transmitted so far, and clears them.
+    pub fn take_transmitted(&self) -> Vec<Vec<u8>> {
+        self.transmitted.take()
+    }
+
+    /// Queues `frame` for reception, as though another node had sent it.
+    pub fn push_frame(&self, frame: &[u8]) {
+        self.rx_queue.borrow_mut().push_back(frame.to_vec());
+        self.deliver();
+    }
+
+    // Copies the oldest queued frame into the receive buffer, if one is
+    // shared. A frame that does not fit is truncated, but the upcall reports
+    // its full length.
+    fn deliver(&self) {
+        let mut rx_buffer = self.rx_buffer.borrow_mut();
+        if rx_buffer.is_empty() {
+            return;
+        }
+        let frame = match self.rx_queue.borrow_mut().pop_front() {
+            Some(frame) => frame,
+            None => return,
+        };
+        let len = frame.len().min(rx_buffer.len());
+        rx_buffer[..len].copy_from_slice(&frame[..len]);
+        self.share_ref
+            .schedule_upcall(FRAME_RECEIVED, (frame.len() as u32, 0, 0))
+            .expect("Unable to schedule upcall");
+    }
+
+    fn transmit(&self, len: usize) -> CommandReturn {
+        if !self.on.get() {
+            return crate::command_return::failure(ErrorCode::Off);
+        }
+        let frame = {
+            let tx_buffer = self.tx_buffer.borrow();
+            if len > tx_buffer.len() {
+                return crate::command_return::failure(ErrorCode::Invalid);
+            }
+            tx_buffer[..len].to_vec()
+        };
+        if len > MAX_FRAME_LEN {
+            return crate::command_return::failure(ErrorCode::Size);
+        }
+        self.transmitted.borrow_mut().push(frame.clone());
+        self.share_ref
+            .schedule_upcall(FRAME_TRANSMITTED, (0, 0, 0))
+            .expect("Unable to schedule upcall");
+        self.push_frame(&frame);
+        crate::command_return::success()
+    }
+}
+
+impl crate::fake::SyscallDriver for Radio {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(2)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_WRITE {
+            Ok(self.tx_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_READ {
+            let old = self.rx_buffer.replace(buffer);
+            self.deliver();
+            Ok(old)
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, _argument1: usize) -> CommandReturn {
+        let (address, pan, channel) = self.pending_config.get();
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            STATUS if self.on.get() => crate::command_return::success(),
+            STATUS => crate::command_return::failure(ErrorCode::Off),
+            SET_SHORT_ADDR => {
+                self.pending_config.set((argument0 as u16, pan, channel));
+                crate::command_return::success()
+            }
+            SET_PAN => {
+                self.pending_config
+                    .set((address, argument0 as u16, channel));
+                crate::command_return::success()
+            }
+            SET_CHAN => {
+                if !(MIN_CHANNEL..=MAX_CHANNEL).contains(&argument0) {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                self.pending_config.set((address, pan, argument0 as u8));
+                crate::command_return::success()
+            }
+            COMMIT_CFG => {
+                self.config.set(self.pending_config.get());
+                crate::command_return::success()
+            }
+            TRANSMIT => self.transmit(argument0),
+            TURN_ON => {
+                self.on.set(true);
+                crate::command_return::success()
+            }
+            TURN_OFF => {
+                self.on.set(false);
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x30001;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const STATUS: u32 = 1;
+const SET_SHORT_ADDR: u32 = 2;
+const SET_PAN: u32 = 4;
+const SET_CHAN: u32 = 5;
+const COMMIT_CFG: u32 = 7;
+const TRANSMIT: u32 = 27;
+const TURN_ON: u32 = 30;
+const TURN_OFF: u32 = 31;
+
+// Upcall numbers
+const FRAME_RECEIVED: u32 = 0;
+const FRAME_TRANSMITTED: u32 = 1;
+
+// Allow numbers
+const ALLOW_WRITE: u32 = 0;
+const ALLOW_READ: u32 = 0;
+
+const MAX_FRAME_LEN: usize = 127;
+const MIN_CHANNEL: usize = 11;
+const MAX_CHANNEL: usize = 26;
@@ -0,0 +1,128 @@
+use crate::fake;
+use fake::ieee802154::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let radio = Radio::new();
+
+    assert!(radio.command(EXISTS, 0, 0).is_success());
+    assert!(radio.command(STATUS, 0, 0).is_success());
+    assert!(radio.command(SET_SHORT_ADDR, 0x1234, 0).is_success());
+    assert!(radio.command(SET_PAN, 0xabcd, 0).is_success());
+    assert!(radio.command(SET_CHAN, 15, 0).is_success());
+    assert_eq!(
+        radio.command(SET_CHAN, 27, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    // Nothing changes until the configuration is committed.
+    assert_eq!(radio.address(), 0);
+    assert!(radio.command(COMMIT_CFG, 0, 0).is_success());
+    assert_eq!(radio.address(), 0x1234);
+    assert_eq!(radio.pan(), 0xabcd);
+    assert_eq!(radio.channel(), 15);
+
+    assert!(radio.command(TURN_OFF, 0, 0).is_success());
+    assert!(!radio.is_on());
+    assert_eq!(
+        radio.command(STATUS, 0, 0).get_failure(),
+        Some(ErrorCode::Off)
+    );
+    assert_eq!(
+        radio.command(TRANSMIT, 0, 0).get_failure(),
+        Some(ErrorCode::Off)
+    );
+    assert!(radio.command(TURN_ON, 0, 0).is_success());
+    assert!(radio.is_on());
+}
+
+// Integration test that verifies Radio works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    let kernel = fake::Kernel::new();
+    let radio = Radio::new();
+    kernel.add_driver(&radio);
+
+    let transmitted: Cell<Option<(u32,)>> = Cell::new(None);
+    let received: Cell<Option<(u32,)>> = Cell::new(None);
+    let mut rx_buffer = [0; 8];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_WRITE>,
+            AllowRw<_, DRIVER_NUM, ALLOW_READ>,
+            Subscribe<_, DRIVER_NUM, FRAME_TRANSMITTED>,
+            Subscribe<_, DRIVER_NUM, FRAME_RECEIVED>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, sub_tx, sub_rx) = handle.split();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, FRAME_TRANSMITTED>(
+            sub_tx,
+            &transmitted,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, FRAME_RECEIVED>(
+            sub_rx, &received,
+        )
+        .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_READ>(allow_rw, &mut rx_buffer)
+            .unwrap();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_WRITE>(allow_ro, b"frame")
+            .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, TRANSMIT, 5, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(transmitted.get(), Some((0,)));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(received.get(), Some((5,)));
+    });
+    assert_eq!(&rx_buffer[..5], b"frame");
+    assert_eq!(radio.take_transmitted(), [b"frame".to_vec()]);
+}
+
+// A frame that arrives while no receive buffer is shared is delivered once
+// one is.
+#[test]
+fn queued_frame() {
+    use core::cell::Cell;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    let kernel = fake::Kernel::new();
+    let radio = Radio::new();
+    kernel.add_driver(&radio);
+    radio.push_frame(b"hello");
+
+    let received: Cell<Option<(u32,)>> = Cell::new(None);
+    let mut rx_buffer = [0; 4];
+    share::scope::<
+        (
+            Subscribe<_, DRIVER_NUM, FRAME_RECEIVED>,
+            AllowRw<_, DRIVER_NUM, ALLOW_READ>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (subscribe, allow_rw) = handle.split();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, FRAME_RECEIVED>(
+            subscribe, &received,
+        )
+        .unwrap();
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_READ>(allow_rw, &mut rx_buffer)
+            .unwrap();
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        // The frame is truncated, but its full length is reported.
+        assert_eq!(received.get(), Some((5,)));
+    });
+    assert_eq!(&rx_buffer, b"hell");
+}
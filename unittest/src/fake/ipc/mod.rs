@@ -0,0 +1,185 @@
+//! Fake implementation of the IPC API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/10000_ipc.md
+//!
+//! `Ipc` simulates a set of processes, each identified by its package name
+//! and an ID. The process under test is created by `new` and has ID 0; other
+//! processes are added with `add_process`. Any process can be discovered and
+//! used as a client or a service:
+//!
+//! - A notification the process under test sends to its own service is routed
+//!   back to it, as a service upcall from client 0.
+//! - A notification it sends to another process is recorded, along with the
+//!   contents of the buffer it shares with that process, and can be retrieved
+//!   with `take_service_notifications`.
+//! - Another process can notify the process under test's service with
+//!   `notify_service`.
+//! - Notifications the process under test sends to clients are recorded and
+//!   can be retrieved with `take_client_notifications`.
+
+use core::cell::RefCell;
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::HashMap;
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+pub struct Ipc {
+    // The package names of the processes, indexed by ID.
+    names: RefCell<Vec<Vec<u8>>>,
+    search: RefCell<RoAllowBuffer>,
+    // The buffers the process under test shares, keyed by service ID.
+    shared: RefCell<HashMap<u32, RwAllowBuffer>>,
+    service_notifications: RefCell<Vec<(u32, Vec<u8>)>>,
+    client_notifications: RefCell<Vec<u32>>,
+    share_ref: DriverShareRef,
+}
+
+impl Ipc {
+    /// Creates an `Ipc` in which the process under test has package name
+    /// `name` and ID 0.
+    pub fn new(name: &[u8]) -> std::rc::Rc<Ipc> {
+        std::rc::Rc::new(Ipc {
+            names: RefCell::new(vec![name.to_vec()]),
+            search: Default::default(),
+            shared: Default::default(),
+            service_notifications: Default::default(),
+            client_notifications: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Adds another process with package name `name`, returning its ID.
+    pub fn add_process(&self, name: &[u8]) -> u32 {
+        let mut names = self.names.borrow_mut();
+        names.push(name.to_vec());
+        (names.len() - 1) as u32
+    }
+
+    /// Notifies the process under test's service on behalf of the client
+    /// process `client_id`, which shares no buffer with it.
+    pub fn notify_service(&self, client_id: u32) {
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_SERVICE, (client_id, 0, 0))
+            .expect("Unable to schedule upcall");
+    }
+
+    /// Returns the notifications the process under test has sent to other
+    /// processes' services since the last call, as the service ID and the
+    /// contents of the buffer shared with it at the time.
+    pub fn take_service_notifications(&self) -> Vec<(u32, Vec<u8>)> {
+        self.service_notifications.take()
+    }
+
+    /// Returns the IDs of the clients the process under test's service has
+    /// notified since the last call.
+    pub fn take_client_notifications(&self) -> Vec<u32> {
+        self.client_notifications.take()
+    }
+
+    fn process_exists(&self, id: usize) -> bool {
+        id < self.names.borrow().len()
+    }
+}
+
+impl crate::fake::SyscallDriver for Ipc {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_SEARCH {
+            Ok(self.search.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    // Read-Write Allow buffers are numbered by the ID of the service they are
+    // shared with.
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if !self.process_exists(buffer_num as usize) {
+            return Err((buffer, ErrorCode::Invalid));
+        }
+        let previous = self.shared.borrow_mut().insert(buffer_num, buffer);
+        Ok(previous.unwrap_or_default())
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            DISCOVER => {
+                let names = self.names.borrow();
+                let search = self.search.borrow();
+                match names.iter().position(|name| **name == **search) {
+                    Some(id) => crate::command_return::success_u32(id as u32),
+                    None => crate::command_return::failure(ErrorCode::NoDevice),
+                }
+            }
+            SERVICE_NOTIFY => {
+                if !self.process_exists(argument0) {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                self.client_notifications
+                    .borrow_mut()
+                    .push(argument0 as u32);
+                crate::command_return::success()
+            }
+            CLIENT_NOTIFY => {
+                if !self.process_exists(argument0) {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                let service_id = argument0 as u32;
+                let contents = self
+                    .shared
+                    .borrow()
+                    .get(&service_id)
+                    .map_or(Vec::new(), |buffer| buffer.to_vec());
+                if service_id == SELF_ID {
+                    self.share_ref
+                        .schedule_upcall(SUBSCRIBE_SERVICE, (SELF_ID, contents.len() as u32, 0))
+                        .expect("Unable to schedule upcall");
+                } else {
+                    self.service_notifications
+                        .borrow_mut()
+                        .push((service_id, contents));
+                }
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x10000;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const DISCOVER: u32 = 1;
+// A client notifies a service.
+const CLIENT_NOTIFY: u32 = 2;
+// A service notifies a client.
+const SERVICE_NOTIFY: u32 = 3;
+
+const SUBSCRIBE_SERVICE: u32 = 0;
+const ALLOW_SEARCH: u32 = 0;
+
+// The ID of the process under test.
+const SELF_ID: u32 = 0;
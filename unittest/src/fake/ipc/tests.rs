@@ -0,0 +1,99 @@
+use crate::fake;
+use fake::ipc::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let ipc = Ipc::new(b"org.tockos.client");
+    let service = ipc.add_process(b"org.tockos.service");
+    assert_eq!(service, 1);
+
+    assert!(ipc.command(EXISTS, 0, 0).is_success());
+    // Nothing has been shared to search for.
+    assert_eq!(
+        ipc.command(DISCOVER, 0, 0).get_failure(),
+        Some(ErrorCode::NoDevice)
+    );
+    assert_eq!(
+        ipc.command(CLIENT_NOTIFY, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        ipc.command(SERVICE_NOTIFY, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+
+    assert!(ipc.command(CLIENT_NOTIFY, 1, 0).is_success());
+    assert_eq!(ipc.take_service_notifications(), [(1, vec![])]);
+    assert!(ipc.command(SERVICE_NOTIFY, 1, 0).is_success());
+    assert_eq!(ipc.take_client_notifications(), [1]);
+    assert_eq!(ipc.take_client_notifications(), []);
+}
+
+// Integration test that verifies Ipc works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    let kernel = fake::Kernel::new();
+    let ipc = Ipc::new(b"org.tockos.client");
+    kernel.add_driver(&ipc);
+    ipc.add_process(b"org.tockos.service");
+
+    let notification: Cell<Option<(u32, u32)>> = Cell::new(None);
+    let mut buffer = *b"ping";
+    let mut own_buffer = [0; 8];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_SEARCH>,
+            AllowRw<_, DRIVER_NUM, 0>,
+            AllowRw<_, DRIVER_NUM, 1>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_SERVICE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_search, allow_self, allow_service, subscribe) = handle.split();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_SERVICE>(
+            subscribe,
+            &notification,
+        )
+        .unwrap();
+
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_SEARCH>(
+            allow_search,
+            b"org.tockos.service",
+        )
+        .unwrap();
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, DISCOVER, 0, 0).get_success_u32(),
+            Some(1)
+        );
+
+        // Notifications to another process are recorded.
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, 1>(allow_service, &mut buffer)
+            .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, CLIENT_NOTIFY, 1, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        assert_eq!(ipc.take_service_notifications(), [(1, b"ping".to_vec())]);
+
+        // Notifications to the process under test's own service are routed
+        // back to it.
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, 0>(allow_self, &mut own_buffer)
+            .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, CLIENT_NOTIFY, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(notification.get(), Some((0, 8)));
+
+        ipc.notify_service(1);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(notification.get(), Some((1, 0)));
+    });
+}
@@ -1,5 +1,9 @@
 use crate::kernel_data::{with_kernel_data, DriverData, KernelData, KERNEL_DATA};
-use crate::{DriverShareRef, ExpectedSyscall, SyscallLogEntry};
+use crate::{
+    DriverShareRef, ExpectedSyscall, ExpectedSyscalls, InvalidSubscribeNum, SyscallLogEntry, Trace,
+    TraceEntry,
+};
+use libtock_platform::CommandReturn;
 use std::cell::Cell;
 
 /// A fake implementation of the Tock kernel. Used with `fake::Syscalls`, which
@@ -35,7 +39,9 @@ impl Kernel {
                 create_location: std::panic::Location::caller(),
                 drivers: Default::default(),
                 expected_syscalls: Default::default(),
+                memop_values: Default::default(),
                 syscall_log: Vec::new(),
+                syscall_log_taken: 0,
                 upcall_queue: Default::default(),
             }))
         });
@@ -55,7 +61,6 @@ impl Kernel {
     // we should probably take the Rc by value. Also, after making that change,
     // maybe we can take a Rc<dyn fake::SyscallDriver> instead of using
     // generics?
-    // TODO: Add a test for add_driver.
     pub fn add_driver<D: crate::fake::SyscallDriver>(&self, driver: &std::rc::Rc<D>) {
         let info = driver.info();
         let driver_data = DriverData {
@@ -104,9 +109,137 @@ impl Kernel {
         });
     }
 
-    /// Returns the system call log and empties it.
+    /// Sets the value that successful memops with number `memop_num` return,
+    /// so tests can describe the process's memory layout (for example,
+    /// `memop::op::MEMORY_START`). Memops return 0 until their value is set.
+    pub fn set_memop_value(&self, memop_num: u32, value: usize) {
+        with_kernel_data(|kernel_data| kernel_data.unwrap().memop_values.insert(memop_num, value));
+    }
+
+    /// Schedules the upcall subscribed to `driver_num` and `subscribe_num` to
+    /// be invoked with `args` by a later Yield call. This is equivalent to the
+    /// driver calling `DriverShareRef::schedule_upcall`, and lets tests deliver
+    /// upcalls for drivers that do not schedule them on their own. Like the
+    /// real kernel, this does nothing if no upcall is subscribed or the upcall
+    /// is the null upcall.
+    ///
+    /// Panics if no driver with number `driver_num` has been added.
+    #[track_caller]
+    pub fn schedule_upcall(
+        &self,
+        driver_num: u32,
+        subscribe_num: u32,
+        args: (u32, u32, u32),
+    ) -> Result<(), InvalidSubscribeNum> {
+        with_kernel_data(|kernel_data| {
+            crate::share_data::schedule_upcall(
+                kernel_data.unwrap(),
+                driver_num,
+                subscribe_num,
+                args,
+            )
+        })
+    }
+
+    /// Returns the system calls made since the last `take_syscall_log` call.
     pub fn take_syscall_log(&self) -> Vec<SyscallLogEntry> {
-        with_kernel_data(|kernel_data| std::mem::take(&mut kernel_data.unwrap().syscall_log))
+        with_kernel_data(|kernel_data| {
+            let kernel_data = kernel_data.unwrap();
+            let taken = kernel_data.syscall_log[kernel_data.syscall_log_taken..]
+                .iter()
+                .map(SyscallLogEntry::from)
+                .collect();
+            kernel_data.syscall_log_taken = kernel_data.syscall_log.len();
+            taken
+        })
+    }
+
+    /// Returns every system call made so far, along with the kernel's
+    /// responses. Unlike `take_syscall_log`, this does not clear the record.
+    pub fn trace(&self) -> Trace {
+        with_kernel_data(|kernel_data| Trace::new(kernel_data.unwrap().syscall_log.clone()))
+    }
+
+    /// Makes the kernel expect the system calls in `trace`, in order, and give
+    /// the responses recorded in it, by adding an expected syscall for each
+    /// entry (see `add_expected_syscall`). Replaying a trace of code that uses
+    /// a driver makes the code see the same results, even if the driver is
+    /// not present.
+    ///
+    /// Subscribe and Allow calls that succeeded in `trace` are still passed to
+    /// the fake drivers, so drivers the code subscribes to or shares buffers
+    /// with must be added. Upcalls are not recorded, so they only run if a fake
+    /// driver schedules them.
+    pub fn replay(&self, trace: &Trace) {
+        for entry in trace.entries() {
+            self.add_expected_syscall(match *entry {
+                TraceEntry::YieldNoWait { result } => ExpectedSyscall::YieldNoWait {
+                    override_return: Some(result),
+                },
+                TraceEntry::YieldWait => ExpectedSyscall::YieldWait { skip_upcall: false },
+                TraceEntry::Subscribe {
+                    driver_num,
+                    subscribe_num,
+                    result,
+                } => ExpectedSyscall::Subscribe {
+                    driver_num,
+                    subscribe_num,
+                    skip_with_error: result.err(),
+                },
+                TraceEntry::Command {
+                    driver_id,
+                    command_id,
+                    argument0,
+                    argument1,
+                    result: (variant, r1, r2, r3),
+                } => ExpectedSyscall::Command {
+                    driver_id,
+                    command_id,
+                    argument0,
+                    argument1,
+                    // Safety: the trace was recorded from (or parsed into)
+                    // valid command return values.
+                    override_return: Some(unsafe { CommandReturn::new(variant, r1, r2, r3) }),
+                },
+                TraceEntry::AllowRo {
+                    driver_num,
+                    buffer_num,
+                    result,
+                    ..
+                } => ExpectedSyscall::AllowRo {
+                    driver_num,
+                    buffer_num,
+                    return_error: result.err(),
+                },
+                TraceEntry::AllowRw {
+                    driver_num,
+                    buffer_num,
+                    result,
+                    ..
+                } => ExpectedSyscall::AllowRw {
+                    driver_num,
+                    buffer_num,
+                    return_error: result.err(),
+                },
+                TraceEntry::Memop {
+                    memop_num,
+                    argument0,
+                    result,
+                } => ExpectedSyscall::Memop {
+                    memop_num,
+                    argument0,
+                    override_return: Some(result),
+                },
+            });
+        }
+    }
+
+    /// Starts building the sequence of system calls the code under test is
+    /// expected to make. Unlike `add_expected_syscall`, this does not alter
+    /// the kernel's behavior: the returned `ExpectedSyscalls` is compared
+    /// against the system call log when its `check` method is called.
+    pub fn expect(&self) -> ExpectedSyscalls<'_> {
+        ExpectedSyscalls::new(self)
     }
 }
 
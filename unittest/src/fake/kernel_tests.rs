@@ -39,9 +39,321 @@ fn syscall_log() {
     assert_eq!(kernel.take_syscall_log(), []);
     with_kernel_data(|kernel_data| {
         let syscall_log = &mut kernel_data.unwrap().syscall_log;
-        syscall_log.push(YieldNoWait);
-        syscall_log.push(YieldWait);
+        syscall_log.push(crate::TraceEntry::YieldNoWait {
+            result: libtock_platform::YieldNoWaitReturn::Upcall,
+        });
+        syscall_log.push(crate::TraceEntry::YieldWait);
     });
     assert_eq!(kernel.take_syscall_log(), [YieldNoWait, YieldWait]);
     assert_eq!(kernel.take_syscall_log(), []);
 }
+
+#[test]
+fn expect() {
+    use libtock_platform::{Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    kernel.expect().check();
+    let _ = fake::Syscalls::command(1, 2, 3, 4);
+    assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    kernel.expect().command(1, 2, 3, 4).yield_no_wait().check();
+    // check() takes the log, so the next check starts from an empty log.
+    kernel.expect().check();
+}
+
+#[test]
+#[should_panic = "do not match the expected system calls"]
+fn expect_mismatch() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let _ = fake::Syscalls::command(1, 2, 3, 4);
+    kernel.expect().command(1, 2, 3, 5).check();
+}
+
+#[test]
+fn schedule_upcall() {
+    use crate::{DriverInfo, InvalidSubscribeNum};
+    use libtock_platform::{
+        share, subscribe::Subscribe, DefaultConfig, Syscalls, YieldNoWaitReturn,
+    };
+    use std::cell::Cell;
+
+    struct MockDriver;
+    impl fake::SyscallDriver for MockDriver {
+        fn info(&self) -> DriverInfo {
+            DriverInfo::new(1).upcall_count(2)
+        }
+        fn command(&self, _: u32, _: usize, _: usize) -> libtock_platform::CommandReturn {
+            crate::command_return::failure(libtock_platform::ErrorCode::NoSupport)
+        }
+    }
+
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&std::rc::Rc::new(MockDriver));
+
+    // Nothing is subscribed yet, so nothing is queued.
+    assert_eq!(kernel.schedule_upcall(1, 1, (1, 2, 3)), Ok(()));
+    assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    assert!(kernel
+        .schedule_upcall(1, 2, (1, 2, 3))
+        .is_err_and(|error: InvalidSubscribeNum| error
+            .to_string()
+            .contains("Upcall number 2 too large")));
+
+    let upcall: Cell<Option<(u32, u32, u32)>> = Cell::new(None);
+    share::scope(|subscribe: share::Handle<Subscribe<_, 1, 1>>| {
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, 1, 1>(subscribe, &upcall).unwrap();
+
+        assert_eq!(kernel.schedule_upcall(1, 1, (1, 2, 3)), Ok(()));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(upcall.take(), Some((1, 2, 3)));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        assert_eq!(kernel.schedule_upcall(1, 1, (4, 5, 6)), Ok(()));
+        fake::Syscalls::yield_wait();
+        assert_eq!(upcall.take(), Some((4, 5, 6)));
+    });
+}
+
+// Adds a driver defined outside libtock_unittest, and checks that system calls
+// reach it.
+#[test]
+fn add_driver() {
+    use crate::{command_return, DriverInfo, RoAllowBuffer};
+    use libtock_platform::{
+        allow_ro::AllowRo, share, CommandReturn, DefaultConfig, ErrorCode, Syscalls,
+    };
+    use std::cell::{Cell, RefCell};
+
+    // Counts up by argument0 when sent command 1, and sums the bytes of its
+    // Read-Only Allow buffer when sent command 2.
+    #[derive(Default)]
+    struct Counter {
+        count: Cell<u32>,
+        buffer: RefCell<RoAllowBuffer>,
+    }
+    impl fake::SyscallDriver for Counter {
+        fn info(&self) -> DriverInfo {
+            DriverInfo::new(0xa0000)
+        }
+        fn command(&self, command_id: u32, argument0: usize, _: usize) -> CommandReturn {
+            match command_id {
+                0 => command_return::success(),
+                1 => {
+                    self.count.set(self.count.get() + argument0 as u32);
+                    command_return::success_u32(self.count.get())
+                }
+                2 => command_return::success_u32(
+                    self.buffer.borrow().iter().map(|&b| b as u32).sum(),
+                ),
+                _ => command_return::failure(ErrorCode::NoSupport),
+            }
+        }
+        fn allow_readonly(
+            &self,
+            buffer_num: u32,
+            buffer: RoAllowBuffer,
+        ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+            match buffer_num {
+                0 => Ok(self.buffer.replace(buffer)),
+                _ => Err((buffer, ErrorCode::NoSupport)),
+            }
+        }
+    }
+
+    let kernel = fake::Kernel::new();
+    assert!(fake::Syscalls::driver_exists(0xa0000).is_err());
+    let counter = std::rc::Rc::new(Counter::default());
+    kernel.add_driver(&counter);
+    assert!(fake::Syscalls::driver_exists(0xa0000).is_ok());
+
+    assert_eq!(
+        fake::Syscalls::command(0xa0000, 1, 2, 0).get_success_u32(),
+        Some(2)
+    );
+    assert_eq!(
+        fake::Syscalls::command(0xa0000, 1, 3, 0).get_success_u32(),
+        Some(5)
+    );
+    assert_eq!(counter.count.get(), 5);
+    assert_eq!(
+        fake::Syscalls::command(0xa0000, 3, 0, 0).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+
+    let bytes = [1, 2, 3];
+    share::scope::<AllowRo<_, 0xa0000, 0>, _, _>(|handle| {
+        fake::Syscalls::allow_ro::<DefaultConfig, 0xa0000, 0>(handle, &bytes).unwrap();
+        assert_eq!(
+            fake::Syscalls::command(0xa0000, 2, 0, 0).get_success_u32(),
+            Some(6)
+        );
+    });
+}
+
+// Opens a scope with four handles to fake::Console, and checks they are all
+// cleaned up, in reverse order, when it ends.
+#[test]
+fn share_scope_four_handles() {
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls};
+    use std::cell::Cell;
+
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new();
+    kernel.add_driver(&console);
+
+    let write_done: Cell<Option<(u32,)>> = Cell::new(None);
+    let read_done: Cell<Option<(u32, u32)>> = Cell::new(None);
+    let mut read_buf = [0; 2];
+    share::scope::<
+        (
+            AllowRo<_, 1, 1>,
+            AllowRw<_, 1, 1>,
+            Subscribe<_, 1, 1>,
+            Subscribe<_, 1, 2>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe_write, subscribe_read) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, 1, 1>(allow_ro, b"hi").unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, 1, 1>(allow_rw, &mut read_buf).unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, 1, 1>(subscribe_write, &write_done)
+            .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, 1, 2>(subscribe_read, &read_done).unwrap();
+        kernel
+            .expect()
+            .allow_ro(1, 1, 2)
+            .allow_rw(1, 1, 2)
+            .subscribe(1, 1)
+            .subscribe(1, 2)
+            .check();
+    });
+    kernel
+        .expect()
+        .subscribe(1, 2)
+        .subscribe(1, 1)
+        .allow_rw(1, 1, 0)
+        .allow_ro(1, 1, 0)
+        .check();
+}
+
+// Records a session with fake::Leds, then replays it without the driver and
+// checks the code under test sees the same results.
+#[test]
+fn trace_replay() {
+    use crate::{Trace, TraceEntry};
+    use libtock_platform::{return_variant, ErrorCode, Syscalls, YieldNoWaitReturn};
+
+    fn session() -> Vec<Option<u32>> {
+        let mut results = vec![fake::Syscalls::command(2, 0, 0, 0).get_success_u32()];
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        for led in [1, 5] {
+            results.push(
+                fake::Syscalls::command(2, 1, led, 0)
+                    .get_failure()
+                    .map(|error| error as u32),
+            );
+        }
+        results
+    }
+
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&fake::Leds::<4>::new());
+    let results = session();
+    assert_eq!(results, [Some(4), None, Some(ErrorCode::Invalid as u32)]);
+    let trace = kernel.trace();
+    assert_eq!(trace.entries().len(), 4);
+    assert_eq!(
+        trace.entries()[0],
+        TraceEntry::Command {
+            driver_id: 2,
+            command_id: 0,
+            argument0: 0,
+            argument1: 0,
+            result: (return_variant::SUCCESS_U32, 4, 0, 0),
+        }
+    );
+    assert_eq!(
+        trace.to_string(),
+        "command 2 0 0 0 -> 129 4 0 0\n\
+         yield-no-wait -> no-upcall\n\
+         command 2 1 1 0 -> 128 0 0 0\n\
+         command 2 1 5 0 -> 0 6 0 0\n"
+    );
+    let parsed: Trace = trace.to_string().parse().unwrap();
+    assert_eq!(parsed, trace);
+    drop(kernel);
+
+    let kernel = fake::Kernel::new();
+    kernel.replay(&parsed);
+    assert_eq!(session(), results);
+    assert_eq!(kernel.trace(), trace);
+}
+
+#[test]
+fn trace_parse_error() {
+    use crate::{ParseTraceError, Trace};
+    let error: ParseTraceError = "yield-wait\n\ncommand 2 1 0 -> 128 0 0 0\n"
+        .parse::<Trace>()
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid trace entry on line 3: \"command 2 1 0 -> 128 0 0 0\""
+    );
+    assert!("subscribe 1 0 -> err 0".parse::<Trace>().is_err());
+    // Memop results need the value the memop returned.
+    assert!("memop 1 0 -> ok".parse::<Trace>().is_err());
+    assert_eq!(
+        "subscribe 1 0 -> err 1\nmemop 1 0 -> ok 0"
+            .parse::<Trace>()
+            .map(|trace| trace.entries().len()),
+        Ok(2)
+    );
+}
+
+// Replaying a memop gives the value it returned when it was recorded.
+#[test]
+fn trace_replay_memop() {
+    use crate::{Trace, TraceEntry};
+    use libtock_platform::{memop, ErrorCode, Syscalls};
+
+    let kernel = fake::Kernel::new();
+    // The value doesn't fit in a u32, to check the trace keeps all of it.
+    kernel.set_memop_value(memop::op::MEMORY_START, 0x1_0000_0000);
+    assert_eq!(
+        fake::Syscalls::memop(memop::op::MEMORY_START, 0).map(usize::from),
+        Ok(0x1_0000_0000)
+    );
+    assert_eq!(fake::Syscalls::memop(12, 0), Err(ErrorCode::NoSupport));
+    // Taking the syscall log does not remove the calls from the trace.
+    assert_eq!(kernel.take_syscall_log().len(), 2);
+    let trace = kernel.trace();
+    assert_eq!(
+        trace.entries()[0],
+        TraceEntry::Memop {
+            memop_num: memop::op::MEMORY_START,
+            argument0: 0,
+            result: Ok(0x1_0000_0000),
+        }
+    );
+    assert_eq!(
+        trace.to_string(),
+        "memop 2 0 -> ok 4294967296\nmemop 12 0 -> err 10\n"
+    );
+    let parsed: Trace = trace.to_string().parse().unwrap();
+    assert_eq!(parsed, trace);
+    drop(kernel);
+
+    // The replaying kernel's memop values are left at 0.
+    let kernel = fake::Kernel::new();
+    kernel.replay(&parsed);
+    assert_eq!(
+        fake::Syscalls::memop(memop::op::MEMORY_START, 0).map(usize::from),
+        Ok(0x1_0000_0000)
+    );
+    assert_eq!(fake::Syscalls::memop(12, 0), Err(ErrorCode::NoSupport));
+    assert_eq!(kernel.trace(), trace);
+}
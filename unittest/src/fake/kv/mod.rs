@@ -0,0 +1,160 @@
+//! Fake implementation of the key-value store API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/50003_key_value.md
+//!
+//! `KvStore` keeps its entries in a `HashMap`, and operations complete
+//! immediately. Entries can be inspected and preloaded with `get` and
+//! `insert`.
+
+use core::cell::RefCell;
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::HashMap;
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+pub struct KvStore {
+    entries: RefCell<HashMap<Vec<u8>, Vec<u8>>>,
+    key: RefCell<RoAllowBuffer>,
+    value_in: RefCell<RoAllowBuffer>,
+    value_out: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl KvStore {
+    pub fn new() -> std::rc::Rc<KvStore> {
+        std::rc::Rc::new(KvStore {
+            entries: Default::default(),
+            key: Default::default(),
+            value_in: Default::default(),
+            value_out: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Returns the value stored under `key`, if any.
+    pub fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.entries.borrow().get(key).cloned()
+    }
+
+    /// Stores `value` under `key`, replacing any existing value.
+    pub fn insert(&self, key: &[u8], value: &[u8]) {
+        self.entries
+            .borrow_mut()
+            .insert(key.to_vec(), value.to_vec());
+    }
+
+    // Runs a command against the store, returning the length reported in the
+    // upcall.
+    fn run(&self, command_num: u32) -> Result<usize, ErrorCode> {
+        let key = self.key.borrow();
+        if key.is_empty() {
+            return Err(ErrorCode::Invalid);
+        }
+        let mut entries = self.entries.borrow_mut();
+        match command_num {
+            GET => {
+                let value = entries.get(&key[..]).ok_or(ErrorCode::NoSupport)?;
+                let mut value_out = self.value_out.borrow_mut();
+                let count = value.len().min(value_out.len());
+                value_out[..count].copy_from_slice(&value[..count]);
+                if count < value.len() {
+                    return Err(ErrorCode::Size);
+                }
+                Ok(value.len())
+            }
+            SET => {
+                entries.insert(key.to_vec(), self.value_in.borrow().to_vec());
+                Ok(0)
+            }
+            DELETE => entries
+                .remove(&key[..])
+                .map(|_| 0)
+                .ok_or(ErrorCode::NoSupport),
+            ADD => {
+                if entries.contains_key(&key[..]) {
+                    return Err(ErrorCode::NoSupport);
+                }
+                entries.insert(key.to_vec(), self.value_in.borrow().to_vec());
+                Ok(0)
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl crate::fake::SyscallDriver for KvStore {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        match buffer_num {
+            ALLOW_KEY => Ok(self.key.replace(buffer)),
+            ALLOW_VALUE_IN => Ok(self.value_in.replace(buffer)),
+            _ => Err((buffer, ErrorCode::Invalid)),
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_VALUE_OUT {
+            Ok(self.value_out.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            GET | SET | DELETE | ADD => {
+                // Like the real driver, errors are reported through the upcall,
+                // along with the length of the value for GET.
+                let args = match self.run(command_num) {
+                    Ok(len) => (0, len as u32, 0),
+                    Err(ErrorCode::Size) => {
+                        let len = self.get(&self.key.borrow()).map_or(0, |value| value.len());
+                        (ErrorCode::Size as u32, len as u32, 0)
+                    }
+                    Err(error) => (error as u32, 0, 0),
+                };
+                self.share_ref
+                    .schedule_upcall(SUBSCRIBE_DONE, args)
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x50003;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const GET: u32 = 1;
+const SET: u32 = 2;
+const DELETE: u32 = 3;
+const ADD: u32 = 4;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_KEY: u32 = 0;
+const ALLOW_VALUE_IN: u32 = 1;
+const ALLOW_VALUE_OUT: u32 = 0;
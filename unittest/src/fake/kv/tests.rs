@@ -0,0 +1,71 @@
+use crate::fake;
+use fake::kv::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let kv = KvStore::new();
+
+    assert!(kv.command(EXISTS, 0, 0).is_success());
+    // Errors are reported through the upcall, so the command succeeds even
+    // without a key.
+    assert!(kv.command(GET, 0, 0).is_success());
+    assert!(kv.command(5, 0, 0).is_failure());
+}
+
+// Integration test that verifies KvStore works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    let kernel = fake::Kernel::new();
+    let kv = KvStore::new();
+    kernel.add_driver(&kv);
+    kv.insert(b"key", b"value");
+
+    let done: Cell<Option<(u32, u32)>> = Cell::new(None);
+    let mut value = [0; 3];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_KEY>,
+            AllowRw<_, DRIVER_NUM, ALLOW_VALUE_OUT>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_key, allow_value, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_KEY>(allow_key, b"key")
+            .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_VALUE_OUT>(
+            allow_value,
+            &mut value,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+
+        // The value doesn't fit, so the prefix that does is copied, and the
+        // upcall reports SIZE along with the full length.
+        assert!(fake::Syscalls::command(DRIVER_NUM, GET, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((ErrorCode::Size as u32, 5)));
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, DELETE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 0)));
+        assert!(fake::Syscalls::command(DRIVER_NUM, DELETE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((ErrorCode::NoSupport as u32, 0)));
+    });
+    assert_eq!(value, *b"val");
+    assert_eq!(kv.get(b"key"), None);
+}
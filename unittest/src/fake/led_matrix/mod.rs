@@ -0,0 +1,125 @@
+//! Fake implementation of an LED matrix driver. Upstream Tock has no LED
+//! matrix driver, so this implements the interface `libtock_led_matrix`
+//! expects: driver number 0x90006, with commands that take an LED's x and y
+//! and complete immediately.
+//!
+//! `LedMatrix` tracks the state of each LED in its grid, for tests to inspect
+//! with `get_led` and `grid`.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, RoAllowBuffer};
+
+pub struct LedMatrix {
+    width: u32,
+    height: u32,
+    // The LEDs, row by row.
+    leds: Vec<Cell<bool>>,
+    frame: RefCell<RoAllowBuffer>,
+}
+
+impl LedMatrix {
+    /// Creates a `width` by `height` matrix with every LED off.
+    pub fn new(width: u32, height: u32) -> std::rc::Rc<LedMatrix> {
+        std::rc::Rc::new(LedMatrix {
+            width,
+            height,
+            leds: (0..width * height).map(|_| Cell::new(false)).collect(),
+            frame: Default::default(),
+        })
+    }
+
+    /// Returns whether the LED at (`x`, `y`) is on, or `None` if it is outside
+    /// the matrix.
+    pub fn get_led(&self, x: u32, y: u32) -> Option<bool> {
+        self.led(x, y).map(Cell::get)
+    }
+
+    /// Returns the state of every LED, row by row from the top.
+    pub fn grid(&self) -> Vec<Vec<bool>> {
+        self.leds
+            .chunks(self.width as usize)
+            .map(|row| row.iter().map(Cell::get).collect())
+            .collect()
+    }
+
+    fn led(&self, x: u32, y: u32) -> Option<&Cell<bool>> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.leds.get((y * self.width + x) as usize)
+    }
+}
+
+impl crate::fake::SyscallDriver for LedMatrix {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM)
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_FRAME {
+            Ok(self.frame.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            DIMENSIONS => crate::command_return::success_2_u32(self.width, self.height),
+            ON | OFF | TOGGLE => {
+                let led = match self.led(argument0 as u32, argument1 as u32) {
+                    Some(led) => led,
+                    None => return crate::command_return::failure(ErrorCode::Invalid),
+                };
+                led.set(match command_num {
+                    ON => true,
+                    OFF => false,
+                    _ => !led.get(),
+                });
+                crate::command_return::success()
+            }
+            CLEAR => {
+                self.leds.iter().for_each(|led| led.set(false));
+                crate::command_return::success()
+            }
+            SET_FRAME => {
+                let frame = self.frame.borrow();
+                if argument0 > frame.len() || argument0 * 8 < self.leds.len() {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                for (i, led) in self.leds.iter().enumerate() {
+                    led.set(frame[i / 8] & 1 << (i % 8) != 0);
+                }
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90006;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const DIMENSIONS: u32 = 1;
+const ON: u32 = 2;
+const OFF: u32 = 3;
+const TOGGLE: u32 = 4;
+const CLEAR: u32 = 5;
+const SET_FRAME: u32 = 6;
+
+const ALLOW_FRAME: u32 = 0;
@@ -0,0 +1,70 @@
+use crate::fake;
+use fake::led_matrix::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let matrix = LedMatrix::new(3, 2);
+
+    assert!(matrix.command(EXISTS, 0, 0).is_success());
+    assert_eq!(
+        matrix.command(DIMENSIONS, 0, 0).get_success_2_u32(),
+        Some((3, 2))
+    );
+    assert!(matrix.command(ON, 2, 1).is_success());
+    assert_eq!(matrix.get_led(2, 1), Some(true));
+    assert!(matrix.command(TOGGLE, 0, 0).is_success());
+    assert!(matrix.command(TOGGLE, 2, 1).is_success());
+    assert_eq!(matrix.grid(), [[true, false, false], [false, false, false]]);
+    assert!(matrix.command(OFF, 0, 0).is_success());
+    assert_eq!(matrix.get_led(0, 0), Some(false));
+    assert_eq!(
+        matrix.command(ON, 3, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        matrix.command(ON, 0, 2).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(matrix.get_led(3, 0), None);
+
+    assert!(matrix.command(ON, 1, 1).is_success());
+    assert!(matrix.command(CLEAR, 0, 0).is_success());
+    assert_eq!(matrix.grid(), [[false; 3], [false; 3]]);
+    assert!(matrix.command(7, 0, 0).is_failure());
+}
+
+// Integration test that verifies LedMatrix works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::{allow_ro::AllowRo, share, DefaultConfig, Syscalls};
+    let kernel = fake::Kernel::new();
+    let matrix = LedMatrix::new(3, 3);
+    kernel.add_driver(&matrix);
+
+    assert!(fake::Syscalls::command(DRIVER_NUM, ON, 1, 2).is_success());
+    assert_eq!(matrix.get_led(1, 2), Some(true));
+
+    // An X: bits 0, 2, 4, 6 and 8.
+    let frame = [0b0101_0101, 0b1];
+    share::scope::<AllowRo<_, DRIVER_NUM, ALLOW_FRAME>, _, _>(|handle| {
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_FRAME>(handle, &frame).unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, SET_FRAME, 2, 0).is_success());
+        // A frame that doesn't cover the matrix is refused.
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, SET_FRAME, 1, 0).get_failure(),
+            Some(ErrorCode::Invalid)
+        );
+    });
+    assert_eq!(
+        matrix.grid(),
+        [
+            [true, false, true],
+            [false, true, false],
+            [true, false, true]
+        ]
+    );
+}
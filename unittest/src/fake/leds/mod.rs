@@ -3,6 +3,9 @@
 //!
 //! Like the real API, `Leds` controls a set of fake LEDs. It provides
 //! a function `get_led` used to retrieve the state of an LED.
+//!
+//! By default, `Leds` behaves like a kernel without the set-mask command, which
+//! it rejects with `NoSupport`. `set_mask_supported` enables the command.
 
 use crate::DriverInfo;
 use core::cell::Cell;
@@ -10,6 +13,7 @@ use libtock_platform::{CommandReturn, ErrorCode};
 
 pub struct Leds<const LEDS_COUNT: usize> {
     leds: [Cell<bool>; LEDS_COUNT],
+    set_mask_supported: Cell<bool>,
 }
 
 impl<const LEDS_COUNT: usize> Leds<LEDS_COUNT> {
@@ -18,12 +22,19 @@ impl<const LEDS_COUNT: usize> Leds<LEDS_COUNT> {
         const OFF: Cell<bool> = Cell::new(false);
         std::rc::Rc::new(Leds {
             leds: [OFF; LEDS_COUNT],
+            set_mask_supported: Cell::new(false),
         })
     }
 
     pub fn get_led(&self, led: u32) -> Option<bool> {
         self.leds.get(led as usize).map(|led| led.get())
     }
+
+    /// Sets whether the set-mask command is supported, to simulate either a
+    /// kernel that has it or a legacy kernel that does not.
+    pub fn set_mask_supported(&self, supported: bool) {
+        self.set_mask_supported.set(supported);
+    }
 }
 
 impl<const LEDS_COUNT: usize> crate::fake::SyscallDriver for Leds<LEDS_COUNT> {
@@ -58,6 +69,13 @@ impl<const LEDS_COUNT: usize> crate::fake::SyscallDriver for Leds<LEDS_COUNT> {
                     crate::command_return::failure(ErrorCode::Invalid)
                 }
             }
+            LED_SET_MASK if self.set_mask_supported.get() => {
+                for (n, led) in self.leds.iter().enumerate() {
+                    let bit = argument0.checked_shr(n as u32).unwrap_or(0) & 1;
+                    led.set(bit != 0);
+                }
+                crate::command_return::success()
+            }
             _ => crate::command_return::failure(ErrorCode::NoSupport),
         }
     }
@@ -77,3 +95,4 @@ const DRIVER_CHECK: u32 = 0;
 const LED_ON: u32 = 1;
 const LED_OFF: u32 = 2;
 const LED_TOGGLE: u32 = 3;
+const LED_SET_MASK: u32 = 4;
@@ -24,6 +24,22 @@ fn command() {
     assert_eq!(leds.get_led(0), Some(false));
 }
 
+#[test]
+fn set_mask() {
+    use fake::SyscallDriver;
+    let leds = Leds::<3>::new();
+    assert_eq!(
+        leds.command(LED_SET_MASK, 0b101, 0).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+    assert_eq!(leds.get_led(0), Some(false));
+    leds.set_mask_supported(true);
+    assert!(leds.command(LED_SET_MASK, 0b101, 0).is_success());
+    assert_eq!(leds.get_led(0), Some(true));
+    assert_eq!(leds.get_led(1), Some(false));
+    assert_eq!(leds.get_led(2), Some(true));
+}
+
 // Integration test that verifies Leds works with fake::Kernel and
 // libtock_platform::Syscalls.
 #[test]
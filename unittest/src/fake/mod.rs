@@ -9,25 +9,93 @@
 //! `use libtock_unittest::fake` and refer to the type with the `fake::` prefix
 //! (e.g. `fake::Console`).
 
+mod adc;
+mod aes;
 mod alarm;
+mod ambient_light;
+mod analog_comparator;
 mod buttons;
+mod buzzer;
+mod can;
 mod console;
+mod crc;
+mod ctap;
+mod dac;
 mod gpio;
+mod hmac;
+mod humidity;
+mod i2c_master;
+mod ieee802154;
+mod ipc;
 mod kernel;
+mod kv;
+mod led_matrix;
 mod leds;
 mod low_level_debug;
+mod moisture;
+mod ninedof;
+mod nonvolatile_storage;
+mod pressure;
+mod proximity;
+mod pwm;
+mod rng;
+mod rtc;
+mod screen;
+mod servo;
+mod sha;
+mod sound_pressure;
+mod spi_controller;
 mod syscall_driver;
 mod syscalls;
+mod temperature;
+mod text_screen;
+mod touch;
+mod udp;
+mod usb;
 
+pub use adc::Adc;
+pub use aes::Aes;
 pub use alarm::Alarm;
+pub use ambient_light::AmbientLight;
+pub use analog_comparator::AnalogComparator;
 pub use buttons::Buttons;
+pub use buzzer::Buzzer;
+pub use can::Can;
 pub use console::Console;
+pub use crc::Crc;
+pub use ctap::Ctap;
+pub use dac::Dac;
 pub use gpio::{Gpio, GpioMode, InterruptEdge, PullMode};
+pub use hmac::Hmac;
+pub use humidity::Humidity;
+pub use i2c_master::I2cMaster;
+pub use ieee802154::Radio;
+pub use ipc::Ipc;
 pub use kernel::Kernel;
+pub use kv::KvStore;
+pub use led_matrix::LedMatrix;
 pub use leds::Leds;
 pub use low_level_debug::{LowLevelDebug, Message};
+pub use moisture::Moisture;
+pub use ninedof::NineDof;
+pub use nonvolatile_storage::NonvolatileStorage;
+pub use pressure::Pressure;
+pub use proximity::Proximity;
+pub use pwm::Pwm;
+pub use rng::Rng;
+pub use rtc::Rtc;
+pub use screen::Screen;
+pub use servo::Servo;
+pub use sha::Sha;
+pub use sound_pressure::SoundPressure;
+pub use spi_controller::SpiController;
 pub use syscall_driver::SyscallDriver;
 pub use syscalls::Syscalls;
+pub use temperature::Temperature;
+pub use text_screen::TextScreen;
+pub use touch::Touch;
+pub use udp::UdpDriver;
+pub use usb::Usb;
 
 #[cfg(test)]
 mod kernel_tests;
@@ -0,0 +1,62 @@
+use crate::fake;
+use fake::moisture::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let moisture = Moisture::new();
+
+    assert!(moisture.command(EXISTS, 0, 0).is_success());
+    assert!(moisture.command(READ_MOISTURE, 0, 0).is_success());
+    assert!(moisture.is_busy());
+    assert_eq!(
+        moisture.command(READ_MOISTURE, 0, 0).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    moisture.push_reading(3150);
+    assert!(!moisture.is_busy());
+
+    moisture.set_enabled(false);
+    assert_eq!(
+        moisture.command(READ_MOISTURE, 0, 0).get_failure(),
+        Some(ErrorCode::Off)
+    );
+    assert!(!moisture.is_busy());
+}
+
+// Integration test that verifies Moisture works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let moisture = Moisture::new();
+    kernel.add_driver(&moisture);
+
+    assert!(fake::Syscalls::command(DRIVER_NUM, EXISTS, 0, 0).is_success());
+
+    let reading: Cell<Option<(u32,)>> = Cell::new(None);
+    moisture.push_reading(3150);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_READING>(
+                subscribe, &reading
+            ),
+            Ok(())
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_MOISTURE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((3150,)));
+
+        // With no queued readings, the request stays outstanding until one is
+        // pushed.
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_MOISTURE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        moisture.push_reading(2975);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((2975,)));
+    });
+}
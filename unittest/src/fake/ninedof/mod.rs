@@ -0,0 +1,86 @@
+//! Fake implementation of the NineDof API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/60004_ninedof.md
+//!
+//! Like the real API, `NineDof` provides (x, y, z) readings from an
+//! accelerometer, a gyroscope and a magnetometer. Tests set the reading each
+//! sensor reports; read requests are answered immediately.
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef};
+
+pub struct NineDof {
+    accelerometer: Cell<(i32, i32, i32)>,
+    gyroscope: Cell<(i32, i32, i32)>,
+    magnetometer: Cell<(i32, i32, i32)>,
+    share_ref: DriverShareRef,
+}
+
+impl NineDof {
+    pub fn new() -> std::rc::Rc<NineDof> {
+        std::rc::Rc::new(NineDof {
+            accelerometer: Cell::new((0, 0, 0)),
+            gyroscope: Cell::new((0, 0, 0)),
+            magnetometer: Cell::new((0, 0, 0)),
+            share_ref: Default::default(),
+        })
+    }
+
+    pub fn set_accelerometer(&self, reading: (i32, i32, i32)) {
+        self.accelerometer.set(reading);
+    }
+
+    pub fn set_gyroscope(&self, reading: (i32, i32, i32)) {
+        self.gyroscope.set(reading);
+    }
+
+    pub fn set_magnetometer(&self, reading: (i32, i32, i32)) {
+        self.magnetometer.set(reading);
+    }
+
+    fn report(&self, sensor: &Cell<(i32, i32, i32)>) -> CommandReturn {
+        let (x, y, z) = sensor.get();
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_READING, (x as u32, y as u32, z as u32))
+            .expect("Unable to schedule upcall");
+        crate::command_return::success()
+    }
+}
+
+impl crate::fake::SyscallDriver for NineDof {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn command(&self, command_num: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            READ_ACCELEROMETER => self.report(&self.accelerometer),
+            READ_MAGNETOMETER => self.report(&self.magnetometer),
+            READ_GYROSCOPE => self.report(&self.gyroscope),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60004;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const READ_ACCELEROMETER: u32 = 1;
+const READ_MAGNETOMETER: u32 = 100;
+const READ_GYROSCOPE: u32 = 200;
+
+const SUBSCRIBE_READING: u32 = 0;
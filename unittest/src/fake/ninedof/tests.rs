@@ -0,0 +1,50 @@
+use crate::fake;
+use fake::ninedof::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let ninedof = NineDof::new();
+
+    assert!(ninedof.command(EXISTS, 0, 0).is_success());
+    assert!(ninedof.command(READ_ACCELEROMETER, 0, 0).is_success());
+    assert!(ninedof.command(READ_MAGNETOMETER, 0, 0).is_success());
+    assert!(ninedof.command(READ_GYROSCOPE, 0, 0).is_success());
+    assert!(ninedof.command(2, 0, 0).is_failure());
+}
+
+// Integration test that verifies NineDof works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let ninedof = NineDof::new();
+    kernel.add_driver(&ninedof);
+
+    ninedof.set_accelerometer((1, 2, 3));
+    ninedof.set_gyroscope((4, 5, 6));
+    ninedof.set_magnetometer((-7, 8, -9));
+    let reading: Cell<Option<(u32, u32, u32)>> = Cell::new(None);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_READING>(
+                subscribe, &reading
+            ),
+            Ok(())
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_ACCELEROMETER, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((1, 2, 3)));
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_GYROSCOPE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((4, 5, 6)));
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_MAGNETOMETER, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((-7i32 as u32, 8, -9i32 as u32)));
+    });
+}
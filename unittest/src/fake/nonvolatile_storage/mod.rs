@@ -0,0 +1,153 @@
+//! Fake implementation of the nonvolatile storage API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/50001_nonvolatile_storage.md
+//!
+//! `NonvolatileStorage` is backed by RAM, which starts out erased (all bytes
+//! 0xff). Reads and writes complete immediately. `set_max_bytes` limits how
+//! many bytes each operation handles, like the kernel's internal buffer does,
+//! to exercise operations that take several upcalls, and `set_extra_bytes`
+//! makes it report handling more bytes than it did, like a faulty kernel.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+pub struct NonvolatileStorage {
+    storage: RefCell<Vec<u8>>,
+    max_bytes: Cell<usize>,
+    extra_bytes: Cell<u32>,
+    read_buffer: RefCell<RwAllowBuffer>,
+    write_buffer: RefCell<RoAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl NonvolatileStorage {
+    /// Creates a `NonvolatileStorage` with `size` bytes of erased storage.
+    pub fn new(size: usize) -> std::rc::Rc<NonvolatileStorage> {
+        std::rc::Rc::new(NonvolatileStorage {
+            storage: RefCell::new(vec![0xff; size]),
+            max_bytes: Cell::new(usize::MAX),
+            extra_bytes: Cell::new(0),
+            read_buffer: Default::default(),
+            write_buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    pub fn set_max_bytes(&self, max_bytes: usize) {
+        self.max_bytes.set(max_bytes);
+    }
+
+    /// Adds `extra_bytes` to the byte count each operation reports.
+    pub fn set_extra_bytes(&self, extra_bytes: u32) {
+        self.extra_bytes.set(extra_bytes);
+    }
+
+    /// Returns a copy of the entire storage.
+    pub fn contents(&self) -> Vec<u8> {
+        self.storage.borrow().clone()
+    }
+
+    // Checks a request against the buffer and storage sizes, and returns the
+    // number of bytes that should be handled.
+    fn request_len(&self, offset: usize, len: usize, buffer_len: usize) -> Option<usize> {
+        if len > buffer_len || offset.checked_add(len)? > self.storage.borrow().len() {
+            return None;
+        }
+        Some(len.min(self.max_bytes.get()))
+    }
+}
+
+impl crate::fake::SyscallDriver for NonvolatileStorage {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(2)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_WRITE {
+            Ok(self.write_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_READ {
+            Ok(self.read_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            GET_SIZE => crate::command_return::success_u32(self.storage.borrow().len() as u32),
+            READ => {
+                let mut buffer = self.read_buffer.borrow_mut();
+                let count = match self.request_len(argument0, argument1, buffer.len()) {
+                    Some(count) => count,
+                    None => return crate::command_return::failure(ErrorCode::Invalid),
+                };
+                buffer[..count]
+                    .copy_from_slice(&self.storage.borrow()[argument0..argument0 + count]);
+                self.share_ref
+                    .schedule_upcall(
+                        SUBSCRIBE_READ_DONE,
+                        (count as u32 + self.extra_bytes.get(), 0, 0),
+                    )
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            WRITE => {
+                let buffer = self.write_buffer.borrow();
+                let count = match self.request_len(argument0, argument1, buffer.len()) {
+                    Some(count) => count,
+                    None => return crate::command_return::failure(ErrorCode::Invalid),
+                };
+                self.storage.borrow_mut()[argument0..argument0 + count]
+                    .copy_from_slice(&buffer[..count]);
+                self.share_ref
+                    .schedule_upcall(
+                        SUBSCRIBE_WRITE_DONE,
+                        (count as u32 + self.extra_bytes.get(), 0, 0),
+                    )
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x50001;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const GET_SIZE: u32 = 1;
+const READ: u32 = 2;
+const WRITE: u32 = 3;
+
+const SUBSCRIBE_READ_DONE: u32 = 0;
+const SUBSCRIBE_WRITE_DONE: u32 = 1;
+const ALLOW_READ: u32 = 0;
+const ALLOW_WRITE: u32 = 0;
@@ -0,0 +1,87 @@
+use crate::fake;
+use fake::nonvolatile_storage::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let storage = NonvolatileStorage::new(32);
+
+    assert!(storage.command(EXISTS, 0, 0).is_success());
+    assert_eq!(storage.command(GET_SIZE, 0, 0).get_success_u32(), Some(32));
+    // Without a buffer, only empty requests are valid.
+    assert!(storage.command(READ, 0, 0).is_success());
+    assert_eq!(
+        storage.command(READ, 0, 4).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        storage.command(WRITE, 0, 4).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+}
+
+// Integration test that verifies NonvolatileStorage works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    let kernel = fake::Kernel::new();
+    let storage = NonvolatileStorage::new(16);
+    kernel.add_driver(&storage);
+    storage.set_max_bytes(3);
+
+    let done: Cell<Option<(u32,)>> = Cell::new(None);
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_WRITE>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_WRITE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_WRITE>(allow_ro, b"abcd")
+            .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_WRITE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, WRITE, 2, 4).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+    });
+    // Only max_bytes bytes are written.
+    assert_eq!(done.get(), Some((3,)));
+    assert_eq!(
+        storage.contents()[..6],
+        [0xff, 0xff, b'a', b'b', b'c', 0xff]
+    );
+
+    let done: Cell<Option<(u32,)>> = Cell::new(None);
+    let mut buffer = [0; 4];
+    share::scope::<
+        (
+            AllowRw<_, DRIVER_NUM, ALLOW_READ>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_READ_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_READ>(allow_rw, &mut buffer)
+            .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_READ_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ, 3, 2).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+    });
+    assert_eq!(done.get(), Some((2,)));
+    assert_eq!(buffer, [b'b', b'c', 0, 0]);
+}
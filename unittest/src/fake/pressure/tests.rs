@@ -0,0 +1,62 @@
+use crate::fake;
+use fake::pressure::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let pressure = Pressure::new();
+
+    assert!(pressure.command(EXISTS, 0, 0).is_success());
+    assert!(pressure.command(READ_PRESSURE, 0, 0).is_success());
+    assert!(pressure.is_busy());
+    assert_eq!(
+        pressure.command(READ_PRESSURE, 0, 0).get_failure(),
+        Some(ErrorCode::Busy)
+    );
+    pressure.push_reading(101_325);
+    assert!(!pressure.is_busy());
+
+    pressure.set_enabled(false);
+    assert_eq!(
+        pressure.command(READ_PRESSURE, 0, 0).get_failure(),
+        Some(ErrorCode::Off)
+    );
+    assert!(!pressure.is_busy());
+}
+
+// Integration test that verifies Pressure works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let pressure = Pressure::new();
+    kernel.add_driver(&pressure);
+
+    assert!(fake::Syscalls::command(DRIVER_NUM, EXISTS, 0, 0).is_success());
+
+    let reading: Cell<Option<(u32,)>> = Cell::new(None);
+    pressure.push_reading(101_325);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_READING>(
+                subscribe, &reading
+            ),
+            Ok(())
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_PRESSURE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((101_325,)));
+
+        // With no queued readings, the request stays outstanding until one is
+        // pushed.
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_PRESSURE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        pressure.push_reading(99_000);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((99_000,)));
+    });
+}
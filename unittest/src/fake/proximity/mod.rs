@@ -0,0 +1,99 @@
+//! Fake implementation of the proximity API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/60005_proximity.md
+//!
+//! Tests script the sensor with `set_value`. Read requests complete
+//! immediately with the current value. Interrupt requests fire as soon as the
+//! value is outside their band, which may be immediately.
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef};
+
+pub struct Proximity {
+    value: Cell<u8>,
+    // The band of an outstanding interrupt request.
+    band: Cell<Option<(u8, u8)>>,
+    share_ref: DriverShareRef,
+}
+
+impl Proximity {
+    pub fn new() -> std::rc::Rc<Proximity> {
+        std::rc::Rc::new(Proximity {
+            value: Cell::new(0),
+            band: Cell::new(None),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Sets the proximity the sensor measures. If this moves it out of the
+    /// band of an outstanding interrupt request, the interrupt fires.
+    pub fn set_value(&self, value: u8) {
+        self.value.set(value);
+        self.check_band();
+    }
+
+    /// Returns true if an interrupt request is waiting for the value to leave
+    /// its band.
+    pub fn is_armed(&self) -> bool {
+        self.band.get().is_some()
+    }
+
+    fn check_band(&self) {
+        let value = self.value.get();
+        if let Some((lower, upper)) = self.band.get() {
+            if value < lower || value > upper {
+                self.band.set(None);
+                self.report(value);
+            }
+        }
+    }
+
+    fn report(&self, value: u8) {
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_READING, (value as u32, 0, 0))
+            .expect("Unable to schedule upcall");
+    }
+}
+
+impl crate::fake::SyscallDriver for Proximity {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => {}
+            READ => self.report(self.value.get()),
+            READ_ON_INTERRUPT => {
+                if argument0 > argument1 || argument1 > u8::MAX as usize {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                self.band.set(Some((argument0 as u8, argument1 as u8)));
+                self.check_band();
+            }
+            _ => return crate::command_return::failure(ErrorCode::NoSupport),
+        }
+        crate::command_return::success()
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60005;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const READ: u32 = 1;
+const READ_ON_INTERRUPT: u32 = 2;
+
+const SUBSCRIBE_READING: u32 = 0;
@@ -0,0 +1,58 @@
+use crate::fake;
+use fake::proximity::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let proximity = Proximity::new();
+
+    assert!(proximity.command(EXISTS, 0, 0).is_success());
+    assert!(proximity.command(READ, 0, 0).is_success());
+    assert_eq!(
+        proximity.command(READ_ON_INTERRUPT, 20, 10).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(!proximity.is_armed());
+    assert!(proximity.command(READ_ON_INTERRUPT, 0, 10).is_success());
+    assert!(proximity.is_armed());
+    proximity.set_value(11);
+    assert!(!proximity.is_armed());
+}
+
+// Integration test that verifies Proximity works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let proximity = Proximity::new();
+    kernel.add_driver(&proximity);
+
+    let reading: Cell<Option<(u32,)>> = Cell::new(None);
+    proximity.set_value(30);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_READING>(
+                subscribe, &reading
+            ),
+            Ok(())
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((30,)));
+
+        // The interrupt only fires once the value leaves the band.
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_ON_INTERRUPT, 20, 40).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        proximity.set_value(19);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((19,)));
+
+        // A value already outside the band fires immediately.
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_ON_INTERRUPT, 20, 40).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+    });
+}
@@ -0,0 +1,90 @@
+//! Fake implementation of the PWM API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/00010_pwm.md
+//!
+//! Like the real API, `Pwm` controls a set of output channels. Tests inspect the
+//! frequency and duty cycle each channel is outputting with `output`.
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::DriverInfo;
+
+pub struct Pwm<const NUM_CHANNELS: usize> {
+    // The (frequency, duty cycle) of each running channel.
+    outputs: [Cell<Option<(u32, u32)>>; NUM_CHANNELS],
+    max_frequency: Cell<u32>,
+}
+
+impl<const NUM_CHANNELS: usize> Pwm<NUM_CHANNELS> {
+    pub fn new() -> std::rc::Rc<Pwm<NUM_CHANNELS>> {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const STOPPED: Cell<Option<(u32, u32)>> = Cell::new(None);
+        std::rc::Rc::new(Pwm {
+            outputs: [STOPPED; NUM_CHANNELS],
+            max_frequency: Cell::new(1_000_000),
+        })
+    }
+
+    /// Returns the (frequency in Hz, duty cycle in hundredths of a percent)
+    /// that `channel` is outputting, or `None` if it is stopped.
+    pub fn output(&self, channel: u32) -> Option<(u32, u32)> {
+        self.outputs.get(channel as usize).and_then(Cell::get)
+    }
+
+    pub fn set_max_frequency(&self, frequency_hz: u32) {
+        self.max_frequency.set(frequency_hz);
+    }
+}
+
+impl<const NUM_CHANNELS: usize> crate::fake::SyscallDriver for Pwm<NUM_CHANNELS> {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM)
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, argument1: usize) -> CommandReturn {
+        if command_num == CHANNEL_COUNT {
+            return crate::command_return::success_u32(NUM_CHANNELS as u32);
+        }
+        // The other commands identify the channel in the low half of the
+        // first argument.
+        let output = match self.outputs.get(argument0 & 0xffff) {
+            None => return crate::command_return::failure(ErrorCode::Invalid),
+            Some(output) => output,
+        };
+        match command_num {
+            START => {
+                let duty_cycle = (argument0 >> 16) as u32;
+                let frequency = argument1 as u32;
+                if duty_cycle > MAX_DUTY_CYCLE || frequency > self.max_frequency.get() {
+                    return crate::command_return::failure(ErrorCode::Invalid);
+                }
+                output.set(Some((frequency, duty_cycle)));
+                crate::command_return::success()
+            }
+            STOP => {
+                output.set(None);
+                crate::command_return::success()
+            }
+            MAX_FREQUENCY => crate::command_return::success_u32(self.max_frequency.get()),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x10;
+
+// Command numbers
+const START: u32 = 1;
+const STOP: u32 = 2;
+const MAX_FREQUENCY: u32 = 3;
+const CHANNEL_COUNT: u32 = 4;
+
+// Duty cycles are in hundredths of a percent.
+const MAX_DUTY_CYCLE: u32 = 10000;
@@ -0,0 +1,54 @@
+use crate::fake;
+use fake::pwm::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let pwm = Pwm::<2>::new();
+
+    assert_eq!(pwm.command(CHANNEL_COUNT, 0, 0).get_success_u32(), Some(2));
+    assert_eq!(
+        pwm.command(MAX_FREQUENCY, 1, 0).get_success_u32(),
+        Some(1_000_000)
+    );
+    assert_eq!(
+        pwm.command(MAX_FREQUENCY, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+
+    assert!(pwm.command(START, 5000 << 16 | 1, 400).is_success());
+    assert_eq!(pwm.output(1), Some((400, 5000)));
+    assert_eq!(
+        pwm.command(START, 10001 << 16, 400).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        pwm.command(START, 0, 1_000_001).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(pwm.output(0), None);
+
+    assert!(pwm.command(STOP, 1, 0).is_success());
+    assert_eq!(pwm.output(1), None);
+}
+
+// Integration test that verifies Pwm works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let pwm = Pwm::<2>::new();
+    kernel.add_driver(&pwm);
+
+    assert_eq!(
+        fake::Syscalls::command(DRIVER_NUM, CHANNEL_COUNT, 0, 0).get_success_u32(),
+        Some(2)
+    );
+    assert!(fake::Syscalls::command(DRIVER_NUM, START, 2500 << 16, 60).is_success());
+    assert_eq!(pwm.output(0), Some((60, 2500)));
+    assert!(fake::Syscalls::command(DRIVER_NUM, STOP, 0, 0).is_success());
+    assert_eq!(pwm.output(0), None);
+}
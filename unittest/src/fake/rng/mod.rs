@@ -0,0 +1,108 @@
+//! Fake implementation of the RNG API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/40001_rng.md
+//!
+//! `Rng` generates bytes from a fixed-seed pseudorandom sequence, so tests are
+//! reproducible. Requests complete immediately. `set_max_bytes` limits how
+//! many bytes each request generates, to exercise short fills.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef, RwAllowBuffer};
+
+pub struct Rng {
+    state: Cell<u32>,
+    max_bytes: Cell<usize>,
+    buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Rng {
+    pub fn new() -> std::rc::Rc<Rng> {
+        Self::new_with_seed(DEFAULT_SEED)
+    }
+
+    /// Creates a `Rng` that generates a different sequence. `seed` must be
+    /// nonzero.
+    pub fn new_with_seed(seed: u32) -> std::rc::Rc<Rng> {
+        assert_ne!(seed, 0, "xorshift seeds must be nonzero");
+        std::rc::Rc::new(Rng {
+            state: Cell::new(seed),
+            max_bytes: Cell::new(usize::MAX),
+            buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    pub fn set_max_bytes(&self, max_bytes: usize) {
+        self.max_bytes.set(max_bytes);
+    }
+
+    // Returns the next byte of the sequence (a 32-bit xorshift generator).
+    fn next_byte(&self) -> u8 {
+        let mut x = self.state.get();
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state.set(x);
+        x as u8
+    }
+}
+
+impl crate::fake::SyscallDriver for Rng {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_BUFFER {
+            Ok(self.buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            GET_RANDOM => {
+                let mut buffer = self.buffer.borrow_mut();
+                let count = argument0.min(buffer.len()).min(self.max_bytes.get());
+                for byte in &mut buffer[..count] {
+                    *byte = self.next_byte();
+                }
+                self.share_ref
+                    .schedule_upcall(SUBSCRIBE_DONE, (0, count as u32, 0))
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40001;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const GET_RANDOM: u32 = 1;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_BUFFER: u32 = 0;
+
+const DEFAULT_SEED: u32 = 0x2545_f491;
@@ -0,0 +1,70 @@
+use crate::fake;
+use fake::rng::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let rng = Rng::new();
+
+    assert!(rng.command(EXISTS, 0, 0).is_success());
+    // Without a buffer, no bytes are generated, but the request succeeds.
+    assert!(rng.command(GET_RANDOM, 4, 0).is_success());
+}
+
+// Integration test that verifies Rng works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    // Fills a buffer of `len` bytes with `count` bytes from a fresh `Rng`.
+    fn fill(rng: &std::rc::Rc<Rng>, len: usize, count: usize) -> (Vec<u8>, Option<(u32, u32)>) {
+        let kernel = fake::Kernel::new();
+        kernel.add_driver(rng);
+        let done: Cell<Option<(u32, u32)>> = Cell::new(None);
+        let mut buffer = vec![0; len];
+        share::scope::<
+            (
+                AllowRw<_, DRIVER_NUM, ALLOW_BUFFER>,
+                Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+            ),
+            _,
+            _,
+        >(|handle| {
+            let (allow_rw, subscribe) = handle.split();
+            fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_BUFFER>(
+                allow_rw,
+                &mut buffer,
+            )
+            .unwrap();
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+                subscribe, &done,
+            )
+            .unwrap();
+            assert!(fake::Syscalls::command(DRIVER_NUM, GET_RANDOM, count, 0).is_success());
+            assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        });
+        (buffer, done.get())
+    }
+
+    // The same seed produces the same bytes; a different seed doesn't.
+    let (first, done) = fill(&Rng::new(), 8, 8);
+    assert_eq!(done, Some((0, 8)));
+    assert_eq!(fill(&Rng::new(), 8, 8).0, first);
+    assert_ne!(fill(&Rng::new_with_seed(7), 8, 8).0, first);
+
+    // Only the requested bytes are written, and no more than max_bytes.
+    let (buffer, done) = fill(&Rng::new(), 8, 5);
+    assert_eq!(done, Some((0, 5)));
+    assert_eq!(buffer[..5], first[..5]);
+    assert_eq!(buffer[5..], [0, 0, 0]);
+    let rng = Rng::new();
+    rng.set_max_bytes(2);
+    let (buffer, done) = fill(&rng, 8, 8);
+    assert_eq!(done, Some((0, 2)));
+    assert_eq!(buffer[2..], [0; 6]);
+}
@@ -0,0 +1,79 @@
+//! Fake implementation of the RTC API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/90007_rtc.md
+//!
+//! `Rtc` holds the date and time in the driver's packed format, which tests
+//! can read and change with `date_time` and `set_date_time`. The clock does not
+//! advance on its own. Requests complete immediately.
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef};
+
+pub struct Rtc {
+    date_time: Cell<(u32, u32)>,
+    share_ref: DriverShareRef,
+}
+
+impl Rtc {
+    pub fn new() -> std::rc::Rc<Rtc> {
+        std::rc::Rc::new(Rtc {
+            date_time: Cell::new((0, 0)),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Returns the date and time as packed `(date, time)` words. The date is
+    /// `year << 9 | month << 5 | day`, and the time is
+    /// `day_of_week << 17 | hour << 12 | minute << 6 | seconds`.
+    pub fn date_time(&self) -> (u32, u32) {
+        self.date_time.get()
+    }
+
+    /// Sets the date and time, packed as described in `date_time`.
+    pub fn set_date_time(&self, date: u32, time: u32) {
+        self.date_time.set((date, time));
+    }
+}
+
+impl crate::fake::SyscallDriver for Rtc {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, argument1: usize) -> CommandReturn {
+        let (date, time) = match command_num {
+            EXISTS => return crate::command_return::success(),
+            GET_DATE_TIME => self.date_time.get(),
+            SET_DATE_TIME => {
+                self.set_date_time(argument0 as u32, argument1 as u32);
+                (0, 0)
+            }
+            _ => return crate::command_return::failure(ErrorCode::NoSupport),
+        };
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_DONE, (0, date, time))
+            .expect("Unable to schedule upcall");
+        crate::command_return::success()
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90007;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const GET_DATE_TIME: u32 = 1;
+const SET_DATE_TIME: u32 = 2;
+
+const SUBSCRIBE_DONE: u32 = 0;
@@ -0,0 +1,44 @@
+use crate::fake;
+use fake::rtc::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let rtc = Rtc::new();
+
+    assert!(rtc.command(EXISTS, 0, 0).is_success());
+    assert!(rtc.command(SET_DATE_TIME, 1234, 5678).is_success());
+    assert_eq!(rtc.date_time(), (1234, 5678));
+    assert!(rtc.command(GET_DATE_TIME, 0, 0).is_success());
+}
+
+// Integration test that verifies Rtc works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let rtc = Rtc::new();
+    kernel.add_driver(&rtc);
+
+    let done: Cell<Option<(u32, u32, u32)>> = Cell::new(None);
+    rtc.set_date_time(12, 34);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+                subscribe, &done
+            ),
+            Ok(())
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, GET_DATE_TIME, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 12, 34)));
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, SET_DATE_TIME, 56, 78).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 0, 0)));
+        assert_eq!(rtc.date_time(), (56, 78));
+    });
+}
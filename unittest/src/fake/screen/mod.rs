@@ -0,0 +1,182 @@
+//! Fake implementation of the screen API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/90001_screen.md
+//!
+//! `Screen` records its settings and the writes made to it, for tests to
+//! inspect. It supports the pixel formats it was created with, and
+//! starts out using the first of them. Commands complete immediately.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer};
+
+// A write's frame, as (x, y, width, height), and its pixels.
+type Write = ((u16, u16, u16, u16), Vec<u8>);
+
+pub struct Screen {
+    width: u32,
+    height: u32,
+    formats: Vec<u32>,
+    pixel_format: Cell<u32>,
+    brightness: Cell<u16>,
+    write_frame: Cell<(u16, u16, u16, u16)>,
+    last_frame: RefCell<Vec<u8>>,
+    writes: RefCell<Vec<Write>>,
+    buffer: RefCell<RoAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Screen {
+    /// Creates a `width` by `height` screen that supports `formats`, which are
+    /// the driver's pixel format numbers. `formats` must not be empty.
+    pub fn new(width: u32, height: u32, formats: &[u32]) -> std::rc::Rc<Screen> {
+        assert!(!formats.is_empty(), "screens support at least one format");
+        std::rc::Rc::new(Screen {
+            width,
+            height,
+            formats: formats.to_vec(),
+            pixel_format: Cell::new(formats[0]),
+            brightness: Cell::new(0),
+            write_frame: Cell::new((0, 0, width as u16, height as u16)),
+            last_frame: Default::default(),
+            writes: Default::default(),
+            buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    pub fn brightness(&self) -> u16 {
+        self.brightness.get()
+    }
+
+    pub fn pixel_format(&self) -> u32 {
+        self.pixel_format.get()
+    }
+
+    /// Returns the write frame as `(x, y, width, height)`.
+    pub fn write_frame(&self) -> (u16, u16, u16, u16) {
+        self.write_frame.get()
+    }
+
+    /// Returns the pixels of the most recent write.
+    pub fn last_frame(&self) -> Vec<u8> {
+        self.last_frame.borrow().clone()
+    }
+
+    /// Returns the writes made since the last call, as the write frame each
+    /// was made to and its pixels.
+    pub fn take_writes(&self) -> Vec<Write> {
+        self.writes.take()
+    }
+
+    // Runs a command that completes with an upcall, returning the upcall's
+    // data arguments.
+    fn run(
+        &self,
+        command_num: u32,
+        argument0: usize,
+        argument1: usize,
+    ) -> Result<(u32, u32), ErrorCode> {
+        match command_num {
+            SET_BRIGHTNESS => self.brightness.set(argument0 as u16),
+            GET_RESOLUTION => return Ok((self.width, self.height)),
+            GET_PIXEL_FORMAT => return Ok((self.pixel_format.get(), 0)),
+            SET_PIXEL_FORMAT => {
+                if !self.formats.contains(&(argument0 as u32)) {
+                    return Err(ErrorCode::Invalid);
+                }
+                self.pixel_format.set(argument0 as u32);
+            }
+            SET_WRITE_FRAME => {
+                let (x, y) = ((argument0 >> 16) as u16, argument0 as u16);
+                let (width, height) = ((argument1 >> 16) as u16, argument1 as u16);
+                if x as u32 + width as u32 > self.width || y as u32 + height as u32 > self.height {
+                    return Err(ErrorCode::Invalid);
+                }
+                self.write_frame.set((x, y, width, height));
+            }
+            WRITE => {
+                let buffer = self.buffer.borrow();
+                if argument0 > buffer.len() {
+                    return Err(ErrorCode::Invalid);
+                }
+                *self.last_frame.borrow_mut() = buffer[..argument0].to_vec();
+                self.writes
+                    .borrow_mut()
+                    .push((self.write_frame.get(), buffer[..argument0].to_vec()));
+            }
+            _ => unreachable!(),
+        }
+        Ok((0, 0))
+    }
+}
+
+impl crate::fake::SyscallDriver for Screen {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_PIXELS {
+            Ok(self.buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            PIXEL_FORMAT_COUNT => crate::command_return::success_u32(self.formats.len() as u32),
+            SUPPORTED_PIXEL_FORMAT => match self.formats.get(argument0) {
+                Some(&format) => crate::command_return::success_u32(format),
+                None => crate::command_return::failure(ErrorCode::Invalid),
+            },
+            SET_BRIGHTNESS | GET_RESOLUTION | GET_PIXEL_FORMAT | SET_PIXEL_FORMAT
+            | SET_WRITE_FRAME | WRITE => {
+                // Like the real driver, the result is reported through the
+                // upcall.
+                let args = match self.run(command_num, argument0, argument1) {
+                    Ok((data0, data1)) => (0, data0, data1),
+                    Err(error) => (error as u32, 0, 0),
+                };
+                self.share_ref
+                    .schedule_upcall(SUBSCRIBE_DONE, args)
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90001;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const SET_BRIGHTNESS: u32 = 3;
+const PIXEL_FORMAT_COUNT: u32 = 13;
+const SUPPORTED_PIXEL_FORMAT: u32 = 14;
+const GET_RESOLUTION: u32 = 23;
+const GET_PIXEL_FORMAT: u32 = 25;
+const SET_PIXEL_FORMAT: u32 = 26;
+const SET_WRITE_FRAME: u32 = 100;
+const WRITE: u32 = 200;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_PIXELS: u32 = 0;
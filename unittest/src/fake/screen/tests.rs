@@ -0,0 +1,82 @@
+use crate::fake;
+use fake::screen::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let screen = Screen::new(128, 64, &[0, 2]);
+
+    assert!(screen.command(EXISTS, 0, 0).is_success());
+    assert_eq!(
+        screen.command(PIXEL_FORMAT_COUNT, 0, 0).get_success_u32(),
+        Some(2)
+    );
+    assert_eq!(
+        screen
+            .command(SUPPORTED_PIXEL_FORMAT, 1, 0)
+            .get_success_u32(),
+        Some(2)
+    );
+    assert_eq!(
+        screen.command(SUPPORTED_PIXEL_FORMAT, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert!(screen.command(SET_PIXEL_FORMAT, 2, 0).is_success());
+    assert_eq!(screen.pixel_format(), 2);
+    // Errors are reported through the upcall.
+    assert!(screen.command(SET_PIXEL_FORMAT, 1, 0).is_success());
+    assert_eq!(screen.pixel_format(), 2);
+    assert!(screen
+        .command(SET_WRITE_FRAME, 8 << 16 | 4, 16 << 16 | 2)
+        .is_success());
+    assert_eq!(screen.write_frame(), (8, 4, 16, 2));
+    assert!(screen
+        .command(SET_WRITE_FRAME, 120 << 16, 16 << 16 | 2)
+        .is_success());
+    assert_eq!(screen.write_frame(), (8, 4, 16, 2));
+}
+
+// Integration test that verifies Screen works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    let kernel = fake::Kernel::new();
+    let screen = Screen::new(128, 64, &[0]);
+    kernel.add_driver(&screen);
+
+    let done: Cell<Option<(u32, u32, u32)>> = Cell::new(None);
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_PIXELS>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_PIXELS>(allow_ro, &[1, 2, 3])
+            .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, GET_RESOLUTION, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 128, 64)));
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, WRITE, 2, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0, 0, 0)));
+        assert_eq!(screen.last_frame(), [1, 2]);
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, WRITE, 4, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((ErrorCode::Invalid as u32, 0, 0)));
+    });
+}
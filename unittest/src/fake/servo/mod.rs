@@ -0,0 +1,73 @@
+//! Fake implementation of the servo API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/90009_servo.md
+//!
+//! Like the real API, `Servo` controls a set of fake servos, which all start at
+//! 0 degrees. It provides a function `get_angle` used to retrieve the angle of
+//! a servo.
+
+use crate::DriverInfo;
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+pub struct Servo<const SERVO_COUNT: usize> {
+    angles: [Cell<u16>; SERVO_COUNT],
+}
+
+impl<const SERVO_COUNT: usize> Servo<SERVO_COUNT> {
+    pub fn new() -> std::rc::Rc<Servo<SERVO_COUNT>> {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const ZERO: Cell<u16> = Cell::new(0);
+        std::rc::Rc::new(Servo {
+            angles: [ZERO; SERVO_COUNT],
+        })
+    }
+
+    pub fn get_angle(&self, index: u32) -> Option<u16> {
+        self.angles.get(index as usize).map(|angle| angle.get())
+    }
+}
+
+impl<const SERVO_COUNT: usize> crate::fake::SyscallDriver for Servo<SERVO_COUNT> {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM)
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            SET_ANGLE => match self.angles.get(argument0) {
+                None => crate::command_return::failure(ErrorCode::NoDevice),
+                Some(_) if argument1 > MAX_ANGLE => {
+                    crate::command_return::failure(ErrorCode::Invalid)
+                }
+                Some(angle) => {
+                    angle.set(argument1 as u16);
+                    crate::command_return::success()
+                }
+            },
+            GET_ANGLE => match self.angles.get(argument0) {
+                None => crate::command_return::failure(ErrorCode::NoDevice),
+                Some(angle) => crate::command_return::success_u32(angle.get() as u32),
+            },
+            COUNT => crate::command_return::success_u32(SERVO_COUNT as u32),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90009;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const SET_ANGLE: u32 = 1;
+const GET_ANGLE: u32 = 2;
+const COUNT: u32 = 3;
+
+const MAX_ANGLE: usize = 180;
@@ -0,0 +1,48 @@
+use crate::fake;
+use fake::servo::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let servo = Servo::<2>::new();
+    assert!(servo.command(EXISTS, 0, 0).is_success());
+    assert_eq!(servo.command(COUNT, 0, 0).get_success_u32(), Some(2));
+    assert!(servo.command(SET_ANGLE, 1, 90).is_success());
+    assert_eq!(servo.get_angle(1), Some(90));
+    assert_eq!(
+        servo.command(SET_ANGLE, 1, 181).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        servo.command(SET_ANGLE, 2, 90).get_failure(),
+        Some(ErrorCode::NoDevice)
+    );
+    assert_eq!(servo.get_angle(1), Some(90));
+    assert_eq!(servo.get_angle(2), None);
+}
+
+// Integration test that verifies Servo works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    let servo = Servo::<2>::new();
+    kernel.add_driver(&servo);
+    assert_eq!(
+        fake::Syscalls::command(DRIVER_NUM, COUNT, 0, 0).get_success_u32(),
+        Some(2)
+    );
+    assert!(fake::Syscalls::command(DRIVER_NUM, SET_ANGLE, 0, 30).is_success());
+    assert_eq!(servo.get_angle(0), Some(30));
+    assert_eq!(
+        fake::Syscalls::command(DRIVER_NUM, GET_ANGLE, 0, 0).get_success_u32(),
+        Some(30)
+    );
+    assert_eq!(
+        fake::Syscalls::command(DRIVER_NUM, GET_ANGLE, 2, 0).get_failure(),
+        Some(ErrorCode::NoDevice)
+    );
+}
@@ -0,0 +1,137 @@
+//! Fake implementation of the SHA API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/40005_sha.md
+//!
+//! `Sha` computes SHA-256 digests in software, so tests can check results
+//! against known test vectors. It does not support the other algorithms.
+//! Operations complete immediately.
+
+use core::cell::RefCell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+mod sha256;
+
+pub(crate) use sha256::{sha256, BLOCK_LEN, DIGEST_LEN};
+
+pub struct Sha {
+    // The data added to the digest in progress.
+    message: RefCell<Vec<u8>>,
+    data: RefCell<RoAllowBuffer>,
+    digest: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Sha {
+    pub fn new() -> std::rc::Rc<Sha> {
+        std::rc::Rc::new(Sha {
+            message: Default::default(),
+            data: Default::default(),
+            digest: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    fn update(&self) {
+        self.message
+            .borrow_mut()
+            .extend_from_slice(&self.data.borrow());
+    }
+
+    fn finish(&self) -> Result<(), ErrorCode> {
+        let mut digest = self.digest.borrow_mut();
+        if digest.len() < DIGEST_LEN {
+            return Err(ErrorCode::Size);
+        }
+        digest[..DIGEST_LEN].copy_from_slice(&sha256(&self.message.take()));
+        Ok(())
+    }
+
+    fn report(&self, result: Result<(), ErrorCode>) {
+        let status = match result {
+            Ok(()) => 0,
+            Err(error) => error as u32,
+        };
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_DONE, (status, 0, 0))
+            .expect("Unable to schedule upcall");
+    }
+}
+
+impl crate::fake::SyscallDriver for Sha {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_DATA {
+            Ok(self.data.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_DIGEST {
+            Ok(self.digest.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            SET_ALGORITHM => {
+                if argument0 != SHA256 {
+                    return crate::command_return::failure(ErrorCode::NoSupport);
+                }
+                self.message.take();
+            }
+            RUN => {
+                self.update();
+                self.report(self.finish());
+            }
+            UPDATE => {
+                self.update();
+                self.report(Ok(()));
+            }
+            FINISH => self.report(self.finish()),
+            _ => return crate::command_return::failure(ErrorCode::NoSupport),
+        }
+        crate::command_return::success()
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x40005;
+
+// Command numbers
+const SET_ALGORITHM: u32 = 0;
+const RUN: u32 = 1;
+const UPDATE: u32 = 2;
+const FINISH: u32 = 3;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_DATA: u32 = 1;
+const ALLOW_DIGEST: u32 = 2;
+
+// Algorithm IDs
+const SHA256: usize = 0;
@@ -0,0 +1,89 @@
+use crate::fake;
+use fake::sha::*;
+
+// Test vectors from FIPS 180-2.
+#[test]
+fn sha256_vectors() {
+    assert_eq!(
+        sha256(b"abc"),
+        [
+            0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+            0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+            0xf2, 0x00, 0x15, 0xad,
+        ]
+    );
+    // Two blocks long once padded.
+    assert_eq!(
+        sha256(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+        [
+            0x24, 0x8d, 0x6a, 0x61, 0xd2, 0x06, 0x38, 0xb8, 0xe5, 0xc0, 0x26, 0x93, 0x0c, 0x3e,
+            0x60, 0x39, 0xa3, 0x3c, 0xe4, 0x59, 0x64, 0xff, 0x21, 0x67, 0xf6, 0xec, 0xed, 0xd4,
+            0x19, 0xdb, 0x06, 0xc1,
+        ]
+    );
+    assert_eq!(
+        sha256(b""),
+        [
+            0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14, 0x9a, 0xfb, 0xf4, 0xc8, 0x99, 0x6f,
+            0xb9, 0x24, 0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c, 0xa4, 0x95, 0x99, 0x1b,
+            0x78, 0x52, 0xb8, 0x55,
+        ]
+    );
+}
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let sha = Sha::new();
+
+    assert!(sha.command(SET_ALGORITHM, SHA256, 0).is_success());
+    assert_eq!(
+        sha.command(SET_ALGORITHM, 2, 0).get_failure(),
+        Some(ErrorCode::NoSupport)
+    );
+    // Errors are reported through the upcall.
+    assert!(sha.command(FINISH, 0, 0).is_success());
+}
+
+// Integration test that verifies Sha works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    let kernel = fake::Kernel::new();
+    let sha = Sha::new();
+    kernel.add_driver(&sha);
+
+    let done: Cell<Option<(u32,)>> = Cell::new(None);
+    let mut digest = [0; 32];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_DATA>,
+            AllowRw<_, DRIVER_NUM, ALLOW_DIGEST>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_DATA>(allow_ro, b"abc")
+            .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_DIGEST>(allow_rw, &mut digest)
+            .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, SET_ALGORITHM, SHA256, 0).is_success());
+        assert!(fake::Syscalls::command(DRIVER_NUM, RUN, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0,)));
+    });
+    assert_eq!(digest, sha256(b"abc"));
+}
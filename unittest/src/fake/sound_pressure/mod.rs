@@ -0,0 +1,81 @@
+//! Fake implementation of the sound pressure API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/60006_sound_pressure.md
+//!
+//! `SoundPressure` starts out disabled. Once enabled, each read request
+//! completes immediately with the level set by `set_level`. Read requests
+//! while disabled fail with `ErrorCode::Off`, like the real driver.
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef};
+
+pub struct SoundPressure {
+    level: Cell<u8>,
+    enabled: Cell<bool>,
+    share_ref: DriverShareRef,
+}
+
+impl SoundPressure {
+    pub fn new() -> std::rc::Rc<SoundPressure> {
+        std::rc::Rc::new(SoundPressure {
+            level: Cell::new(0),
+            enabled: Cell::new(false),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Sets the level reported by future read requests.
+    pub fn set_level(&self, level: u8) {
+        self.level.set(level);
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+}
+
+impl crate::fake::SyscallDriver for SoundPressure {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn command(&self, command_num: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => {}
+            READ => {
+                if !self.enabled.get() {
+                    return crate::command_return::failure(ErrorCode::Off);
+                }
+                self.share_ref
+                    .schedule_upcall(SUBSCRIBE_READING, (0, self.level.get() as u32, 0))
+                    .expect("Unable to schedule upcall");
+            }
+            ENABLE => self.enabled.set(true),
+            DISABLE => self.enabled.set(false),
+            _ => return crate::command_return::failure(ErrorCode::NoSupport),
+        }
+        crate::command_return::success()
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60006;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const READ: u32 = 1;
+const ENABLE: u32 = 2;
+const DISABLE: u32 = 3;
+
+const SUBSCRIBE_READING: u32 = 0;
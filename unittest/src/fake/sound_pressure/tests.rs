@@ -0,0 +1,49 @@
+use crate::fake;
+use fake::sound_pressure::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let sound_pressure = SoundPressure::new();
+
+    assert!(sound_pressure.command(EXISTS, 0, 0).is_success());
+    assert_eq!(
+        sound_pressure.command(READ, 0, 0).get_failure(),
+        Some(ErrorCode::Off)
+    );
+    assert!(sound_pressure.command(ENABLE, 0, 0).is_success());
+    assert!(sound_pressure.is_enabled());
+    assert!(sound_pressure.command(DISABLE, 0, 0).is_success());
+    assert!(!sound_pressure.is_enabled());
+}
+
+// Integration test that verifies SoundPressure works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let sound_pressure = SoundPressure::new();
+    kernel.add_driver(&sound_pressure);
+
+    let reading: Cell<Option<(u32, u32)>> = Cell::new(None);
+    sound_pressure.set_level(120);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_READING>(
+                subscribe, &reading
+            ),
+            Ok(())
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ, 0, 0).is_failure());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, ENABLE, 0, 0).is_success());
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((0, 120)));
+    });
+}
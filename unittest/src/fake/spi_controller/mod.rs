@@ -0,0 +1,167 @@
+//! Fake implementation of the SPI controller API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/20001_spi_controller.md
+//!
+//! Like the real API, `SpiController` writes the shared write buffer while
+//! filling the shared read buffer. By default the peripheral echoes the bytes
+//! written; tests can program a different response with `set_response`.
+//! Transfers complete immediately. The bytes written so far can be retrieved
+//! via `take_bytes`.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+pub struct SpiController {
+    written: RefCell<Vec<u8>>,
+    // Returned by the next transfer instead of echoing the written bytes.
+    response: RefCell<Option<Vec<u8>>>,
+    rate: Cell<u32>,
+    polarity: Cell<u32>,
+    phase: Cell<u32>,
+    write_buffer: RefCell<RoAllowBuffer>,
+    read_buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl SpiController {
+    pub fn new() -> std::rc::Rc<SpiController> {
+        std::rc::Rc::new(SpiController {
+            written: Default::default(),
+            response: Default::default(),
+            rate: Cell::new(0),
+            polarity: Cell::new(0),
+            phase: Cell::new(0),
+            write_buffer: Default::default(),
+            read_buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Returns the bytes that have been written so far, and clears them.
+    pub fn take_bytes(&self) -> Vec<u8> {
+        self.written.take()
+    }
+
+    /// Sets the bytes read by the next transfer. If `response` is shorter than
+    /// the transfer, the rest of the read buffer is left untouched.
+    pub fn set_response(&self, response: &[u8]) {
+        self.response.replace(Some(Vec::from(response)));
+    }
+
+    pub fn rate(&self) -> u32 {
+        self.rate.get()
+    }
+
+    pub fn polarity(&self) -> u32 {
+        self.polarity.get()
+    }
+
+    pub fn phase(&self) -> u32 {
+        self.phase.get()
+    }
+
+    fn transfer(&self, len: usize) -> CommandReturn {
+        let write_buffer = self.write_buffer.borrow();
+        let mut read_buffer = self.read_buffer.borrow_mut();
+        // The read buffer is optional; without one, the transfer only writes.
+        if len > write_buffer.len() || (!read_buffer.is_empty() && len > read_buffer.len()) {
+            return crate::command_return::failure(ErrorCode::Size);
+        }
+        let written = &write_buffer[..len];
+        self.written.borrow_mut().extend_from_slice(written);
+        if !read_buffer.is_empty() {
+            let response = self.response.take();
+            let response = response.as_deref().unwrap_or(written);
+            let read_len = response.len().min(len);
+            read_buffer[..read_len].copy_from_slice(&response[..read_len]);
+        }
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_DONE, (len as u32, 0, 0))
+            .expect("Unable to schedule upcall");
+        crate::command_return::success()
+    }
+}
+
+impl crate::fake::SyscallDriver for SpiController {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_WRITE {
+            Ok(self.write_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_READ {
+            Ok(self.read_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            READ_WRITE => self.transfer(argument0),
+            SET_RATE => {
+                self.rate.set(argument0 as u32);
+                crate::command_return::success()
+            }
+            GET_RATE => crate::command_return::success_u32(self.rate.get()),
+            SET_PHASE | SET_POLARITY if argument0 > 1 => {
+                crate::command_return::failure(ErrorCode::Invalid)
+            }
+            SET_PHASE => {
+                self.phase.set(argument0 as u32);
+                crate::command_return::success()
+            }
+            GET_PHASE => crate::command_return::success_u32(self.phase.get()),
+            SET_POLARITY => {
+                self.polarity.set(argument0 as u32);
+                crate::command_return::success()
+            }
+            GET_POLARITY => crate::command_return::success_u32(self.polarity.get()),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20001;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const READ_WRITE: u32 = 2;
+const SET_RATE: u32 = 5;
+const GET_RATE: u32 = 6;
+const SET_PHASE: u32 = 7;
+const GET_PHASE: u32 = 8;
+const SET_POLARITY: u32 = 9;
+const GET_POLARITY: u32 = 10;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_WRITE: u32 = 0;
+const ALLOW_READ: u32 = 0;
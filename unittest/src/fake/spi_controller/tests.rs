@@ -0,0 +1,79 @@
+use crate::fake;
+use fake::spi_controller::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let spi = SpiController::new();
+
+    assert!(spi.command(EXISTS, 0, 0).is_success());
+    assert!(spi.command(SET_RATE, 400_000, 0).is_success());
+    assert_eq!(spi.command(GET_RATE, 0, 0).get_success_u32(), Some(400_000));
+    assert!(spi.command(SET_PHASE, 1, 0).is_success());
+    assert_eq!(spi.command(GET_PHASE, 0, 0).get_success_u32(), Some(1));
+    assert!(spi.command(SET_POLARITY, 1, 0).is_success());
+    assert_eq!(spi.command(GET_POLARITY, 0, 0).get_success_u32(), Some(1));
+    assert_eq!(
+        spi.command(SET_POLARITY, 2, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(spi.polarity(), 1);
+
+    // Without a write buffer, only an empty transfer fits.
+    assert_eq!(
+        spi.command(READ_WRITE, 1, 0).get_failure(),
+        Some(ErrorCode::Size)
+    );
+    assert!(spi.command(READ_WRITE, 0, 0).is_success());
+}
+
+// Integration test that verifies SpiController works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let spi = SpiController::new();
+    kernel.add_driver(&spi);
+
+    let done: Cell<Option<(u32,)>> = Cell::new(None);
+    let mut read_buffer = [0; 3];
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_WRITE>,
+            AllowRw<_, DRIVER_NUM, ALLOW_READ>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_WRITE>(allow_ro, b"abc")
+            .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_READ>(
+            allow_rw,
+            &mut read_buffer,
+        )
+        .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+
+        // Echoes by default, then returns the programmed response.
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_WRITE, 3, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((3,)));
+        spi.set_response(b"xy");
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_WRITE, 3, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+    });
+    assert_eq!(read_buffer, *b"xyc");
+    assert_eq!(spi.take_bytes(), b"abcabc");
+}
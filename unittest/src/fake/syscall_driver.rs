@@ -4,6 +4,16 @@ use libtock_platform::{CommandReturn, ErrorCode};
 /// The `fake::SyscallDriver` trait is implemented by fake versions of Tock's
 /// kernel APIs. It is used by `fake::Kernel` to route system calls to the fake
 /// kernel APIs.
+///
+/// Tests can implement it to fake drivers that `libtock_unittest` does not
+/// provide, such as out-of-tree drivers, and add them with
+/// `fake::Kernel::add_driver`. Subscribe calls are handled by `fake::Kernel`
+/// itself: a driver declares how many upcalls it has in `info`, and schedules
+/// them through the `DriverShareRef` it is given in `register`.
+///
+/// `add_driver` takes the driver as an `Rc` rather than a `&dyn SyscallDriver`
+/// because `fake::Kernel` keeps a clone of it, so the driver stays registered
+/// even if the test drops its own handle.
 pub trait SyscallDriver: 'static {
     // -------------------------------------------------------------------------
     // Functions called by `fake::Kernel` during driver registration.
@@ -1,5 +1,5 @@
 use crate::kernel_data::with_kernel_data;
-use crate::{ExpectedSyscall, SyscallLogEntry};
+use crate::{ExpectedSyscall, TraceEntry};
 use libtock_platform::{return_variant, ErrorCode, Register};
 use std::convert::TryInto;
 
@@ -15,10 +15,11 @@ pub(super) unsafe fn allow_ro(
         let kernel_data =
             option_kernel_data.expect("Read-Only Allow called but no fake::Kernel exists");
 
-        kernel_data.syscall_log.push(SyscallLogEntry::AllowRo {
+        kernel_data.syscall_log.push(TraceEntry::AllowRo {
             driver_num,
             buffer_num,
             len: len.into(),
+            result: Ok(()),
         });
 
         // Check for an expected syscall entry. Returns an error from the lambda
@@ -1,8 +1,8 @@
 //! `fake::Kernel`'s implementation of the Command system call.
 
 use crate::kernel_data::with_kernel_data;
-use crate::{command_return, ExpectedSyscall, SyscallLogEntry};
-use libtock_platform::{ErrorCode, Register};
+use crate::{command_return, ExpectedSyscall, TraceEntry};
+use libtock_platform::{return_variant, ErrorCode, Register};
 use std::convert::TryInto;
 
 pub(super) fn command(
@@ -19,11 +19,12 @@ pub(super) fn command(
     let (driver, override_return) = with_kernel_data(|option_kernel_data| {
         let kernel_data = option_kernel_data.expect("Command called but no fake::Kernel exists");
 
-        kernel_data.syscall_log.push(SyscallLogEntry::Command {
+        kernel_data.syscall_log.push(TraceEntry::Command {
             driver_id,
             command_id,
             argument0,
             argument1,
+            result: (return_variant::SUCCESS, 0, 0, 0),
         });
 
         // Check for an expected syscall entry. Sets override_return to None if
@@ -31,7 +31,7 @@ fn driver_support() {
         fn info(&self) -> DriverInfo {
             DriverInfo::new(42)
         }
-        fn command(&self, _command_id: u32, _argument0: u32, _argument1: u32) -> CommandReturn {
+        fn command(&self, _command_id: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
             command_return::success_3_u32(1, 2, 3)
         }
     }
@@ -231,3 +231,98 @@ fn too_large_driver_id() {
         .expect("wrong panic payload type")
         .contains("Too large driver ID"));
 }
+
+#[test]
+fn driver_exists() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    // fake::LowLevelDebug answers command 0 with Success, fake::Leds with
+    // Success with u32.
+    kernel.add_driver(&fake::LowLevelDebug::new());
+    kernel.add_driver(&fake::Leds::<4>::new());
+
+    assert_eq!(fake::Syscalls::driver_exists(8), Ok(()));
+    assert_eq!(fake::Syscalls::driver_exists(2), Ok(()));
+    assert_eq!(fake::Syscalls::driver_exists(42), Err(ErrorCode::NoDevice));
+
+    // Failures other than NoDevice are passed on.
+    kernel.add_expected_syscall(ExpectedSyscall::Command {
+        driver_id: 8,
+        command_id: 0,
+        argument0: 0,
+        argument1: 0,
+        override_return: Some(command_return::failure(ErrorCode::NoMem)),
+    });
+    assert_eq!(fake::Syscalls::driver_exists(8), Err(ErrorCode::NoMem));
+}
+
+#[test]
+fn command_retry() {
+    use libtock_platform::Syscalls;
+    use ErrorCode::{Busy, Fail};
+    let kernel = fake::Kernel::new();
+    let expect_command = |override_return| {
+        kernel.add_expected_syscall(ExpectedSyscall::Command {
+            driver_id: 1,
+            command_id: 2,
+            argument0: 3,
+            argument1: 4,
+            override_return: Some(override_return),
+        })
+    };
+    let expect_yield = || {
+        kernel.add_expected_syscall(ExpectedSyscall::YieldNoWait {
+            override_return: None,
+        })
+    };
+
+    // The driver is busy twice, then the third attempt succeeds.
+    expect_command(command_return::failure(Busy));
+    expect_yield();
+    expect_command(command_return::failure(Busy));
+    expect_yield();
+    expect_command(command_return::success_u32(5));
+    assert_eq!(
+        fake::Syscalls::command_retry(1, 2, 3, 4, 2).get_success_u32(),
+        Some(5)
+    );
+
+    // Once the retries run out, the last Busy is returned.
+    expect_command(command_return::failure(Busy));
+    expect_yield();
+    expect_command(command_return::failure(Busy));
+    assert_eq!(
+        fake::Syscalls::command_retry(1, 2, 3, 4, 1).get_failure(),
+        Some(Busy)
+    );
+
+    // Other failures are not retried.
+    expect_command(command_return::failure(Fail));
+    assert_eq!(
+        fake::Syscalls::command_retry(1, 2, 3, 4, 5).get_failure(),
+        Some(Fail)
+    );
+    assert_eq!(kernel.take_syscall_log().len(), 9);
+}
+
+#[test]
+fn driver_count() {
+    use libtock_platform::Syscalls;
+    let kernel = fake::Kernel::new();
+    kernel.add_driver(&fake::LowLevelDebug::new());
+    kernel.add_driver(&fake::Leds::<4>::new());
+
+    assert_eq!(fake::Syscalls::driver_count(2), Ok(4));
+    // LowLevelDebug exists, but does not report a count.
+    assert_eq!(fake::Syscalls::driver_count(8), Err(ErrorCode::BadRVal));
+    assert_eq!(fake::Syscalls::driver_count(42), Err(ErrorCode::NoDevice));
+    assert_eq!(
+        kernel.take_syscall_log(),
+        [2, 8, 42].map(|driver_id| SyscallLogEntry::Command {
+            driver_id,
+            command_id: 0,
+            argument0: 0,
+            argument1: 0,
+        })
+    );
+}
@@ -1,5 +1,5 @@
 use super::exit_impl::*;
-use crate::{exit_test, ExitCall};
+use crate::{exit_test, fake, ExitCall};
 
 #[test]
 fn exit_restart() {
@@ -16,3 +16,25 @@ fn exit_terminate() {
     });
     assert_eq!(exit_call, ExitCall::Terminate(9265));
 }
+
+#[test]
+fn termination_err() {
+    use libtock_platform::{ErrorCode, Termination};
+    let exit_call = exit_test("fake::syscalls::exit_impl_tests::termination_err", || {
+        let kernel = fake::Kernel::new();
+        let low_level_debug = fake::LowLevelDebug::new();
+        kernel.add_driver(&low_level_debug);
+        Err::<(), _>(ErrorCode::Busy).complete::<fake::Syscalls>()
+    });
+    assert_eq!(exit_call, ExitCall::Terminate(ErrorCode::Busy as u32));
+}
+
+#[test]
+fn termination_ok() {
+    use libtock_platform::{ErrorCode, Termination};
+    let exit_call = exit_test("fake::syscalls::exit_impl_tests::termination_ok", || {
+        let _kernel = fake::Kernel::new();
+        Ok::<(), ErrorCode>(()).complete::<fake::Syscalls>()
+    });
+    assert_eq!(exit_call, ExitCall::Terminate(0));
+}
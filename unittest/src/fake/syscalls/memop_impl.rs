@@ -0,0 +1,71 @@
+//! `fake::Kernel`'s implementation of the Memop system call.
+//!
+//! The fake kernel does not model a process's memory layout: every memory
+//! operation that TRD 104 defines succeeds and returns the value set by
+//! `fake::Kernel::set_memop_value` (0 by default). Tests that need a memop to
+//! fail, or to return a particular value once, can override its result with
+//! `ExpectedSyscall::Memop`.
+
+use crate::kernel_data::with_kernel_data;
+use crate::{ExpectedSyscall, TraceEntry};
+use libtock_platform::{memop, return_variant, ErrorCode, Register};
+use std::convert::TryInto;
+
+pub(super) fn memop(memop_num: Register, argument0: Register) -> [Register; 2] {
+    let memop_num = memop_num.try_into().expect("Too large memop number");
+    let argument0 = argument0.into();
+
+    let (override_return, value) = with_kernel_data(|option_kernel_data| {
+        let kernel_data = option_kernel_data.expect("Memop called but no fake::Kernel exists");
+
+        kernel_data.syscall_log.push(TraceEntry::Memop {
+            memop_num,
+            argument0,
+            result: Ok(0),
+        });
+
+        let value = kernel_data
+            .memop_values
+            .get(&memop_num)
+            .copied()
+            .unwrap_or(0);
+        let override_return = match kernel_data.expected_syscalls.pop_front() {
+            None => None,
+            Some(ExpectedSyscall::Memop {
+                memop_num: expected_memop_num,
+                argument0: expected_argument0,
+                override_return,
+            }) => {
+                assert_eq!(
+                    memop_num, expected_memop_num,
+                    "expected different memop_num"
+                );
+                assert_eq!(
+                    argument0, expected_argument0,
+                    "expected different argument0"
+                );
+                override_return
+            }
+            Some(expected_syscall) => expected_syscall.panic_wrong_call("Memop"),
+        };
+        (override_return, value)
+    });
+
+    let result = match override_return {
+        Some(result) => result,
+        None => match memop_num {
+            memop::op::BRK..=memop::op::SPECIFY_HEAP_START => Ok(value),
+            _ => Err(ErrorCode::NoSupport),
+        },
+    };
+    match result {
+        Err(error) => {
+            let r0: u32 = return_variant::FAILURE.into();
+            [r0.into(), error.into()]
+        }
+        Ok(value) => {
+            let r0: u32 = return_variant::SUCCESS_U32.into();
+            [r0.into(), value.into()]
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use super::memop_impl::*;
+use crate::{fake, ExpectedSyscall, SyscallLogEntry};
+use libtock_platform::{memop, ErrorCode};
+use std::panic::catch_unwind;
+
+#[test]
+fn no_kernel() {
+    let result = catch_unwind(|| memop(2u32.into(), 0u32.into()));
+    assert!(result
+        .expect_err("failed to catch missing kernel")
+        .downcast_ref::<String>()
+        .expect("wrong panic payload type")
+        .contains("no fake::Kernel exists"));
+}
+
+#[test]
+fn op_numbers() {
+    let kernel = fake::Kernel::new();
+    assert!(memop::memop_brk::<fake::Syscalls>(0x1000).is_ok());
+    assert!(memop::memory_start::<fake::Syscalls>().is_ok());
+    assert!(memop::memory_end::<fake::Syscalls>().is_ok());
+    assert!(memop::flash_start::<fake::Syscalls>().is_ok());
+    assert!(memop::flash_end::<fake::Syscalls>().is_ok());
+    assert!(memop::grant_start::<fake::Syscalls>().is_ok());
+    assert!(memop::specify_stack_top::<fake::Syscalls>(0x2000).is_ok());
+    assert!(memop::specify_heap_start::<fake::Syscalls>(0x3000).is_ok());
+    let expected = [
+        (memop::op::BRK, 0x1000),
+        (memop::op::MEMORY_START, 0),
+        (memop::op::MEMORY_END, 0),
+        (memop::op::FLASH_START, 0),
+        (memop::op::FLASH_END, 0),
+        (memop::op::GRANT_START, 0),
+        (memop::op::SPECIFY_STACK_TOP, 0x2000),
+        (memop::op::SPECIFY_HEAP_START, 0x3000),
+    ];
+    let log = kernel.take_syscall_log();
+    assert_eq!(log.len(), expected.len());
+    for (entry, &(memop_num, argument0)) in log.iter().zip(expected.iter()) {
+        assert_eq!(
+            *entry,
+            SyscallLogEntry::Memop {
+                memop_num,
+                argument0
+            }
+        );
+    }
+}
+
+#[test]
+fn unknown_op() {
+    use libtock_platform::Syscalls;
+    let _kernel = fake::Kernel::new();
+    assert_eq!(fake::Syscalls::memop(12, 0), Err(ErrorCode::NoSupport));
+}
+
+#[test]
+fn expected_error() {
+    let kernel = fake::Kernel::new();
+    kernel.add_expected_syscall(ExpectedSyscall::Memop {
+        memop_num: memop::op::BRK,
+        argument0: 0x1000,
+        override_return: Some(Err(ErrorCode::NoMem)),
+    });
+    assert_eq!(
+        memop::memop_brk::<fake::Syscalls>(0x1000),
+        Err(ErrorCode::NoMem)
+    );
+    assert!(memop::memop_brk::<fake::Syscalls>(0x1000).is_ok());
+}
+
+#[test]
+fn app_layout() {
+    let kernel = fake::Kernel::new();
+    kernel.set_memop_value(memop::op::MEMORY_START, 0x8000_0000);
+    kernel.set_memop_value(memop::op::MEMORY_END, 0x8000_4000);
+    kernel.set_memop_value(memop::op::FLASH_START, 0x2004_0000);
+    kernel.set_memop_value(memop::op::FLASH_END, 0x2004_8000);
+    kernel.set_memop_value(memop::op::SBRK, 0x8000_1000);
+    let layout = memop::AppLayout::current::<fake::Syscalls>().unwrap();
+    assert_eq!(layout.memory_start, 0x8000_0000);
+    assert_eq!(layout.memory_end, 0x8000_4000);
+    assert_eq!(layout.flash_start, 0x2004_0000);
+    assert_eq!(layout.flash_end, 0x2004_8000);
+    assert_eq!(layout.heap_break, 0x8000_1000);
+    assert_eq!(layout.memory_len(), 0x4000);
+    assert_eq!(layout.flash_len(), 0x8000);
+    // Querying the break must not move it.
+    assert!(kernel.take_syscall_log().contains(&SyscallLogEntry::Memop {
+        memop_num: memop::op::SBRK,
+        argument0: 0,
+    }));
+
+    kernel.add_expected_syscall(ExpectedSyscall::Memop {
+        memop_num: memop::op::MEMORY_START,
+        argument0: 0,
+        override_return: Some(Err(ErrorCode::NoSupport)),
+    });
+    assert_eq!(
+        memop::AppLayout::current::<fake::Syscalls>(),
+        Err(ErrorCode::NoSupport)
+    );
+
+    // A layout whose end is before its start has no length, rather than
+    // overflowing.
+    kernel.set_memop_value(memop::op::MEMORY_END, 0x7fff_0000);
+    kernel.set_memop_value(memop::op::FLASH_END, 0);
+    let layout = memop::AppLayout::current::<fake::Syscalls>().unwrap();
+    assert_eq!(layout.memory_len(), 0);
+    assert_eq!(layout.flash_len(), 0);
+}
@@ -2,6 +2,7 @@ mod allow_ro_impl;
 mod allow_rw_impl;
 mod command_impl;
 mod exit_impl;
+mod memop_impl;
 mod raw_syscalls_impl;
 mod subscribe_impl;
 mod yield_impl;
@@ -20,6 +21,8 @@ mod command_impl_tests;
 #[cfg(all(not(miri), test))]
 mod exit_impl_tests;
 #[cfg(test)]
+mod memop_impl_tests;
+#[cfg(test)]
 mod raw_syscalls_impl_tests;
 #[cfg(test)]
 mod subscribe_impl_tests;
@@ -1,4 +1,6 @@
-use libtock_platform::{syscall_class, yield_id, RawSyscalls, Register};
+use crate::kernel_data::with_kernel_data;
+use crate::trace::{call_result, TraceEntry};
+use libtock_platform::{syscall_class, yield_id, RawSyscalls, Register, YieldNoWaitReturn};
 use std::convert::TryInto;
 
 unsafe impl RawSyscalls for crate::fake::Syscalls {
@@ -14,7 +16,19 @@ unsafe impl RawSyscalls for crate::fake::Syscalls {
     unsafe fn yield2([r0, r1]: [Register; 2]) {
         crate::fake::syscalls::assert_valid((r0, r1));
         match r0.try_into().expect("too-large Yield ID passed") {
-            yield_id::NO_WAIT => unsafe { super::yield_impl::yield_no_wait(r1.into()) },
+            yield_id::NO_WAIT => {
+                // Upcalls run by this yield may make system calls, which are
+                // logged after it, so its entry is found by index.
+                let index = log_len();
+                let return_ptr: *mut YieldNoWaitReturn = r1.into();
+                // Safety: return_ptr is a valid pointer to a YieldNoWaitReturn,
+                // as required by yield2's contract.
+                let result = unsafe {
+                    super::yield_impl::yield_no_wait(return_ptr);
+                    *return_ptr
+                };
+                record(index, TraceEntry::YieldNoWait { result });
+            }
             yield_id::WAIT => {
                 // Technically it is acceptable to call yield_wait with an
                 // argument, but it shouldn't be done because it's wasteful so
@@ -35,7 +49,20 @@ unsafe impl RawSyscalls for crate::fake::Syscalls {
     unsafe fn syscall2<const CLASS: usize>([r0, r1]: [Register; 2]) -> [Register; 2] {
         crate::fake::syscalls::assert_valid((r0, r1));
         match CLASS {
-            syscall_class::MEMOP => unimplemented!("TODO: Add Memop"),
+            syscall_class::MEMOP => {
+                let index = log_len();
+                let [r0_out, r1_out] = super::memop_impl::memop(r0, r1);
+                record(
+                    index,
+                    TraceEntry::Memop {
+                        memop_num: r0.try_into().expect("too-large memop number"),
+                        argument0: r1.into(),
+                        result: call_result(to_u32(r0_out), r1_out.as_u32())
+                            .map(|()| r1_out.into()),
+                    },
+                );
+                [r0_out, r1_out]
+            }
             syscall_class::EXIT => super::exit_impl::exit(r0, r1),
             _ => panic!("Unknown syscall2 call. Class: {}", CLASS),
         }
@@ -43,12 +70,69 @@ unsafe impl RawSyscalls for crate::fake::Syscalls {
 
     unsafe fn syscall4<const CLASS: usize>([r0, r1, r2, r3]: [Register; 4]) -> [Register; 4] {
         crate::fake::syscalls::assert_valid((r0, r1, r2, r3));
-        match CLASS {
+        let index = log_len();
+        let out = match CLASS {
             syscall_class::SUBSCRIBE => unsafe { super::subscribe_impl::subscribe(r0, r1, r2, r3) },
             syscall_class::COMMAND => super::command_impl::command(r0, r1, r2, r3),
             syscall_class::ALLOW_RW => unsafe { super::allow_rw_impl::allow_rw(r0, r1, r2, r3) },
             syscall_class::ALLOW_RO => unsafe { super::allow_ro_impl::allow_ro(r0, r1, r2, r3) },
             _ => panic!("Unknown syscall4 call. Class: {}", CLASS),
-        }
+        };
+        // The system call implementations have already checked that these
+        // arguments fit.
+        let (driver_num, num) = (to_u32(r0), to_u32(r1));
+        let result = call_result(to_u32(out[0]), out[1].as_u32());
+        let entry = match CLASS {
+            syscall_class::SUBSCRIBE => TraceEntry::Subscribe {
+                driver_num,
+                subscribe_num: num,
+                result,
+            },
+            syscall_class::COMMAND => TraceEntry::Command {
+                driver_id: driver_num,
+                command_id: num,
+                argument0: r2.into(),
+                argument1: r3.into(),
+                result: (
+                    to_u32(out[0]).into(),
+                    out[1].into(),
+                    out[2].into(),
+                    out[3].into(),
+                ),
+            },
+            syscall_class::ALLOW_RW => TraceEntry::AllowRw {
+                driver_num,
+                buffer_num: num,
+                len: r3.into(),
+                result,
+            },
+            _ => TraceEntry::AllowRo {
+                driver_num,
+                buffer_num: num,
+                len: r3.into(),
+                result,
+            },
+        };
+        record(index, entry);
+        out
     }
 }
+
+// Returns the index the next system call's entry in the syscall log will have.
+fn log_len() -> usize {
+    with_kernel_data(|kernel_data| kernel_data.map_or(0, |data| data.syscall_log.len()))
+}
+
+// Replaces the syscall log entry at index, which the system call's
+// implementation logged before its result was known, with entry.
+fn record(index: usize, entry: TraceEntry) {
+    with_kernel_data(|kernel_data| {
+        if let Some(logged) = kernel_data.and_then(|data| data.syscall_log.get_mut(index)) {
+            *logged = entry;
+        }
+    });
+}
+
+fn to_u32(register: Register) -> u32 {
+    register.try_into().expect("too-large value in trace")
+}
@@ -1,5 +1,5 @@
 use crate::kernel_data::with_kernel_data;
-use crate::{ExpectedSyscall, SyscallLogEntry};
+use crate::{ExpectedSyscall, TraceEntry};
 use libtock_platform::{return_variant, ErrorCode, Register};
 use std::convert::TryInto;
 
@@ -18,9 +18,10 @@ pub(super) unsafe fn subscribe(
     let (skip_with_error, num_upcalls) = with_kernel_data(|option_kernel_data| {
         let kernel_data = option_kernel_data.expect("Subscribe called but no fake::Kernel exists");
 
-        kernel_data.syscall_log.push(SyscallLogEntry::Subscribe {
+        kernel_data.syscall_log.push(TraceEntry::Subscribe {
             driver_num,
             subscribe_num,
+            result: Ok(()),
         });
 
         // Check for an expected syscall. Panics if an expected syscall exists
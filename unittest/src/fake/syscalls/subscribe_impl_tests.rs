@@ -172,3 +172,42 @@ fn too_large_inputs() {
         .expect("wrong panic payload type")
         .contains("Too large subscribe number"));
 }
+
+#[test]
+fn subscribe_boxed() {
+    use libtock_platform::subscribe_boxed::subscribe_boxed;
+    use libtock_platform::{Syscalls, YieldNoWaitReturn};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    let kernel = fake::Kernel::new();
+    let console = fake::Console::new();
+    kernel.add_driver(&console);
+
+    let count = Rc::new(Cell::new(0));
+    let counter = count.clone();
+    let subscription = subscribe_boxed::<fake::Syscalls>(
+        1,
+        1,
+        Box::new(move |arg0, _, _| counter.set(counter.get() + arg0)),
+    )
+    .unwrap();
+    kernel.schedule_upcall(1, 1, (2, 0, 0)).unwrap();
+    kernel.schedule_upcall(1, 1, (3, 0, 0)).unwrap();
+    fake::Syscalls::yield_wait();
+    fake::Syscalls::yield_wait();
+    assert_eq!(count.get(), 5);
+
+    // Dropping the subscription unsubscribes the closure and frees it.
+    drop(subscription);
+    assert_eq!(Rc::strong_count(&count), 1);
+    kernel.schedule_upcall(1, 1, (2, 0, 0)).unwrap();
+    assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    assert_eq!(count.get(), 5);
+
+    // Subscribing to a driver that does not exist fails.
+    assert!(matches!(
+        subscribe_boxed::<fake::Syscalls>(11, 0, Box::new(|_, _, _| {})),
+        Err(ErrorCode::NoMem)
+    ));
+}
@@ -1,7 +1,7 @@
 //! Implementations of Yield system calls.
 
 use crate::kernel_data::{with_kernel_data, KERNEL_DATA};
-use crate::{ExpectedSyscall, SyscallLogEntry};
+use crate::{ExpectedSyscall, TraceEntry};
 
 /// # Safety
 /// It must be valid to write a `libtock_platform::YieldNoWaitReturn` into the
@@ -14,7 +14,9 @@ pub(super) unsafe fn yield_no_wait(return_ptr: *mut libtock_platform::YieldNoWai
             .as_mut()
             .expect("yield-no-wait called but no fake::Kernel exists");
 
-        kernel_data.syscall_log.push(SyscallLogEntry::YieldNoWait);
+        kernel_data.syscall_log.push(TraceEntry::YieldNoWait {
+            result: libtock_platform::YieldNoWaitReturn::NoUpcall,
+        });
 
         match kernel_data.expected_syscalls.pop_front() {
             None => None,
@@ -40,7 +42,7 @@ pub(super) fn yield_wait() {
             .as_mut()
             .expect("yield-wait called but no fake::Kernel exists");
 
-        kernel_data.syscall_log.push(SyscallLogEntry::YieldWait);
+        kernel_data.syscall_log.push(TraceEntry::YieldWait);
 
         match kernel_data.expected_syscalls.pop_front() {
             None => false,
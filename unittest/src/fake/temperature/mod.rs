@@ -0,0 +1,79 @@
+//! Fake implementation of the temperature API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/60000_ambient_temperature.md
+//!
+//! Like the real API, `Temperature` reports the temperature in hundredths of a
+//! degree Celsius. Tests set the value to report with `set_value`; each read
+//! request is answered immediately with that value. `set_enabled(false)`
+//! simulates a disabled sensor, which rejects read requests with `OFF`.
+
+use core::cell::Cell;
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef};
+
+pub struct Temperature {
+    value: Cell<i32>,
+    enabled: Cell<bool>,
+    share_ref: DriverShareRef,
+}
+
+impl Temperature {
+    pub fn new() -> std::rc::Rc<Temperature> {
+        std::rc::Rc::new(Temperature {
+            value: Cell::new(0),
+            enabled: Cell::new(true),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Sets the temperature, in hundredths of a degree Celsius, returned by
+    /// future reads.
+    pub fn set_value(&self, centi_celsius: i32) {
+        self.value.set(centi_celsius);
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.set(enabled);
+    }
+}
+
+impl crate::fake::SyscallDriver for Temperature {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn command(&self, command_num: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            READ_TEMPERATURE => {
+                if !self.enabled.get() {
+                    return crate::command_return::failure(ErrorCode::Off);
+                }
+                self.share_ref
+                    .schedule_upcall(SUBSCRIBE_READING, (self.value.get() as u32, 0, 0))
+                    .expect("Unable to schedule upcall");
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x60000;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const READ_TEMPERATURE: u32 = 1;
+
+const SUBSCRIBE_READING: u32 = 0;
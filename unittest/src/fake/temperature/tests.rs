@@ -0,0 +1,54 @@
+use crate::fake;
+use fake::temperature::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let temperature = Temperature::new();
+
+    assert!(temperature.command(EXISTS, 0, 0).is_success());
+    assert!(temperature.command(READ_TEMPERATURE, 0, 0).is_success());
+    temperature.set_enabled(false);
+    assert_eq!(
+        temperature.command(READ_TEMPERATURE, 0, 0).get_failure(),
+        Some(ErrorCode::Off)
+    );
+    temperature.set_enabled(true);
+    assert!(temperature.command(READ_TEMPERATURE, 0, 0).is_success());
+}
+
+// Integration test that verifies Temperature works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let temperature = Temperature::new();
+    kernel.add_driver(&temperature);
+
+    assert!(fake::Syscalls::command(DRIVER_NUM, EXISTS, 0, 0).is_success());
+
+    let reading: Cell<Option<(u32,)>> = Cell::new(None);
+    temperature.set_value(-1250);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_READING>(
+                subscribe, &reading
+            ),
+            Ok(())
+        );
+        assert!(fake::Syscalls::command(DRIVER_NUM, READ_TEMPERATURE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(reading.get(), Some((-1250i32 as u32,)));
+
+        temperature.set_enabled(false);
+        assert_eq!(
+            fake::Syscalls::command(DRIVER_NUM, READ_TEMPERATURE, 0, 0).get_failure(),
+            Some(ErrorCode::Off)
+        );
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+    });
+}
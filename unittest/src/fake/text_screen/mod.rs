@@ -0,0 +1,164 @@
+//! Fake implementation of the text screen API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/90003_text_screen.md
+//!
+//! `TextScreen` renders writes onto a virtual character grid, which tests can
+//! inspect with `rows` and `cursor`. Text that runs past the end of a row
+//! continues on the next one, and text past the last row is dropped. Commands
+//! complete immediately.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer};
+
+pub struct TextScreen {
+    width: u32,
+    height: u32,
+    grid: RefCell<Vec<Vec<u8>>>,
+    cursor: Cell<(u32, u32)>,
+    on: Cell<bool>,
+    buffer: RefCell<RoAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl TextScreen {
+    /// Creates a blank `width` by `height` screen, which is initially off.
+    pub fn new(width: u32, height: u32) -> std::rc::Rc<TextScreen> {
+        std::rc::Rc::new(TextScreen {
+            width,
+            height,
+            grid: RefCell::new(vec![vec![b' '; width as usize]; height as usize]),
+            cursor: Cell::new((0, 0)),
+            on: Cell::new(false),
+            buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Returns the contents of the screen, one string per row.
+    pub fn rows(&self) -> Vec<String> {
+        self.grid
+            .borrow()
+            .iter()
+            .map(|row| String::from_utf8_lossy(row).into_owned())
+            .collect()
+    }
+
+    /// Returns the cursor position as `(column, row)`.
+    pub fn cursor(&self) -> (u32, u32) {
+        self.cursor.get()
+    }
+
+    pub fn is_on(&self) -> bool {
+        self.on.get()
+    }
+
+    fn write(&self, len: usize) {
+        let buffer = self.buffer.borrow();
+        let mut grid = self.grid.borrow_mut();
+        let (mut col, mut row) = self.cursor.get();
+        for &byte in &buffer[..len.min(buffer.len())] {
+            if row >= self.height {
+                break;
+            }
+            grid[row as usize][col as usize] = byte;
+            col += 1;
+            if col == self.width {
+                col = 0;
+                row += 1;
+            }
+        }
+        self.cursor.set((col, row.min(self.height - 1)));
+    }
+
+    fn clear(&self) {
+        for row in self.grid.borrow_mut().iter_mut() {
+            row.fill(b' ');
+        }
+        self.cursor.set((0, 0));
+    }
+}
+
+impl crate::fake::SyscallDriver for TextScreen {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_WRITE {
+            Ok(self.buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, argument0: usize, argument1: usize) -> CommandReturn {
+        let status = match command_num {
+            EXISTS => return crate::command_return::success(),
+            GET_SIZE => return crate::command_return::success_2_u32(self.width, self.height),
+            ON => {
+                self.on.set(true);
+                Ok(())
+            }
+            OFF => {
+                self.on.set(false);
+                Ok(())
+            }
+            WRITE => {
+                self.write(argument0);
+                Ok(())
+            }
+            CLEAR => {
+                self.clear();
+                Ok(())
+            }
+            SET_CURSOR => {
+                if argument0 < self.width as usize && argument1 < self.height as usize {
+                    self.cursor.set((argument0 as u32, argument1 as u32));
+                    Ok(())
+                } else {
+                    Err(ErrorCode::Invalid)
+                }
+            }
+            _ => return crate::command_return::failure(ErrorCode::NoSupport),
+        };
+        // Like the real driver, the result is reported through the upcall.
+        let status = match status {
+            Ok(()) => 0,
+            Err(error) => error as u32,
+        };
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_DONE, (status, 0, 0))
+            .expect("Unable to schedule upcall");
+        crate::command_return::success()
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90003;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const GET_SIZE: u32 = 1;
+const ON: u32 = 2;
+const OFF: u32 = 3;
+const WRITE: u32 = 8;
+const CLEAR: u32 = 9;
+const SET_CURSOR: u32 = 11;
+
+const SUBSCRIBE_DONE: u32 = 0;
+const ALLOW_WRITE: u32 = 0;
@@ -0,0 +1,68 @@
+use crate::fake;
+use fake::text_screen::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let screen = TextScreen::new(16, 2);
+
+    assert!(screen.command(EXISTS, 0, 0).is_success());
+    assert_eq!(
+        screen.command(GET_SIZE, 0, 0).get_success_2_u32(),
+        Some((16, 2))
+    );
+    assert!(screen.command(SET_CURSOR, 3, 1).is_success());
+    assert_eq!(screen.cursor(), (3, 1));
+    // Errors are reported through the upcall.
+    assert!(screen.command(SET_CURSOR, 16, 0).is_success());
+    assert_eq!(screen.cursor(), (3, 1));
+    assert!(screen.command(CLEAR, 0, 0).is_success());
+    assert_eq!(screen.cursor(), (0, 0));
+}
+
+// Integration test that verifies TextScreen works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    let kernel = fake::Kernel::new();
+    let screen = TextScreen::new(4, 2);
+    kernel.add_driver(&screen);
+
+    let done: Cell<Option<(u32,)>> = Cell::new(None);
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_WRITE>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_DONE>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_WRITE>(allow_ro, b"abcdefghij")
+            .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_DONE>(
+            subscribe, &done,
+        )
+        .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, WRITE, 6, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((0,)));
+        assert_eq!(screen.rows(), ["abcd", "ef  "]);
+        assert_eq!(screen.cursor(), (2, 1));
+
+        // Text past the last row is dropped.
+        assert!(fake::Syscalls::command(DRIVER_NUM, WRITE, 10, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(screen.rows(), ["abcd", "efab"]);
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, SET_CURSOR, 0, 2).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(done.get(), Some((ErrorCode::Invalid as u32,)));
+    });
+}
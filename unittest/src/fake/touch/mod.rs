@@ -0,0 +1,172 @@
+//! Fake implementation of the touch API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/90002_touch.md
+//!
+//! Tests script the touches `Touch` reports by queueing them with
+//! `push_event` (for single-touch events) and `push_touches` (for multi-touch
+//! events). Like the ADC fake's samples, each time events are enabled the next
+//! queued event is reported; if none is queued, the next one pushed is
+//! reported as soon as it is pushed.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::{DriverInfo, DriverShareRef, RwAllowBuffer};
+
+// A touch in a multi-touch event, as its ID, status, x and y.
+type TouchPoint = (u8, u32, u16, u16);
+
+pub struct Touch {
+    // Whether an enable is waiting for its single-touch event.
+    waiting: Cell<bool>,
+    events: RefCell<VecDeque<(u32, u16, u16)>>,
+    // Whether an enable is waiting for its multi-touch event.
+    multi_touch_waiting: Cell<bool>,
+    touches: RefCell<VecDeque<Vec<TouchPoint>>>,
+    buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl Touch {
+    pub fn new() -> std::rc::Rc<Touch> {
+        std::rc::Rc::new(Touch {
+            waiting: Cell::new(false),
+            events: Default::default(),
+            multi_touch_waiting: Cell::new(false),
+            touches: Default::default(),
+            buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Queues a single-touch event with the driver's number for `status`, at
+    /// (`x`, `y`).
+    pub fn push_event(&self, status: u32, x: u16, y: u16) {
+        self.events.borrow_mut().push_back((status, x, y));
+        self.deliver();
+    }
+
+    /// Queues a multi-touch event reporting `touches`, each given as its ID,
+    /// the driver's number for its status, x and y.
+    pub fn push_touches(&self, touches: &[TouchPoint]) {
+        self.touches.borrow_mut().push_back(touches.to_vec());
+        self.deliver_touches();
+    }
+
+    /// Returns true if an enable is waiting for a single-touch event.
+    pub fn is_waiting(&self) -> bool {
+        self.waiting.get()
+    }
+
+    fn deliver(&self) {
+        if !self.waiting.get() {
+            return;
+        }
+        let (status, x, y) = match self.events.borrow_mut().pop_front() {
+            Some(event) => event,
+            None => return,
+        };
+        self.waiting.set(false);
+        self.share_ref
+            .schedule_upcall(
+                SUBSCRIBE_SINGLE_TOUCH,
+                (status, (x as u32) << 16 | y as u32, 0),
+            )
+            .expect("Unable to schedule upcall");
+    }
+
+    fn deliver_touches(&self) {
+        if !self.multi_touch_waiting.get() {
+            return;
+        }
+        let touches = match self.touches.borrow_mut().pop_front() {
+            Some(touches) => touches,
+            None => return,
+        };
+        self.multi_touch_waiting.set(false);
+        // Like the real driver, touches that don't fit in the buffer are
+        // dropped.
+        let mut buffer = self.buffer.borrow_mut();
+        let count = touches.len().min(buffer.len() / TOUCH_LEN);
+        for (&(id, status, x, y), bytes) in touches.iter().zip(buffer.chunks_exact_mut(TOUCH_LEN)) {
+            bytes[0] = id;
+            bytes[1] = status as u8;
+            bytes[2..4].copy_from_slice(&x.to_be_bytes());
+            bytes[4..6].copy_from_slice(&y.to_be_bytes());
+            bytes[6..8].fill(0);
+        }
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_MULTI_TOUCH, (count as u32, 0, 0))
+            .expect("Unable to schedule upcall");
+    }
+}
+
+impl crate::fake::SyscallDriver for Touch {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(3)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_TOUCHES {
+            Ok(self.buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn command(&self, command_num: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            ENABLE_SINGLE_TOUCH => {
+                self.waiting.set(true);
+                self.deliver();
+                crate::command_return::success()
+            }
+            DISABLE_SINGLE_TOUCH => {
+                self.waiting.set(false);
+                crate::command_return::success()
+            }
+            ENABLE_MULTI_TOUCH => {
+                self.multi_touch_waiting.set(true);
+                self.deliver_touches();
+                crate::command_return::success()
+            }
+            DISABLE_MULTI_TOUCH => {
+                self.multi_touch_waiting.set(false);
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x90002;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const ENABLE_SINGLE_TOUCH: u32 = 1;
+const DISABLE_SINGLE_TOUCH: u32 = 2;
+const ENABLE_MULTI_TOUCH: u32 = 11;
+const DISABLE_MULTI_TOUCH: u32 = 12;
+
+const SUBSCRIBE_SINGLE_TOUCH: u32 = 0;
+const SUBSCRIBE_MULTI_TOUCH: u32 = 2;
+const ALLOW_TOUCHES: u32 = 2;
+
+// The size of each touch in the multi-touch buffer.
+const TOUCH_LEN: usize = 8;
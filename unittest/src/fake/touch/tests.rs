@@ -0,0 +1,57 @@
+use crate::fake;
+use fake::touch::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let touch = Touch::new();
+
+    assert!(touch.command(EXISTS, 0, 0).is_success());
+    assert!(touch.command(ENABLE_SINGLE_TOUCH, 0, 0).is_success());
+    assert!(touch.is_waiting());
+    assert!(touch.command(DISABLE_SINGLE_TOUCH, 0, 0).is_success());
+    assert!(!touch.is_waiting());
+    assert!(touch.command(ENABLE_MULTI_TOUCH, 0, 0).is_success());
+    assert!(touch.command(DISABLE_MULTI_TOUCH, 0, 0).is_success());
+    assert!(touch.command(3, 0, 0).is_failure());
+}
+
+// Integration test that verifies Touch works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let touch = Touch::new();
+    kernel.add_driver(&touch);
+
+    touch.push_event(1, 10, 20);
+    touch.push_event(2, 11, 21);
+    let event: Cell<Option<(u32, u32)>> = Cell::new(None);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_SINGLE_TOUCH>(
+                subscribe, &event
+            ),
+            Ok(())
+        );
+        // Each enable reports one event.
+        assert!(fake::Syscalls::command(DRIVER_NUM, ENABLE_SINGLE_TOUCH, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(event.get(), Some((1, 10 << 16 | 20)));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, ENABLE_SINGLE_TOUCH, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(event.get(), Some((2, 11 << 16 | 21)));
+
+        // With nothing queued, the next event is reported when pushed.
+        assert!(fake::Syscalls::command(DRIVER_NUM, ENABLE_SINGLE_TOUCH, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        touch.push_event(0, 12, 22);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(event.get(), Some((0, 12 << 16 | 22)));
+    });
+}
@@ -0,0 +1,199 @@
+//! Fake implementation of the UDP API, documented here:
+//! https://github.com/tock/tock/blob/master/doc/syscalls/30002_udp.md
+//!
+//! `UdpDriver` connects the sockets of a single process to each other: a
+//! datagram sent to a bound port is queued for that port, and delivered as soon
+//! as a receive buffer is shared for it. Datagrams appear to come from the
+//! loopback address, `::1`. Datagrams sent to a port nothing is bound to are
+//! dropped.
+
+use core::cell::RefCell;
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::{HashMap, VecDeque};
+
+use crate::{DriverInfo, DriverShareRef, RoAllowBuffer, RwAllowBuffer};
+
+// Datagrams waiting to be received on a port, as (source port, payload).
+type Queue = VecDeque<(u16, Vec<u8>)>;
+
+pub struct UdpDriver {
+    // Keyed by bound port.
+    queues: RefCell<HashMap<u16, Queue>>,
+    tx_buffer: RefCell<RoAllowBuffer>,
+    tx_cfg: RefCell<RwAllowBuffer>,
+    rx_cfg: RefCell<RwAllowBuffer>,
+    rx_buffer: RefCell<RwAllowBuffer>,
+    share_ref: DriverShareRef,
+}
+
+impl UdpDriver {
+    pub fn new() -> std::rc::Rc<UdpDriver> {
+        std::rc::Rc::new(UdpDriver {
+            queues: Default::default(),
+            tx_buffer: Default::default(),
+            tx_cfg: Default::default(),
+            rx_cfg: Default::default(),
+            rx_buffer: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    pub fn is_bound(&self, port: u16) -> bool {
+        self.queues.borrow().contains_key(&port)
+    }
+
+    /// Returns the number of datagrams waiting to be received on `port`.
+    pub fn pending(&self, port: u16) -> usize {
+        self.queues.borrow().get(&port).map_or(0, Queue::len)
+    }
+
+    fn bind(&self) -> CommandReturn {
+        let port = match sockaddr_port(&self.rx_cfg.borrow(), 1) {
+            Some(port) if port != 0 => port,
+            _ => return crate::command_return::failure(ErrorCode::Invalid),
+        };
+        self.queues.borrow_mut().entry(port).or_default();
+        crate::command_return::success()
+    }
+
+    fn send(&self) -> CommandReturn {
+        let (src_port, dst_port) = {
+            let tx_cfg = self.tx_cfg.borrow();
+            match (sockaddr_port(&tx_cfg, 0), sockaddr_port(&tx_cfg, 1)) {
+                (Some(src_port), Some(dst_port)) => (src_port, dst_port),
+                _ => return crate::command_return::failure(ErrorCode::Invalid),
+            }
+        };
+        if !self.is_bound(src_port) || dst_port == 0 {
+            return crate::command_return::failure(ErrorCode::Invalid);
+        }
+        let payload = self.tx_buffer.borrow().to_vec();
+        if payload.len() > MAX_PAYLOAD_LEN {
+            return crate::command_return::failure(ErrorCode::Size);
+        }
+        if let Some(queue) = self.queues.borrow_mut().get_mut(&dst_port) {
+            queue.push_back((src_port, payload));
+        }
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_TX, (0, 0, 0))
+            .expect("Unable to schedule upcall");
+        self.deliver();
+        crate::command_return::success()
+    }
+
+    // Delivers the oldest datagram queued for the port in the receive
+    // configuration, if a receive buffer is shared. A datagram that does not
+    // fit is truncated, but the upcall reports its full length.
+    fn deliver(&self) {
+        let mut rx_buffer = self.rx_buffer.borrow_mut();
+        let mut rx_cfg = self.rx_cfg.borrow_mut();
+        if rx_buffer.is_empty() {
+            return;
+        }
+        let port = match sockaddr_port(&rx_cfg, 1) {
+            Some(port) => port,
+            None => return,
+        };
+        let (src_port, payload) = match self
+            .queues
+            .borrow_mut()
+            .get_mut(&port)
+            .and_then(Queue::pop_front)
+        {
+            Some(datagram) => datagram,
+            None => return,
+        };
+        let len = payload.len().min(rx_buffer.len());
+        rx_buffer[..len].copy_from_slice(&payload[..len]);
+        rx_cfg[..16].copy_from_slice(&LOCALHOST);
+        rx_cfg[16..SOCKADDR_LEN].copy_from_slice(&src_port.to_ne_bytes());
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_RX, (payload.len() as u32, 0, 0))
+            .expect("Unable to schedule upcall");
+    }
+}
+
+// Returns the port of the `index`th socket address in `config`, or `None` if
+// `config` is too short to hold it.
+fn sockaddr_port(config: &[u8], index: usize) -> Option<u16> {
+    let port = config.get(index * SOCKADDR_LEN + 16..(index + 1) * SOCKADDR_LEN)?;
+    Some(u16::from_ne_bytes([port[0], port[1]]))
+}
+
+impl crate::fake::SyscallDriver for UdpDriver {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(2)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn allow_readonly(
+        &self,
+        buffer_num: u32,
+        buffer: RoAllowBuffer,
+    ) -> Result<RoAllowBuffer, (RoAllowBuffer, ErrorCode)> {
+        if buffer_num == ALLOW_WRITE {
+            Ok(self.tx_buffer.replace(buffer))
+        } else {
+            Err((buffer, ErrorCode::Invalid))
+        }
+    }
+
+    fn allow_readwrite(
+        &self,
+        buffer_num: u32,
+        buffer: RwAllowBuffer,
+    ) -> Result<RwAllowBuffer, (RwAllowBuffer, ErrorCode)> {
+        let old = match buffer_num {
+            ALLOW_READ => self.rx_buffer.replace(buffer),
+            ALLOW_TX_CFG => return Ok(self.tx_cfg.replace(buffer)),
+            ALLOW_RX_CFG => self.rx_cfg.replace(buffer),
+            _ => return Err((buffer, ErrorCode::Invalid)),
+        };
+        self.deliver();
+        Ok(old)
+    }
+
+    fn command(&self, command_num: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            SEND => self.send(),
+            BIND => self.bind(),
+            GET_MAX_TX_LEN => crate::command_return::success_u32(MAX_PAYLOAD_LEN as u32),
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x30002;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const SEND: u32 = 2;
+const BIND: u32 = 3;
+const GET_MAX_TX_LEN: u32 = 4;
+
+// Upcall numbers
+const SUBSCRIBE_RX: u32 = 0;
+const SUBSCRIBE_TX: u32 = 1;
+
+// Allow numbers
+const ALLOW_WRITE: u32 = 0;
+const ALLOW_READ: u32 = 0;
+const ALLOW_TX_CFG: u32 = 1;
+const ALLOW_RX_CFG: u32 = 2;
+
+// A socket address is a 16-byte IPv6 address followed by a native-endian port.
+const SOCKADDR_LEN: usize = 18;
+const LOCALHOST: [u8; 16] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+
+const MAX_PAYLOAD_LEN: usize = 1232;
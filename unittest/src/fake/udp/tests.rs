@@ -0,0 +1,122 @@
+use crate::fake;
+use fake::udp::*;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let udp = UdpDriver::new();
+
+    assert!(udp.command(EXISTS, 0, 0).is_success());
+    assert_eq!(
+        udp.command(GET_MAX_TX_LEN, 0, 0).get_success_u32(),
+        Some(MAX_PAYLOAD_LEN as u32)
+    );
+    // Binding and sending need configuration buffers.
+    assert_eq!(
+        udp.command(BIND, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+    assert_eq!(
+        udp.command(SEND, 0, 0).get_failure(),
+        Some(ErrorCode::Invalid)
+    );
+}
+
+// Returns a configuration buffer holding `src_port` and `dst_port`, with
+// unspecified addresses.
+fn config(src_port: u16, dst_port: u16) -> [u8; 2 * SOCKADDR_LEN] {
+    let mut config = [0; 2 * SOCKADDR_LEN];
+    config[16..SOCKADDR_LEN].copy_from_slice(&src_port.to_ne_bytes());
+    config[SOCKADDR_LEN + 16..].copy_from_slice(&dst_port.to_ne_bytes());
+    config
+}
+
+fn bind(port: u16) {
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::{share, DefaultConfig, Syscalls};
+
+    let mut rx_cfg = config(0, port);
+    share::scope::<AllowRw<_, DRIVER_NUM, ALLOW_RX_CFG>, _, _>(|allow_rw| {
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_RX_CFG>(allow_rw, &mut rx_cfg)
+            .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, BIND, 0, 0).is_success());
+    });
+}
+
+// Integration test that verifies UdpDriver works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::allow_ro::AllowRo;
+    use libtock_platform::allow_rw::AllowRw;
+    use libtock_platform::subscribe::Subscribe;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+
+    let kernel = fake::Kernel::new();
+    let udp = UdpDriver::new();
+    kernel.add_driver(&udp);
+    bind(1000);
+    bind(2000);
+    assert!(udp.is_bound(1000));
+    assert!(udp.is_bound(2000));
+
+    let sent: Cell<Option<(u32,)>> = Cell::new(None);
+    let mut tx_cfg = config(1000, 2000);
+    share::scope::<
+        (
+            AllowRo<_, DRIVER_NUM, ALLOW_WRITE>,
+            AllowRw<_, DRIVER_NUM, ALLOW_TX_CFG>,
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_TX>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (allow_ro, allow_rw, subscribe) = handle.split();
+        fake::Syscalls::allow_ro::<DefaultConfig, DRIVER_NUM, ALLOW_WRITE>(allow_ro, b"hello")
+            .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_TX_CFG>(allow_rw, &mut tx_cfg)
+            .unwrap();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_TX>(
+            subscribe, &sent,
+        )
+        .unwrap();
+        assert!(fake::Syscalls::command(DRIVER_NUM, SEND, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(sent.get(), Some((0,)));
+    });
+    assert_eq!(udp.pending(2000), 1);
+
+    let received: Cell<Option<(u32,)>> = Cell::new(None);
+    let mut rx_cfg = config(0, 2000);
+    let mut rx_buffer = [0; 8];
+    share::scope::<
+        (
+            Subscribe<_, DRIVER_NUM, SUBSCRIBE_RX>,
+            AllowRw<_, DRIVER_NUM, ALLOW_RX_CFG>,
+            AllowRw<_, DRIVER_NUM, ALLOW_READ>,
+        ),
+        _,
+        _,
+    >(|handle| {
+        let (subscribe, rx_cfg_handle, read) = handle.split();
+        fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_RX>(
+            subscribe, &received,
+        )
+        .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_RX_CFG>(
+            rx_cfg_handle,
+            &mut rx_cfg,
+        )
+        .unwrap();
+        fake::Syscalls::allow_rw::<DefaultConfig, DRIVER_NUM, ALLOW_READ>(read, &mut rx_buffer)
+            .unwrap();
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(received.get(), Some((5,)));
+    });
+    assert_eq!(&rx_buffer[..5], b"hello");
+    assert_eq!(rx_cfg[..16], LOCALHOST);
+    assert_eq!(rx_cfg[16..SOCKADDR_LEN], 1000u16.to_ne_bytes());
+    assert_eq!(udp.pending(2000), 0);
+}
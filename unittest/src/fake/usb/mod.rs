@@ -0,0 +1,120 @@
+//! Fake implementation of the USB controller API.
+//!
+//! Tests script the state changes the controller reports by queueing them with
+//! `push_states`. Each time the process asks to be notified of the next state
+//! change, the next queued state is reported; if none is queued, the next one
+//! pushed is reported as soon as it is pushed. The fake tracks whether the
+//! controller is enabled and attached, but does not change state on its own.
+
+use core::cell::{Cell, RefCell};
+use libtock_platform::{CommandReturn, ErrorCode};
+use std::collections::VecDeque;
+
+use crate::{DriverInfo, DriverShareRef};
+
+pub struct Usb {
+    enabled: Cell<bool>,
+    attached: Cell<bool>,
+    // Whether the process is waiting for a state change.
+    waiting: Cell<bool>,
+    states: RefCell<VecDeque<u32>>,
+    share_ref: DriverShareRef,
+}
+
+impl Usb {
+    pub fn new() -> std::rc::Rc<Usb> {
+        std::rc::Rc::new(Usb {
+            enabled: Cell::new(false),
+            attached: Cell::new(false),
+            waiting: Cell::new(false),
+            states: Default::default(),
+            share_ref: Default::default(),
+        })
+    }
+
+    /// Queues state changes to report, given as the driver's numbers for the
+    /// states.
+    pub fn push_states(&self, states: &[u32]) {
+        self.states.borrow_mut().extend(states);
+        self.deliver();
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.get()
+    }
+
+    pub fn is_attached(&self) -> bool {
+        self.attached.get()
+    }
+
+    fn deliver(&self) {
+        if !self.waiting.get() {
+            return;
+        }
+        let state = match self.states.borrow_mut().pop_front() {
+            Some(state) => state,
+            None => return,
+        };
+        self.waiting.set(false);
+        self.share_ref
+            .schedule_upcall(SUBSCRIBE_STATE, (state, 0, 0))
+            .expect("Unable to schedule upcall");
+    }
+}
+
+impl crate::fake::SyscallDriver for Usb {
+    fn info(&self) -> DriverInfo {
+        DriverInfo::new(DRIVER_NUM).upcall_count(1)
+    }
+
+    fn register(&self, share_ref: DriverShareRef) {
+        self.share_ref.replace(share_ref);
+    }
+
+    fn command(&self, command_num: u32, _argument0: usize, _argument1: usize) -> CommandReturn {
+        match command_num {
+            EXISTS => crate::command_return::success(),
+            ENABLE => {
+                if self.enabled.replace(true) {
+                    return crate::command_return::failure(ErrorCode::Already);
+                }
+                crate::command_return::success()
+            }
+            ATTACH => {
+                if !self.enabled.get() {
+                    return crate::command_return::failure(ErrorCode::Off);
+                }
+                self.attached.set(true);
+                crate::command_return::success()
+            }
+            DETACH => {
+                self.attached.set(false);
+                crate::command_return::success()
+            }
+            NOTIFY_STATE => {
+                self.waiting.set(true);
+                self.deliver();
+                crate::command_return::success()
+            }
+            _ => crate::command_return::failure(ErrorCode::NoSupport),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
+
+// -----------------------------------------------------------------------------
+// Implementation details below
+// -----------------------------------------------------------------------------
+
+const DRIVER_NUM: u32 = 0x20005;
+
+// Command numbers
+const EXISTS: u32 = 0;
+const ENABLE: u32 = 1;
+const ATTACH: u32 = 2;
+const DETACH: u32 = 3;
+const NOTIFY_STATE: u32 = 4;
+
+const SUBSCRIBE_STATE: u32 = 0;
@@ -0,0 +1,66 @@
+use crate::fake;
+use fake::usb::*;
+use libtock_platform::ErrorCode;
+
+// Tests the command implementation.
+#[test]
+fn command() {
+    use fake::SyscallDriver;
+    let usb = Usb::new();
+
+    assert!(usb.command(EXISTS, 0, 0).is_success());
+    assert_eq!(
+        usb.command(ATTACH, 0, 0).get_failure(),
+        Some(ErrorCode::Off)
+    );
+    assert!(usb.command(ENABLE, 0, 0).is_success());
+    assert!(usb.is_enabled());
+    assert_eq!(
+        usb.command(ENABLE, 0, 0).get_failure(),
+        Some(ErrorCode::Already)
+    );
+    assert!(usb.command(ATTACH, 0, 0).is_success());
+    assert!(usb.is_attached());
+    assert!(usb.command(DETACH, 0, 0).is_success());
+    assert!(!usb.is_attached());
+    assert!(usb.command(NOTIFY_STATE, 0, 0).is_success());
+    assert!(usb.command(5, 0, 0).is_failure());
+}
+
+// Integration test that verifies Usb works with fake::Kernel and
+// libtock_platform::Syscalls.
+#[test]
+fn kernel_integration() {
+    use core::cell::Cell;
+    use libtock_platform::{share, DefaultConfig, Syscalls, YieldNoWaitReturn};
+    let kernel = fake::Kernel::new();
+    let usb = Usb::new();
+    kernel.add_driver(&usb);
+
+    usb.push_states(&[2, 4]);
+    let state: Cell<Option<(u32,)>> = Cell::new(None);
+    share::scope(|subscribe| {
+        assert_eq!(
+            fake::Syscalls::subscribe::<_, _, DefaultConfig, DRIVER_NUM, SUBSCRIBE_STATE>(
+                subscribe, &state
+            ),
+            Ok(())
+        );
+        // Each request reports one state change.
+        assert!(fake::Syscalls::command(DRIVER_NUM, NOTIFY_STATE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(state.get(), Some((2,)));
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+
+        assert!(fake::Syscalls::command(DRIVER_NUM, NOTIFY_STATE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(state.get(), Some((4,)));
+
+        // With nothing queued, the next state is reported when pushed.
+        assert!(fake::Syscalls::command(DRIVER_NUM, NOTIFY_STATE, 0, 0).is_success());
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::NoUpcall);
+        usb.push_states(&[5]);
+        assert_eq!(fake::Syscalls::yield_no_wait(), YieldNoWaitReturn::Upcall);
+        assert_eq!(state.get(), Some((5,)));
+    });
+}
@@ -21,7 +21,19 @@ pub(crate) struct KernelData {
 
     pub drivers: std::collections::HashMap<u32, DriverData>,
     pub expected_syscalls: std::collections::VecDeque<crate::ExpectedSyscall>,
-    pub syscall_log: Vec<crate::SyscallLogEntry>,
+
+    // The values returned by successful memops, set by
+    // `fake::Kernel::set_memop_value`. The key is the memop number. Memops
+    // without an entry return 0.
+    pub memop_values: std::collections::HashMap<u32, usize>,
+
+    // Every system call made, in order. Each call is logged when it starts, so
+    // that calls that panic are still logged, and `fake::Syscalls` fills in
+    // its result when it returns. `fake::Kernel::take_syscall_log` returns the
+    // entries from `syscall_log_taken` on, while `fake::Kernel::trace` returns
+    // all of them.
+    pub syscall_log: Vec<crate::TraceEntry>,
+    pub syscall_log_taken: usize,
     pub upcall_queue: crate::upcall::UpcallQueue,
 }
 
@@ -9,10 +9,12 @@ mod driver_info;
 #[cfg(not(miri))]
 mod exit_test;
 mod expected_syscall;
+mod expected_syscalls;
 pub mod fake;
 mod kernel_data;
 mod share_data;
 mod syscall_log;
+mod trace;
 pub mod upcall;
 
 pub use allow_db::{RoAllowBuffer, RwAllowBuffer};
@@ -20,8 +22,10 @@ pub use driver_info::DriverInfo;
 #[cfg(not(miri))]
 pub use exit_test::{exit_test, ExitCall};
 pub use expected_syscall::ExpectedSyscall;
-pub use share_data::DriverShareRef;
+pub use expected_syscalls::ExpectedSyscalls;
+pub use share_data::{DriverShareRef, InvalidSubscribeNum};
 pub use syscall_log::SyscallLogEntry;
+pub use trace::{ParseTraceError, Trace, TraceEntry};
 
 #[cfg(test)]
 mod allow_db_test;
@@ -1,4 +1,4 @@
-use crate::kernel_data::with_kernel_data;
+use crate::kernel_data::{with_kernel_data, KernelData};
 use crate::upcall::{UpcallId, UpcallQueueEntry};
 use std::cell::Cell;
 
@@ -30,44 +30,54 @@ impl DriverShareRef {
         subscribe_num: u32,
         args: (u32, u32, u32),
     ) -> Result<(), InvalidSubscribeNum> {
-        with_kernel_data(|kernel_data| {
-            let kernel_data = match kernel_data {
-                Some(kernel_data) => kernel_data,
-                None => return Ok(()),
-            };
-            let driver_data = kernel_data
-                .drivers
-                .get(&self.driver_num.get())
-                .expect("DriverShareRef: registered but nonexistent?");
-            if subscribe_num >= driver_data.num_upcalls {
-                return Err(InvalidSubscribeNum {
-                    upcall_count: driver_data.num_upcalls,
-                    requested: subscribe_num,
-                });
-            }
-            let upcall = match driver_data.upcalls.get(&subscribe_num) {
-                Some(&upcall) => upcall,
-                None => return Ok(()),
-            };
-            // Don't bother queueing a null upcall, as they don't do anything
-            // when invoked anyway, and the core kernel does not queue them
-            // either.
-            if upcall.is_null() {
-                return Ok(());
+        with_kernel_data(|kernel_data| match kernel_data {
+            Some(kernel_data) => {
+                schedule_upcall(kernel_data, self.driver_num.get(), subscribe_num, args)
             }
-            kernel_data.upcall_queue.push_back(UpcallQueueEntry {
-                args,
-                id: UpcallId {
-                    driver_num: self.driver_num.get(),
-                    subscribe_num,
-                },
-                upcall,
-            });
-            Ok(())
+            None => Ok(()),
         })
     }
 }
 
+// Queues the upcall with the given driver and subscribe numbers, if one is
+// subscribed and it is not the null upcall. Panics if there is no driver with
+// number `driver_num`.
+pub(crate) fn schedule_upcall(
+    kernel_data: &mut KernelData,
+    driver_num: u32,
+    subscribe_num: u32,
+    args: (u32, u32, u32),
+) -> Result<(), InvalidSubscribeNum> {
+    let driver_data = kernel_data
+        .drivers
+        .get(&driver_num)
+        .unwrap_or_else(|| panic!("No fake driver with number {}", driver_num));
+    if subscribe_num >= driver_data.num_upcalls {
+        return Err(InvalidSubscribeNum {
+            upcall_count: driver_data.num_upcalls,
+            requested: subscribe_num,
+        });
+    }
+    let upcall = match driver_data.upcalls.get(&subscribe_num) {
+        Some(&upcall) => upcall,
+        None => return Ok(()),
+    };
+    // Don't bother queueing a null upcall, as they don't do anything when
+    // invoked anyway, and the core kernel does not queue them either.
+    if upcall.is_null() {
+        return Ok(());
+    }
+    kernel_data.upcall_queue.push_back(UpcallQueueEntry {
+        args,
+        id: UpcallId {
+            driver_num,
+            subscribe_num,
+        },
+        upcall,
+    });
+    Ok(())
+}
+
 #[derive(Debug, Eq, PartialEq, thiserror::Error)]
 #[error("Upcall number {requested} too large, expected < {upcall_count}.")]
 pub struct InvalidSubscribeNum {
@@ -93,7 +103,7 @@ mod tests {
         fn register(&self, share_ref: DriverShareRef) {
             self.share_ref.replace(share_ref);
         }
-        fn command(&self, _: u32, _: u32, _: u32) -> libtock_platform::CommandReturn {
+        fn command(&self, _: u32, _: usize, _: usize) -> libtock_platform::CommandReturn {
             crate::command_return::failure(libtock_platform::ErrorCode::NoSupport)
         }
     }
@@ -43,6 +43,71 @@ pub enum SyscallLogEntry {
         buffer_num: u32,
         len: usize,
     },
-    // TODO: Add Memop.
+
+    // -------------------------------------------------------------------------
+    // Memop
+    // -------------------------------------------------------------------------
+    Memop {
+        memop_num: u32,
+        argument0: usize,
+    },
     // TODO: Add Exit.
 }
+
+impl From<&crate::TraceEntry> for SyscallLogEntry {
+    fn from(entry: &crate::TraceEntry) -> SyscallLogEntry {
+        use crate::TraceEntry::*;
+        match *entry {
+            YieldNoWait { .. } => SyscallLogEntry::YieldNoWait,
+            YieldWait => SyscallLogEntry::YieldWait,
+            Subscribe {
+                driver_num,
+                subscribe_num,
+                ..
+            } => SyscallLogEntry::Subscribe {
+                driver_num,
+                subscribe_num,
+            },
+            Command {
+                driver_id,
+                command_id,
+                argument0,
+                argument1,
+                ..
+            } => SyscallLogEntry::Command {
+                driver_id,
+                command_id,
+                argument0,
+                argument1,
+            },
+            AllowRo {
+                driver_num,
+                buffer_num,
+                len,
+                ..
+            } => SyscallLogEntry::AllowRo {
+                driver_num,
+                buffer_num,
+                len,
+            },
+            AllowRw {
+                driver_num,
+                buffer_num,
+                len,
+                ..
+            } => SyscallLogEntry::AllowRw {
+                driver_num,
+                buffer_num,
+                len,
+            },
+            Memop {
+                memop_num,
+                argument0,
+                ..
+            } => SyscallLogEntry::Memop {
+                memop_num,
+                argument0,
+            },
+        }
+    }
+}
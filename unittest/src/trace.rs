@@ -0,0 +1,317 @@
+//! `fake::Kernel` records every system call made through `fake::Syscalls`,
+//! along with the kernel's response, as a `Trace`. A trace can be written out
+//! as text, parsed back, and replayed with `fake::Kernel::replay`, which makes
+//! the kernel give the recorded responses again.
+
+use libtock_platform::{return_variant, ErrorCode, ReturnVariant, YieldNoWaitReturn};
+use std::fmt;
+use std::str::FromStr;
+
+/// A system call recorded in a `Trace`, with the kernel's response.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TraceEntry {
+    YieldNoWait {
+        result: YieldNoWaitReturn,
+    },
+
+    // Yield-wait returns nothing, so there is no response to record.
+    YieldWait,
+
+    Subscribe {
+        driver_num: u32,
+        subscribe_num: u32,
+        result: Result<(), ErrorCode>,
+    },
+
+    Command {
+        driver_id: u32,
+        command_id: u32,
+        argument0: usize,
+        argument1: usize,
+        /// The returned `CommandReturn`'s `raw_values`.
+        result: (ReturnVariant, usize, usize, usize),
+    },
+
+    AllowRo {
+        driver_num: u32,
+        buffer_num: u32,
+        len: usize,
+        result: Result<(), ErrorCode>,
+    },
+
+    AllowRw {
+        driver_num: u32,
+        buffer_num: u32,
+        len: usize,
+        result: Result<(), ErrorCode>,
+    },
+
+    Memop {
+        memop_num: u32,
+        argument0: usize,
+        /// The error, or the value the memop returned.
+        result: Result<usize, ErrorCode>,
+    },
+}
+
+/// The system calls made during a test, and the kernel's responses, as
+/// returned by `fake::Kernel::trace`.
+///
+/// A trace is written out by `Display`, one system call per line, and read
+/// back by `FromStr`:
+///
+/// ```text
+/// command 2 1 0 0 -> 128 0 0 0
+/// subscribe 3 0 -> ok
+/// allow-ro 1 1 12 -> err 6
+/// memop 1 0 -> ok 4096
+/// yield-no-wait -> upcall
+/// ```
+///
+/// Each line gives the system call and its arguments, then its result. Command
+/// results are the return variant and the three returned values, successful
+/// memops give the value they returned, and error codes are given as numbers.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Trace {
+    entries: Vec<TraceEntry>,
+}
+
+impl Trace {
+    pub fn new(entries: Vec<TraceEntry>) -> Trace {
+        Trace { entries }
+    }
+
+    pub fn entries(&self) -> &[TraceEntry] {
+        &self.entries
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use TraceEntry::*;
+        for entry in &self.entries {
+            match *entry {
+                YieldNoWait { result } => match result {
+                    YieldNoWaitReturn::Upcall => writeln!(f, "yield-no-wait -> upcall")?,
+                    YieldNoWaitReturn::NoUpcall => writeln!(f, "yield-no-wait -> no-upcall")?,
+                },
+                YieldWait => writeln!(f, "yield-wait")?,
+                Subscribe {
+                    driver_num,
+                    subscribe_num,
+                    result,
+                } => writeln!(
+                    f,
+                    "subscribe {} {} -> {}",
+                    driver_num,
+                    subscribe_num,
+                    ResultText(result)
+                )?,
+                Command {
+                    driver_id,
+                    command_id,
+                    argument0,
+                    argument1,
+                    result: (variant, r1, r2, r3),
+                } => writeln!(
+                    f,
+                    "command {} {} {} {} -> {} {} {} {}",
+                    driver_id,
+                    command_id,
+                    argument0,
+                    argument1,
+                    u32::from(variant),
+                    r1,
+                    r2,
+                    r3
+                )?,
+                AllowRo {
+                    driver_num,
+                    buffer_num,
+                    len,
+                    result,
+                } => writeln!(
+                    f,
+                    "allow-ro {} {} {} -> {}",
+                    driver_num,
+                    buffer_num,
+                    len,
+                    ResultText(result)
+                )?,
+                AllowRw {
+                    driver_num,
+                    buffer_num,
+                    len,
+                    result,
+                } => writeln!(
+                    f,
+                    "allow-rw {} {} {} -> {}",
+                    driver_num,
+                    buffer_num,
+                    len,
+                    ResultText(result)
+                )?,
+                Memop {
+                    memop_num,
+                    argument0,
+                    result,
+                } => match result {
+                    Ok(value) => writeln!(f, "memop {} {} -> ok {}", memop_num, argument0, value)?,
+                    Err(error) => writeln!(
+                        f,
+                        "memop {} {} -> {}",
+                        memop_num,
+                        argument0,
+                        ResultText(Err(error))
+                    )?,
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Trace {
+    type Err = ParseTraceError;
+
+    fn from_str(text: &str) -> Result<Trace, ParseTraceError> {
+        let entries = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                parse_entry(line).ok_or_else(|| ParseTraceError {
+                    line: index + 1,
+                    text: line.to_string(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Trace { entries })
+    }
+}
+
+/// The error returned when a `Trace` cannot be parsed.
+#[derive(Debug, Eq, PartialEq, thiserror::Error)]
+#[error("Invalid trace entry on line {line}: {text:?}")]
+pub struct ParseTraceError {
+    line: usize,
+    text: String,
+}
+
+// Writes a Result<(), ErrorCode> as it appears in a trace.
+struct ResultText(Result<(), ErrorCode>);
+
+impl fmt::Display for ResultText {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Ok(()) => write!(f, "ok"),
+            Err(error) => write!(f, "err {}", error as u32),
+        }
+    }
+}
+
+// Parses a single line of a trace, returning None if it is invalid.
+fn parse_entry(line: &str) -> Option<TraceEntry> {
+    let (call, result) = match line.split_once(" -> ") {
+        Some((call, result)) => (call, Some(result)),
+        None => (line, None),
+    };
+    let mut call = call.split_whitespace();
+    let class = call.next()?;
+    let mut numbers = call.map(|number| number.parse::<usize>());
+    let mut next = || -> Option<usize> { numbers.next()?.ok() };
+    let entry = match (class, result) {
+        ("yield-no-wait", Some("upcall")) => TraceEntry::YieldNoWait {
+            result: YieldNoWaitReturn::Upcall,
+        },
+        ("yield-no-wait", Some("no-upcall")) => TraceEntry::YieldNoWait {
+            result: YieldNoWaitReturn::NoUpcall,
+        },
+        ("yield-wait", None) => TraceEntry::YieldWait,
+        ("subscribe", Some(result)) => TraceEntry::Subscribe {
+            driver_num: next()?.try_into().ok()?,
+            subscribe_num: next()?.try_into().ok()?,
+            result: parse_result(result)?,
+        },
+        ("command", Some(result)) => {
+            let mut values = result.split_whitespace().map(|value| value.parse().ok());
+            let mut value = || -> Option<usize> { values.next()? };
+            let entry = TraceEntry::Command {
+                driver_id: next()?.try_into().ok()?,
+                command_id: next()?.try_into().ok()?,
+                argument0: next()?,
+                argument1: next()?,
+                result: (
+                    u32::try_from(value()?).ok()?.into(),
+                    value()?,
+                    value()?,
+                    value()?,
+                ),
+            };
+            if values.next().is_some() {
+                return None;
+            }
+            entry
+        }
+        ("allow-ro", Some(result)) => TraceEntry::AllowRo {
+            driver_num: next()?.try_into().ok()?,
+            buffer_num: next()?.try_into().ok()?,
+            len: next()?,
+            result: parse_result(result)?,
+        },
+        ("allow-rw", Some(result)) => TraceEntry::AllowRw {
+            driver_num: next()?.try_into().ok()?,
+            buffer_num: next()?.try_into().ok()?,
+            len: next()?,
+            result: parse_result(result)?,
+        },
+        ("memop", Some(result)) => TraceEntry::Memop {
+            memop_num: next()?.try_into().ok()?,
+            argument0: next()?,
+            result: parse_memop_result(result)?,
+        },
+        _ => return None,
+    };
+    // Reject lines with extra arguments.
+    match numbers.next() {
+        None => Some(entry),
+        Some(_) => None,
+    }
+}
+
+fn parse_result(text: &str) -> Option<Result<(), ErrorCode>> {
+    let mut words = text.split_whitespace();
+    let result = match (words.next()?, words.next()) {
+        ("ok", None) => Ok(()),
+        ("err", Some(code)) => Err(code.parse::<u32>().ok()?.try_into().ok()?),
+        _ => return None,
+    };
+    match words.next() {
+        None => Some(result),
+        Some(_) => None,
+    }
+}
+
+fn parse_memop_result(text: &str) -> Option<Result<usize, ErrorCode>> {
+    let mut words = text.split_whitespace();
+    let result = match (words.next()?, words.next()?) {
+        ("ok", value) => Ok(value.parse().ok()?),
+        ("err", code) => Err(code.parse::<u32>().ok()?.try_into().ok()?),
+        _ => return None,
+    };
+    match words.next() {
+        None => Some(result),
+        Some(_) => None,
+    }
+}
+
+// Returns the error a Subscribe, Allow, or Memop call returned, given its
+// return registers. r1 is only used if the call failed, in which case it is an
+// error code, so callers may truncate it.
+pub(crate) fn call_result(r0: u32, r1: u32) -> Result<(), ErrorCode> {
+    let variant: ReturnVariant = r0.into();
+    if variant == return_variant::FAILURE || variant == return_variant::FAILURE_2_U32 {
+        return Err(ErrorCode::from_kernel(r1));
+    }
+    Ok(())
+}